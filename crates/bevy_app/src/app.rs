@@ -1,4 +1,6 @@
-use crate::{First, Main, MainSchedulePlugin, Plugin, Plugins, StateTransition};
+use crate::{
+    First, Main, MainSchedulePlugin, Plugin, PluginDependencies, Plugins, StateTransition,
+};
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
     prelude::*,
@@ -8,8 +10,14 @@ use bevy_ecs::{
         ScheduleBuildSettings, ScheduleLabel, StateTransitionEvent,
     },
 };
-use bevy_utils::{intern::Interned, thiserror::Error, tracing::debug, HashMap, HashSet};
+use bevy_utils::{
+    intern::Interned,
+    thiserror::Error,
+    tracing::{debug, warn},
+    HashMap, HashSet,
+};
 use std::{
+    any::TypeId,
     fmt::Debug,
     panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
 };
@@ -32,6 +40,11 @@ pub type InternedAppLabel = Interned<dyn AppLabel>;
 pub(crate) enum AppError {
     #[error("duplicate plugin {plugin_name:?}")]
     DuplicatePlugin { plugin_name: String },
+    #[error("plugin {plugin_name:?} is missing required plugins: {missing:?}")]
+    MissingPluginDependencies {
+        plugin_name: String,
+        missing: Vec<String>,
+    },
 }
 
 #[allow(clippy::needless_doctest_main)]
@@ -80,6 +93,8 @@ pub struct App {
     sub_apps: HashMap<InternedAppLabel, SubApp>,
     plugin_registry: Vec<Box<dyn Plugin>>,
     plugin_name_added: HashSet<String>,
+    /// The [`TypeId`]s of every added plugin, including the ones whose `build` is still running.
+    plugin_types_added: HashSet<TypeId>,
     /// A private counter to prevent incorrect calls to `App::run()` from `Plugin::build()`
     building_plugin_depth: usize,
     plugins_state: PluginsState,
@@ -237,6 +252,7 @@ impl App {
             sub_apps: HashMap::default(),
             plugin_registry: Vec::default(),
             plugin_name_added: Default::default(),
+            plugin_types_added: Default::default(),
             main_schedule_label: Main.intern(),
             building_plugin_depth: 0,
             plugins_state: PluginsState::Adding,
@@ -327,11 +343,14 @@ impl App {
 
     /// Run [`Plugin::finish`] for each plugin. This is usually called by the event loop once all
     /// plugins are ready, but can be useful for situations where you want to use [`App::update`].
+    ///
+    /// Plugins are finished after the plugins they [depend on](Plugin::dependencies), and
+    /// otherwise in the order they were added.
     pub fn finish(&mut self) {
         // temporarily remove the plugin registry to run each plugin's setup function on app.
         let plugin_registry = std::mem::take(&mut self.plugin_registry);
-        for plugin in &plugin_registry {
-            plugin.finish(self);
+        for index in dependency_order(&plugin_registry) {
+            plugin_registry[index].finish(self);
         }
         self.plugin_registry = plugin_registry;
         self.plugins_state = PluginsState::Finished;
@@ -339,11 +358,13 @@ impl App {
 
     /// Run [`Plugin::cleanup`] for each plugin. This is usually called by the event loop after
     /// [`App::finish`], but can be useful for situations where you want to use [`App::update`].
+    ///
+    /// Like [`App::finish`], plugins are cleaned up after the plugins they depend on.
     pub fn cleanup(&mut self) {
         // temporarily remove the plugin registry to run each plugin's setup function on app.
         let plugin_registry = std::mem::take(&mut self.plugin_registry);
-        for plugin in &plugin_registry {
-            plugin.cleanup(self);
+        for index in dependency_order(&plugin_registry) {
+            plugin_registry[index].cleanup(self);
         }
         self.plugin_registry = plugin_registry;
         self.plugins_state = PluginsState::Cleaned;
@@ -649,6 +670,21 @@ impl App {
             })?;
         }
 
+        let missing = self.missing_plugin_dependencies(plugin.as_ref());
+        if !missing.is_empty() {
+            if plugin.is_unique() {
+                self.plugin_name_added.remove(plugin.name());
+            }
+            Err(AppError::MissingPluginDependencies {
+                plugin_name: plugin.name().to_string(),
+                missing,
+            })?;
+        }
+
+        // Record the plugin type before building it, so sub-plugins added from its `build` can
+        // depend on it while it only has a placeholder in the registry
+        self.plugin_types_added.insert(plugin.as_any().type_id());
+
         // Reserve that position in the plugin registry. if a plugin adds plugins, they will be correctly ordered
         let plugin_position_in_registry = self.plugin_registry.len();
         self.plugin_registry.push(Box::new(PlaceholderPlugin));
@@ -674,6 +710,24 @@ impl App {
         self.plugin_registry.iter().any(|p| p.is::<T>())
     }
 
+    /// Checks if a [`Plugin`] with the given [`TypeId`] has already been added.
+    ///
+    /// Unlike [`App::is_plugin_added`], this also includes plugins whose `build` is still running.
+    pub(crate) fn is_plugin_type_added(&self, type_id: TypeId) -> bool {
+        self.plugin_types_added.contains(&type_id)
+    }
+
+    /// Returns the names of the required [dependencies](Plugin::dependencies) of `plugin` that
+    /// have not been added yet.
+    pub(crate) fn missing_plugin_dependencies(&self, plugin: &dyn Plugin) -> Vec<String> {
+        PluginDependencies::of(plugin)
+            .required()
+            .iter()
+            .filter(|dependency| !self.is_plugin_type_added(dependency.type_id()))
+            .map(|dependency| dependency.name().to_string())
+            .collect()
+    }
+
     /// Returns a vector of references to any plugins of type `T` that have been added.
     ///
     /// This can be used to read the settings of any already added plugins.
@@ -1058,16 +1112,68 @@ fn run_once(mut app: App) {
 #[derive(Event, Debug, Clone, Default)]
 pub struct AppExit;
 
+/// Returns the indices of `plugins` ordered so that every plugin comes after the plugins it
+/// [depends on](Plugin::dependencies), keeping the registration order otherwise.
+fn dependency_order(plugins: &[Box<dyn Plugin>]) -> Vec<usize> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        Unvisited,
+        Visiting,
+        Visited,
+    }
+
+    fn visit(
+        index: usize,
+        plugins: &[Box<dyn Plugin>],
+        dependencies: &[PluginDependencies],
+        marks: &mut [Mark],
+        order: &mut Vec<usize>,
+    ) {
+        match marks[index] {
+            Mark::Visited => return,
+            Mark::Visiting => {
+                warn!(
+                    "Plugin {} is part of a dependency cycle, ignoring the dependency",
+                    plugins[index].name()
+                );
+                return;
+            }
+            Mark::Unvisited => {}
+        }
+        marks[index] = Mark::Visiting;
+        for dependency in dependencies[index].iter() {
+            for (dependency_index, plugin) in plugins.iter().enumerate() {
+                if dependency_index != index && plugin.as_any().type_id() == dependency.type_id() {
+                    visit(dependency_index, plugins, dependencies, marks, order);
+                }
+            }
+        }
+        marks[index] = Mark::Visited;
+        order.push(index);
+    }
+
+    let dependencies: Vec<_> = plugins
+        .iter()
+        .map(|plugin| PluginDependencies::of(plugin.as_ref()))
+        .collect();
+    let mut marks = vec![Mark::Unvisited; plugins.len()];
+    let mut order = Vec::with_capacity(plugins.len());
+    for index in 0..plugins.len() {
+        visit(index, plugins, &dependencies, &mut marks, &mut order);
+    }
+    order
+}
+
 #[cfg(test)]
 mod tests {
     use std::marker::PhantomData;
 
     use bevy_ecs::{
         schedule::{OnEnter, States},
-        system::Commands,
+        system::{Commands, Resource},
     };
 
-    use crate::{App, Plugin};
+    use crate::{App, Plugin, PluginDependencies};
 
     struct PluginA;
     impl Plugin for PluginA {
@@ -1126,6 +1232,82 @@ mod tests {
         App::new().add_plugins(PluginRun);
     }
 
+    #[derive(Resource, Default)]
+    struct FinishOrder(Vec<&'static str>);
+
+    struct PluginRequiringA;
+    impl Plugin for PluginRequiringA {
+        fn build(&self, app: &mut App) {
+            app.init_resource::<FinishOrder>();
+        }
+        fn finish(&self, app: &mut App) {
+            app.world
+                .resource_mut::<FinishOrder>()
+                .0
+                .push("requiring_a");
+        }
+        fn dependencies(&self, dependencies: &mut PluginDependencies) {
+            dependencies.require::<PluginA>().optional::<PluginLate>();
+        }
+    }
+
+    struct PluginLate;
+    impl Plugin for PluginLate {
+        fn build(&self, _app: &mut App) {}
+        fn finish(&self, app: &mut App) {
+            app.world.resource_mut::<FinishOrder>().0.push("late");
+        }
+    }
+
+    #[test]
+    fn can_add_plugin_after_its_dependencies() {
+        let mut app = App::new();
+        app.add_plugins((PluginA, PluginRequiringA));
+        assert!(app.is_plugin_added::<PluginRequiringA>());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "required plugins were not added before it: bevy_app::app::tests::PluginA"
+    )]
+    fn cant_add_plugin_without_its_dependencies() {
+        App::new().add_plugins((PluginRequiringA, PluginA));
+    }
+
+    #[test]
+    fn can_add_sub_plugin_depending_on_its_parent_during_build() {
+        struct ParentPlugin;
+        impl Plugin for ParentPlugin {
+            fn build(&self, app: &mut App) {
+                app.add_plugins(ChildPlugin);
+            }
+        }
+
+        struct ChildPlugin;
+        impl Plugin for ChildPlugin {
+            fn build(&self, _app: &mut App) {}
+            fn dependencies(&self, dependencies: &mut PluginDependencies) {
+                dependencies.require::<ParentPlugin>();
+            }
+        }
+
+        let mut app = App::new();
+        app.add_plugins(ParentPlugin);
+        assert!(app.is_plugin_added::<ParentPlugin>());
+        assert!(app.is_plugin_added::<ChildPlugin>());
+    }
+
+    #[test]
+    fn finish_follows_optional_dependencies() {
+        let mut app = App::new();
+        app.add_plugins((PluginA, PluginRequiringA, PluginLate));
+        app.finish();
+        assert_eq!(
+            app.world.resource::<FinishOrder>().0,
+            vec!["late", "requiring_a"]
+        );
+    }
+
     #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone)]
     enum AppState {
         #[default]
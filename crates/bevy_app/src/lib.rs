@@ -9,6 +9,9 @@ mod schedule_runner;
 #[cfg(feature = "bevy_ci_testing")]
 pub mod ci_testing;
 
+#[cfg(feature = "settings")]
+pub mod settings;
+
 pub use app::*;
 pub use bevy_derive::DynamicPlugin;
 pub use main_schedule::*;
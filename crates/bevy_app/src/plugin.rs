@@ -1,7 +1,7 @@
 use downcast_rs::{impl_downcast, Downcast};
 
 use crate::App;
-use std::any::Any;
+use std::any::{Any, TypeId};
 
 /// A collection of Bevy app logic and configuration.
 ///
@@ -22,6 +22,42 @@ use std::any::Any;
 /// * it will then call all registered [`Plugin::finish`]
 /// * and call all registered [`Plugin::cleanup`]
 ///
+/// ## Plugin dependencies
+///
+/// A plugin can declare the other plugins it relies on by overriding [`Plugin::dependencies`].
+/// Required dependencies must already be added to the [`App`] when the plugin is added, otherwise
+/// [`App::add_plugins`] panics with a message listing every missing plugin. Optional dependencies
+/// don't need to be present, but when they are, they are also taken into account when ordering
+/// [`Plugin::finish`] and [`Plugin::cleanup`]: a plugin always finishes and cleans up after the
+/// plugins it depends on.
+///
+/// ```
+/// # use bevy_app::*;
+/// # struct AssetPlugin;
+/// # impl Plugin for AssetPlugin {
+/// #     fn build(&self, _: &mut App) {}
+/// # }
+/// # struct DiagnosticsPlugin;
+/// # impl Plugin for DiagnosticsPlugin {
+/// #     fn build(&self, _: &mut App) {}
+/// # }
+/// pub struct LevelPlugin;
+///
+/// impl Plugin for LevelPlugin {
+///     fn build(&self, app: &mut App) {
+///         // ...
+///     }
+///
+///     fn dependencies(&self, dependencies: &mut PluginDependencies) {
+///         dependencies
+///             .require::<AssetPlugin>()
+///             .optional::<DiagnosticsPlugin>();
+///     }
+/// }
+///
+/// App::new().add_plugins((AssetPlugin, LevelPlugin));
+/// ```
+///
 /// ## Defining a plugin.
 ///
 /// Most plugins are simply functions that add configuration to an [`App`].
@@ -90,6 +126,13 @@ pub trait Plugin: Downcast + Any + Send + Sync {
     fn is_unique(&self) -> bool {
         true
     }
+
+    /// Declares the other [`Plugin`]s this plugin depends on.
+    ///
+    /// See the [plugin dependencies](Plugin#plugin-dependencies) section for details.
+    fn dependencies(&self, _dependencies: &mut PluginDependencies) {
+        // no dependencies
+    }
 }
 
 impl_downcast!(Plugin);
@@ -100,12 +143,126 @@ impl<T: Fn(&mut App) + Send + Sync + 'static> Plugin for T {
     }
 }
 
+/// A reference to a [`Plugin`] type, used to declare [`PluginDependencies`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PluginDependency {
+    type_id: TypeId,
+    name: &'static str,
+}
+
+impl PluginDependency {
+    /// Creates a reference to the plugin type `T`.
+    pub fn of<T: Plugin>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            name: std::any::type_name::<T>(),
+        }
+    }
+
+    /// The [`TypeId`] of the plugin.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The type name of the plugin.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// The plugins a [`Plugin`] depends on, as declared by [`Plugin::dependencies`].
+#[derive(Debug, Default, Clone)]
+pub struct PluginDependencies {
+    required: Vec<PluginDependency>,
+    optional: Vec<PluginDependency>,
+}
+
+impl PluginDependencies {
+    /// Collects the dependencies declared by `plugin`.
+    pub fn of(plugin: &dyn Plugin) -> Self {
+        let mut dependencies = Self::default();
+        plugin.dependencies(&mut dependencies);
+        dependencies
+    }
+
+    /// Declares that the plugin `T` must be added to the [`App`] before this plugin.
+    pub fn require<T: Plugin>(&mut self) -> &mut Self {
+        let dependency = PluginDependency::of::<T>();
+        if !self.required.contains(&dependency) {
+            self.required.push(dependency);
+        }
+        self
+    }
+
+    /// Declares that this plugin should finish and clean up after the plugin `T` if it is added
+    /// to the [`App`], without requiring it.
+    pub fn optional<T: Plugin>(&mut self) -> &mut Self {
+        let dependency = PluginDependency::of::<T>();
+        if !self.optional.contains(&dependency) {
+            self.optional.push(dependency);
+        }
+        self
+    }
+
+    /// The plugins that must be added before this plugin.
+    pub fn required(&self) -> &[PluginDependency] {
+        &self.required
+    }
+
+    /// The plugins this plugin can use if they are added, but doesn't require.
+    pub fn optional_dependencies(&self) -> &[PluginDependency] {
+        &self.optional
+    }
+
+    /// Iterates over both required and optional dependencies.
+    pub fn iter(&self) -> impl Iterator<Item = &PluginDependency> {
+        self.required.iter().chain(self.optional.iter())
+    }
+
+    /// Returns `true` if no dependencies were declared.
+    pub fn is_empty(&self) -> bool {
+        self.required.is_empty() && self.optional.is_empty()
+    }
+}
+
 /// A type representing an unsafe function that returns a mutable pointer to a [`Plugin`].
 /// It is used for dynamically loading plugins.
 ///
 /// See `bevy_dynamic_plugin/src/loader.rs#dynamically_load_plugin`.
 pub type CreatePlugin = unsafe fn() -> *mut dyn Plugin;
 
+/// A type representing a function that returns a fingerprint of the Bevy build a dynamic plugin was
+/// compiled against. It is generated alongside [`CreatePlugin`] when deriving `DynamicPlugin`.
+///
+/// See `bevy_dynamic_plugin/src/reload.rs`.
+pub type DynamicPluginAbi = unsafe extern "C" fn() -> u64;
+
+/// Computes the fingerprint returned by [`DynamicPluginAbi`] functions, from the [`TypeId`] and
+/// the size and alignment of [`App`] as seen by the crate calling it.
+///
+/// The [`TypeId`] of [`App`] depends on the metadata cargo compiles `bevy_app` with, which covers
+/// the compiler version, the target, and the version, features and dependencies of `bevy_app`.
+/// Matching fingerprints don't guarantee ABI compatibility: they catch the common mistakes of
+/// building a plugin with another compiler or against another build of Bevy, but not every
+/// difference in the features of the other Bevy crates.
+///
+/// [`TypeId`]: std::any::TypeId
+#[doc(hidden)]
+pub fn dynamic_plugin_abi_fingerprint(
+    app_type_id: std::any::TypeId,
+    app_size: usize,
+    app_align: usize,
+) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    app_type_id.hash(&mut hasher);
+    app_size.hash(&mut hasher);
+    app_align.hash(&mut hasher);
+    cfg!(debug_assertions).hash(&mut hasher);
+    hasher.finish()
+}
+
 /// Types that represent a set of [`Plugin`]s.
 ///
 /// This is implemented for all types which implement [`Plugin`],
@@ -131,12 +288,18 @@ mod sealed {
     impl<P: Plugin> Plugins<PluginMarker> for P {
         #[track_caller]
         fn add_to_app(self, app: &mut App) {
-            if let Err(AppError::DuplicatePlugin { plugin_name }) =
-                app.add_boxed_plugin(Box::new(self))
-            {
-                panic!(
+            match app.add_boxed_plugin(Box::new(self)) {
+                Err(AppError::DuplicatePlugin { plugin_name }) => panic!(
                     "Error adding plugin {plugin_name}: : plugin was already added in application"
-                )
+                ),
+                Err(AppError::MissingPluginDependencies {
+                    plugin_name,
+                    missing,
+                }) => panic!(
+                    "Error adding plugin {plugin_name}: required plugins were not added before it: {}",
+                    missing.join(", ")
+                ),
+                Ok(_) => {}
             }
         }
     }
@@ -1,4 +1,4 @@
-use crate::{App, AppError, Plugin};
+use crate::{App, AppError, Plugin, PluginDependencies};
 use bevy_utils::{tracing::debug, tracing::warn, TypeIdMap};
 use std::any::TypeId;
 
@@ -107,6 +107,32 @@ impl PluginGroupBuilder {
         self
     }
 
+    /// Overrides the configuration of the plugin of type `T` with the matching entries of
+    /// `settings`, if it is part of this group. `registry` must contain `T` and the types of the
+    /// overridden fields.
+    ///
+    /// Errors are logged and leave the plugin unchanged.
+    #[cfg(feature = "settings")]
+    pub fn apply_settings<T: Plugin + bevy_reflect::Reflect>(
+        mut self,
+        settings: &crate::settings::Settings,
+        registry: &bevy_reflect::TypeRegistry,
+    ) -> Self {
+        if let Some(plugin) = self
+            .plugins
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|entry| entry.plugin.downcast_mut::<T>())
+        {
+            if let Err(error) = settings.apply(plugin, registry) {
+                bevy_utils::tracing::error!(
+                    "Failed to apply settings to plugin {}: {error}",
+                    std::any::type_name::<T>()
+                );
+            }
+        }
+        self
+    }
+
     /// Adds the plugin [`Plugin`] at the end of this [`PluginGroupBuilder`]. If the plugin was
     /// already in the group, it is removed from its previous place.
     // This is not confusing, clippy!
@@ -166,26 +192,83 @@ impl PluginGroupBuilder {
         self
     }
 
+    /// Returns a description of every required [dependency](Plugin::dependencies) of the
+    /// enabled plugins in this group that is part of the group but disabled or ordered after the
+    /// plugin requiring it, and not already added to `app`.
+    ///
+    /// Dependencies that aren't part of the group are checked by the [`App`] when each plugin is
+    /// added, as they may be added by another plugin while it builds.
+    fn missing_dependencies(&self, app: &App) -> Vec<String> {
+        let mut missing = Vec::new();
+        let mut available = Vec::with_capacity(self.order.len());
+        for ty in &self.order {
+            let Some(entry) = self.plugins.get(ty) else {
+                continue;
+            };
+            if !entry.enabled {
+                continue;
+            }
+            for dependency in PluginDependencies::of(entry.plugin.as_ref()).required() {
+                let type_id = dependency.type_id();
+                if available.contains(&type_id) || app.is_plugin_type_added(type_id) {
+                    continue;
+                }
+                let reason = match self.plugins.get(&type_id) {
+                    Some(dependency_entry) if !dependency_entry.enabled => {
+                        "it is disabled in this group"
+                    }
+                    Some(_) => "it is ordered after it in this group",
+                    None => continue,
+                };
+                missing.push(format!(
+                    "{} requires {} but {reason}",
+                    entry.plugin.name(),
+                    dependency.name()
+                ));
+            }
+            available.push(*ty);
+        }
+        missing
+    }
+
     /// Consumes the [`PluginGroupBuilder`] and [builds](Plugin::build) the contained [`Plugin`]s
     /// in the order specified.
     ///
     /// # Panics
     ///
-    /// Panics if one of the plugin in the group was already added to the application.
+    /// Panics if one of the plugin in the group was already added to the application, or if the
+    /// required [dependencies](Plugin::dependencies) of an enabled plugin are not added before
+    /// it, for example because they were [disabled](Self::disable).
     #[track_caller]
     pub fn finish(mut self, app: &mut App) {
+        let missing = self.missing_dependencies(app);
+        if !missing.is_empty() {
+            panic!(
+                "Error adding plugin group {}: missing plugin dependencies:\n{}",
+                self.group_name,
+                missing.join("\n")
+            );
+        }
         for ty in &self.order {
             if let Some(entry) = self.plugins.remove(ty) {
                 if entry.enabled {
                     debug!("added plugin: {}", entry.plugin.name());
-                    if let Err(AppError::DuplicatePlugin { plugin_name }) =
-                        app.add_boxed_plugin(entry.plugin)
-                    {
-                        panic!(
+                    match app.add_boxed_plugin(entry.plugin) {
+                        Err(AppError::DuplicatePlugin { plugin_name }) => panic!(
                             "Error adding plugin {} in group {}: plugin was already added in application",
                             plugin_name,
                             self.group_name
-                        );
+                        ),
+                        Err(AppError::MissingPluginDependencies {
+                            plugin_name,
+                            missing,
+                        }) => panic!(
+                            "Error adding plugin {} in group {}: required plugins were not added before it: {}",
+                            plugin_name,
+                            self.group_name,
+                            missing.join(", ")
+                        ),
+                        Ok(_) => {}
                     }
                 }
             }
@@ -214,7 +297,7 @@ impl PluginGroup for NoopPluginGroup {
 #[cfg(test)]
 mod tests {
     use super::PluginGroupBuilder;
-    use crate::{App, NoopPluginGroup, Plugin};
+    use crate::{App, NoopPluginGroup, Plugin, PluginDependencies};
 
     struct PluginA;
     impl Plugin for PluginA {
@@ -335,4 +418,55 @@ mod tests {
             ]
         );
     }
+
+    struct PluginRequiringA;
+    impl Plugin for PluginRequiringA {
+        fn build(&self, _: &mut App) {}
+        fn dependencies(&self, dependencies: &mut PluginDependencies) {
+            dependencies.require::<PluginA>();
+        }
+    }
+
+    #[test]
+    fn dependencies_in_group() {
+        let mut app = App::new();
+        PluginGroupBuilder::start::<NoopPluginGroup>()
+            .add(PluginA)
+            .add(PluginRequiringA)
+            .finish(&mut app);
+
+        assert!(app.is_plugin_added::<PluginRequiringA>());
+    }
+
+    #[test]
+    fn dependencies_already_in_app() {
+        let mut app = App::new();
+        app.add_plugins(PluginA);
+        PluginGroupBuilder::start::<NoopPluginGroup>()
+            .add(PluginA)
+            .add(PluginRequiringA)
+            .disable::<PluginA>()
+            .finish(&mut app);
+
+        assert!(app.is_plugin_added::<PluginRequiringA>());
+    }
+
+    #[test]
+    #[should_panic(expected = "requires bevy_app::plugin_group::tests::PluginA but it is disabled")]
+    fn disabled_dependency() {
+        PluginGroupBuilder::start::<NoopPluginGroup>()
+            .add(PluginA)
+            .add(PluginRequiringA)
+            .disable::<PluginA>()
+            .finish(&mut App::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "but it is ordered after it in this group")]
+    fn dependency_ordered_after() {
+        PluginGroupBuilder::start::<NoopPluginGroup>()
+            .add(PluginRequiringA)
+            .add(PluginA)
+            .finish(&mut App::new());
+    }
 }
@@ -0,0 +1,882 @@
+//! Overriding plugin, resource and component configuration from settings files and environment
+//! variables at startup.
+//!
+//! A settings file is a map from [type paths](bevy_reflect::TypePath) to a partial value of that
+//! type: only the fields present in the file are overridden, everything else keeps the value set in
+//! code. Both the full type path and the short type path can be used.
+//!
+//! In RON:
+//!
+//! ```ron
+//! {
+//!     "bevy_window::window::Window": (
+//!         present_mode: Immediate,
+//!         title: "QA build",
+//!     ),
+//!     "Difficulty": (level: 3),
+//! }
+//! ```
+//!
+//! And in TOML:
+//!
+//! ```toml
+//! ["bevy_window::window::Window"]
+//! present_mode = "Immediate"
+//! title = "QA build"
+//!
+//! [Difficulty]
+//! level = 3
+//! ```
+//!
+//! Environment variables take precedence over files. They are named
+//! `{prefix}{TYPE}__{FIELD}__{NESTED_FIELD}...`, where the type is its short type path and names
+//! are compared case-insensitively and ignoring underscores, and their value is written in RON.
+//! With the [default prefix](DEFAULT_ENV_PREFIX), `BEVY_SETTINGS__WINDOW__PRESENT_MODE=Immediate`
+//! overrides the present mode of every window.
+//!
+//! Deserialization goes through the [`TypeRegistry`], so every overridden type, and the types of
+//! its overridden fields, must be registered.
+//!
+//! [`SettingsPlugin`] applies the settings to resources and components. Plugins implementing
+//! [`Reflect`], such as `LogPlugin`, `WindowPlugin`, `RenderPlugin` and `AssetPlugin`, can be
+//! configured before they are added:
+//!
+//! ```ignore (bevy_asset, bevy_log and bevy_internal are not available)
+//! let settings = Settings::from_default_sources()?;
+//! let mut registry = TypeRegistry::new();
+//! registry.register::<LogPlugin>();
+//! registry.register::<AssetPlugin>();
+//! registry.register::<AssetMode>();
+//! registry.register::<Option<bool>>();
+//!
+//! App::new().add_plugins((
+//!     DefaultPlugins
+//!         .build()
+//!         .apply_settings::<LogPlugin>(&settings, &registry)
+//!         .apply_settings::<AssetPlugin>(&settings, &registry),
+//!     SettingsPlugin::new(settings),
+//! ));
+//! ```
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use bevy_ecs::{
+    prelude::*,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+};
+use bevy_reflect::{
+    serde::TypedReflectDeserializer, DynamicStruct, GetTypeRegistration, Reflect, TypeInfo,
+    TypeRegistration, TypeRegistry,
+};
+use bevy_utils::{
+    thiserror::{self, Error},
+    tracing::{error, warn},
+};
+use serde::de::{DeserializeSeed, Error as _, IgnoredAny, MapAccess, Visitor};
+
+use crate::{App, Plugin};
+
+/// The prefix of the environment variables read by [`Settings::from_default_sources`].
+pub const DEFAULT_ENV_PREFIX: &str = "BEVY_SETTINGS__";
+
+/// The environment variable holding the path of the settings file read by
+/// [`Settings::from_default_sources`].
+pub const SETTINGS_FILE_ENV_VAR: &str = "BEVY_SETTINGS_FILE";
+
+/// The format of a settings file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsFormat {
+    /// [RON](https://github.com/ron-rs/ron), using the `.ron` extension.
+    Ron,
+    /// [TOML](https://toml.io), using the `.toml` extension.
+    Toml,
+}
+
+impl SettingsFormat {
+    /// Guesses the format of a settings file from its extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "ron" => Some(Self::Ron),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// An error that occurs when loading or applying [`Settings`].
+#[derive(Debug, Error)]
+pub enum SettingsError {
+    /// The settings file could not be read.
+    #[error("failed to read settings file {path:?}: {error}")]
+    Io {
+        /// The path of the settings file.
+        path: PathBuf,
+        /// The underlying error.
+        error: std::io::Error,
+    },
+    /// The format of the settings file could not be determined from its extension.
+    #[error("unknown settings format for {0:?}, expected a `.ron` or `.toml` file")]
+    UnknownFormat(PathBuf),
+    /// A settings source could not be deserialized.
+    #[error("invalid settings in {source_name}: {message}")]
+    Deserialize {
+        /// The file name or environment variable the settings come from.
+        source_name: String,
+        /// A description of the error.
+        message: String,
+    },
+    /// An environment variable refers to a field that doesn't exist.
+    #[error("environment variable {variable} doesn't match a field of {type_path}")]
+    UnknownEnvField {
+        /// The name of the environment variable.
+        variable: String,
+        /// The type the variable refers to.
+        type_path: &'static str,
+    },
+    /// A value was overridden, but its type isn't registered.
+    #[error("settings override a value of type {0}, which isn't registered in the type registry")]
+    UnregisteredType(String),
+}
+
+#[derive(Debug, Clone)]
+struct SettingsSource {
+    name: String,
+    format: SettingsFormat,
+    text: String,
+}
+
+#[derive(Debug, Clone)]
+struct EnvOverride {
+    variable: String,
+    /// The uppercase segments of the variable name following the prefix: the type, then the
+    /// path of the overridden field.
+    segments: Vec<String>,
+    value: String,
+}
+
+/// Configuration overrides read from settings files and environment variables.
+///
+/// See the [module documentation](self) for the file formats. Use [`SettingsPlugin`] to apply
+/// them to resources and components, and [`PluginGroupBuilder::apply_settings`] or
+/// [`Settings::apply`] for plugin settings and other values.
+///
+/// [`PluginGroupBuilder::apply_settings`]: crate::PluginGroupBuilder::apply_settings
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Settings {
+    sources: Vec<SettingsSource>,
+    env: Vec<EnvOverride>,
+}
+
+impl Settings {
+    /// Creates empty settings, which don't override anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the settings file named by the [`SETTINGS_FILE_ENV_VAR`] environment variable if it
+    /// is set, and the environment variables starting with [`DEFAULT_ENV_PREFIX`].
+    pub fn from_default_sources() -> Result<Self, SettingsError> {
+        let settings = match std::env::var_os(SETTINGS_FILE_ENV_VAR) {
+            Some(path) => Self::new().with_file(path)?,
+            None => Self::new(),
+        };
+        Ok(settings.with_env_prefix(DEFAULT_ENV_PREFIX))
+    }
+
+    /// Adds the settings in the file at `path`, overriding the sources added before it.
+    ///
+    /// The format is chosen from the file extension.
+    pub fn with_file(self, path: impl AsRef<Path>) -> Result<Self, SettingsError> {
+        let path = path.as_ref();
+        let format = SettingsFormat::from_path(path)
+            .ok_or_else(|| SettingsError::UnknownFormat(path.to_owned()))?;
+        let text = std::fs::read_to_string(path).map_err(|error| SettingsError::Io {
+            path: path.to_owned(),
+            error,
+        })?;
+        Ok(self.with_str(path.display().to_string(), format, text))
+    }
+
+    /// Same as [`Settings::with_file`], but doesn't fail if the file doesn't exist.
+    pub fn with_optional_file(self, path: impl AsRef<Path>) -> Result<Self, SettingsError> {
+        if path.as_ref().exists() {
+            self.with_file(path)
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Adds settings from a string, overriding the sources added before it. `name` is used in
+    /// error messages.
+    pub fn with_str(
+        mut self,
+        name: impl Into<String>,
+        format: SettingsFormat,
+        text: impl Into<String>,
+    ) -> Self {
+        self.sources.push(SettingsSource {
+            name: name.into(),
+            format,
+            text: text.into(),
+        });
+        self
+    }
+
+    /// Adds the environment variables whose names start with `prefix`. They are read when this is
+    /// called, and override every file.
+    pub fn with_env_prefix(self, prefix: &str) -> Self {
+        self.with_env_vars(prefix, std::env::vars())
+    }
+
+    /// Adds the given variables whose names start with `prefix`, as if they were environment
+    /// variables.
+    pub fn with_env_vars(
+        mut self,
+        prefix: &str,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        for (variable, value) in vars {
+            let Some(name) = variable.strip_prefix(prefix) else {
+                continue;
+            };
+            let segments: Vec<String> = name.split("__").map(normalize_name).collect();
+            if segments.len() < 2 || segments.iter().any(String::is_empty) {
+                continue;
+            }
+            self.env.push(EnvOverride {
+                variable,
+                segments,
+                value,
+            });
+        }
+        self
+    }
+
+    /// Returns `true` if there are no settings sources.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty() && self.env.is_empty()
+    }
+
+    /// Returns the registrations of every type these settings override.
+    ///
+    /// Sections of the settings files whose type isn't registered are skipped with a warning.
+    pub fn overridden_types<'r>(
+        &self,
+        registry: &'r TypeRegistry,
+    ) -> Result<Vec<&'r TypeRegistration>, SettingsError> {
+        let mut registrations: Vec<&TypeRegistration> = Vec::new();
+        let mut push = |registration: &'r TypeRegistration| {
+            if !registrations
+                .iter()
+                .any(|r| r.type_id() == registration.type_id())
+            {
+                registrations.push(registration);
+            }
+        };
+        for registration in self.section_types(registry)? {
+            push(registration);
+        }
+        for registration in registry.iter() {
+            let short_path =
+                normalize_name(registration.type_info().type_path_table().short_path());
+            if self.env.iter().any(|env| env.segments[0] == short_path) {
+                push(registration);
+            }
+        }
+        Ok(registrations)
+    }
+
+    /// Applies the overrides for the type of `value` to it. Returns `true` if anything was
+    /// overridden.
+    ///
+    /// Sections of the settings files whose type isn't registered are skipped with a warning.
+    pub fn apply(
+        &self,
+        value: &mut dyn Reflect,
+        registry: &TypeRegistry,
+    ) -> Result<bool, SettingsError> {
+        self.section_types(registry)?;
+        self.apply_registered(value, registry)
+    }
+
+    /// Applies the overrides for `T` to `value`, using a registry containing only `T` and the
+    /// primitive types.
+    ///
+    /// Use [`Settings::apply`] when overriding fields of other types. Since the registry only
+    /// contains `T`, sections of other types are skipped without warnings.
+    pub fn apply_to<T: Reflect + GetTypeRegistration>(
+        &self,
+        value: &mut T,
+    ) -> Result<bool, SettingsError> {
+        let mut registry = TypeRegistry::new();
+        registry.register::<T>();
+        self.apply_registered(value, &registry)
+    }
+
+    fn apply_registered(
+        &self,
+        value: &mut dyn Reflect,
+        registry: &TypeRegistry,
+    ) -> Result<bool, SettingsError> {
+        let type_id = match value.get_represented_type_info() {
+            Some(info) => info.type_id(),
+            None => value.as_any().type_id(),
+        };
+        let registration = registry
+            .get(type_id)
+            .ok_or_else(|| SettingsError::UnregisteredType(value.reflect_type_path().into()))?;
+        let patches = self.patches(registration, registry)?;
+        for patch in &patches {
+            value.apply(patch.as_ref());
+        }
+        Ok(!patches.is_empty())
+    }
+
+    /// Applies the overrides to every resource and component of `world` they target, using the
+    /// [`AppTypeRegistry`].
+    ///
+    /// Types that are neither a [reflected resource](ReflectResource) nor a
+    /// [reflected component](ReflectComponent) are ignored.
+    pub fn apply_to_world(&self, world: &mut World) -> Result<(), SettingsError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        for registration in self.overridden_types(&registry)? {
+            let patches = self.patches(registration, &registry)?;
+            if let Some(reflect_resource) = registration.data::<ReflectResource>() {
+                if let Some(mut resource) = reflect_resource.reflect_mut(world) {
+                    for patch in &patches {
+                        resource.apply(patch.as_ref());
+                    }
+                }
+            }
+            if let Some(reflect_component) = registration.data::<ReflectComponent>() {
+                let entities: Vec<Entity> = world
+                    .iter_entities()
+                    .filter(|entity| reflect_component.contains(*entity))
+                    .map(|entity| entity.id())
+                    .collect();
+                for entity in entities {
+                    let mut entity = world.entity_mut(entity);
+                    if let Some(mut component) = reflect_component.reflect_mut(&mut entity) {
+                        for patch in &patches {
+                            component.apply(patch.as_ref());
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the registrations of the types of the sections of the settings files, warning about
+    /// the sections whose type isn't registered.
+    fn section_types<'r>(
+        &self,
+        registry: &'r TypeRegistry,
+    ) -> Result<Vec<&'r TypeRegistration>, SettingsError> {
+        let mut registrations = Vec::new();
+        for source in &self.sources {
+            for key in source.deserialize(SectionKeys)? {
+                match registry
+                    .get_with_type_path(&key)
+                    .or_else(|| registry.get_with_short_type_path(&key))
+                {
+                    Some(registration) => registrations.push(registration),
+                    None => warn!(
+                        "Skipping the settings of {key} in {}: the type isn't registered in the type registry",
+                        source.name
+                    ),
+                }
+            }
+        }
+        Ok(registrations)
+    }
+
+    /// Returns the partial values overriding the type of `registration`, in the order they should
+    /// be applied.
+    fn patches(
+        &self,
+        registration: &TypeRegistration,
+        registry: &TypeRegistry,
+    ) -> Result<Vec<Box<dyn Reflect>>, SettingsError> {
+        let mut patches = Vec::new();
+        for source in &self.sources {
+            patches.extend(source.deserialize(SectionPatches {
+                registration,
+                registry,
+            })?);
+        }
+
+        let short_path = normalize_name(registration.type_info().type_path_table().short_path());
+        for env in self.env.iter().filter(|env| env.segments[0] == short_path) {
+            let text = env.to_ron(registration.type_info(), registry)?;
+            let patch = ron::Deserializer::from_str(&text)
+                .map_err(|error| error.to_string())
+                .and_then(|mut deserializer| {
+                    PatchDeserializer {
+                        registration,
+                        registry,
+                    }
+                    .deserialize(&mut deserializer)
+                    .map_err(|error| error.to_string())
+                })
+                .map_err(|message| SettingsError::Deserialize {
+                    source_name: env.variable.clone(),
+                    message,
+                })?;
+            patches.push(patch);
+        }
+        Ok(patches)
+    }
+}
+
+impl SettingsSource {
+    fn deserialize<T>(
+        &self,
+        seed: impl for<'de> DeserializeSeed<'de, Value = T>,
+    ) -> Result<T, SettingsError> {
+        let result = match self.format {
+            SettingsFormat::Ron => ron::Deserializer::from_str(&self.text)
+                .map_err(|error| error.to_string())
+                .and_then(|mut deserializer| {
+                    seed.deserialize(&mut deserializer)
+                        .map_err(|error| error.to_string())
+                }),
+            SettingsFormat::Toml => seed
+                .deserialize(toml::Deserializer::new(&self.text))
+                .map_err(|error| error.to_string()),
+        };
+        result.map_err(|message| SettingsError::Deserialize {
+            source_name: self.name.clone(),
+            message,
+        })
+    }
+}
+
+impl EnvOverride {
+    /// Converts the override to a nested RON struct, resolving the field names of the variable
+    /// against the type information of the overridden type.
+    fn to_ron(
+        &self,
+        mut type_info: &'static TypeInfo,
+        registry: &TypeRegistry,
+    ) -> Result<String, SettingsError> {
+        let type_path = type_info.type_path();
+        let mut text = String::new();
+        let fields = &self.segments[1..];
+        for (index, segment) in fields.iter().enumerate() {
+            let field = match type_info {
+                TypeInfo::Struct(info) => info
+                    .iter()
+                    .find(|field| normalize_name(field.name()) == *segment),
+                _ => None,
+            }
+            .ok_or_else(|| SettingsError::UnknownEnvField {
+                variable: self.variable.clone(),
+                type_path,
+            })?;
+            text.push('(');
+            text.push_str(field.name());
+            text.push(':');
+            if index + 1 < fields.len() {
+                type_info = registry
+                    .get_type_info(field.type_id())
+                    .ok_or_else(|| SettingsError::UnregisteredType(field.type_path().into()))?;
+            }
+        }
+        text.push_str(&self.value);
+        text.push_str(&")".repeat(fields.len()));
+        Ok(text)
+    }
+}
+
+/// Uppercases `name` and removes its underscores, so that `present_mode`, `PRESENT_MODE` and
+/// `PresentMode` all compare equal.
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_')
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Deserializes the keys of the top-level map of a settings source.
+struct SectionKeys;
+
+impl<'de> DeserializeSeed<'de> for SectionKeys {
+    type Value = Vec<String>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SectionKeys {
+    type Value = Vec<String>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of type paths to settings")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut keys = Vec::new();
+        while let Some(key) = map.next_key::<String>()? {
+            map.next_value::<IgnoredAny>()?;
+            keys.push(key);
+        }
+        Ok(keys)
+    }
+}
+
+/// Deserializes the patches of the top-level map of a settings source that target the type of
+/// `registration`, ignoring the others.
+struct SectionPatches<'a> {
+    registration: &'a TypeRegistration,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SectionPatches<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for SectionPatches<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of type paths to settings")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let type_path_table = self.registration.type_info().type_path_table();
+        let mut patches = Vec::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if key == type_path_table.path() || key == type_path_table.short_path() {
+                patches.push(map.next_value_seed(PatchDeserializer {
+                    registration: self.registration,
+                    registry: self.registry,
+                })?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(patches)
+    }
+}
+
+/// A struct field name, deserialized as an identifier.
+struct FieldName(String);
+
+impl<'de> serde::Deserialize<'de> for FieldName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct FieldNameVisitor;
+
+        impl<'de> Visitor<'de> for FieldNameVisitor {
+            type Value = FieldName;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a field name")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(FieldName(value.to_string()))
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldNameVisitor)
+    }
+}
+
+/// Deserializes a partial value of the type of `registration`.
+///
+/// Structs are deserialized as a [`DynamicStruct`] containing only the fields that are present,
+/// recursively. Other types are deserialized as a whole with a [`TypedReflectDeserializer`].
+struct PatchDeserializer<'a> {
+    registration: &'a TypeRegistration,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PatchDeserializer<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match self.registration.type_info() {
+            TypeInfo::Struct(info) => deserializer.deserialize_struct(
+                info.type_path_table().ident().unwrap_or_default(),
+                info.field_names(),
+                self,
+            ),
+            _ => TypedReflectDeserializer::new(self.registration, self.registry)
+                .deserialize(deserializer),
+        }
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for PatchDeserializer<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "fields of {}",
+            self.registration.type_info().type_path()
+        )
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let TypeInfo::Struct(info) = self.registration.type_info() else {
+            return Err(A::Error::custom("expected a struct"));
+        };
+        let mut patch = DynamicStruct::default();
+        while let Some(FieldName(key)) = map.next_key::<FieldName>()? {
+            let field = info.field(&key).ok_or_else(|| {
+                A::Error::custom(format_args!(
+                    "unknown field `{key}` of `{}`",
+                    info.type_path()
+                ))
+            })?;
+            let registration = self.registry.get(field.type_id()).ok_or_else(|| {
+                A::Error::custom(format_args!(
+                    "no registration found for type `{}` of field `{key}`",
+                    field.type_path()
+                ))
+            })?;
+            let value = map.next_value_seed(PatchDeserializer {
+                registration,
+                registry: self.registry,
+            })?;
+            patch.insert_boxed(&key, value);
+        }
+        patch.set_represented_type(Some(self.registration.type_info()));
+        Ok(Box::new(patch))
+    }
+}
+
+/// Applies [`Settings`] to the resources and components of the [`App`] once all plugins are
+/// built, before the app starts running.
+///
+/// The settings are also inserted as a resource, so that other plugins can
+/// [apply](Settings::apply) them to their own configuration.
+///
+/// Errors are logged rather than stopping the app.
+pub struct SettingsPlugin {
+    /// The settings to apply.
+    pub settings: Settings,
+}
+
+impl SettingsPlugin {
+    /// Creates a plugin applying `settings`.
+    pub fn new(settings: Settings) -> Self {
+        Self { settings }
+    }
+}
+
+impl Default for SettingsPlugin {
+    /// Reads the settings from their [default sources](Settings::from_default_sources), logging an
+    /// error and ignoring the settings file if it can't be read.
+    fn default() -> Self {
+        let settings = Settings::from_default_sources().unwrap_or_else(|error| {
+            error!("{error}");
+            Settings::new().with_env_prefix(DEFAULT_ENV_PREFIX)
+        });
+        Self { settings }
+    }
+}
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AppTypeRegistry>()
+            .insert_resource(self.settings.clone());
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Err(error) = self.settings.apply_to_world(&mut app.world) {
+            error!("Failed to apply settings: {error}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::prelude::*;
+    use bevy_reflect::{Reflect, TypeRegistry};
+
+    use super::{Settings, SettingsError, SettingsFormat, SettingsPlugin};
+    use crate::App;
+
+    #[derive(Reflect, Debug, Default, PartialEq)]
+    enum Mode {
+        #[default]
+        Fifo,
+        Immediate,
+    }
+
+    #[derive(Reflect, Debug, Default, PartialEq)]
+    struct Inner {
+        mode: Mode,
+        scale: f32,
+    }
+
+    #[derive(Resource, Reflect, Debug, Default, PartialEq)]
+    #[reflect(Resource)]
+    struct Config {
+        name: String,
+        level: u32,
+        inner: Inner,
+    }
+
+    #[derive(Component, Reflect, Debug, Default, PartialEq)]
+    #[reflect(Component)]
+    struct Speed(f32);
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Config>();
+        registry.register::<Inner>();
+        registry.register::<Mode>();
+        registry.register::<Speed>();
+        registry
+    }
+
+    fn config() -> Config {
+        Config {
+            name: "game".to_string(),
+            level: 1,
+            inner: Inner {
+                mode: Mode::Fifo,
+                scale: 2.0,
+            },
+        }
+    }
+
+    #[test]
+    fn ron_overrides_only_present_fields() {
+        let settings = Settings::new().with_str(
+            "settings.ron",
+            SettingsFormat::Ron,
+            r#"{
+                "bevy_app::settings::tests::Config": (
+                    level: 3,
+                    inner: (mode: Immediate),
+                ),
+                "Unrelated": (value: 1),
+            }"#,
+        );
+        let mut value = config();
+        assert!(settings.apply(&mut value, &registry()).unwrap());
+        assert_eq!(value.name, "game");
+        assert_eq!(value.level, 3);
+        assert_eq!(value.inner.mode, Mode::Immediate);
+        assert_eq!(value.inner.scale, 2.0);
+    }
+
+    #[test]
+    fn toml_overrides_with_short_type_path() {
+        let settings = Settings::new().with_str(
+            "settings.toml",
+            SettingsFormat::Toml,
+            r#"
+                [Config]
+                name = "qa"
+
+                [Config.inner]
+                mode = "Immediate"
+            "#,
+        );
+        let mut value = config();
+        assert!(settings.apply(&mut value, &registry()).unwrap());
+        assert_eq!(value.name, "qa");
+        assert_eq!(value.level, 1);
+        assert_eq!(value.inner.mode, Mode::Immediate);
+    }
+
+    #[test]
+    fn env_vars_override_files() {
+        let settings = Settings::new()
+            .with_str(
+                "settings.ron",
+                SettingsFormat::Ron,
+                "{ \"Config\": (level: 3) }",
+            )
+            .with_env_vars(
+                "GAME_",
+                [
+                    ("GAME_CONFIG__LEVEL".to_string(), "5".to_string()),
+                    ("GAME_CONFIG__INNER__SCALE".to_string(), "0.5".to_string()),
+                    ("OTHER_CONFIG__LEVEL".to_string(), "7".to_string()),
+                ],
+            );
+        let mut value = config();
+        assert!(settings.apply(&mut value, &registry()).unwrap());
+        assert_eq!(value.level, 5);
+        assert_eq!(value.inner.scale, 0.5);
+    }
+
+    #[test]
+    fn unknown_fields_are_errors() {
+        let settings = Settings::new().with_str(
+            "settings.ron",
+            SettingsFormat::Ron,
+            "{ \"Config\": (levle: 3) }",
+        );
+        let result = settings.apply(&mut config(), &registry());
+        assert!(matches!(result, Err(SettingsError::Deserialize { .. })));
+
+        let settings = Settings::new().with_env_vars(
+            "GAME_",
+            [("GAME_CONFIG__LEVLE".to_string(), "5".to_string())],
+        );
+        let result = settings.apply(&mut config(), &registry());
+        assert!(matches!(result, Err(SettingsError::UnknownEnvField { .. })));
+    }
+
+    #[test]
+    fn plugin_applies_to_resources_and_components() {
+        let settings = Settings::new().with_str(
+            "settings.ron",
+            SettingsFormat::Ron,
+            "{ \"Config\": (level: 4), \"Unregistered\": (value: 1), \"Speed\": (10.0) }",
+        );
+        let mut app = App::new();
+        app.register_type::<Config>()
+            .register_type::<Inner>()
+            .register_type::<Mode>()
+            .register_type::<Speed>()
+            .insert_resource(config())
+            .add_plugins(SettingsPlugin::new(settings));
+        let entity = app.world.spawn(Speed(1.0)).id();
+        app.finish();
+
+        assert_eq!(app.world.resource::<Config>().level, 4);
+        assert_eq!(app.world.get::<Speed>(entity), Some(&Speed(10.0)));
+    }
+}
@@ -276,6 +276,9 @@ pub struct Assets<A: Asset> {
     hash_map: HashMap<Uuid, A>,
     handle_provider: AssetHandleProvider,
     queued_events: Vec<AssetEvent<A>>,
+    fallback: Option<Handle<A>>,
+    /// Assets that share the value of another asset, see [`Assets::shared_with`].
+    shared: HashMap<AssetId<A>, AssetId<A>>,
 }
 
 impl<A: Asset> Default for Assets<A> {
@@ -288,6 +291,8 @@ impl<A: Asset> Default for Assets<A> {
             handle_provider,
             hash_map: Default::default(),
             queued_events: Default::default(),
+            fallback: None,
+            shared: Default::default(),
         }
     }
 }
@@ -307,6 +312,7 @@ impl<A: Asset> Assets<A> {
     /// Inserts the given `asset`, identified by the given `id`. If an asset already exists for `id`, it will be replaced.
     pub fn insert(&mut self, id: impl Into<AssetId<A>>, asset: A) {
         let id: AssetId<A> = id.into();
+        self.shared.remove(&id);
         match id {
             AssetId::Index { index, .. } => {
                 self.insert_with_index(index, asset).unwrap();
@@ -379,43 +385,124 @@ impl<A: Asset> Assets<A> {
     /// Note that this supports anything that implements `Into<AssetId<A>>`, which includes [`Handle`] and [`AssetId`].
     #[inline]
     pub fn get(&self, id: impl Into<AssetId<A>>) -> Option<&A> {
-        let id: AssetId<A> = id.into();
+        let id = self.resolve(id.into());
         match id {
             AssetId::Index { index, .. } => self.dense_storage.get(index),
             AssetId::Uuid { uuid } => self.hash_map.get(&uuid),
         }
     }
 
+    /// Retrieves a reference to the [`Asset`] with the given `id` if it exists, or to the
+    /// [fallback asset](Assets::set_fallback) otherwise.
+    ///
+    /// This makes it possible to render something sensible while an asset is still loading or
+    /// after it failed to load. Once the asset is loaded, for example by a later reload or
+    /// a retry, the asset itself is returned.
+    #[inline]
+    pub fn get_or_fallback(&self, id: impl Into<AssetId<A>>) -> Option<&A> {
+        self.get(id).or_else(|| {
+            self.fallback
+                .as_ref()
+                .and_then(|fallback| self.get(fallback))
+        })
+    }
+
+    /// Sets the asset returned by [`Assets::get_or_fallback`] for assets that do not exist in
+    /// this collection. The fallback asset is kept alive until it is replaced or cleared.
+    ///
+    /// See [`AssetApp::set_fallback_asset`](crate::AssetApp::set_fallback_asset) to add and set
+    /// a fallback asset when building an [`App`](bevy_app::App).
+    pub fn set_fallback(&mut self, handle: Handle<A>) {
+        self.fallback = Some(handle);
+    }
+
+    /// Returns the handle of the fallback asset, if one is set.
+    pub fn fallback(&self) -> Option<&Handle<A>> {
+        self.fallback.as_ref()
+    }
+
+    /// Clears the fallback asset, see [`Assets::set_fallback`].
+    pub fn clear_fallback(&mut self) -> Option<Handle<A>> {
+        self.fallback.take()
+    }
+
     /// Retrieves a mutable reference to the [`Asset`] with the given `id`, if its exists.
     /// Note that this supports anything that implements `Into<AssetId<A>>`, which includes [`Handle`] and [`AssetId`].
+    ///
+    /// If the value is [shared](Assets::shared_with), it is modified for every asset sharing it.
     #[inline]
     pub fn get_mut(&mut self, id: impl Into<AssetId<A>>) -> Option<&mut A> {
-        let id: AssetId<A> = id.into();
+        let id = self.resolve(id.into());
         let result = match id {
             AssetId::Index { index, .. } => self.dense_storage.get_mut(index),
             AssetId::Uuid { uuid } => self.hash_map.get_mut(&uuid),
         };
         if result.is_some() {
-            self.queued_events.push(AssetEvent::Modified { id });
+            queue_shared_event(&mut self.queued_events, &self.shared, id, |id| {
+                AssetEvent::Modified { id }
+            });
         }
         result
     }
 
+    /// Returns the id of the asset whose value the asset with the given `id` shares, if it shares one.
+    ///
+    /// The [`AssetServer`] loads assets whose processed content is identical only once: the
+    /// assets loaded later share the value of the first one. [`Assets::get`] and
+    /// [`Assets::get_mut`] return the shared value for all of them, and modifying or removing it
+    /// modifies or removes it for all of them, sending an [`AssetEvent`] for each of them.
+    /// Inserting a value for `id` or removing `id` stops sharing.
+    pub fn shared_with(&self, id: impl Into<AssetId<A>>) -> Option<AssetId<A>> {
+        self.shared.get(&id.into()).copied()
+    }
+
+    /// Makes the asset with the given `id` share the value of the asset with the `target` id,
+    /// replacing its own value. See [`Assets::shared_with`].
+    pub(crate) fn share(&mut self, id: AssetId<A>, target: AssetId<A>) {
+        let replaced = self.remove_untracked(id).is_some();
+        self.shared.insert(id, target);
+        if replaced {
+            self.queued_events.push(AssetEvent::Modified { id });
+        } else {
+            self.queued_events.push(AssetEvent::Added { id });
+        }
+    }
+
+    fn resolve(&self, id: AssetId<A>) -> AssetId<A> {
+        self.shared.get(&id).copied().unwrap_or(id)
+    }
+
     /// Removes (and returns) the [`Asset`] with the given `id`, if its exists.
     /// Note that this supports anything that implements `Into<AssetId<A>>`, which includes [`Handle`] and [`AssetId`].
+    ///
+    /// Removing an asset that [shares](Assets::shared_with) the value of another asset stops
+    /// sharing, and returns [`None`] since the value is still used by the other asset. Removing
+    /// a shared value removes it for every asset sharing it.
     pub fn remove(&mut self, id: impl Into<AssetId<A>>) -> Option<A> {
         let id: AssetId<A> = id.into();
+        if self.shared.remove(&id).is_some() {
+            self.queued_events.push(AssetEvent::Removed { id });
+            return None;
+        }
         let result = self.remove_untracked(id);
         if result.is_some() {
-            self.queued_events.push(AssetEvent::Removed { id });
+            queue_shared_event(&mut self.queued_events, &self.shared, id, |id| {
+                AssetEvent::Removed { id }
+            });
         }
         result
     }
 
     /// Removes (and returns) the [`Asset`] with the given `id`, if its exists. This skips emitting [`AssetEvent::Removed`].
     /// Note that this supports anything that implements `Into<AssetId<A>>`, which includes [`Handle`] and [`AssetId`].
+    ///
+    /// Like [`Assets::remove`], removing an asset that shares the value of another asset stops
+    /// sharing and returns [`None`].
     pub fn remove_untracked(&mut self, id: impl Into<AssetId<A>>) -> Option<A> {
         let id: AssetId<A> = id.into();
+        if self.shared.remove(&id).is_some() {
+            return None;
+        }
         match id {
             AssetId::Index { index, .. } => self.dense_storage.remove_still_alive(index),
             AssetId::Uuid { uuid } => self.hash_map.remove(&uuid),
@@ -426,6 +513,7 @@ impl<A: Asset> Assets<A> {
     /// Note that this supports anything that implements `Into<AssetId<A>>`, which includes [`Handle`] and [`AssetId`].
     pub(crate) fn remove_dropped(&mut self, id: impl Into<AssetId<A>>) -> Option<A> {
         let id: AssetId<A> = id.into();
+        self.shared.remove(&id);
         let result = match id {
             AssetId::Index { index, .. } => self.dense_storage.remove_dropped(index),
             AssetId::Uuid { uuid } => self.hash_map.remove(&uuid),
@@ -487,6 +575,7 @@ impl<A: Asset> Assets<A> {
             dense_storage: self.dense_storage.storage.iter_mut().enumerate(),
             hash_map: self.hash_map.iter_mut(),
             queued_events: &mut self.queued_events,
+            shared: &self.shared,
         }
     }
 
@@ -546,9 +635,26 @@ impl<A: Asset> Assets<A> {
     }
 }
 
+/// Queues the event created by `event` for `id`, and for every asset sharing its value.
+fn queue_shared_event<A: Asset>(
+    queued_events: &mut Vec<AssetEvent<A>>,
+    shared: &HashMap<AssetId<A>, AssetId<A>>,
+    id: AssetId<A>,
+    event: fn(AssetId<A>) -> AssetEvent<A>,
+) {
+    queued_events.push(event(id));
+    queued_events.extend(
+        shared
+            .iter()
+            .filter(|(_, original)| **original == id)
+            .map(|(sharer, _)| event(*sharer)),
+    );
+}
+
 /// A mutable iterator over [`Assets`].
 pub struct AssetsMutIterator<'a, A: Asset> {
     queued_events: &'a mut Vec<AssetEvent<A>>,
+    shared: &'a HashMap<AssetId<A>, AssetId<A>>,
     dense_storage: Enumerate<std::slice::IterMut<'a, Entry<A>>>,
     hash_map: bevy_utils::hashbrown::hash_map::IterMut<'a, Uuid, A>,
 }
@@ -570,7 +676,9 @@ impl<'a, A: Asset> Iterator for AssetsMutIterator<'a, A> {
                         },
                         marker: PhantomData,
                     };
-                    self.queued_events.push(AssetEvent::Modified { id });
+                    queue_shared_event(self.queued_events, self.shared, id, |id| {
+                        AssetEvent::Modified { id }
+                    });
                     if let Some(value) = value {
                         return Some((id, value));
                     }
@@ -579,7 +687,9 @@ impl<'a, A: Asset> Iterator for AssetsMutIterator<'a, A> {
         }
         if let Some((key, value)) = self.hash_map.next() {
             let id = AssetId::Uuid { uuid: *key };
-            self.queued_events.push(AssetEvent::Modified { id });
+            queue_shared_event(self.queued_events, self.shared, id, |id| {
+                AssetEvent::Modified { id }
+            });
             Some((id, value))
         } else {
             None
@@ -593,3 +703,62 @@ pub struct InvalidGenerationError {
     index: AssetIndex,
     current_generation: u32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::CoolText;
+
+    #[test]
+    fn shared_values_send_events_for_every_sharer() {
+        let mut texts = Assets::<CoolText>::default();
+        let original = texts
+            .add(CoolText {
+                text: "same".to_string(),
+                embedded: String::new(),
+                dependencies: Vec::new(),
+                sub_texts: Vec::new(),
+            })
+            .id();
+        let (a, b) = (texts.reserve_handle().id(), texts.reserve_handle().id());
+        texts.share(a, original);
+        texts.share(b, original);
+        texts.queued_events.clear();
+
+        let events = |texts: &mut Assets<CoolText>| {
+            let mut events: Vec<_> = texts.queued_events.drain(..).collect();
+            events.sort_by_key(|event| format!("{event:?}"));
+            events
+        };
+        let sorted = |mut expected: Vec<AssetEvent<CoolText>>| {
+            expected.sort_by_key(|event| format!("{event:?}"));
+            expected
+        };
+
+        texts.get_mut(a).unwrap().text = "modified".to_string();
+        assert_eq!(texts.get(original).unwrap().text, "modified");
+        assert_eq!(
+            events(&mut texts),
+            sorted(vec![
+                AssetEvent::Modified { id: original },
+                AssetEvent::Modified { id: a },
+                AssetEvent::Modified { id: b },
+            ])
+        );
+
+        // Removing a sharer only stops sharing.
+        assert!(texts.remove(a).is_none());
+        assert!(texts.get(a).is_none());
+        assert_eq!(events(&mut texts), vec![AssetEvent::Removed { id: a }]);
+
+        assert!(texts.remove(original).is_some());
+        assert!(texts.get(b).is_none());
+        assert_eq!(
+            events(&mut texts),
+            sorted(vec![
+                AssetEvent::Removed { id: original },
+                AssetEvent::Removed { id: b },
+            ])
+        );
+    }
+}
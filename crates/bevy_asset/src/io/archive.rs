@@ -0,0 +1,1038 @@
+//! A simple archive ("pak") format bundling many assets into a single file, an
+//! [`AssetReader`] serving assets out of one or more such archives, and an [`AssetWriter`]
+//! writing assets into an archive.
+//!
+//! An archive starts with [`ARCHIVE_MAGIC`], followed by the bytes of every entry, an index and
+//! a footer. The index lists, for every entry, its path (using `/` as the separator), the
+//! offset of its bytes and their length. The footer stores the offset of the index, the number
+//! of entries and [`ARCHIVE_MAGIC`] again. All integers are little endian. Meta files are
+//! stored as regular entries, with the `.meta` extension appended to their asset's path.
+
+use crate::io::{
+    get_meta_path, AssetReader, AssetReaderError, AssetWriter, AssetWriterError, PathStream,
+    Reader, VecReader, Writer,
+};
+use bevy_utils::{tracing::error, BoxedFuture, HashMap};
+use futures_io::AsyncWrite;
+use parking_lot::Mutex;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use thiserror::Error;
+
+/// The bytes starting and ending every asset archive.
+pub const ARCHIVE_MAGIC: [u8; 8] = *b"BEVYPAK1";
+
+/// Size of the footer: index offset, entry count and magic.
+const FOOTER_LEN: u64 = 8 + 4 + ARCHIVE_MAGIC.len() as u64;
+
+/// Errors that occur while reading or writing an asset archive.
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    /// Encountered an I/O error while reading or writing an archive.
+    #[error("encountered an io error while accessing an asset archive: {0}")]
+    Io(#[from] io::Error),
+    /// The file is not an asset archive, or it is corrupted.
+    #[error("{0:?} is not a valid asset archive")]
+    InvalidFormat(PathBuf),
+    /// The path of an entry is absolute, not valid UTF-8 or escapes the archive root.
+    #[error("the path {0:?} can't be stored in an asset archive")]
+    InvalidPath(PathBuf),
+}
+
+/// Converts `path` to the key of an archive entry, or [`None`] if it can't be stored in an archive.
+fn entry_key(path: &Path) -> Option<String> {
+    let mut key = String::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => {
+                if !key.is_empty() {
+                    key.push('/');
+                }
+                key.push_str(name.to_str()?);
+            }
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(key)
+}
+
+fn is_meta(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("meta"))
+}
+
+/// Where the bytes of an entry added to an [`ArchiveBuilder`] come from.
+enum EntrySource {
+    Bytes(Vec<u8>),
+    File(PathBuf),
+}
+
+/// Builds an asset archive, to be read with an [`ArchiveAssetReader`].
+///
+/// Entries are written sorted by path. Adding an entry with the path of an existing entry
+/// replaces it.
+///
+/// ```no_run
+/// # use bevy_asset::io::archive::ArchiveBuilder;
+/// // Pack the output of the asset processor, including meta files.
+/// let mut builder = ArchiveBuilder::new();
+/// builder.add_directory("imported_assets/Default")?;
+/// builder.write("assets.pak")?;
+/// # Ok::<(), bevy_asset::io::archive::ArchiveError>(())
+/// ```
+#[derive(Default)]
+pub struct ArchiveBuilder {
+    entries: BTreeMap<String, EntrySource>,
+}
+
+impl ArchiveBuilder {
+    /// Creates an empty [`ArchiveBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an entry at `path` containing `bytes`.
+    pub fn add_bytes(
+        &mut self,
+        path: impl AsRef<Path>,
+        bytes: impl Into<Vec<u8>>,
+    ) -> Result<&mut Self, ArchiveError> {
+        let key = Self::key(path.as_ref())?;
+        self.entries.insert(key, EntrySource::Bytes(bytes.into()));
+        Ok(self)
+    }
+
+    /// Adds an entry at `path` containing the bytes of the file at `file_path`. The file is only
+    /// read once the archive is written.
+    pub fn add_file(
+        &mut self,
+        path: impl AsRef<Path>,
+        file_path: impl Into<PathBuf>,
+    ) -> Result<&mut Self, ArchiveError> {
+        let key = Self::key(path.as_ref())?;
+        self.entries
+            .insert(key, EntrySource::File(file_path.into()));
+        Ok(self)
+    }
+
+    /// Recursively adds every file in the folder at `root`, using their path relative to `root`.
+    pub fn add_directory(&mut self, root: impl AsRef<Path>) -> Result<&mut Self, ArchiveError> {
+        let root = root.as_ref();
+        let mut pending = vec![root.to_path_buf()];
+        while let Some(directory) = pending.pop() {
+            for entry in fs::read_dir(&directory)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else {
+                    let relative_path = path.strip_prefix(root).unwrap().to_owned();
+                    self.add_file(relative_path, path)?;
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Returns the number of entries added so far.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no entries have been added.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Writes the archive to the file at `path`, replacing it if it exists.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), ArchiveError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the archive to `writer`.
+    pub fn write_to(&self, writer: &mut impl Write) -> Result<(), ArchiveError> {
+        writer.write_all(&ARCHIVE_MAGIC)?;
+        let mut offset = ARCHIVE_MAGIC.len() as u64;
+        let mut index = Vec::with_capacity(self.entries.len());
+        for (key, source) in &self.entries {
+            let len = match source {
+                EntrySource::Bytes(bytes) => {
+                    writer.write_all(bytes)?;
+                    bytes.len() as u64
+                }
+                EntrySource::File(file_path) => {
+                    io::copy(&mut BufReader::new(File::open(file_path)?), writer)?
+                }
+            };
+            index.push((key, offset, len));
+            offset += len;
+        }
+
+        write_index(
+            writer,
+            offset,
+            index
+                .iter()
+                .map(|(key, offset, len)| (key.as_str(), *offset, *len)),
+        )?;
+        Ok(())
+    }
+
+    fn key(path: &Path) -> Result<String, ArchiveError> {
+        entry_key(path)
+            .filter(|key| !key.is_empty())
+            .ok_or_else(|| ArchiveError::InvalidPath(path.to_owned()))
+    }
+}
+
+/// Writes the index listing `entries` and the footer of an archive whose index starts at
+/// `index_offset`.
+fn write_index<'a>(
+    writer: &mut impl Write,
+    index_offset: u64,
+    entries: impl ExactSizeIterator<Item = (&'a str, u64, u64)>,
+) -> io::Result<()> {
+    let entry_count = entries.len() as u32;
+    for (key, offset, len) in entries {
+        writer.write_all(&(key.len() as u32).to_le_bytes())?;
+        writer.write_all(key.as_bytes())?;
+        writer.write_all(&offset.to_le_bytes())?;
+        writer.write_all(&len.to_le_bytes())?;
+    }
+    writer.write_all(&index_offset.to_le_bytes())?;
+    writer.write_all(&entry_count.to_le_bytes())?;
+    writer.write_all(&ARCHIVE_MAGIC)?;
+    Ok(())
+}
+
+/// Reads the index of the archive `file` stored at `path`, returning the offset of the index and
+/// the key, offset and length of every entry.
+fn read_index(
+    file: &mut File,
+    path: &Path,
+) -> Result<(u64, Vec<(String, u64, u64)>), ArchiveError> {
+    let invalid = || ArchiveError::InvalidFormat(path.to_owned());
+    let file_len = file.metadata()?.len();
+    if file_len < ARCHIVE_MAGIC.len() as u64 + FOOTER_LEN {
+        return Err(invalid());
+    }
+
+    let mut magic = [0; ARCHIVE_MAGIC.len()];
+    file.seek(SeekFrom::Start(0))?;
+    file.read_exact(&mut magic)?;
+    if magic != ARCHIVE_MAGIC {
+        return Err(invalid());
+    }
+
+    let mut footer = [0; FOOTER_LEN as usize];
+    file.seek(SeekFrom::End(-(FOOTER_LEN as i64)))?;
+    file.read_exact(&mut footer)?;
+    let index_offset = u64::from_le_bytes(footer[0..8].try_into().unwrap());
+    let entry_count = u32::from_le_bytes(footer[8..12].try_into().unwrap());
+    if footer[12..] != ARCHIVE_MAGIC
+        || index_offset < ARCHIVE_MAGIC.len() as u64
+        || index_offset > file_len - FOOTER_LEN
+    {
+        return Err(invalid());
+    }
+
+    file.seek(SeekFrom::Start(index_offset))?;
+    let mut index = vec![0; (file_len - FOOTER_LEN - index_offset) as usize];
+    file.read_exact(&mut index)?;
+
+    let mut entries = Vec::new();
+    let mut index = index.as_slice();
+    for _ in 0..entry_count {
+        let key_len = read_u32(&mut index).ok_or_else(invalid)? as usize;
+        if index.len() < key_len {
+            return Err(invalid());
+        }
+        let (key, rest) = index.split_at(key_len);
+        index = rest;
+        let key = std::str::from_utf8(key).map_err(|_| invalid())?.to_owned();
+        let offset = read_u64(&mut index).ok_or_else(invalid)?;
+        let len = read_u64(&mut index).ok_or_else(invalid)?;
+        match offset.checked_add(len) {
+            Some(end) if end <= index_offset => {}
+            _ => return Err(invalid()),
+        }
+        entries.push((key, offset, len));
+    }
+    Ok((index_offset, entries))
+}
+
+/// An opened archive file.
+struct Archive {
+    path: PathBuf,
+    file: File,
+}
+
+impl Archive {
+    /// Reads the bytes at `offset` into `bytes`, without moving the cursor of the file, so that
+    /// entries can be read in parallel.
+    #[cfg(unix)]
+    fn read_at(&self, bytes: &mut [u8], offset: u64) -> io::Result<()> {
+        std::os::unix::fs::FileExt::read_exact_at(&self.file, bytes, offset)
+    }
+
+    /// Reads the bytes at `offset` into `bytes`, without moving the cursor of the file, so that
+    /// entries can be read in parallel.
+    #[cfg(windows)]
+    fn read_at(&self, mut bytes: &mut [u8], mut offset: u64) -> io::Result<()> {
+        use std::os::windows::fs::FileExt;
+        while !bytes.is_empty() {
+            match self.file.seek_read(bytes, offset) {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(read) => {
+                    bytes = &mut bytes[read..];
+                    offset += read as u64;
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the bytes at `offset` into `bytes` from a new handle to the file, so that entries can
+    /// be read in parallel.
+    #[cfg(not(any(unix, windows)))]
+    fn read_at(&self, bytes: &mut [u8], offset: u64) -> io::Result<()> {
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(bytes)
+    }
+}
+
+/// The location of the bytes of an entry.
+#[derive(Clone, Copy)]
+struct ArchiveEntry {
+    archive: usize,
+    offset: u64,
+    len: u64,
+}
+
+#[derive(Default)]
+struct ArchiveIndex {
+    archives: Vec<Archive>,
+    entries: HashMap<String, ArchiveEntry>,
+    /// Assets (excluding meta files) and folders directly contained in each folder.
+    directories: HashMap<String, BTreeSet<String>>,
+}
+
+impl ArchiveIndex {
+    fn add_archive(&mut self, path: &Path) -> Result<(), ArchiveError> {
+        let mut file = File::open(path)?;
+        let (_, entries) = read_index(&mut file, path)?;
+        let archive = self.archives.len();
+        for (key, offset, len) in entries {
+            self.insert(
+                key,
+                ArchiveEntry {
+                    archive,
+                    offset,
+                    len,
+                },
+            );
+        }
+
+        self.archives.push(Archive {
+            path: path.to_owned(),
+            file,
+        });
+        Ok(())
+    }
+
+    fn insert(&mut self, key: String, entry: ArchiveEntry) {
+        if !is_meta(&key) {
+            let mut child = key.as_str();
+            while let Some((parent, _)) = child.rsplit_once('/') {
+                self.directories
+                    .entry(parent.to_owned())
+                    .or_default()
+                    .insert(child.to_owned());
+                child = parent;
+            }
+            self.directories
+                .entry(String::new())
+                .or_default()
+                .insert(child.to_owned());
+        }
+        self.entries.insert(key, entry);
+    }
+
+    fn entry(&self, path: &Path) -> Result<ArchiveEntry, AssetReaderError> {
+        let not_found = || AssetReaderError::NotFound(path.to_owned());
+        let key = entry_key(path).ok_or_else(not_found)?;
+        self.entries.get(&key).copied().ok_or_else(not_found)
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>, AssetReaderError> {
+        let entry = self.entry(path)?;
+        let archive = &self.archives[entry.archive];
+        let mut bytes = vec![0; entry.len as usize];
+        archive.read_at(&mut bytes, entry.offset)?;
+        Ok(bytes)
+    }
+}
+
+fn read_array<const N: usize>(bytes: &mut &[u8]) -> Option<[u8; N]> {
+    if bytes.len() < N {
+        return None;
+    }
+    let (value, rest) = bytes.split_at(N);
+    *bytes = rest;
+    value.try_into().ok()
+}
+
+fn read_u32(bytes: &mut &[u8]) -> Option<u32> {
+    read_array(bytes).map(u32::from_le_bytes)
+}
+
+fn read_u64(bytes: &mut &[u8]) -> Option<u64> {
+    read_array(bytes).map(u64::from_le_bytes)
+}
+
+/// An [`AssetReader`] serving assets out of one or more archives written by an [`ArchiveBuilder`].
+///
+/// Archives are layered: when several archives contain an entry at the same path, the one
+/// from the archive opened last is used. This allows shipping a base archive and applying
+/// patches as smaller archives containing only the changed assets. Folders are the union of
+/// the folders of all archives.
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_asset::{io::{archive::ArchiveAssetReader, AssetSource, AssetSourceId}, AssetApp};
+/// let reader = ArchiveAssetReader::open_layered(["assets.pak", "patch_1.pak"])?;
+/// App::new().register_asset_source(
+///     AssetSourceId::Default,
+///     AssetSource::build().with_reader(move || Box::new(reader.clone())),
+/// );
+/// # Ok::<(), bevy_asset::io::archive::ArchiveError>(())
+/// ```
+#[derive(Clone)]
+pub struct ArchiveAssetReader {
+    index: Arc<ArchiveIndex>,
+}
+
+impl ArchiveAssetReader {
+    /// Opens the archive at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ArchiveError> {
+        Self::open_layered([path])
+    }
+
+    /// Opens the archives at `paths`, entries of later archives overriding entries of earlier ones.
+    pub fn open_layered(
+        paths: impl IntoIterator<Item = impl AsRef<Path>>,
+    ) -> Result<Self, ArchiveError> {
+        let mut index = ArchiveIndex::default();
+        for path in paths {
+            index.add_archive(path.as_ref())?;
+        }
+        Ok(Self {
+            index: Arc::new(index),
+        })
+    }
+
+    /// Returns the paths of the opened archives, in the order they are layered.
+    pub fn archives(&self) -> impl Iterator<Item = &Path> {
+        self.index
+            .archives
+            .iter()
+            .map(|archive| archive.path.as_path())
+    }
+
+    fn read_bytes<'a>(&self, path: &Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        let bytes = self.index.read(path)?;
+        let reader: Box<Reader> = Box::new(VecReader::new(bytes));
+        Ok(reader)
+    }
+}
+
+impl AssetReader for ArchiveAssetReader {
+    fn read<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move { self.read_bytes(path) })
+    }
+
+    fn read_with_byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(Box<Reader<'a>>, Option<u64>), AssetReaderError>> {
+        Box::pin(async move {
+            let reader = self.read_bytes(path)?;
+            Ok((reader, Some(self.index.entry(path)?.len)))
+        })
+    }
+
+    fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move { self.read_bytes(&get_meta_path(path)) })
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<PathStream>, AssetReaderError>> {
+        Box::pin(async move {
+            let children = entry_key(path)
+                .and_then(|key| self.index.directories.get(&key))
+                .ok_or_else(|| AssetReaderError::NotFound(path.to_owned()))?;
+            let children: Vec<PathBuf> = children.iter().map(PathBuf::from).collect();
+            let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(children));
+            Ok(stream)
+        })
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
+        Box::pin(async move {
+            Ok(entry_key(path).is_some_and(|key| self.index.directories.contains_key(&key)))
+        })
+    }
+
+    fn byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Option<u64>, AssetReaderError>> {
+        Box::pin(async move { Ok(Some(self.index.entry(path)?.len)) })
+    }
+}
+
+/// An archive opened for writing by an [`ArchiveAssetWriter`].
+struct WritableArchive {
+    path: PathBuf,
+    file: File,
+    /// Offset of the index, which is also the end of the entry bytes.
+    index_offset: u64,
+    entries: BTreeMap<String, (u64, u64)>,
+    /// Whether the entries changed since the index was last written.
+    index_dirty: bool,
+}
+
+impl WritableArchive {
+    /// Appends the bytes of the entry `key` after the current entries, overwriting the index.
+    fn insert(&mut self, key: String, bytes: &[u8]) -> io::Result<()> {
+        self.file.seek(SeekFrom::Start(self.index_offset))?;
+        self.file.write_all(bytes)?;
+        self.entries
+            .insert(key, (self.index_offset, bytes.len() as u64));
+        self.index_offset += bytes.len() as u64;
+        self.index_dirty = true;
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> io::Result<()> {
+        if self.entries.remove(key).is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{key:?} is not in the archive {:?}", self.path),
+            ));
+        }
+        self.index_dirty = true;
+        Ok(())
+    }
+
+    fn rename(&mut self, old_key: &str, new_key: String) -> io::Result<()> {
+        let Some(entry) = self.entries.remove(old_key) else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{old_key:?} is not in the archive {:?}", self.path),
+            ));
+        };
+        self.entries.insert(new_key, entry);
+        self.index_dirty = true;
+        Ok(())
+    }
+
+    /// Removes every entry in the folder `key`.
+    fn remove_directory(&mut self, key: &str) -> io::Result<()> {
+        let prefix = format!("{key}/");
+        self.entries
+            .retain(|entry_key, _| !(key.is_empty() || entry_key.starts_with(&prefix)));
+        self.index_dirty = true;
+        Ok(())
+    }
+
+    /// Writes the index after the entries if they changed since it was last written.
+    fn finish(&mut self) -> io::Result<()> {
+        if self.index_dirty {
+            self.write_index()?;
+        }
+        Ok(())
+    }
+
+    fn write_index(&mut self) -> io::Result<()> {
+        let mut index = Vec::new();
+        write_index(
+            &mut index,
+            self.index_offset,
+            self.entries
+                .iter()
+                .map(|(key, (offset, len))| (key.as_str(), *offset, *len)),
+        )?;
+        self.file.seek(SeekFrom::Start(self.index_offset))?;
+        self.file.write_all(&index)?;
+        self.file.set_len(self.index_offset + index.len() as u64)?;
+        self.file.flush()?;
+        self.index_dirty = false;
+        Ok(())
+    }
+}
+
+impl Drop for WritableArchive {
+    fn drop(&mut self) {
+        if let Err(err) = self.finish() {
+            error!(
+                "Failed to write the index of the asset archive {:?}: {err}",
+                self.path
+            );
+        }
+    }
+}
+
+/// An [`AssetWriter`] writing assets into an archive, which can then be read with an
+/// [`ArchiveAssetReader`].
+///
+/// Entries are appended to the archive as soon as their [`Writer`] is flushed or closed, but the
+/// index is only written by [`ArchiveAssetWriter::finish`], or once the last clone of the writer
+/// is dropped. Until then, the archive can't be opened by an [`ArchiveAssetReader`]. Overwritten
+/// and removed entries keep taking space in the archive until it is
+/// [compacted](ArchiveAssetWriter::compact). Readers opened before a change keep seeing the
+/// archive as it was when they were opened.
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_asset::{io::{archive::ArchiveAssetWriter, AssetSource, AssetSourceId}, AssetApp};
+/// // Write the output of the asset processor straight into an archive.
+/// let writer = ArchiveAssetWriter::open("imported_assets.pak")?;
+/// App::new().register_asset_source(
+///     AssetSourceId::Default,
+///     AssetSource::build()
+///         .with_reader(AssetSource::get_default_reader("assets".to_string()))
+///         .with_processed_writer(move |_| Some(Box::new(writer.clone()))),
+/// );
+/// # Ok::<(), bevy_asset::io::archive::ArchiveError>(())
+/// ```
+#[derive(Clone)]
+pub struct ArchiveAssetWriter {
+    archive: Arc<Mutex<WritableArchive>>,
+}
+
+impl ArchiveAssetWriter {
+    /// Opens the archive at `path` for writing, creating an empty archive if it doesn't exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ArchiveError> {
+        let path = path.as_ref();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let mut archive = if file.metadata()?.len() == 0 {
+            file.write_all(&ARCHIVE_MAGIC)?;
+            let mut archive = WritableArchive {
+                path: path.to_owned(),
+                file,
+                index_offset: ARCHIVE_MAGIC.len() as u64,
+                entries: BTreeMap::new(),
+                index_dirty: false,
+            };
+            archive.write_index()?;
+            archive
+        } else {
+            let (index_offset, entries) = read_index(&mut file, path)?;
+            WritableArchive {
+                path: path.to_owned(),
+                file,
+                index_offset,
+                entries: entries
+                    .into_iter()
+                    .map(|(key, offset, len)| (key, (offset, len)))
+                    .collect(),
+                index_dirty: false,
+            }
+        };
+        archive.file.flush()?;
+        Ok(Self {
+            archive: Arc::new(Mutex::new(archive)),
+        })
+    }
+
+    /// Writes the index of the archive if entries changed since it was last written, so that the
+    /// archive can be opened by an [`ArchiveAssetReader`].
+    pub fn finish(&self) -> Result<(), ArchiveError> {
+        Ok(self.archive.lock().finish()?)
+    }
+
+    /// Rewrites the archive without the bytes of overwritten and removed entries.
+    pub fn compact(&self) -> Result<(), ArchiveError> {
+        let mut guard = self.archive.lock();
+        let archive = &mut *guard;
+        let mut builder = ArchiveBuilder::new();
+        for (key, (offset, len)) in &archive.entries {
+            let mut bytes = vec![0; *len as usize];
+            archive.file.seek(SeekFrom::Start(*offset))?;
+            archive.file.read_exact(&mut bytes)?;
+            builder.add_bytes(key, bytes)?;
+        }
+
+        let mut compacted = archive.path.clone().into_os_string();
+        compacted.push(".compacted");
+        let compacted = PathBuf::from(compacted);
+        builder.write(&compacted)?;
+        fs::rename(&compacted, &archive.path)?;
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&archive.path)?;
+        let (index_offset, entries) = read_index(&mut file, &archive.path)?;
+        archive.file = file;
+        archive.index_offset = index_offset;
+        archive.entries = entries
+            .into_iter()
+            .map(|(key, offset, len)| (key, (offset, len)))
+            .collect();
+        archive.index_dirty = false;
+        Ok(())
+    }
+
+    fn key(path: &Path) -> Result<String, AssetWriterError> {
+        ArchiveBuilder::key(path).map_err(|error| {
+            AssetWriterError::Io(io::Error::new(io::ErrorKind::InvalidInput, error))
+        })
+    }
+
+    fn entry_writer(&self, path: &Path) -> Result<Box<Writer>, AssetWriterError> {
+        let writer: Box<Writer> = Box::new(ArchiveEntryWriter {
+            archive: self.archive.clone(),
+            key: Self::key(path)?,
+            bytes: Vec::new(),
+            dirty: true,
+        });
+        Ok(writer)
+    }
+
+    fn directory_key(path: &Path) -> Result<String, AssetWriterError> {
+        entry_key(path).ok_or_else(|| {
+            AssetWriterError::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                ArchiveError::InvalidPath(path.to_owned()),
+            ))
+        })
+    }
+}
+
+/// Buffers the bytes of an entry, and writes them to the archive when flushed, closed or
+/// dropped.
+struct ArchiveEntryWriter {
+    archive: Arc<Mutex<WritableArchive>>,
+    key: String,
+    bytes: Vec<u8>,
+    /// Whether `bytes` changed since they were last written to the archive.
+    dirty: bool,
+}
+
+impl ArchiveEntryWriter {
+    fn commit(&mut self) -> io::Result<()> {
+        if self.dirty {
+            self.archive.lock().insert(self.key.clone(), &self.bytes)?;
+            self.dirty = false;
+        }
+        Ok(())
+    }
+}
+
+impl AsyncWrite for ArchiveEntryWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.bytes.extend_from_slice(buf);
+        this.dirty = true;
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.get_mut().commit())
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.get_mut().commit())
+    }
+}
+
+impl Drop for ArchiveEntryWriter {
+    fn drop(&mut self) {
+        if let Err(err) = self.commit() {
+            error!("Failed to write {:?} to an asset archive: {err}", self.key);
+        }
+    }
+}
+
+impl AssetWriter for ArchiveAssetWriter {
+    fn write<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Writer>, AssetWriterError>> {
+        Box::pin(async move { self.entry_writer(path) })
+    }
+
+    fn write_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Writer>, AssetWriterError>> {
+        Box::pin(async move { self.entry_writer(&get_meta_path(path)) })
+    }
+
+    fn remove<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        Box::pin(async move { Ok(self.archive.lock().remove(&Self::key(path)?)?) })
+    }
+
+    fn remove_meta<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        Box::pin(async move {
+            let key = Self::key(&get_meta_path(path))?;
+            Ok(self.archive.lock().remove(&key)?)
+        })
+    }
+
+    fn rename<'a>(
+        &'a self,
+        old_path: &'a Path,
+        new_path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        Box::pin(async move {
+            let (old_key, new_key) = (Self::key(old_path)?, Self::key(new_path)?);
+            Ok(self.archive.lock().rename(&old_key, new_key)?)
+        })
+    }
+
+    fn rename_meta<'a>(
+        &'a self,
+        old_path: &'a Path,
+        new_path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        Box::pin(async move {
+            let old_key = Self::key(&get_meta_path(old_path))?;
+            let new_key = Self::key(&get_meta_path(new_path))?;
+            Ok(self.archive.lock().rename(&old_key, new_key)?)
+        })
+    }
+
+    fn remove_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        Box::pin(async move {
+            let key = Self::directory_key(path)?;
+            Ok(self.archive.lock().remove_directory(&key)?)
+        })
+    }
+
+    fn remove_empty_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        // Folders only exist through the entries they contain.
+        Box::pin(async move { Ok(()) })
+    }
+
+    fn remove_assets_in_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        Box::pin(async move {
+            let key = Self::directory_key(path)?;
+            Ok(self.archive.lock().remove_directory(&key)?)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::{future::block_on, AsyncReadExt, StreamExt};
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("bevy_asset_{}_{name}", std::process::id()))
+    }
+
+    fn read(reader: &ArchiveAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        block_on(async {
+            let mut reader = reader.read(Path::new(path)).await?;
+            let mut text = String::new();
+            reader.read_to_string(&mut text).await?;
+            Ok(text)
+        })
+    }
+
+    #[test]
+    fn reads_assets_meta_and_directories() {
+        let path = temp_path("base.pak");
+        let mut builder = ArchiveBuilder::new();
+        builder
+            .add_bytes("a.txt", "a")
+            .unwrap()
+            .add_bytes("a.txt.meta", "a meta")
+            .unwrap()
+            .add_bytes("nested/deeper/b.txt", "b")
+            .unwrap();
+        builder.write(&path).unwrap();
+
+        let reader = ArchiveAssetReader::open(&path).unwrap();
+        assert_eq!(read(&reader, "a.txt").unwrap(), "a");
+        assert_eq!(read(&reader, "./nested/deeper/b.txt").unwrap(), "b");
+        assert!(matches!(
+            read(&reader, "missing.txt"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+
+        let meta = block_on(reader.read_meta_bytes(Path::new("a.txt"))).unwrap();
+        assert_eq!(meta, b"a meta");
+
+        let root: Vec<PathBuf> = block_on(
+            block_on(reader.read_directory(Path::new("")))
+                .unwrap()
+                .collect(),
+        );
+        assert_eq!(root, [PathBuf::from("a.txt"), PathBuf::from("nested")]);
+        let nested: Vec<PathBuf> = block_on(
+            block_on(reader.read_directory(Path::new("nested")))
+                .unwrap()
+                .collect(),
+        );
+        assert_eq!(nested, [PathBuf::from("nested/deeper")]);
+        assert!(block_on(reader.is_directory(Path::new("nested/deeper"))).unwrap());
+        assert!(!block_on(reader.is_directory(Path::new("a.txt"))).unwrap());
+
+        drop(reader);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn later_archives_override_earlier_ones() {
+        let base = temp_path("layered_base.pak");
+        let patch = temp_path("layered_patch.pak");
+        let mut builder = ArchiveBuilder::new();
+        builder
+            .add_bytes("a.txt", "a")
+            .unwrap()
+            .add_bytes("b.txt", "b")
+            .unwrap();
+        builder.write(&base).unwrap();
+        let mut builder = ArchiveBuilder::new();
+        builder
+            .add_bytes("b.txt", "patched b")
+            .unwrap()
+            .add_bytes("c.txt", "c")
+            .unwrap();
+        builder.write(&patch).unwrap();
+
+        let reader = ArchiveAssetReader::open_layered([&base, &patch]).unwrap();
+        assert_eq!(read(&reader, "a.txt").unwrap(), "a");
+        assert_eq!(read(&reader, "b.txt").unwrap(), "patched b");
+        assert_eq!(read(&reader, "c.txt").unwrap(), "c");
+
+        drop(reader);
+        fs::remove_file(base).unwrap();
+        fs::remove_file(patch).unwrap();
+    }
+
+    #[test]
+    fn writer_appends_removes_and_renames_entries() {
+        let path = temp_path("written.pak");
+        let _ = fs::remove_file(&path);
+        let writer = ArchiveAssetWriter::open(&path).unwrap();
+        block_on(async {
+            writer.write_bytes(Path::new("a.txt"), b"a").await.unwrap();
+            writer
+                .write_meta_bytes(Path::new("a.txt"), b"a meta")
+                .await
+                .unwrap();
+            writer
+                .write_bytes(Path::new("nested/b.txt"), b"b")
+                .await
+                .unwrap();
+            writer
+                .write_bytes(Path::new("nested/c.txt"), b"c")
+                .await
+                .unwrap();
+            writer
+                .write_bytes(Path::new("a.txt"), b"new a")
+                .await
+                .unwrap();
+            writer
+                .rename(Path::new("nested/b.txt"), Path::new("b.txt"))
+                .await
+                .unwrap();
+            writer.remove_directory(Path::new("nested")).await.unwrap();
+        });
+        // The index is only written once the writer is finished.
+        assert!(ArchiveAssetReader::open(&path).is_err());
+        writer.finish().unwrap();
+        let reader = ArchiveAssetReader::open(&path).unwrap();
+        assert_eq!(read(&reader, "b.txt").unwrap(), "b");
+        drop(reader);
+        drop(writer);
+
+        let reader = ArchiveAssetReader::open(&path).unwrap();
+        assert_eq!(read(&reader, "a.txt").unwrap(), "new a");
+        assert_eq!(read(&reader, "b.txt").unwrap(), "b");
+        assert!(read(&reader, "nested/c.txt").is_err());
+        let meta = block_on(reader.read_meta_bytes(Path::new("a.txt"))).unwrap();
+        assert_eq!(meta, b"a meta");
+        drop(reader);
+
+        // Reopening keeps the entries, and compacting drops the overwritten bytes.
+        let writer = ArchiveAssetWriter::open(&path).unwrap();
+        let len = fs::metadata(&path).unwrap().len();
+        writer.compact().unwrap();
+        assert!(fs::metadata(&path).unwrap().len() < len);
+        block_on(writer.write_bytes(Path::new("d.txt"), b"d")).unwrap();
+        drop(writer);
+
+        let reader = ArchiveAssetReader::open(&path).unwrap();
+        assert_eq!(read(&reader, "a.txt").unwrap(), "new a");
+        assert_eq!(read(&reader, "d.txt").unwrap(), "d");
+
+        drop(reader);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rejects_invalid_archives_and_paths() {
+        let path = temp_path("invalid.pak");
+        fs::write(&path, b"definitely not an archive").unwrap();
+        assert!(matches!(
+            ArchiveAssetReader::open(&path),
+            Err(ArchiveError::InvalidFormat(_))
+        ));
+        fs::remove_file(path).unwrap();
+
+        let mut builder = ArchiveBuilder::new();
+        assert!(matches!(
+            builder.add_bytes("../escape.txt", "nope"),
+            Err(ArchiveError::InvalidPath(_))
+        ));
+    }
+}
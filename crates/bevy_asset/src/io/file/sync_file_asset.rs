@@ -80,12 +80,20 @@ impl AssetReader for FileAssetReader {
         &'a self,
         path: &'a Path,
     ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move { Ok(self.read_with_byte_len(path).await?.0) })
+    }
+
+    fn read_with_byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(Box<Reader<'a>>, Option<u64>), AssetReaderError>> {
         Box::pin(async move {
             let full_path = self.root_path.join(path);
             match File::open(&full_path) {
                 Ok(file) => {
+                    let byte_len = file.metadata().ok().map(|metadata| metadata.len());
                     let reader: Box<Reader> = Box::new(FileReader(file));
-                    Ok(reader)
+                    Ok((reader, byte_len))
                 }
                 Err(e) => {
                     if e.kind() == std::io::ErrorKind::NotFound {
@@ -169,6 +177,19 @@ impl AssetReader for FileAssetReader {
             Ok(metadata.file_type().is_dir())
         })
     }
+
+    fn byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Option<u64>, AssetReaderError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            let metadata = full_path
+                .metadata()
+                .map_err(|_e| AssetReaderError::NotFound(full_path.clone()))?;
+            Ok(Some(metadata.len()))
+        })
+    }
 }
 
 impl AssetWriter for FileAssetWriter {
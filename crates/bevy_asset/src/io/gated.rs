@@ -62,6 +62,13 @@ impl<R: AssetReader> AssetReader for GatedReader<R> {
         &'a self,
         path: &'a Path,
     ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move { Ok(self.read_with_byte_len(path).await?.0) })
+    }
+
+    fn read_with_byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(Box<Reader<'a>>, Option<u64>), AssetReaderError>> {
         let receiver = {
             let mut gates = self.gates.write();
             let gates = gates
@@ -71,7 +78,7 @@ impl<R: AssetReader> AssetReader for GatedReader<R> {
         };
         Box::pin(async move {
             receiver.recv().unwrap();
-            let result = self.reader.read(path).await?;
+            let result = self.reader.read_with_byte_len(path).await?;
             Ok(result)
         })
     }
@@ -96,4 +103,11 @@ impl<R: AssetReader> AssetReader for GatedReader<R> {
     ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
         self.reader.is_directory(path)
     }
+
+    fn byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Option<u64>, AssetReaderError>> {
+        self.reader.byte_len(path)
+    }
 }
@@ -0,0 +1,411 @@
+use crate::io::{
+    get_meta_path, AssetReader, AssetReaderError, AssetSource, AssetSourceBuilders,
+    EmptyPathStream, PathStream, Reader, RetryPolicy, VecReader,
+};
+use bevy_log::{error, warn};
+use bevy_utils::BoxedFuture;
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Reader implementation for loading assets from HTTP(S) URLs on native platforms.
+///
+/// The reader is registered for a URL scheme, which is also used as the asset source id:
+/// with readers registered for the `https` and `http` sources (which [`AssetPlugin`] does
+/// by default when the `http_source` feature is enabled), loading
+/// `https://cdn.example.com/level3.glb` requests that exact URL.
+///
+/// Responses carrying an `ETag` header are cached on disk. Subsequent loads of the same URL
+/// send the cached `ETag` in an `If-None-Match` header and use the cached bytes if the server
+/// answers `304 Not Modified`. If the server can't be reached at all, cached bytes are used
+/// as well, so that previously downloaded content keeps working offline.
+///
+/// Meta files are requested at the asset URL with `.meta` appended. A `404 Not Found` response
+/// falls back to the default meta, like a missing meta file on disk. Since every meta check
+/// costs a request, consider configuring [`AssetMetaCheck`] to only check the paths that
+/// actually have a meta file.
+///
+/// Requests that fail before a status code is received, including timeouts, are reported as
+/// [`AssetReaderError::Io`]. Timeouts use [`io::ErrorKind::TimedOut`].
+///
+/// [`AssetPlugin`]: crate::AssetPlugin
+/// [`AssetMetaCheck`]: crate::AssetMetaCheck
+#[derive(Clone)]
+pub struct HttpAssetReader {
+    scheme: String,
+    cache_path: Option<PathBuf>,
+    agent: ureq::Agent,
+}
+
+impl HttpAssetReader {
+    /// The default timeout of a request, from connecting to reading the full response.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Creates a new [`HttpAssetReader`] for URLs with the given `scheme` (`http` or `https`),
+    /// without a cache.
+    pub fn new(scheme: impl Into<String>) -> Self {
+        Self {
+            scheme: scheme.into(),
+            cache_path: None,
+            agent: Self::agent(Self::DEFAULT_TIMEOUT),
+        }
+    }
+
+    /// Caches responses with an `ETag` in the folder at `path`, which is created if needed.
+    pub fn with_cache(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cache_path = Some(path.into());
+        self
+    }
+
+    /// Sets the timeout of a request, from connecting to reading the full response.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.agent = Self::agent(timeout);
+        self
+    }
+
+    /// Returns the default folder used to cache downloaded assets.
+    pub fn default_cache_path() -> PathBuf {
+        std::env::temp_dir().join("bevy_asset_http_cache")
+    }
+
+    fn agent(timeout: Duration) -> ureq::Agent {
+        ureq::AgentBuilder::new().timeout(timeout).build()
+    }
+
+    fn url(&self, path: &Path) -> String {
+        let path = path.to_string_lossy().replace('\\', "/");
+        format!("{}://{}", self.scheme, path)
+    }
+
+    /// Downloads the asset at `path`, returning a reader over its bytes and their length.
+    async fn fetch_bytes<'a>(
+        &self,
+        path: PathBuf,
+    ) -> Result<(Box<Reader<'a>>, u64), AssetReaderError> {
+        let url = self.url(&path);
+        let cache = self
+            .cache_path
+            .as_ref()
+            .map(|cache_path| CacheEntry::new(cache_path, &url));
+        let agent = self.agent.clone();
+        let bytes = blocking::unblock(move || fetch(&agent, &url, path, cache.as_ref())).await?;
+        let byte_len = bytes.len() as u64;
+        Ok((Box::new(VecReader::new(bytes)), byte_len))
+    }
+}
+
+/// Registers [`HttpAssetReader`] sources for the `http` and `https` schemes, unless sources with
+/// these ids have already been registered. Loads from these sources are retried with the default
+/// [`RetryPolicy`].
+pub(crate) fn register_http_sources(sources: &mut AssetSourceBuilders) {
+    for scheme in ["http", "https"] {
+        if sources.get_mut(scheme).is_some() {
+            continue;
+        }
+        let reader = HttpAssetReader::new(scheme).with_cache(HttpAssetReader::default_cache_path());
+        let processed_reader = reader.clone();
+        // Remote assets are never processed, so they are also read as is in processed mode.
+        sources.insert(
+            scheme,
+            AssetSource::build()
+                .with_reader(move || Box::new(reader.clone()))
+                .with_processed_reader(move || Box::new(processed_reader.clone()))
+                .with_retry_policy(RetryPolicy::default()),
+        );
+    }
+}
+
+/// The files caching the response for a single URL.
+struct CacheEntry {
+    body: PathBuf,
+    etag: PathBuf,
+}
+
+impl CacheEntry {
+    fn new(cache_path: &Path, url: &str) -> Self {
+        let key = blake3::hash(url.as_bytes()).to_hex();
+        Self {
+            body: cache_path.join(format!("{key}.body")),
+            etag: cache_path.join(format!("{key}.etag")),
+        }
+    }
+
+    fn etag(&self) -> Option<String> {
+        if !self.body.exists() {
+            return None;
+        }
+        fs::read_to_string(&self.etag).ok()
+    }
+
+    fn read(&self) -> io::Result<Vec<u8>> {
+        fs::read(&self.body)
+    }
+
+    fn write(&self, etag: &str, bytes: &[u8]) -> io::Result<()> {
+        if let Some(parent) = self.body.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Write the body first and rename it into place, so that a crash never leaves an
+        // ETag pointing to a partially written body.
+        let temp = self.body.with_extension("body.tmp");
+        fs::write(&temp, bytes)?;
+        fs::rename(&temp, &self.body)?;
+        fs::write(&self.etag, etag)
+    }
+}
+
+/// Performs a blocking request for `url`. `path` is only used for error reporting.
+fn fetch(
+    agent: &ureq::Agent,
+    url: &str,
+    path: PathBuf,
+    cache: Option<&CacheEntry>,
+) -> Result<Vec<u8>, AssetReaderError> {
+    let cached_etag = cache.and_then(CacheEntry::etag);
+    let mut request = agent.get(url);
+    if let Some(etag) = &cached_etag {
+        request = request.set("If-None-Match", etag);
+    }
+
+    let response = match request.call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(404, _)) => return Err(AssetReaderError::NotFound(path)),
+        Err(ureq::Error::Status(status, _)) => return Err(AssetReaderError::HttpError(status)),
+        Err(ureq::Error::Transport(transport)) => {
+            let error = transport_error(transport);
+            return match cache {
+                Some(cache) if cached_etag.is_some() => {
+                    warn!("Failed to request {url}, using the cached response instead: {error}");
+                    Ok(cache.read()?)
+                }
+                _ => Err(error.into()),
+            };
+        }
+    };
+
+    if response.status() == 304 {
+        if let Some(cache) = cache {
+            return Ok(cache.read()?);
+        }
+    }
+    if response.status() != 200 {
+        return Err(AssetReaderError::HttpError(response.status()));
+    }
+
+    let etag = response.header("ETag").map(ToString::to_string);
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .read_to_end(&mut bytes)
+        .map_err(timeout_error)?;
+
+    if let (Some(cache), Some(etag)) = (cache, etag) {
+        if let Err(err) = cache.write(&etag, &bytes) {
+            warn!("Failed to cache the response of {url}: {err}");
+        }
+    }
+    Ok(bytes)
+}
+
+/// Converts a ureq transport error into an [`io::Error`], keeping its kind if it wraps one.
+fn transport_error(transport: ureq::Transport) -> io::Error {
+    let message = transport.to_string();
+    let kind = std::error::Error::source(&transport)
+        .and_then(|source| source.downcast_ref::<io::Error>())
+        .map(io::Error::kind);
+    match kind {
+        Some(kind) => timeout_error(io::Error::new(kind, message)),
+        None => io::Error::other(message),
+    }
+}
+
+/// Reports timed out socket operations consistently across platforms.
+fn timeout_error(error: io::Error) -> io::Error {
+    if error.kind() == io::ErrorKind::WouldBlock {
+        io::Error::new(io::ErrorKind::TimedOut, error.to_string())
+    } else {
+        error
+    }
+}
+
+impl AssetReader for HttpAssetReader {
+    fn read<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move { Ok(self.fetch_bytes(path.to_owned()).await?.0) })
+    }
+
+    fn read_with_byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(Box<Reader<'a>>, Option<u64>), AssetReaderError>> {
+        Box::pin(async move {
+            let (reader, byte_len) = self.fetch_bytes(path.to_owned()).await?;
+            Ok((reader, Some(byte_len)))
+        })
+    }
+
+    fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move { Ok(self.fetch_bytes(get_meta_path(path)).await?.0) })
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<PathStream>, AssetReaderError>> {
+        let stream: Box<PathStream> = Box::new(EmptyPathStream);
+        error!("Reading directories is not supported with the HttpAssetReader");
+        Box::pin(async move { Ok(stream) })
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
+        error!("Reading directories is not supported with the HttpAssetReader");
+        Box::pin(async move { Ok(false) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::{future::block_on, AsyncReadExt};
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::{TcpListener, TcpStream},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
+
+    /// A minimal stand-in for a CDN, serving a fixed set of routes on a local port.
+    struct TestServer {
+        address: String,
+        not_modified: Arc<AtomicUsize>,
+    }
+
+    impl TestServer {
+        fn start() -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let address = listener.local_addr().unwrap().to_string();
+            let not_modified = Arc::new(AtomicUsize::new(0));
+            let counter = not_modified.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let counter = counter.clone();
+                    thread::spawn(move || Self::respond(stream.unwrap(), &counter));
+                }
+            });
+            Self {
+                address,
+                not_modified,
+            }
+        }
+
+        fn respond(mut stream: TcpStream, not_modified: &AtomicUsize) {
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let route = request_line
+                .split(' ')
+                .nth(1)
+                .unwrap_or_default()
+                .to_string();
+            let mut if_none_match = None;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':') {
+                    if name.eq_ignore_ascii_case("if-none-match") {
+                        if_none_match = Some(value.trim().to_string());
+                    }
+                }
+            }
+
+            let (status, headers, body) = match route.as_str() {
+                "/text.txt" if if_none_match.as_deref() == Some("\"v1\"") => {
+                    not_modified.fetch_add(1, Ordering::SeqCst);
+                    ("304 Not Modified", "ETag: \"v1\"\r\n", "")
+                }
+                "/text.txt" => ("200 OK", "ETag: \"v1\"\r\n", "hello"),
+                "/broken.txt" => ("500 Internal Server Error", "", ""),
+                "/slow.txt" => {
+                    thread::sleep(Duration::from_secs(2));
+                    ("200 OK", "", "too late")
+                }
+                _ => ("404 Not Found", "", ""),
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    }
+
+    fn read_to_string(reader: &HttpAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        block_on(async {
+            let mut reader = reader.read(Path::new(path)).await?;
+            let mut text = String::new();
+            reader.read_to_string(&mut text).await?;
+            Ok(text)
+        })
+    }
+
+    #[test]
+    fn reads_and_caches_by_etag() {
+        let server = TestServer::start();
+        let cache = std::env::temp_dir().join(format!(
+            "bevy_asset_http_test_{}_{}",
+            std::process::id(),
+            server.address.replace([':', '.'], "_")
+        ));
+        let reader = HttpAssetReader::new("http").with_cache(&cache);
+        let path = format!("{}/text.txt", server.address);
+
+        assert_eq!(read_to_string(&reader, &path).unwrap(), "hello");
+        assert_eq!(server.not_modified.load(Ordering::SeqCst), 0);
+        assert_eq!(read_to_string(&reader, &path).unwrap(), "hello");
+        assert_eq!(server.not_modified.load(Ordering::SeqCst), 1);
+
+        let _ = fs::remove_dir_all(cache);
+    }
+
+    #[test]
+    fn reports_http_errors() {
+        let server = TestServer::start();
+        let reader = HttpAssetReader::new("http");
+
+        let path = format!("{}/text.txt", server.address);
+        let meta = block_on(reader.read_meta(Path::new(&path)));
+        assert!(matches!(meta, Err(AssetReaderError::NotFound(_))));
+
+        let broken = read_to_string(&reader, &format!("{}/broken.txt", server.address));
+        assert!(matches!(broken, Err(AssetReaderError::HttpError(500))));
+    }
+
+    #[test]
+    fn reports_timeouts() {
+        let server = TestServer::start();
+        let reader = HttpAssetReader::new("http").with_timeout(Duration::from_millis(200));
+
+        let slow = read_to_string(&reader, &format!("{}/slow.txt", server.address));
+        let Err(AssetReaderError::Io(error)) = slow else {
+            panic!("expected an I/O error, got {slow:?}");
+        };
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    }
+}
@@ -1,6 +1,8 @@
-use crate::io::{AssetReader, AssetReaderError, PathStream, Reader};
+use crate::io::{
+    AssetReader, AssetReaderError, AssetWriter, AssetWriterError, PathStream, Reader, Writer,
+};
 use bevy_utils::{BoxedFuture, HashMap};
-use futures_io::AsyncRead;
+use futures_io::{AsyncRead, AsyncWrite};
 use futures_lite::{ready, Stream};
 use parking_lot::RwLock;
 use std::{
@@ -19,7 +21,8 @@ struct DirInternal {
 }
 
 /// A clone-able (internally Arc-ed) / thread-safe "in memory" filesystem.
-/// This is built for [`MemoryAssetReader`] and is primarily intended for unit tests.
+/// This is built for [`MemoryAssetReader`] and [`MemoryAssetWriter`] and is primarily intended
+/// for unit tests.
 #[derive(Default, Clone, Debug)]
 pub struct Dir(Arc<RwLock<DirInternal>>);
 
@@ -68,6 +71,44 @@ impl Dir {
         );
     }
 
+    /// Removes the asset at `path`, returning its data if it existed.
+    pub fn remove_asset(&self, path: &Path) -> Option<Data> {
+        let dir = self.get_dir(path.parent().unwrap_or(Path::new("")))?;
+        let key = path.file_name()?.to_string_lossy();
+        let removed = dir.0.write().assets.remove(key.as_ref());
+        removed
+    }
+
+    /// Removes the metadata of the asset at `path`, returning its data if it existed.
+    pub fn remove_metadata(&self, path: &Path) -> Option<Data> {
+        let dir = self.get_dir(path.parent().unwrap_or(Path::new("")))?;
+        let key = path.file_name()?.to_string_lossy();
+        let removed = dir.0.write().metadata.remove(key.as_ref());
+        removed
+    }
+
+    /// Removes the directory at `path` and everything in it, returning it if it existed.
+    pub fn remove_dir(&self, path: &Path) -> Option<Dir> {
+        let dir = self.get_dir(path.parent().unwrap_or(Path::new("")))?;
+        let key = path.file_name()?.to_string_lossy();
+        let removed = dir.0.write().dirs.remove(key.as_ref());
+        removed
+    }
+
+    /// Removes every asset, metadata and directory in this directory.
+    pub fn clear(&self) {
+        let mut dir = self.0.write();
+        dir.assets.clear();
+        dir.metadata.clear();
+        dir.dirs.clear();
+    }
+
+    /// Returns `true` if this directory contains no assets, metadata or directories.
+    pub fn is_empty(&self) -> bool {
+        let dir = self.0.read();
+        dir.assets.is_empty() && dir.metadata.is_empty() && dir.dirs.is_empty()
+    }
+
     pub fn get_or_insert_dir(&self, path: &Path) -> Dir {
         let mut dir = self.clone();
         let mut full_path = PathBuf::new();
@@ -165,6 +206,13 @@ pub struct MemoryAssetReader {
     pub root: Dir,
 }
 
+/// In-memory [`AssetWriter`] implementation, writing to the same kind of [`Dir`] as
+/// [`MemoryAssetReader`]. This is primarily intended for unit tests.
+#[derive(Default, Clone)]
+pub struct MemoryAssetWriter {
+    pub root: Dir,
+}
+
 /// Asset data stored in a [`Dir`].
 #[derive(Clone, Debug)]
 pub struct Data {
@@ -183,7 +231,8 @@ impl Data {
     fn path(&self) -> &Path {
         &self.path
     }
-    fn value(&self) -> &[u8] {
+    /// Returns the bytes of the data.
+    pub fn value(&self) -> &[u8] {
         match &self.value {
             Value::Vec(vec) => vec,
             Value::Static(value) => value,
@@ -236,15 +285,23 @@ impl AssetReader for MemoryAssetReader {
         &'a self,
         path: &'a Path,
     ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move { Ok(self.read_with_byte_len(path).await?.0) })
+    }
+
+    fn read_with_byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(Box<Reader<'a>>, Option<u64>), AssetReaderError>> {
         Box::pin(async move {
             self.root
                 .get_asset(path)
                 .map(|data| {
+                    let byte_len = data.value().len() as u64;
                     let reader: Box<Reader> = Box::new(DataReader {
                         data,
                         bytes_read: 0,
                     });
-                    reader
+                    (reader, Some(byte_len))
                 })
                 .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))
         })
@@ -289,6 +346,190 @@ impl AssetReader for MemoryAssetReader {
     ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
         Box::pin(async move { Ok(self.root.get_dir(path).is_some()) })
     }
+
+    fn byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Option<u64>, AssetReaderError>> {
+        Box::pin(async move {
+            self.root
+                .get_asset(path)
+                .map(|data| Some(data.value().len() as u64))
+                .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))
+        })
+    }
+}
+
+/// Buffers the bytes written to an asset, and stores them in a [`Dir`] when flushed or closed.
+struct DataWriter {
+    root: Dir,
+    path: PathBuf,
+    bytes: Vec<u8>,
+    is_meta: bool,
+}
+
+impl DataWriter {
+    fn commit(&self) {
+        if self.is_meta {
+            self.root.insert_meta(&self.path, self.bytes.clone());
+        } else {
+            self.root.insert_asset(&self.path, self.bytes.clone());
+        }
+    }
+}
+
+impl AsyncWrite for DataWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> Poll<futures_io::Result<usize>> {
+        self.get_mut().bytes.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> Poll<futures_io::Result<()>> {
+        self.commit();
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> Poll<futures_io::Result<()>> {
+        self.commit();
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Drop for DataWriter {
+    fn drop(&mut self) {
+        self.commit();
+    }
+}
+
+impl MemoryAssetWriter {
+    fn writer(&self, path: &Path, is_meta: bool) -> Box<Writer> {
+        let writer = DataWriter {
+            root: self.root.clone(),
+            path: path.to_owned(),
+            bytes: Vec::new(),
+            is_meta,
+        };
+        // Creates the asset right away, like creating a file would.
+        writer.commit();
+        Box::new(writer)
+    }
+}
+
+fn not_found(path: &Path) -> AssetWriterError {
+    std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("{} does not exist", path.display()),
+    )
+    .into()
+}
+
+impl AssetWriter for MemoryAssetWriter {
+    fn write<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Writer>, AssetWriterError>> {
+        Box::pin(async move { Ok(self.writer(path, false)) })
+    }
+
+    fn write_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Writer>, AssetWriterError>> {
+        Box::pin(async move { Ok(self.writer(path, true)) })
+    }
+
+    fn remove<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        Box::pin(async move {
+            self.root
+                .remove_asset(path)
+                .ok_or_else(|| not_found(path))?;
+            Ok(())
+        })
+    }
+
+    fn remove_meta<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        Box::pin(async move {
+            self.root
+                .remove_metadata(path)
+                .ok_or_else(|| not_found(path))?;
+            Ok(())
+        })
+    }
+
+    fn rename<'a>(
+        &'a self,
+        old_path: &'a Path,
+        new_path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        Box::pin(async move {
+            let data = self
+                .root
+                .remove_asset(old_path)
+                .ok_or_else(|| not_found(old_path))?;
+            self.root.insert_asset(new_path, data.value);
+            Ok(())
+        })
+    }
+
+    fn rename_meta<'a>(
+        &'a self,
+        old_path: &'a Path,
+        new_path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        Box::pin(async move {
+            let data = self
+                .root
+                .remove_metadata(old_path)
+                .ok_or_else(|| not_found(old_path))?;
+            self.root.insert_meta(new_path, data.value);
+            Ok(())
+        })
+    }
+
+    fn remove_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        Box::pin(async move {
+            self.root.remove_dir(path).ok_or_else(|| not_found(path))?;
+            Ok(())
+        })
+    }
+
+    fn remove_empty_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        Box::pin(async move {
+            let dir = self.root.get_dir(path).ok_or_else(|| not_found(path))?;
+            if !dir.is_empty() {
+                let error = std::io::Error::other(format!("{} is not empty", path.display()));
+                return Err(error.into());
+            }
+            self.root.remove_dir(path);
+            Ok(())
+        })
+    }
+
+    fn remove_assets_in_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetWriterError>> {
+        Box::pin(async move {
+            self.root.get_or_insert_dir(path).clear();
+            Ok(())
+        })
+    }
 }
 
 #[cfg(test)]
@@ -7,18 +7,26 @@ compile_error!(
 
 #[cfg(target_os = "android")]
 pub mod android;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod archive;
 pub mod embedded;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod file;
 pub mod gated;
+#[cfg(all(feature = "http_source", not(target_arch = "wasm32")))]
+pub mod http;
 pub mod memory;
 pub mod processor_gated;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
 
+mod mount;
+mod retry;
 mod source;
 
 pub use futures_lite::{AsyncReadExt, AsyncWriteExt};
+pub use mount::*;
+pub use retry::*;
 pub use source::*;
 
 use bevy_utils::BoxedFuture;
@@ -84,6 +92,28 @@ pub trait AssetReader: Send + Sync + 'static {
         path: &'a Path,
     ) -> BoxedFuture<'a, Result<bool, AssetReaderError>>;
 
+    /// Returns a future to load the full file data at the provided path, along with its length in
+    /// bytes if the reader knows it without extra work, for example from the metadata of the
+    /// opened file. This is used to report byte-level loading progress, see
+    /// [`AssetLoadTracker`](crate::AssetLoadTracker).
+    ///
+    /// Calls [`AssetReader::read`] and reports no length by default.
+    fn read_with_byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(Box<Reader<'a>>, Option<u64>), AssetReaderError>> {
+        Box::pin(async move { Ok((self.read(path).await?, None)) })
+    }
+
+    /// Returns the length in bytes of the asset at the provided path, if it can be determined
+    /// without reading the asset. Returns `None` by default.
+    fn byte_len<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Option<u64>, AssetReaderError>> {
+        Box::pin(async move { Ok(None) })
+    }
+
     /// Reads asset metadata bytes at the given `path` into a [`Vec<u8>`]. This is a convenience
     /// function that wraps [`AssetReader::read_meta`] by default.
     fn read_meta_bytes<'a>(
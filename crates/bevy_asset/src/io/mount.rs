@@ -0,0 +1,427 @@
+use crate::{
+    io::{
+        processor_gated::ProcessorGatedReader, AssetReader, AssetReaderError, AssetSourceEvent,
+        AssetSourceId, AssetWatcher, PathStream, Reader,
+    },
+    processor::AssetProcessorData,
+};
+use bevy_utils::{BoxedFuture, HashSet};
+use crossbeam_channel::{Receiver, Select, Sender};
+use futures_io::AsyncRead;
+use futures_lite::StreamExt;
+use std::{
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// A mount point of a virtual asset source, registered with
+/// [`AssetApp::register_asset_mount`](crate::AssetApp::register_asset_mount).
+///
+/// A virtual asset source has no storage of its own. Its paths resolve through an ordered list of
+/// mounts, where the path `path` of a mount resolves to `prefix/path` in the asset `source`. The
+/// first mount that contains a path is used, and mounts registered later take priority over mounts
+/// registered earlier. This allows mods or DLC to override individual files of the base content.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssetMount {
+    /// The asset source this mount resolves paths in.
+    pub source: AssetSourceId<'static>,
+    /// The folder in `source` that is mounted.
+    pub prefix: PathBuf,
+}
+
+impl AssetMount {
+    /// Creates a mount of the `prefix` folder of the asset `source`. An empty `prefix` mounts the
+    /// whole source.
+    pub fn new(source: impl Into<AssetSourceId<'static>>, prefix: impl Into<PathBuf>) -> Self {
+        Self {
+            source: source.into(),
+            prefix: prefix.into(),
+        }
+    }
+}
+
+struct MountedReader {
+    source: AssetSourceId<'static>,
+    prefix: PathBuf,
+    reader: Box<dyn AssetReader>,
+}
+
+impl MountedReader {
+    async fn read<'a>(
+        &'a self,
+        path: &'a Path,
+        meta: bool,
+    ) -> Result<(Box<Reader<'a>>, Option<u64>), AssetReaderError> {
+        if self.prefix.as_os_str().is_empty() {
+            return if meta {
+                Ok((self.reader.read_meta(path).await?, None))
+            } else {
+                self.reader.read_with_byte_len(path).await
+            };
+        }
+        let path: Arc<Path> = self.prefix.join(path).into();
+        // SAFETY: the path is owned by the returned `PrefixedReader`, which drops the reader
+        // borrowing it first. The `Arc` is never handed out, so the path is not moved or freed
+        // while it is borrowed.
+        let borrowed_path = unsafe { &*Arc::as_ptr(&path) };
+        let (reader, byte_len) = if meta {
+            (self.reader.read_meta(borrowed_path).await?, None)
+        } else {
+            self.reader.read_with_byte_len(borrowed_path).await?
+        };
+        let reader = PrefixedReader {
+            reader,
+            _path: path,
+        };
+        Ok((Box::new(reader), byte_len))
+    }
+
+    /// Returns `true` if the mount contains an asset at `path`, checking its byte length before
+    /// opening it.
+    async fn contains(&self, path: &Path) -> bool {
+        let path = self.prefix.join(path);
+        match self.reader.byte_len(&path).await {
+            Ok(Some(_)) => true,
+            Err(_) => false,
+            Ok(None) => self.reader.read(&path).await.is_ok(),
+        }
+    }
+}
+
+/// A reader of a path in a prefixed mount, which owns the joined path the inner reader may borrow.
+struct PrefixedReader<'a> {
+    // Declared before `_path`, so that it is dropped before the path it borrows.
+    reader: Box<Reader<'a>>,
+    _path: Arc<Path>,
+}
+
+impl AsyncRead for PrefixedReader<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<futures_io::Result<usize>> {
+        Pin::new(&mut self.reader).poll_read(cx, buf)
+    }
+}
+
+/// An [`AssetReader`] that overlays the readers of a list of [`AssetMount`]s. Paths resolve to the
+/// first mount that contains them, and directories list the entries of all mounts.
+pub struct VirtualAssetReader {
+    mounts: Vec<MountedReader>,
+}
+
+impl VirtualAssetReader {
+    /// Creates a new [`VirtualAssetReader`] from the readers of the given mounts, in priority order.
+    pub fn new(mounts: impl IntoIterator<Item = (AssetMount, Box<dyn AssetReader>)>) -> Self {
+        Self {
+            mounts: mounts
+                .into_iter()
+                .map(|(mount, reader)| MountedReader {
+                    source: mount.source,
+                    prefix: mount.prefix,
+                    reader,
+                })
+                .collect(),
+        }
+    }
+
+    /// Wraps the reader of every mount in a [`ProcessorGatedReader`] for its asset source, so
+    /// reads wait until the [`AssetProcessor`](crate::AssetProcessor) has processed the asset.
+    pub fn gate_on_processor(self, processor_data: Arc<AssetProcessorData>) -> Self {
+        let mounts = self.mounts.into_iter().map(|mount| {
+            let reader = ProcessorGatedReader::new(
+                mount.source.clone(),
+                mount.reader,
+                processor_data.clone(),
+            );
+            let mount = AssetMount::new(mount.source, mount.prefix);
+            (mount, Box::new(reader) as Box<dyn AssetReader>)
+        });
+        Self::new(mounts)
+    }
+}
+
+impl AssetReader for VirtualAssetReader {
+    fn read<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move { Ok(self.read_with_byte_len(path).await?.0) })
+    }
+
+    fn read_with_byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(Box<Reader<'a>>, Option<u64>), AssetReaderError>> {
+        Box::pin(async move {
+            for mount in &self.mounts {
+                match mount.read(path, false).await {
+                    Err(AssetReaderError::NotFound(_)) => continue,
+                    result => return result,
+                }
+            }
+            Err(AssetReaderError::NotFound(path.to_path_buf()))
+        })
+    }
+
+    fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move {
+            for mount in &self.mounts {
+                match mount.read(path, true).await {
+                    Err(AssetReaderError::NotFound(_)) => {
+                        // The meta of an overridden asset must not be used for the asset
+                        // overriding it.
+                        if mount.contains(path).await {
+                            break;
+                        }
+                    }
+                    result => return result.map(|(reader, _)| reader),
+                }
+            }
+            Err(AssetReaderError::NotFound(path.to_path_buf()))
+        })
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<PathStream>, AssetReaderError>> {
+        Box::pin(async move {
+            let mut found = false;
+            let mut seen = HashSet::new();
+            let mut paths = Vec::new();
+            for mount in &self.mounts {
+                let mut entries = match mount.reader.read_directory(&mount.prefix.join(path)).await
+                {
+                    Err(AssetReaderError::NotFound(_)) => continue,
+                    result => result?,
+                };
+                found = true;
+                while let Some(entry) = entries.next().await {
+                    let Ok(entry) = entry.strip_prefix(&mount.prefix) else {
+                        continue;
+                    };
+                    if seen.insert(entry.to_path_buf()) {
+                        paths.push(entry.to_path_buf());
+                    }
+                }
+            }
+            if !found {
+                return Err(AssetReaderError::NotFound(path.to_path_buf()));
+            }
+            let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(paths));
+            Ok(stream)
+        })
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
+        Box::pin(async move {
+            for mount in &self.mounts {
+                match mount.reader.is_directory(&mount.prefix.join(path)).await {
+                    Ok(false) | Err(AssetReaderError::NotFound(_)) => continue,
+                    result => return result,
+                }
+            }
+            Ok(false)
+        })
+    }
+
+    fn byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Option<u64>, AssetReaderError>> {
+        Box::pin(async move {
+            for mount in &self.mounts {
+                match mount.reader.byte_len(&mount.prefix.join(path)).await {
+                    Err(AssetReaderError::NotFound(_)) => continue,
+                    result => return result,
+                }
+            }
+            Err(AssetReaderError::NotFound(path.to_path_buf()))
+        })
+    }
+}
+
+/// An [`AssetWatcher`] that keeps the watchers of the mounts of a virtual asset source alive.
+pub(crate) struct VirtualAssetWatcher {
+    _watchers: Vec<Box<dyn AssetWatcher>>,
+}
+
+impl VirtualAssetWatcher {
+    pub(crate) fn new(watchers: Vec<Box<dyn AssetWatcher>>) -> Self {
+        Self {
+            _watchers: watchers,
+        }
+    }
+}
+
+impl AssetWatcher for VirtualAssetWatcher {}
+
+/// Forwards the events of the watchers of the mounts of a virtual asset source in a single
+/// background thread, with paths relative to the mount. Events outside of a mount are dropped.
+///
+/// Events for paths overridden by another mount are forwarded as well, which at worst reloads an
+/// unchanged asset.
+#[derive(Default)]
+pub(crate) struct MountEventForwarder {
+    mounts: Vec<(
+        Receiver<AssetSourceEvent>,
+        PathBuf,
+        Sender<AssetSourceEvent>,
+    )>,
+}
+
+impl MountEventForwarder {
+    /// Returns a sender for the watcher of a mount with the given `prefix`, whose events are
+    /// forwarded to `sender`.
+    pub(crate) fn add(
+        &mut self,
+        prefix: PathBuf,
+        sender: Sender<AssetSourceEvent>,
+    ) -> Sender<AssetSourceEvent> {
+        let (mount_sender, mount_receiver) = crossbeam_channel::unbounded();
+        self.mounts.push((mount_receiver, prefix, sender));
+        mount_sender
+    }
+
+    /// Starts forwarding the events of the added mounts, if there are any.
+    pub(crate) fn spawn(mut self) {
+        if self.mounts.is_empty() {
+            return;
+        }
+        std::thread::spawn(move || {
+            // A mount stops being forwarded once its watcher or the asset source is dropped.
+            while !self.mounts.is_empty() {
+                let mut select = Select::new();
+                for (receiver, _, _) in &self.mounts {
+                    select.recv(receiver);
+                }
+                let operation = select.select();
+                let index = operation.index();
+                let (receiver, prefix, sender) = &self.mounts[index];
+                let forwarded = match operation.recv(receiver) {
+                    Ok(event) => match map_mount_event(prefix, event) {
+                        Some(event) => sender.send(event).is_ok(),
+                        None => true,
+                    },
+                    Err(_) => false,
+                };
+                if !forwarded {
+                    self.mounts.remove(index);
+                }
+            }
+        });
+    }
+}
+
+fn map_mount_event(prefix: &Path, event: AssetSourceEvent) -> Option<AssetSourceEvent> {
+    let map = |path: PathBuf| path.strip_prefix(prefix).ok().map(Path::to_path_buf);
+    let event = match event {
+        AssetSourceEvent::AddedAsset(path) => AssetSourceEvent::AddedAsset(map(path)?),
+        AssetSourceEvent::ModifiedAsset(path) => AssetSourceEvent::ModifiedAsset(map(path)?),
+        AssetSourceEvent::RemovedAsset(path) => AssetSourceEvent::RemovedAsset(map(path)?),
+        AssetSourceEvent::RenamedAsset { old, new } => match (map(old), map(new)) {
+            (Some(old), Some(new)) => AssetSourceEvent::RenamedAsset { old, new },
+            (None, Some(new)) => AssetSourceEvent::AddedAsset(new),
+            (Some(old), None) => AssetSourceEvent::RemovedAsset(old),
+            (None, None) => return None,
+        },
+        AssetSourceEvent::AddedMeta(path) => AssetSourceEvent::AddedMeta(map(path)?),
+        AssetSourceEvent::ModifiedMeta(path) => AssetSourceEvent::ModifiedMeta(map(path)?),
+        AssetSourceEvent::RemovedMeta(path) => AssetSourceEvent::RemovedMeta(map(path)?),
+        AssetSourceEvent::RenamedMeta { old, new } => match (map(old), map(new)) {
+            (Some(old), Some(new)) => AssetSourceEvent::RenamedMeta { old, new },
+            (None, Some(new)) => AssetSourceEvent::AddedMeta(new),
+            (Some(old), None) => AssetSourceEvent::RemovedMeta(old),
+            (None, None) => return None,
+        },
+        AssetSourceEvent::AddedFolder(path) => AssetSourceEvent::AddedFolder(map(path)?),
+        AssetSourceEvent::RemovedFolder(path) => AssetSourceEvent::RemovedFolder(map(path)?),
+        AssetSourceEvent::RenamedFolder { old, new } => match (map(old), map(new)) {
+            (Some(old), Some(new)) => AssetSourceEvent::RenamedFolder { old, new },
+            (None, Some(new)) => AssetSourceEvent::AddedFolder(new),
+            (Some(old), None) => AssetSourceEvent::RemovedFolder(old),
+            (None, None) => return None,
+        },
+        AssetSourceEvent::RemovedUnknown { path, is_meta } => AssetSourceEvent::RemovedUnknown {
+            path: map(path)?,
+            is_meta,
+        },
+    };
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mount_events_are_relative_to_the_mount() {
+        let prefix = Path::new("mods/swords");
+        assert_eq!(
+            map_mount_event(
+                prefix,
+                AssetSourceEvent::ModifiedAsset("mods/swords/icons/sword.png".into())
+            ),
+            Some(AssetSourceEvent::ModifiedAsset("icons/sword.png".into()))
+        );
+        assert_eq!(
+            map_mount_event(prefix, AssetSourceEvent::AddedAsset("base/a.png".into())),
+            None
+        );
+        assert_eq!(
+            map_mount_event(
+                prefix,
+                AssetSourceEvent::RenamedAsset {
+                    old: "mods/swords/a.png".into(),
+                    new: "trash/a.png".into(),
+                }
+            ),
+            Some(AssetSourceEvent::RemovedAsset("a.png".into()))
+        );
+        assert_eq!(
+            map_mount_event(Path::new(""), AssetSourceEvent::RemovedMeta("a.png".into())),
+            Some(AssetSourceEvent::RemovedMeta("a.png".into()))
+        );
+    }
+
+    #[test]
+    fn mount_events_are_forwarded_by_one_forwarder() {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let mut forwarder = MountEventForwarder::default();
+        let base = forwarder.add("base".into(), sender.clone());
+        let mods = forwarder.add("mods".into(), sender);
+        forwarder.spawn();
+
+        // A dropped mount watcher doesn't stop the events of the other mounts.
+        drop(base);
+        mods.send(AssetSourceEvent::AddedAsset("mods/a.png".into()))
+            .unwrap();
+        mods.send(AssetSourceEvent::AddedAsset("base/b.png".into()))
+            .unwrap();
+        mods.send(AssetSourceEvent::RemovedAsset("mods/c.png".into()))
+            .unwrap();
+        let timeout = std::time::Duration::from_secs(5);
+        assert_eq!(
+            receiver.recv_timeout(timeout),
+            Ok(AssetSourceEvent::AddedAsset("a.png".into()))
+        );
+        assert_eq!(
+            receiver.recv_timeout(timeout),
+            Ok(AssetSourceEvent::RemovedAsset("c.png".into()))
+        );
+
+        // The forwarder stops once every mount watcher is dropped.
+        drop(mods);
+        assert!(receiver.recv_timeout(timeout).is_err());
+    }
+}
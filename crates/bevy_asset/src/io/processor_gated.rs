@@ -52,6 +52,13 @@ impl AssetReader for ProcessorGatedReader {
         &'a self,
         path: &'a Path,
     ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move { Ok(self.read_with_byte_len(path).await?.0) })
+    }
+
+    fn read_with_byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(Box<Reader<'a>>, Option<u64>), AssetReaderError>> {
         Box::pin(async move {
             let asset_path = AssetPath::from(path.to_path_buf()).with_source(self.source.clone());
             trace!("Waiting for processing to finish before reading {asset_path}");
@@ -67,10 +74,10 @@ impl AssetReader for ProcessorGatedReader {
             }
             trace!("Processing finished with {asset_path}, reading {process_result:?}",);
             let lock = self.get_transaction_lock(&asset_path).await?;
-            let asset_reader = self.reader.read(path).await?;
+            let (asset_reader, byte_len) = self.reader.read_with_byte_len(path).await?;
             let reader: Box<Reader<'a>> =
                 Box::new(TransactionLockedReader::new(asset_reader, lock));
-            Ok(reader)
+            Ok((reader, byte_len))
         })
     }
 
@@ -130,6 +137,28 @@ impl AssetReader for ProcessorGatedReader {
             Ok(result)
         })
     }
+
+    fn byte_len<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Option<u64>, AssetReaderError>> {
+        Box::pin(async move {
+            let asset_path = AssetPath::from(path.to_path_buf()).with_source(self.source.clone());
+            trace!("Waiting for processing to finish before getting the length of {asset_path}");
+            match self
+                .processor_data
+                .wait_until_processed(asset_path.clone())
+                .await
+            {
+                ProcessStatus::Processed => {}
+                ProcessStatus::Failed | ProcessStatus::NonExistent => {
+                    return Err(AssetReaderError::NotFound(path.to_owned()));
+                }
+            }
+            let _lock = self.get_transaction_lock(&asset_path).await?;
+            self.reader.byte_len(path).await
+        })
+    }
 }
 
 /// An [`AsyncRead`] impl that will hold its asset's transaction lock until [`TransactionLockedReader`] is dropped.
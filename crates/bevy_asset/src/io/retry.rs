@@ -0,0 +1,115 @@
+use crate::io::AssetReaderError;
+use bevy_utils::Duration;
+use std::io::ErrorKind;
+
+/// Configures how loads from an [`AssetSource`](crate::io::AssetSource) are retried when they
+/// fail with a transient [`AssetReaderError`], see [`AssetReaderError::is_transient`].
+///
+/// Retries are delayed with an exponential backoff: the first retry happens after
+/// [`initial_delay`](RetryPolicy::initial_delay), and every following retry waits
+/// [`backoff_factor`](RetryPolicy::backoff_factor) times longer than the previous one, up to
+/// [`max_delay`](RetryPolicy::max_delay). While a load is waiting to be retried, the asset stays in
+/// [`LoadState::Loading`](crate::LoadState::Loading), and failure events are only sent once the
+/// last retry failed.
+///
+/// Set it with [`AssetSourceBuilder::with_retry_policy`](crate::io::AssetSourceBuilder::with_retry_policy).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// The maximum number of times a failed load is retried.
+    pub max_retries: u32,
+    /// The delay before the first retry.
+    pub initial_delay: Duration,
+    /// The factor applied to the delay after every retry.
+    pub backoff_factor: f32,
+    /// The maximum delay between two retries.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay: Duration::from_millis(500),
+            backoff_factor: 2.0,
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns the delay before retrying a load that already has been retried `retries` times.
+    pub fn delay(&self, retries: u32) -> Duration {
+        let factor =
+            (self.backoff_factor.max(1.0) as f64).powi(retries.min(i32::MAX as u32) as i32);
+        let nanos = self.initial_delay.as_nanos() as f64 * factor;
+        if nanos >= self.max_delay.as_nanos() as f64 {
+            self.max_delay
+        } else {
+            Duration::from_nanos(nanos.round() as u64)
+        }
+    }
+
+    /// Returns `true` if a load that failed with `error` after being retried `retries` times
+    /// should be retried.
+    pub fn should_retry(&self, retries: u32, error: &AssetReaderError) -> bool {
+        retries < self.max_retries && error.is_transient()
+    }
+}
+
+impl AssetReaderError {
+    /// Returns `true` if this error is likely to be temporary, such as a timeout, a dropped
+    /// connection or an HTTP status like `503 Service Unavailable`, so that retrying the read may
+    /// succeed.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            AssetReaderError::NotFound(_) => false,
+            AssetReaderError::Io(error) => matches!(
+                error.kind(),
+                ErrorKind::TimedOut
+                    | ErrorKind::ConnectionRefused
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::NotConnected
+                    | ErrorKind::BrokenPipe
+                    | ErrorKind::Interrupted
+                    | ErrorKind::WouldBlock
+                    | ErrorKind::UnexpectedEof
+            ),
+            AssetReaderError::HttpError(status) => {
+                matches!(status, 408 | 425 | 429 | 500..=599)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io, path::PathBuf, sync::Arc};
+
+    #[test]
+    fn backoff_is_capped() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            initial_delay: Duration::from_millis(100),
+            backoff_factor: 2.0,
+            max_delay: Duration::from_millis(500),
+        };
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(1), Duration::from_millis(200));
+        assert_eq!(policy.delay(2), Duration::from_millis(400));
+        assert_eq!(policy.delay(3), Duration::from_millis(500));
+        assert_eq!(policy.delay(1000), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn only_transient_errors_are_retried() {
+        let policy = RetryPolicy::default();
+        let timed_out = AssetReaderError::Io(Arc::new(io::Error::from(ErrorKind::TimedOut)));
+        assert!(policy.should_retry(0, &timed_out));
+        assert!(!policy.should_retry(policy.max_retries, &timed_out));
+        assert!(policy.should_retry(0, &AssetReaderError::HttpError(503)));
+        assert!(!policy.should_retry(0, &AssetReaderError::HttpError(404)));
+        assert!(!policy.should_retry(0, &AssetReaderError::NotFound(PathBuf::new())));
+    }
+}
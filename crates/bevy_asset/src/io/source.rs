@@ -1,9 +1,11 @@
 use crate::{
     io::{
-        processor_gated::ProcessorGatedReader, AssetReader, AssetSourceEvent, AssetWatcher,
-        AssetWriter,
+        processor_gated::ProcessorGatedReader, AssetMount, AssetReader, AssetSourceEvent,
+        AssetWatcher, AssetWriter, MountEventForwarder, RetryPolicy, VirtualAssetReader,
+        VirtualAssetWatcher,
     },
     processor::AssetProcessorData,
+    server::load_queue::LoadQueue,
 };
 use bevy_ecs::system::Resource;
 use bevy_log::{error, warn};
@@ -131,6 +133,8 @@ pub struct AssetSourceBuilder {
     >,
     pub watch_warning: Option<&'static str>,
     pub processed_watch_warning: Option<&'static str>,
+    pub max_concurrent_loads: Option<usize>,
+    pub retry_policy: Option<RetryPolicy>,
 }
 
 impl AssetSourceBuilder {
@@ -155,6 +159,10 @@ impl AssetSourceBuilder {
             watcher: None,
             processed_event_receiver: None,
             processed_watcher: None,
+            load_queue: self.max_concurrent_loads.map(LoadQueue::new),
+            retry_policy: self.retry_policy.clone(),
+            mounts: Vec::new(),
+            mounted_processed_reader: None,
         };
 
         if watch {
@@ -249,6 +257,25 @@ impl AssetSourceBuilder {
         self
     }
 
+    /// Limits the number of assets loaded concurrently from this source to `limit`. Additional
+    /// loads wait until a running load completes, and are started by order of priority, see
+    /// [`AssetServer::load_with_priority`](crate::AssetServer::load_with_priority).
+    ///
+    /// By default, the number of concurrent loads is not limited.
+    pub fn with_max_concurrent_loads(mut self, limit: usize) -> Self {
+        self.max_concurrent_loads = Some(limit);
+        self
+    }
+
+    /// Retries loads from this source that fail with a transient [`AssetReaderError`](crate::io::AssetReaderError),
+    /// according to the given [`RetryPolicy`].
+    ///
+    /// By default, failed loads are not retried.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
     /// Enables a warning for the unprocessed source watcher, which will print when watching is enabled and the unprocessed source doesn't have a watcher.
     pub fn with_watch_warning(mut self, warning: &'static str) -> Self {
         self.watch_warning = Some(warning);
@@ -294,6 +321,7 @@ impl AssetSourceBuilder {
 pub struct AssetSourceBuilders {
     sources: HashMap<CowArc<'static, str>, AssetSourceBuilder>,
     default: Option<AssetSourceBuilder>,
+    mounts: HashMap<AssetSourceId<'static>, Vec<AssetMount>>,
 }
 
 impl AssetSourceBuilders {
@@ -309,6 +337,14 @@ impl AssetSourceBuilders {
         }
     }
 
+    /// Adds the `mount` to the virtual asset source with the given `id`, with a higher priority than
+    /// the mounts added before it. See [`AssetMount`] for more info.
+    ///
+    /// A virtual source replaces a regular source with the same `id`, but can mount it.
+    pub fn mount(&mut self, id: impl Into<AssetSourceId<'static>>, mount: AssetMount) {
+        self.mounts.entry(id.into()).or_default().push(mount);
+    }
+
     /// Gets a mutable builder with the given `id`, if it exists.
     pub fn get_mut<'a, 'b>(
         &'a mut self,
@@ -334,13 +370,90 @@ impl AssetSourceBuilders {
             }
         }
 
-        AssetSources {
+        let mut sources = AssetSources {
             sources,
             default: self
                 .default
                 .as_mut()
                 .and_then(|p| p.build(AssetSourceId::Default, watch, watch_processed))
                 .expect(MISSING_DEFAULT_SOURCE),
+        };
+
+        let mounts = std::mem::take(&mut self.mounts);
+        for (id, mounts) in &mounts {
+            let source = self.build_virtual_source(id.clone(), mounts, watch, watch_processed);
+            match source.id() {
+                AssetSourceId::Default => sources.default = source,
+                AssetSourceId::Name(name) => {
+                    sources.sources.insert(name, source);
+                }
+            }
+        }
+        self.mounts = mounts;
+        sources
+    }
+
+    /// Builds the virtual [`AssetSource`] with the given `id` from the builders of its `mounts`.
+    fn build_virtual_source(
+        &mut self,
+        id: AssetSourceId<'static>,
+        mounts: &[AssetMount],
+        watch: bool,
+        watch_processed: bool,
+    ) -> AssetSource {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let (processed_sender, processed_receiver) = crossbeam_channel::unbounded();
+        let mut readers = Vec::new();
+        let mut processed_readers = Some(Vec::new());
+        let mut watchers = Vec::new();
+        let mut processed_watchers = Vec::new();
+        let mut forwarder = MountEventForwarder::default();
+        // Mounts added later take priority.
+        for mount in mounts.iter().rev() {
+            let Some(builder) = self.get_mut(mount.source.clone()) else {
+                warn!(
+                    "{id} mounts the asset source {}, which does not exist",
+                    mount.source
+                );
+                continue;
+            };
+            let Some(reader) = builder.reader.as_mut() else {
+                continue;
+            };
+            readers.push((mount.clone(), reader()));
+            processed_readers = processed_readers.and_then(|mut readers| {
+                readers.push((mount.clone(), builder.processed_reader.as_mut()?()));
+                Some(readers)
+            });
+            if watch {
+                let sender = forwarder.add(mount.prefix.clone(), sender.clone());
+                watchers.extend(builder.watcher.as_mut().and_then(|w| w(sender)));
+            }
+            if watch_processed {
+                let sender = forwarder.add(mount.prefix.clone(), processed_sender.clone());
+                processed_watchers
+                    .extend(builder.processed_watcher.as_mut().and_then(|w| w(sender)));
+            }
+        }
+
+        forwarder.spawn();
+        let watcher = (!watchers.is_empty()).then(|| VirtualAssetWatcher::new(watchers));
+        let processed_watcher =
+            (!processed_watchers.is_empty()).then(|| VirtualAssetWatcher::new(processed_watchers));
+        AssetSource {
+            id,
+            reader: Box::new(VirtualAssetReader::new(readers)),
+            writer: None,
+            processed_reader: None,
+            processed_writer: None,
+            event_receiver: watcher.is_some().then_some(receiver),
+            watcher: watcher.map(|w| Box::new(w) as Box<dyn AssetWatcher>),
+            processed_event_receiver: processed_watcher.is_some().then_some(processed_receiver),
+            processed_watcher: processed_watcher.map(|w| Box::new(w) as Box<dyn AssetWatcher>),
+            load_queue: None,
+            retry_policy: None,
+            mounts: mounts.to_vec(),
+            mounted_processed_reader: processed_readers.map(VirtualAssetReader::new),
         }
     }
 
@@ -363,6 +476,11 @@ pub struct AssetSource {
     processed_watcher: Option<Box<dyn AssetWatcher>>,
     event_receiver: Option<crossbeam_channel::Receiver<AssetSourceEvent>>,
     processed_event_receiver: Option<crossbeam_channel::Receiver<AssetSourceEvent>>,
+    load_queue: Option<LoadQueue>,
+    retry_policy: Option<RetryPolicy>,
+    mounts: Vec<AssetMount>,
+    /// The processed reader of a virtual source, which is gated on the processor per mount.
+    mounted_processed_reader: Option<VirtualAssetReader>,
 }
 
 impl AssetSource {
@@ -377,6 +495,33 @@ impl AssetSource {
         self.id.clone()
     }
 
+    /// Returns the maximum number of assets loaded concurrently from this source, if it is limited.
+    /// See [`AssetSourceBuilder::with_max_concurrent_loads`].
+    #[inline]
+    pub fn max_concurrent_loads(&self) -> Option<usize> {
+        self.load_queue.as_ref().map(LoadQueue::limit)
+    }
+
+    /// Returns the queue limiting the number of concurrent loads from this source, if any.
+    #[inline]
+    pub(crate) fn load_queue(&self) -> Option<&LoadQueue> {
+        self.load_queue.as_ref()
+    }
+
+    /// Returns the [`RetryPolicy`] of loads from this source, if they are retried.
+    /// See [`AssetSourceBuilder::with_retry_policy`].
+    #[inline]
+    pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
+
+    /// Returns the mounts of this source in the order they were added, if it is a virtual source.
+    /// See [`AssetMount`].
+    #[inline]
+    pub fn mounts(&self) -> &[AssetMount] {
+        &self.mounts
+    }
+
     /// Return's this source's unprocessed [`AssetReader`].
     #[inline]
     pub fn reader(&self) -> &dyn AssetReader {
@@ -394,9 +539,13 @@ impl AssetSource {
     /// Return's this source's processed [`AssetReader`], if it exists.
     #[inline]
     pub fn processed_reader(&self) -> Result<&dyn AssetReader, MissingProcessedAssetReaderError> {
-        self.processed_reader
-            .as_deref()
-            .ok_or_else(|| MissingProcessedAssetReaderError(self.id.clone_owned()))
+        match &self.mounted_processed_reader {
+            Some(reader) => Ok(reader),
+            None => self
+                .processed_reader
+                .as_deref()
+                .ok_or_else(|| MissingProcessedAssetReaderError(self.id.clone_owned())),
+        }
     }
 
     /// Return's this source's processed [`AssetWriter`], if it exists.
@@ -504,6 +653,9 @@ impl AssetSource {
     /// This will cause processed [`AssetReader`] futures (such as [`AssetReader::read`]) to wait until
     /// the [`AssetProcessor`](crate::AssetProcessor) has finished processing the requested asset.
     pub fn gate_on_processor(&mut self, processor_data: Arc<AssetProcessorData>) {
+        if let Some(reader) = self.mounted_processed_reader.take() {
+            self.mounted_processed_reader = Some(reader.gate_on_processor(processor_data.clone()));
+        }
         if let Some(reader) = self.processed_reader.take() {
             self.processed_reader = Some(Box::new(ProcessorGatedReader::new(
                 self.id(),
@@ -566,7 +718,10 @@ impl AssetSources {
     /// This will cause processed [`AssetReader`] futures (such as [`AssetReader::read`]) to wait until
     /// the [`AssetProcessor`](crate::AssetProcessor) has finished processing the requested asset.
     pub fn gate_on_processor(&mut self, processor_data: Arc<AssetProcessorData>) {
-        for source in self.iter_processed_mut() {
+        let sources = self
+            .iter_mut()
+            .filter(|source| source.should_process() || !source.mounts.is_empty());
+        for source in sources {
             source.gate_on_processor(processor_data.clone());
         }
     }
@@ -23,7 +23,10 @@ mod id;
 mod loader;
 mod path;
 mod reflect;
+mod reflect_loader;
+mod residency;
 mod server;
+mod tracker;
 
 pub use assets::*;
 pub use bevy_asset_macros::Asset;
@@ -35,7 +38,10 @@ pub use id::*;
 pub use loader::*;
 pub use path::*;
 pub use reflect::*;
+pub use reflect_loader::*;
+pub use residency::*;
 pub use server::*;
+pub use tracker::*;
 
 pub use bevy_utils::BoxedFuture;
 
@@ -45,8 +51,10 @@ pub use ron;
 use crate::{
     io::{embedded::EmbeddedAssetRegistry, AssetSourceBuilder, AssetSourceBuilders, AssetSourceId},
     processor::{AssetProcessor, Process},
+    saver::AssetSaver,
 };
 use bevy_app::{App, First, MainScheduleOrder, Plugin, PostUpdate};
+use bevy_diagnostic::{Diagnostic, RegisterDiagnostic};
 use bevy_ecs::{
     reflect::AppTypeRegistry,
     schedule::{IntoSystemConfigs, IntoSystemSetConfigs, ScheduleLabel, SystemSet},
@@ -56,6 +64,7 @@ use bevy_ecs::{
 use bevy_log::error;
 use bevy_reflect::{FromReflect, GetTypeRegistration, Reflect, TypePath};
 use bevy_utils::HashSet;
+use serde::{Deserialize, Serialize};
 use std::{any::TypeId, sync::Arc};
 
 #[cfg(all(feature = "file_watcher", not(feature = "multi-threaded")))]
@@ -72,6 +81,7 @@ compile_error!(
 /// [`AssetMode::Unprocessed`] that enable using the asset workflow that best suits your project.
 ///
 /// [`AssetSource`]: io::AssetSource
+#[derive(Reflect)]
 pub struct AssetPlugin {
     /// The default file path to use (relative to the project root) for unprocessed assets.
     pub file_path: String,
@@ -88,7 +98,7 @@ pub struct AssetPlugin {
     pub mode: AssetMode,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Reflect)]
 pub enum AssetMode {
     /// Loads assets from their [`AssetSource`]'s default [`AssetReader`] without any "preprocessing".
     ///
@@ -158,6 +168,8 @@ impl Plugin for AssetPlugin {
                     .then_some(self.processed_file_path.as_str()),
             );
             embedded.register_source(&mut sources);
+            #[cfg(all(feature = "http_source", not(target_arch = "wasm32")))]
+            io::http::register_http_sources(&mut sources);
         }
         {
             let mut watch = cfg!(feature = "watch");
@@ -222,8 +234,16 @@ impl Plugin for AssetPlugin {
                 UpdateAssets,
                 TrackAssets.after(handle_internal_asset_events),
             )
+            .add_event::<AssetGroupLoaded>()
             .add_systems(UpdateAssets, handle_internal_asset_events)
-            .register_type::<AssetPath>();
+            .add_systems(
+                UpdateAssets,
+                update_asset_load_trackers.after(handle_internal_asset_events),
+            )
+            .register_type::<AssetPath>()
+            .register_type::<AssetPlugin>()
+            .register_type::<AssetMode>()
+            .register_type::<Option<bool>>();
 
         let mut order = app.world.resource_mut::<MainScheduleOrder>();
         order.insert_after(First, UpdateAssets);
@@ -287,6 +307,9 @@ pub trait AssetApp {
     fn register_asset_loader<L: AssetLoader>(&mut self, loader: L) -> &mut Self;
     /// Registers the given `processor` in the [`App`]'s [`AssetProcessor`].
     fn register_asset_processor<P: Process>(&mut self, processor: P) -> &mut Self;
+    /// Registers the given `saver` in the [`App`]'s [`AssetServer`], which uses it to save assets at runtime with
+    /// [`AssetServer::save`] and [`AssetServer::save_asset`].
+    fn register_asset_saver<S: AssetSaver>(&mut self, saver: S) -> &mut Self;
     /// Registers the given [`AssetSourceBuilder`] with the given `id`.
     ///
     /// Note that asset sources must be registered before adding [`AssetPlugin`] to your application,
@@ -296,8 +319,44 @@ pub trait AssetApp {
         id: impl Into<AssetSourceId<'static>>,
         source: AssetSourceBuilder,
     ) -> &mut Self;
+    /// Adds the `mount` to the virtual asset source with the given `id`, with a higher priority than
+    /// the mounts added before it. See [`AssetMount`](io::AssetMount) for more info.
+    ///
+    /// Like asset sources, mounts must be registered before adding [`AssetPlugin`] to your application.
+    fn register_asset_mount(
+        &mut self,
+        id: impl Into<AssetSourceId<'static>>,
+        mount: io::AssetMount,
+    ) -> &mut Self;
     /// Sets the default asset processor for the given `extension`.
     fn set_default_asset_processor<P: Process>(&mut self, extension: &str) -> &mut Self;
+    /// Registers a migration of the settings of the [`AssetLoader`] `L` from `from_version` to `from_version + 1`.
+    ///
+    /// `From` is the settings type of `from_version`, `To` the settings type of the next version. See
+    /// [`MetaMigrations`](meta::MetaMigrations) for more info.
+    fn register_asset_loader_settings_migration<L, From, To>(
+        &mut self,
+        from_version: u32,
+        migrate: impl Fn(From) -> To + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        L: AssetLoader,
+        From: for<'a> Deserialize<'a> + 'static,
+        To: Serialize + 'static;
+    /// Registers a migration of the settings of the [`Process`] implementation `P` from `from_version` to
+    /// `from_version + 1`.
+    ///
+    /// `From` is the settings type of `from_version`, `To` the settings type of the next version. See
+    /// [`MetaMigrations`](meta::MetaMigrations) for more info.
+    fn register_asset_processor_settings_migration<P, From, To>(
+        &mut self,
+        from_version: u32,
+        migrate: impl Fn(From) -> To + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        P: Process,
+        From: for<'a> Deserialize<'a> + 'static,
+        To: Serialize + 'static;
     /// Initializes the given loader in the [`App`]'s [`AssetServer`].
     fn init_asset_loader<L: AssetLoader + FromWorld>(&mut self) -> &mut Self;
     /// Initializes the given [`Asset`] in the [`App`] by:
@@ -313,11 +372,30 @@ pub trait AssetApp {
     ///
     /// This enables reflection code to access assets. For detailed information, see the docs on [`ReflectAsset`] and [`ReflectHandle`].
     fn register_asset_reflect<A>(&mut self) -> &mut Self
+    where
+        A: Asset + Reflect + FromReflect + GetTypeRegistration;
+    /// Registers the asset type `A` for reflection like [`AssetApp::register_asset_reflect`], and
+    /// registers a [`ReflectAssetLoader`] that loads it from RON or JSON files with the given
+    /// `extensions`, such as `["item.ron", "item.json"]`.
+    ///
+    /// The asset type must have been initialized with [`AssetApp::init_asset`] first.
+    fn register_reflect_asset_loader<A>(&mut self, extensions: &[&'static str]) -> &mut Self
     where
         A: Asset + Reflect + FromReflect + GetTypeRegistration;
     /// Preregisters a loader for the given extensions, that will block asset loads until a real loader
     /// is registered.
     fn preregister_asset_loader<L: AssetLoader>(&mut self, extensions: &[&str]) -> &mut Self;
+    /// Tracks the assets of type `A` loaded from a path in the [`AssetResidency`], which keeps them
+    /// in memory while they are unused and evicts them when its budget is exceeded.
+    ///
+    /// The asset type must have been initialized with [`AssetApp::init_asset`], and the
+    /// [`AssetResidencyPlugin`] must have been added first.
+    fn register_asset_residency<A: Asset + AssetSize>(&mut self) -> &mut Self;
+    /// Adds `asset` to the [`Assets`] of its type and sets it as the fallback asset, which
+    /// [`Assets::get_or_fallback`] returns while an asset is loading or after it failed to load.
+    ///
+    /// The asset type must have been initialized with [`AssetApp::init_asset`] first.
+    fn set_fallback_asset<A: Asset>(&mut self, asset: A) -> &mut Self;
 }
 
 impl AssetApp for App {
@@ -333,6 +411,11 @@ impl AssetApp for App {
         self
     }
 
+    fn register_asset_saver<S: AssetSaver>(&mut self, saver: S) -> &mut Self {
+        self.world.resource::<AssetServer>().register_saver(saver);
+        self
+    }
+
     fn register_asset_source(
         &mut self,
         id: impl Into<AssetSourceId<'static>>,
@@ -353,6 +436,22 @@ impl AssetApp for App {
         self
     }
 
+    fn register_asset_mount(
+        &mut self,
+        id: impl Into<AssetSourceId<'static>>,
+        mount: io::AssetMount,
+    ) -> &mut Self {
+        let id = id.into();
+        if self.world.get_resource::<AssetServer>().is_some() {
+            error!("Mounts of {} must be registered before `AssetPlugin` (typically added as part of `DefaultPlugins`)", id);
+        }
+
+        self.world
+            .get_resource_or_insert_with(AssetSourceBuilders::default)
+            .mount(id, mount);
+        self
+    }
+
     fn set_default_asset_processor<P: Process>(&mut self, extension: &str) -> &mut Self {
         if let Some(asset_processor) = self.world.get_resource::<AssetProcessor>() {
             asset_processor.set_default_processor::<P>(extension);
@@ -360,6 +459,38 @@ impl AssetApp for App {
         self
     }
 
+    fn register_asset_loader_settings_migration<L, From, To>(
+        &mut self,
+        from_version: u32,
+        migrate: impl Fn(From) -> To + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        L: AssetLoader,
+        From: for<'a> Deserialize<'a> + 'static,
+        To: Serialize + 'static,
+    {
+        self.world
+            .resource::<AssetServer>()
+            .register_loader_settings_migration::<L, From, To>(from_version, migrate);
+        self
+    }
+
+    fn register_asset_processor_settings_migration<P, From, To>(
+        &mut self,
+        from_version: u32,
+        migrate: impl Fn(From) -> To + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        P: Process,
+        From: for<'a> Deserialize<'a> + 'static,
+        To: Serialize + 'static,
+    {
+        self.world
+            .resource::<AssetServer>()
+            .register_processor_settings_migration::<P, From, To>(from_version, migrate);
+        self
+    }
+
     fn init_asset_loader<L: AssetLoader + FromWorld>(&mut self) -> &mut Self {
         let loader = L::from_world(&mut self.world);
         self.register_asset_loader(loader)
@@ -385,6 +516,7 @@ impl AssetApp for App {
             .add_event::<AssetEvent<A>>()
             .add_event::<AssetLoadFailedEvent<A>>()
             .register_type::<Handle<A>>()
+            .register_type_data::<Handle<A>, ReflectHandle>()
             .register_type::<AssetId<A>>()
             .add_systems(
                 AssetEvents,
@@ -410,12 +542,45 @@ impl AssetApp for App {
         self
     }
 
+    fn register_reflect_asset_loader<A>(&mut self, extensions: &[&'static str]) -> &mut Self
+    where
+        A: Asset + Reflect + FromReflect + GetTypeRegistration,
+    {
+        self.register_asset_reflect::<A>();
+        let type_registry = self.world.resource::<AppTypeRegistry>().0.clone();
+        self.register_asset_loader(ReflectAssetLoader::<A>::new(type_registry, extensions))
+    }
+
     fn preregister_asset_loader<L: AssetLoader>(&mut self, extensions: &[&str]) -> &mut Self {
         self.world
             .resource_mut::<AssetServer>()
             .preregister_loader::<L>(extensions);
         self
     }
+
+    fn register_asset_residency<A: Asset + AssetSize>(&mut self) -> &mut Self {
+        let Some(mut residency) = self.world.get_resource_mut::<AssetResidency>() else {
+            error!(
+                "`AssetResidencyPlugin` must be added before registering the residency of {}",
+                std::any::type_name::<A>()
+            );
+            return self;
+        };
+        for path in residency.register::<A>() {
+            self.register_diagnostic(Diagnostic::new(path).with_smoothing_factor(0.0));
+        }
+        self.add_systems(
+            AssetEvents,
+            residency::track_asset_residency::<A>.after(Assets::<A>::asset_events),
+        )
+    }
+
+    fn set_fallback_asset<A: Asset>(&mut self, asset: A) -> &mut Self {
+        let mut assets = self.world.resource_mut::<Assets<A>>();
+        let handle = assets.add(asset);
+        assets.set_fallback(handle);
+        self
+    }
 }
 
 /// A system set that holds all "track asset" operations.
@@ -441,24 +606,33 @@ mod tests {
         io::{
             gated::{GateOpener, GatedReader},
             memory::{Dir, MemoryAssetReader},
-            AssetReader, AssetReaderError, AssetSource, AssetSourceId, Reader,
+            AssetMount, AssetReader, AssetReaderError, AssetSource, AssetSourceId, Reader,
+            RetryPolicy, Writer,
         },
         loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, DependencyLoadState, LoadState,
-        RecursiveDependencyLoadState,
+        meta::{AssetAction, AssetMeta, AssetMetaDyn, ProcessedInfo},
+        saver::{AssetSaver, SavedAsset},
+        Asset, AssetApp, AssetEvent, AssetGroupLoaded, AssetId, AssetLoadError,
+        AssetLoadFailedEvent, AssetLoadTracker, AssetPath, AssetPlugin, AssetResidency,
+        AssetResidencyPlugin, AssetServer, AssetSize, Assets, DependencyLoadState,
+        DeserializeMetaError, LoadState, RecursiveDependencyLoadState, UntypedAssetLoadFailedEvent,
     };
     use bevy_app::{App, Update};
     use bevy_core::TaskPoolPlugin;
+    use bevy_diagnostic::DiagnosticsStore;
     use bevy_ecs::prelude::*;
     use bevy_ecs::{
         event::ManualEventReader,
         schedule::{LogLevel, ScheduleBuildSettings},
     };
     use bevy_log::LogPlugin;
-    use bevy_reflect::TypePath;
+    use bevy_reflect::{
+        serde::{Migration, ReflectMigrations},
+        Reflect, TypePath,
+    };
+    use bevy_tasks::TaskPriority;
     use bevy_utils::{BoxedFuture, Duration, HashMap};
-    use futures_lite::AsyncReadExt;
+    use futures_lite::{future::block_on, AsyncReadExt, AsyncWriteExt};
     use serde::{Deserialize, Serialize};
     use std::{
         path::{Path, PathBuf},
@@ -466,7 +640,7 @@ mod tests {
     };
     use thiserror::Error;
 
-    #[derive(Asset, TypePath, Debug)]
+    #[derive(Asset, TypePath, Debug, Clone)]
     pub struct CoolText {
         pub text: String,
         pub embedded: String,
@@ -643,6 +817,49 @@ mod tests {
         (app, gate_opener)
     }
 
+    /// Serializes the tests running an [`AssetProcessor`], as they share the processor's
+    /// transaction log file.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
+    static PROCESSOR_LOG_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    /// Creates an [`AssetProcessor`] processing the assets of `source` into `processed`.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
+    fn test_processor(source: Dir, processed: Dir) -> crate::processor::AssetProcessor {
+        use crate::{
+            io::{memory::MemoryAssetWriter, AssetSourceBuilders},
+            processor::AssetProcessor,
+        };
+
+        bevy_tasks::IoTaskPool::get_or_init(Default::default);
+        let (source_writer, processed_writer) = (source.clone(), processed.clone());
+        let mut builders = AssetSourceBuilders::default();
+        builders.insert(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || {
+                    Box::new(MemoryAssetReader {
+                        root: source.clone(),
+                    })
+                })
+                .with_writer(move |_| {
+                    Some(Box::new(MemoryAssetWriter {
+                        root: source_writer.clone(),
+                    }))
+                })
+                .with_processed_reader(move || {
+                    Box::new(MemoryAssetReader {
+                        root: processed.clone(),
+                    })
+                })
+                .with_processed_writer(move |_| {
+                    Some(Box::new(MemoryAssetWriter {
+                        root: processed_writer.clone(),
+                    }))
+                }),
+        );
+        AssetProcessor::new(&mut builders)
+    }
+
     pub fn run_app_until(app: &mut App, mut predicate: impl FnMut(&mut World) -> Option<()>) {
         for _ in 0..LARGE_ITERATION_COUNT {
             app.update();
@@ -1114,6 +1331,378 @@ mod tests {
         });
     }
 
+    #[test]
+    fn load_tracker() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi-threaded"))]
+        panic!("This test requires the \"multi-threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi-threaded");
+
+        let dir = Dir::default();
+
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "b.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: []
+)"#;
+        let b_path = "b.cool.ron";
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: []
+)"#;
+        let c_path = "c.cool.ron";
+        let c_ron = r#"
+(
+    text: "c",
+    dependencies: [],
+    OH NO THIS ASSET IS MALFORMED
+    embedded_dependencies: [],
+    sub_texts: []
+)"#;
+
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), b_ron);
+        dir.insert_asset_text(Path::new(c_path), c_ron);
+
+        let (mut app, gate_opener) = test_app(dir);
+        #[derive(Resource, Default)]
+        struct LoadedGroups(Vec<Entity>);
+
+        app.init_asset::<CoolText>()
+            .register_asset_loader(CoolTextLoader)
+            .init_resource::<LoadedGroups>()
+            .add_systems(
+                Update,
+                |mut events: EventReader<AssetGroupLoaded>, mut loaded: ResMut<LoadedGroups>| {
+                    loaded.0.extend(events.read().map(|event| event.entity));
+                },
+            );
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load(a_path);
+        let c: Handle<CoolText> = asset_server.load(c_path);
+        // Assets added directly are not managed by the asset server and are not counted.
+        let added = app.world.resource_mut::<Assets<CoolText>>().add(CoolText {
+            text: "added".to_string(),
+            embedded: String::new(),
+            dependencies: Vec::new(),
+            sub_texts: Vec::new(),
+        });
+        let loaded_group = app
+            .world
+            .spawn(AssetLoadTracker::from_handles([a.clone(), added]))
+            .id();
+        let failed_group = app
+            .world
+            .spawn(AssetLoadTracker::from_handles([a.clone(), c.clone(), a]))
+            .id();
+
+        app.update();
+        let progress = app
+            .world
+            .get::<AssetLoadTracker>(failed_group)
+            .unwrap()
+            .progress();
+        assert_eq!(progress.total, 2);
+        assert_eq!(progress.loaded, 0);
+        assert!(!progress.is_finished());
+
+        gate_opener.open(a_path);
+        gate_opener.open(b_path);
+        gate_opener.open(c_path);
+
+        run_app_until(&mut app, |world| {
+            let tracker = world.get::<AssetLoadTracker>(failed_group).unwrap();
+            tracker.progress().is_finished().then_some(())
+        });
+
+        let tracker = app.world.get::<AssetLoadTracker>(loaded_group).unwrap();
+        let progress = tracker.progress();
+        assert!(progress.is_loaded());
+        assert_eq!(progress.total, 1);
+        assert_eq!(progress.bytes_read, a_ron.len() as u64);
+        assert_eq!(progress.bytes_total, a_ron.len() as u64);
+        assert!(tracker.failures().is_empty());
+
+        let tracker = app.world.get::<AssetLoadTracker>(failed_group).unwrap();
+        let progress = tracker.progress();
+        assert_eq!((progress.loaded, progress.failed), (1, 1));
+        assert_eq!(progress.bytes_total, (a_ron.len() + c_ron.len()) as u64);
+        assert_eq!(tracker.failures().len(), 1);
+        assert_eq!(tracker.failures()[0].id, c.id().untyped());
+        assert!(matches!(
+            tracker.failures()[0].error,
+            AssetLoadError::AssetLoaderError { .. }
+        ));
+        assert_eq!(
+            asset_server
+                .get_load_error(&c)
+                .map(|error| error.to_string()),
+            Some(tracker.failures()[0].error.to_string())
+        );
+
+        let loaded = app.world.resource::<LoadedGroups>();
+        assert_eq!(loaded.0, vec![loaded_group]);
+    }
+
+    #[test]
+    fn asset_graph() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi-threaded"))]
+        panic!("This test requires the \"multi-threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi-threaded");
+
+        let dir = Dir::default();
+
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "b.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: []
+)"#;
+        let b_path = "b.cool.ron";
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: ["hello"]
+)"#;
+
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), b_ron);
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load(a_path);
+        gate_opener.open(a_path);
+        gate_opener.open(b_path);
+
+        run_app_until(&mut app, |_| {
+            asset_server.is_loaded_with_dependencies(&a).then_some(())
+        });
+
+        let graph = asset_server.asset_graph();
+        let b = asset_server.get_handle::<CoolText>(b_path).unwrap();
+        let hello = asset_server
+            .get_handle::<SubText>("b.cool.ron#hello")
+            .unwrap();
+        assert_eq!(graph.len(), 3);
+
+        let a_node = graph.get(&a).unwrap();
+        assert_eq!(a_node.path, Some(AssetPath::from(a_path)));
+        assert!(a_node.loader.unwrap().contains("CoolTextLoader"));
+        assert_eq!(a_node.strong_handles, 1);
+        assert_eq!(graph.dependencies(&a), vec![b.id().untyped()]);
+        assert_eq!(graph.recursive_dependencies(&a), vec![b.id().untyped()]);
+
+        assert_eq!(graph.dependants(&b), vec![a.id().untyped()]);
+        assert_eq!(graph.labeled_assets(&b), vec![hello.id().untyped()]);
+        assert!(graph.recursive_dependants(&hello).is_empty());
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph assets {"));
+        assert!(dot.contains("0 [label=\"a.cool.ron\\nLoaded, 1 handle(s)\"];"));
+        assert!(dot.contains("0 -> 1;"));
+        assert!(dot.contains("1 -> 2 [style=dashed];"));
+
+        let json = graph.to_json();
+        assert!(json.starts_with("{\"assets\":[{\"id\":0,"));
+        assert!(json.contains("\"path\":\"b.cool.ron#hello\""));
+        assert!(json.contains("\"dependencies\":[],\"dependants\":[0],\"labeled_assets\":[2]"));
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["assets"][2]["load_state"], "Loaded");
+        assert_eq!(json["assets"][2]["loader"], serde_json::Value::Null);
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
+    #[test]
+    fn process_once_reports_outcomes() {
+        use crate::processor::AssetProcessOutcome;
+
+        let _log_lock = PROCESSOR_LOG_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let source = Dir::default();
+        let a_ron = "(text: \"a\", dependencies: [], embedded_dependencies: [], sub_texts: [])";
+        source.insert_asset_text(Path::new("a.cool.ron"), a_ron);
+        source.insert_asset_text(Path::new("texts/b.cool.ron"), a_ron);
+        source.insert_asset_text(Path::new("c.cool.ron"), a_ron);
+        source.insert_meta_text(
+            Path::new("c.cool.ron"),
+            r#"(meta_format_version: "1.0", asset: Process(processor: "Missing", settings: ()))"#,
+        );
+        let processed = Dir::default();
+        let processor = test_processor(source, processed.clone());
+        processor.server().register_loader(CoolTextLoader);
+
+        let report = processor.process_once();
+        assert!(report.error().is_none());
+        assert_eq!(
+            (
+                report.processed_count(),
+                report.skipped_count(),
+                report.failed_count()
+            ),
+            (2, 0, 1)
+        );
+        assert!(matches!(
+            report.get(&AssetPath::from("c.cool.ron")),
+            Some(AssetProcessOutcome::Failed(_))
+        ));
+        assert_eq!(report.exit_code(), std::process::ExitCode::FAILURE);
+        for path in ["a.cool.ron", "texts/b.cool.ron"] {
+            let asset = processed.get_asset(Path::new(path)).unwrap();
+            assert_eq!(asset.value(), a_ron.as_bytes());
+            assert!(processed.get_metadata(Path::new(path)).is_some());
+        }
+        assert!(processed.get_asset(Path::new("c.cool.ron")).is_none());
+
+        // Unchanged assets are not processed again.
+        let report = processor.process_once();
+        assert_eq!(
+            (
+                report.processed_count(),
+                report.skipped_count(),
+                report.failed_count()
+            ),
+            (0, 2, 1)
+        );
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
+    #[test]
+    fn duplicates_load_labeled_assets_from_original() {
+        let _log_lock = PROCESSOR_LOG_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let source = Dir::default();
+        let ron =
+            "(text: \"a\", dependencies: [], embedded_dependencies: [], sub_texts: [\"hello\"])";
+        source.insert_asset_text(Path::new("a.cool.ron"), ron);
+        source.insert_asset_text(Path::new("b.cool.ron"), ron);
+        let processed = Dir::default();
+        let processor = test_processor(source, processed.clone());
+        processor.server().register_loader(CoolTextLoader);
+        processor.set_deduplicate(true);
+        assert!(processor.process_once().is_success());
+
+        // Either asset can become the original of the other.
+        let duplicate = ["a.cool.ron", "b.cool.ron"]
+            .into_iter()
+            .find(|path| {
+                processed
+                    .get_asset(Path::new(path))
+                    .is_some_and(|asset| asset.value().is_empty())
+            })
+            .unwrap();
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build().with_reader(move || {
+                Box::new(MemoryAssetReader {
+                    root: processed.clone(),
+                })
+            }),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader);
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let sub_text: Handle<SubText> = asset_server.load(format!("{duplicate}#hello"));
+        let text: Handle<CoolText> = asset_server.load_with_settings(duplicate, |_: &mut ()| {});
+        run_app_until(&mut app, |_| {
+            (asset_server.get_load_state(&sub_text) == Some(LoadState::Loaded)
+                && asset_server.get_load_state(&text) == Some(LoadState::Loaded))
+            .then_some(())
+        });
+
+        let sub_texts = app.world.resource::<Assets<SubText>>();
+        assert_eq!(sub_texts.get(&sub_text).unwrap().text, "hello");
+        let texts = app.world.resource::<Assets<CoolText>>();
+        assert_eq!(texts.get(&text).unwrap().text, "a");
+    }
+
+    #[test]
+    fn load_priorities_and_concurrency_limit() {
+        let dir = Dir::default();
+        for name in ["a", "b", "c", "d"] {
+            let ron = format!(
+                "(text: \"{name}\", dependencies: [], embedded_dependencies: [], sub_texts: [])"
+            );
+            dir.insert_asset_text(Path::new(&format!("{name}.cool.ron")), &ron);
+        }
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() }))
+                .with_max_concurrent_loads(1),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .register_asset_loader(CoolTextLoader)
+        .init_resource::<StoredEvents>()
+        .add_systems(Update, store_asset_events);
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let source = asset_server.get_source(AssetSourceId::Default).unwrap();
+        assert_eq!(source.max_concurrent_loads(), Some(1));
+        let queue = source.load_queue().unwrap();
+
+        // Occupy the only slot, so that every load waits in the queue.
+        let permit = futures_lite::future::block_on(queue.acquire(None, TaskPriority::Normal));
+        let a: Handle<CoolText> = asset_server.load_with_priority("a.cool.ron", TaskPriority::Low);
+        let b: Handle<CoolText> = asset_server.load("b.cool.ron");
+        let c: Handle<CoolText> = asset_server.load_with_priority("c.cool.ron", TaskPriority::Low);
+        run_app_until(&mut app, |_| (queue.counts() == (1, 3)).then_some(()));
+
+        // c becomes urgent while waiting.
+        asset_server.set_load_priority(&c, TaskPriority::High);
+        assert_eq!(asset_server.get_load_priority(&c), Some(TaskPriority::High));
+        drop(permit);
+
+        run_app_until(&mut app, |world| {
+            (world.resource::<StoredEvents>().0.len() == 6).then_some(())
+        });
+        let loaded: Vec<_> = app
+            .world
+            .resource::<StoredEvents>()
+            .0
+            .iter()
+            .filter_map(|event| match event {
+                AssetEvent::LoadedWithDependencies { id } => Some(*id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(loaded, vec![c.id(), b.id(), a.id()]);
+        assert_eq!(queue.counts(), (0, 0));
+    }
+
     #[test]
     fn manual_asset_management() {
         // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
@@ -1444,6 +2033,694 @@ mod tests {
         });
     }
 
+    #[test]
+    fn load_retries() {
+        let dir = Dir::default();
+        dir.insert_asset_text(
+            Path::new("a.cool.ron"),
+            "(text: \"a\", dependencies: [], embedded_dependencies: [], sub_texts: [])",
+        );
+        let unstable_reader = UnstableMemoryAssetReader::new(dir, 2);
+        let attempts = unstable_reader.attempt_counters.clone();
+
+        let mut app = App::new();
+        app.register_asset_source(
+            "unstable",
+            AssetSource::build()
+                .with_reader(move || Box::new(unstable_reader.clone()))
+                .with_retry_policy(RetryPolicy {
+                    max_retries: 3,
+                    initial_delay: Duration::from_millis(1),
+                    backoff_factor: 2.0,
+                    max_delay: Duration::from_millis(10),
+                }),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .register_asset_loader(CoolTextLoader);
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let handle: Handle<CoolText> = asset_server.load("unstable://a.cool.ron");
+        run_app_until(&mut app, |world| {
+            let state = asset_server.get_load_state(&handle).unwrap();
+            assert_ne!(state, LoadState::Failed);
+            world
+                .resource::<Assets<CoolText>>()
+                .get(&handle)
+                .map(|_| ())
+        });
+        // The load succeeded after two retries, without sending failure events.
+        assert_eq!(attempts.lock().unwrap()[Path::new("a.cool.ron")], 3);
+        let failures = app.world.resource::<Events<UntypedAssetLoadFailedEvent>>();
+        assert!(failures.is_empty());
+    }
+
+    #[derive(Asset, Reflect, Debug)]
+    struct Item {
+        name: String,
+        count: u32,
+        #[dependency]
+        icon: Handle<CoolText>,
+    }
+
+    #[test]
+    fn reflect_asset_loader() {
+        let dir = Dir::default();
+        dir.insert_asset_text(
+            Path::new("items/sword.item.ron"),
+            "(name: \"Sword\", count: 2, icon: \"a.cool.ron\")",
+        );
+        dir.insert_asset_text(
+            Path::new("items/shield.item.json"),
+            r#"{"name": "Shield", "count": 1, "icon": "../b.cool.ron"}"#,
+        );
+        dir.insert_asset_text(
+            Path::new("items/a.cool.ron"),
+            "(text: \"a\", dependencies: [], embedded_dependencies: [], sub_texts: [])",
+        );
+        dir.insert_asset_text(
+            Path::new("b.cool.ron"),
+            "(text: \"b\", dependencies: [], embedded_dependencies: [], sub_texts: [])",
+        );
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .register_asset_loader(CoolTextLoader)
+            .init_asset::<Item>()
+            .register_reflect_asset_loader::<Item>(&["item.ron", "item.json"]);
+        for path in [
+            "items/sword.item.ron",
+            "items/shield.item.json",
+            "items/a.cool.ron",
+            "b.cool.ron",
+        ] {
+            gate_opener.open(path);
+        }
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let sword: Handle<Item> = asset_server.load("items/sword.item.ron");
+        let shield: Handle<Item> = asset_server.load("items/shield.item.json");
+        run_app_until(&mut app, |_| {
+            let loaded = |handle: &Handle<Item>| {
+                asset_server.get_recursive_dependency_load_state(handle)
+                    == Some(RecursiveDependencyLoadState::Loaded)
+            };
+            (loaded(&sword) && loaded(&shield)).then_some(())
+        });
+
+        let items = app.world.resource::<Assets<Item>>();
+        let texts = app.world.resource::<Assets<CoolText>>();
+        let sword = items.get(&sword).unwrap();
+        assert_eq!((sword.name.as_str(), sword.count), ("Sword", 2));
+        assert_eq!(
+            sword.icon.path(),
+            Some(&AssetPath::from("items/a.cool.ron"))
+        );
+        assert_eq!(texts.get(&sword.icon).unwrap().text, "a");
+        let shield = items.get(&shield).unwrap();
+        assert_eq!((shield.name.as_str(), shield.count), ("Shield", 1));
+        assert_eq!(texts.get(&shield.icon).unwrap().text, "b");
+    }
+
+    #[test]
+    fn reflect_asset_loader_migrates_older_versions() {
+        let dir = Dir::default();
+        dir.insert_asset_text(
+            Path::new("old.item.ron"),
+            "(title: \"Sword\", icon: \"a.cool.ron\")",
+        );
+        dir.insert_asset_text(
+            Path::new("new.item.ron"),
+            "(name: \"Shield\", count: 2, icon: \"a.cool.ron\")",
+        );
+        dir.insert_asset_text(
+            Path::new("a.cool.ron"),
+            "(text: \"a\", dependencies: [], embedded_dependencies: [], sub_texts: [])",
+        );
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .register_asset_loader(CoolTextLoader)
+            .init_asset::<Item>()
+            .register_reflect_asset_loader::<Item>(&["item.ron"]);
+        gate_opener.open("new.item.ron");
+        gate_opener.open("a.cool.ron");
+
+        // Migrations registered after the loader was used are still applied.
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let new_handle: Handle<Item> = asset_server.load("new.item.ron");
+        run_app_until(&mut app, |_| {
+            (asset_server.get_recursive_dependency_load_state(&new_handle)
+                == Some(RecursiveDependencyLoadState::Loaded))
+            .then_some(())
+        });
+        app.world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register_migrations::<Item>(
+                ReflectMigrations::new().with_migration(
+                    Migration::new()
+                        .rename_field("title", "name")
+                        .add_field("count", 1u32),
+                ),
+            );
+        gate_opener.open("old.item.ron");
+
+        let handle: Handle<Item> = asset_server.load("old.item.ron");
+        run_app_until(&mut app, |_| {
+            (asset_server.get_recursive_dependency_load_state(&handle)
+                == Some(RecursiveDependencyLoadState::Loaded))
+            .then_some(())
+        });
+
+        let item = app.world.resource::<Assets<Item>>().get(&handle).unwrap();
+        assert_eq!((item.name.as_str(), item.count), ("Sword", 1));
+        assert_eq!(item.icon.path(), Some(&AssetPath::from("a.cool.ron")));
+    }
+
+    #[test]
+    fn identical_content_is_loaded_once() {
+        let dir = Dir::default();
+        let text = "(text: \"same\", dependencies: [], embedded_dependencies: [], sub_texts: [])";
+        for (path, text, duplicate_of) in [
+            ("a.cool.ron", text, None),
+            ("b.cool.ron", text, None),
+            // Deduplicated by the processor: the asset is loaded from its original.
+            ("c.cool.ron", "", Some("a.cool.ron")),
+        ] {
+            let mut meta = AssetMeta::<CoolTextLoader, ()>::new(AssetAction::Load {
+                loader: std::any::type_name::<CoolTextLoader>().to_string(),
+                settings: (),
+            });
+            meta.processed_info = Some(ProcessedInfo {
+                content_hash: Some([1; 32]),
+                duplicate_of: duplicate_of.map(AssetPath::from),
+                ..Default::default()
+            });
+            dir.insert_asset_text(Path::new(path), text);
+            dir.insert_meta(Path::new(path), AssetMetaDyn::serialize(&meta));
+        }
+        let reader_dir = dir.clone();
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build().with_reader(move || {
+                Box::new(MemoryAssetReader {
+                    root: reader_dir.clone(),
+                })
+            }),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .register_asset_loader(CoolTextLoader);
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let handles: Vec<Handle<CoolText>> = ["c.cool.ron", "a.cool.ron", "b.cool.ron"]
+            .into_iter()
+            .map(|path| asset_server.load(path))
+            .collect();
+        run_app_until(&mut app, |_| {
+            handles
+                .iter()
+                .all(|handle| asset_server.get_load_state(handle) == Some(LoadState::Loaded))
+                .then_some(())
+        });
+
+        let texts = app.world.resource::<Assets<CoolText>>();
+        assert_eq!(texts.len(), 1);
+        let shared = handles
+            .iter()
+            .filter(|handle| texts.shared_with(*handle).is_some())
+            .count();
+        assert_eq!(shared, 2);
+        for handle in &handles {
+            assert_eq!(texts.get(handle).unwrap().text, "same");
+        }
+
+        // Dropping the original keeps its value alive for the assets sharing it.
+        let (original, _) = handles
+            .iter()
+            .map(|handle| (handle.id(), texts.shared_with(handle)))
+            .find(|(_, shared_with)| shared_with.is_none())
+            .unwrap();
+        let remaining: Vec<_> = handles
+            .into_iter()
+            .filter(|handle| handle.id() != original)
+            .collect();
+        for _ in 0..5 {
+            app.update();
+        }
+        let texts = app.world.resource::<Assets<CoolText>>();
+        for handle in &remaining {
+            assert_eq!(texts.get(handle).unwrap().text, "same");
+        }
+    }
+
+    /// The current settings of [`PrefixLoader`]. Version 0 was a bare prefix string, version 1 had no `repeat`.
+    #[derive(Serialize, Deserialize, Default)]
+    struct PrefixSettings {
+        prefix: String,
+        repeat: usize,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct PrefixSettingsV1 {
+        prefix: String,
+    }
+
+    /// Loads text files as [`CoolText`] with a prefix repeated [`PrefixSettings::repeat`] times.
+    struct PrefixLoader;
+
+    impl AssetLoader for PrefixLoader {
+        type Asset = CoolText;
+        type Settings = PrefixSettings;
+        type Error = std::io::Error;
+        const SETTINGS_VERSION: u32 = 2;
+
+        fn load<'a>(
+            &'a self,
+            reader: &'a mut Reader,
+            settings: &'a PrefixSettings,
+            _load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<CoolText, std::io::Error>> {
+            Box::pin(async move {
+                let mut text = String::new();
+                reader.read_to_string(&mut text).await?;
+                Ok(CoolText {
+                    text: settings.prefix.repeat(settings.repeat) + &text,
+                    embedded: String::new(),
+                    dependencies: Vec::new(),
+                    sub_texts: Vec::new(),
+                })
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    #[test]
+    fn migrate_meta_settings() {
+        let dir = Dir::default();
+        let loader = std::any::type_name::<PrefixLoader>();
+        dir.insert_asset_text(Path::new("old.txt"), "a");
+        dir.insert_meta_text(
+            Path::new("old.txt"),
+            &format!(
+                r#"(meta_format_version: "1.0", asset: Load(loader: "{loader}", settings: "b"))"#
+            ),
+        );
+        dir.insert_asset_text(Path::new("new.txt"), "a");
+        dir.insert_meta_text(
+            Path::new("new.txt"),
+            &format!(
+                r#"(meta_format_version: "1.0", settings_version: 3, asset: Load(loader: "{loader}", settings: (prefix: "b", repeat: 2)))"#
+            ),
+        );
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .register_asset_loader(PrefixLoader)
+            .register_asset_loader_settings_migration::<PrefixLoader, String, _>(0, |prefix| {
+                PrefixSettingsV1 { prefix }
+            })
+            .register_asset_loader_settings_migration::<PrefixLoader, PrefixSettingsV1, _>(
+                1,
+                |settings| PrefixSettings {
+                    prefix: settings.prefix,
+                    repeat: 3,
+                },
+            );
+        gate_opener.open("old.txt");
+        gate_opener.open("new.txt");
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let old: Handle<CoolText> = asset_server.load("old.txt");
+        let new: Handle<CoolText> = asset_server.load("new.txt");
+        run_app_until(&mut app, |world| {
+            let text = get(world, old.id())?;
+            assert_eq!(text.text, "bbba");
+            (asset_server.get_load_state(&new) == Some(LoadState::Failed)).then_some(())
+        });
+
+        // Meta written for a newer settings version has no migration path.
+        let failures = app.world.resource::<Events<UntypedAssetLoadFailedEvent>>();
+        let mut reader = failures.get_reader();
+        let failure = reader.read(failures).next().unwrap();
+        assert!(matches!(
+            &failure.error,
+            AssetLoadError::DeserializeMeta { error, .. }
+                if matches!(**error, DeserializeMetaError::NoMigration { version: 3, current_version: 2, .. })
+        ));
+    }
+
+    struct CoolTextSaver;
+
+    impl AssetSaver for CoolTextSaver {
+        type Asset = CoolText;
+        type Settings = ();
+        type OutputLoader = CoolTextLoader;
+        type Error = std::io::Error;
+
+        fn save<'a>(
+            &'a self,
+            writer: &'a mut Writer,
+            asset: SavedAsset<'a, CoolText>,
+            _settings: &'a (),
+        ) -> BoxedFuture<'a, Result<(), std::io::Error>> {
+            Box::pin(async move {
+                let ron = CoolTextRon {
+                    text: asset.text.clone(),
+                    dependencies: Vec::new(),
+                    embedded_dependencies: Vec::new(),
+                    sub_texts: Vec::new(),
+                };
+                writer
+                    .write_all(ron::ser::to_string(&ron).unwrap().as_bytes())
+                    .await
+            })
+        }
+    }
+
+    #[test]
+    fn save_assets() {
+        let dir = std::env::temp_dir().join(format!("bevy_asset_save_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin {
+                file_path: dir.to_str().unwrap().to_string(),
+                ..Default::default()
+            },
+        ))
+        .init_asset::<CoolText>()
+        .register_asset_loader(CoolTextLoader)
+        .register_asset_saver(CoolTextSaver)
+        .init_resource::<StoredEvents>()
+        .add_systems(Update, store_asset_events);
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let text = CoolText {
+            text: "saved".to_string(),
+            embedded: String::new(),
+            dependencies: Vec::new(),
+            sub_texts: Vec::new(),
+        };
+        let handle = block_on(asset_server.save_asset("texts/a.cool.ron", text)).unwrap();
+        assert!(dir.join("texts/a.cool.ron.meta").exists());
+
+        // The saved asset is the loaded value of its path.
+        assert_eq!(asset_server.load::<CoolText>("texts/a.cool.ron"), handle);
+        run_app_until(&mut app, |world| get(world, handle.id()).map(|_| ()));
+        assert_eq!(get(&app.world, handle.id()).unwrap().text, "saved");
+
+        // Reloading the file written by the save is skipped.
+        app.update();
+        app.world.resource_mut::<StoredEvents>().0.clear();
+        asset_server.reload("texts/a.cool.ron");
+        for _ in 0..10 {
+            std::thread::sleep(Duration::from_millis(10));
+            app.update();
+        }
+        assert!(app.world.resource::<StoredEvents>().0.is_empty());
+
+        // Modified assets are saved to the path of their handle.
+        let mut texts = app.world.resource_mut::<Assets<CoolText>>();
+        texts.get_mut(&handle).unwrap().text = "edited".to_string();
+        let save = asset_server.save(&handle, &texts);
+        block_on(save).unwrap();
+        let saved = std::fs::read_to_string(dir.join("texts/a.cool.ron")).unwrap();
+        assert!(saved.contains("edited"));
+
+        // Changes made to the file after the save are reloaded.
+        std::fs::write(
+            dir.join("texts/a.cool.ron"),
+            "(text: \"external\", dependencies: [], embedded_dependencies: [], sub_texts: [])",
+        )
+        .unwrap();
+        asset_server.reload("texts/a.cool.ron");
+        run_app_until(&mut app, |world| {
+            (get(world, handle.id()).unwrap().text == "external").then_some(())
+        });
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    impl AssetSize for CoolText {
+        fn approximate_size(&self) -> usize {
+            self.text.len()
+        }
+    }
+
+    #[test]
+    fn evict_unused_assets() {
+        let dir = Dir::default();
+        for (path, text) in [
+            ("a.cool.ron", "aaaaaaaaaa"),
+            ("b.cool.ron", "bbbbbbbbbb"),
+            ("c.cool.ron", "cccccccccc"),
+        ] {
+            dir.insert_asset_text(
+                Path::new(path),
+                &format!(
+                    "(text: \"{text}\", dependencies: [], embedded_dependencies: [], sub_texts: [])"
+                ),
+            );
+        }
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+            AssetResidencyPlugin { budget: 25 },
+        ))
+        .init_asset::<CoolText>()
+        .register_asset_loader(CoolTextLoader)
+        .register_asset_residency::<CoolText>();
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let b: Handle<CoolText> = asset_server.load("b.cool.ron");
+        let c: Handle<CoolText> = asset_server.load("c.cool.ron");
+        let (a_id, b_id) = (a.id(), b.id());
+        run_app_until(&mut app, |world| {
+            (world.resource::<AssetResidency>().resident_bytes() == 30).then_some(())
+        });
+        // Used assets are never evicted, even over budget.
+        app.update();
+        assert_eq!(app.world.resource::<Assets<CoolText>>().len(), 3);
+
+        // The least recently used unused asset is evicted until the budget is met.
+        drop(a);
+        app.update();
+        let residency = app.world.resource::<AssetResidency>();
+        assert!(residency.is_evicted(a_id));
+        assert_eq!(residency.resident_bytes(), 20);
+        assert!(get(&app.world, a_id).is_none());
+        assert_eq!(
+            asset_server.get_load_state(a_id),
+            Some(LoadState::NotLoaded)
+        );
+
+        // Unused assets are cached while the budget allows it.
+        drop(b);
+        app.update();
+        app.update();
+        assert!(get(&app.world, b_id).is_some());
+        let usage = app.world.resource::<AssetResidency>().usage();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].resident_count, 2);
+        assert_eq!(usage[0].unused_bytes, 10);
+        assert_eq!(usage[0].evicted_count, 1);
+        assert_eq!(usage[0].evicted_bytes, 10);
+        let evicted_bytes = app
+            .world
+            .resource::<DiagnosticsStore>()
+            .get_measurement(&AssetResidencyPlugin::diagnostic_path::<CoolText>(
+                "evicted_bytes",
+            ))
+            .map(|measurement| measurement.value);
+        assert_eq!(evicted_bytes, Some(10.0));
+
+        // Evicted assets are reloaded with the same id on demand.
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        assert_eq!(a.id(), a_id);
+        run_app_until(&mut app, |world| get(world, a_id).map(|_| ()));
+        app.update();
+        let residency = app.world.resource::<AssetResidency>();
+        assert!(!residency.is_evicted(a_id));
+        assert!(residency.is_evicted(b_id));
+        assert_eq!(residency.resident_bytes(), 20);
+        drop(c);
+    }
+
+    #[test]
+    fn virtual_mounts() {
+        fn cool_text(text: &str, dependencies: &str) -> String {
+            format!("(text: \"{text}\", dependencies: [{dependencies}], embedded_dependencies: [], sub_texts: [])")
+        }
+        let base = Dir::default();
+        base.insert_asset_text(
+            Path::new("ui/icons/sword.cool.ron"),
+            &cool_text("sword", ""),
+        );
+        base.insert_asset_text(
+            Path::new("ui/icons/shield.cool.ron"),
+            &cool_text("shield", ""),
+        );
+        let mods = Dir::default();
+        mods.insert_asset_text(
+            Path::new("swords/icons/sword.cool.ron"),
+            &cool_text("modded sword", ""),
+        );
+        mods.insert_asset_text(
+            Path::new("swords/icons/axe.cool.ron"),
+            &cool_text("axe", "\"ui://icons/shield.cool.ron\""),
+        );
+
+        let mut app = App::new();
+        app.register_asset_source(
+            "base",
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: base.clone() })),
+        )
+        .register_asset_source(
+            "mods",
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: mods.clone() })),
+        )
+        .register_asset_mount("ui", AssetMount::new("base", "ui"))
+        .register_asset_mount("ui", AssetMount::new("mods", "swords"))
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader);
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        assert_eq!(
+            asset_server
+                .get_source(AssetSourceId::new(Some("ui")))
+                .unwrap()
+                .mounts(),
+            &[
+                AssetMount::new("base", "ui"),
+                AssetMount::new("mods", "swords")
+            ]
+        );
+        let sword: Handle<CoolText> = asset_server.load("ui://icons/sword.cool.ron");
+        let axe: Handle<CoolText> = asset_server.load("ui://icons/axe.cool.ron");
+        let folder = asset_server.load_folder("ui://icons");
+        run_app_until(&mut app, |_world| {
+            let loaded = asset_server.is_loaded_with_dependencies(&axe)
+                && asset_server.is_loaded_with_dependencies(&sword)
+                && asset_server.is_loaded_with_dependencies(&folder);
+            loaded.then_some(())
+        });
+
+        // Later mounts override files of earlier mounts.
+        assert_eq!(get(&app.world, sword.id()).unwrap().text, "modded sword");
+        // Dependencies resolve through the virtual source.
+        let axe = get(&app.world, axe.id()).unwrap();
+        let shield = get(&app.world, axe.dependencies[0].id()).unwrap();
+        assert_eq!(shield.text, "shield");
+        assert_eq!(
+            asset_server.get_path(&axe.dependencies[0]).unwrap(),
+            AssetPath::parse("ui://icons/shield.cool.ron")
+        );
+        // Folders list the files of all mounts once.
+        let folder = app.world.resource::<Assets<LoadedFolder>>().get(&folder);
+        assert_eq!(folder.unwrap().handles.len(), 3);
+    }
+
+    #[test]
+    fn fallback_assets() {
+        let dir = Dir::default();
+        let reader_dir = dir.clone();
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build().with_reader(move || {
+                Box::new(MemoryAssetReader {
+                    root: reader_dir.clone(),
+                })
+            }),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .register_asset_loader(CoolTextLoader)
+        .set_fallback_asset(CoolText {
+            text: "fallback".to_string(),
+            embedded: String::new(),
+            dependencies: Vec::new(),
+            sub_texts: Vec::new(),
+        });
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let handle: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let text = |world: &World| {
+            world
+                .resource::<Assets<CoolText>>()
+                .get_or_fallback(&handle)
+                .map(|text| text.text.clone())
+        };
+        assert_eq!(text(&app.world).as_deref(), Some("fallback"));
+        run_app_until(&mut app, |_| {
+            (asset_server.get_load_state(&handle) == Some(LoadState::Failed)).then_some(())
+        });
+        assert_eq!(text(&app.world).as_deref(), Some("fallback"));
+
+        // Once the missing asset is loaded, it replaces the fallback.
+        dir.insert_asset_text(
+            Path::new("a.cool.ron"),
+            "(text: \"a\", dependencies: [], embedded_dependencies: [], sub_texts: [])",
+        );
+        asset_server.reload("a.cool.ron");
+        run_app_until(&mut app, |world| {
+            (text(world).as_deref() == Some("a")).then_some(())
+        });
+
+        let fallback = app
+            .world
+            .resource_mut::<Assets<CoolText>>()
+            .clear_fallback();
+        assert!(fallback.is_some());
+        let missing: Handle<CoolText> = asset_server.load("b.cool.ron");
+        assert!(text(&app.world).is_some());
+        assert!(app
+            .world
+            .resource::<Assets<CoolText>>()
+            .get_or_fallback(&missing)
+            .is_none());
+    }
+
     #[test]
     fn ignore_system_ambiguities_on_assets() {
         let mut app = App::new();
@@ -29,6 +29,9 @@ pub trait AssetLoader: Send + Sync + 'static {
     type Settings: Settings + Default + Serialize + for<'a> Deserialize<'a>;
     /// The type of [error](`std::error::Error`) which could be encountered by this loader.
     type Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>;
+    /// The version of [`AssetLoader::Settings`]. Increase it whenever the settings change in a way that breaks existing
+    /// meta files, and register a migration from the previous version, see [`MetaMigrations`](crate::meta::MetaMigrations).
+    const SETTINGS_VERSION: u32 = 0;
     /// Asynchronously loads [`AssetLoader::Asset`] (and any other labeled assets) from the bytes provided by [`Reader`].
     fn load<'a>(
         &'a self,
@@ -63,6 +66,8 @@ pub trait ErasedAssetLoader: Send + Sync + 'static {
     fn deserialize_meta(&self, meta: &[u8]) -> Result<Box<dyn AssetMetaDyn>, DeserializeMetaError>;
     /// Returns the default meta value for the [`AssetLoader`] (erased as [`Box<dyn AssetMetaDyn>`]).
     fn default_meta(&self) -> Box<dyn AssetMetaDyn>;
+    /// Returns the [`AssetLoader::SETTINGS_VERSION`] of the [`AssetLoader`].
+    fn settings_version(&self) -> u32;
     /// Returns the type name of the [`AssetLoader`].
     fn type_name(&self) -> &'static str;
     /// Returns the [`TypeId`] of the [`AssetLoader`].
@@ -116,6 +121,10 @@ where
         }))
     }
 
+    fn settings_version(&self) -> u32 {
+        L::SETTINGS_VERSION
+    }
+
     fn type_name(&self) -> &'static str {
         std::any::type_name::<L>()
     }
@@ -263,16 +272,22 @@ pub enum DeserializeMetaError {
     DeserializeSettings(#[from] SpannedError),
     #[error("Failed to deserialize minimal asset meta: {0:?}")]
     DeserializeMinimal(SpannedError),
+    #[error("The settings of {name} have version {version}, and no migration to version {current_version} exists")]
+    NoMigration {
+        name: String,
+        version: u32,
+        current_version: u32,
+    },
 }
 
 /// A context that provides access to assets in [`AssetLoader`]s, tracks dependencies, and collects asset load state.
 /// Any asset state accessed by [`LoadContext`] will be tracked and stored for use in dependency events and asset preprocessing.
 pub struct LoadContext<'a> {
-    asset_server: &'a AssetServer,
-    should_load_dependencies: bool,
+    pub(crate) asset_server: &'a AssetServer,
+    pub(crate) should_load_dependencies: bool,
     populate_hashes: bool,
     asset_path: AssetPath<'static>,
-    dependencies: HashSet<UntypedAssetId>,
+    pub(crate) dependencies: HashSet<UntypedAssetId>,
     /// Direct dependencies used by this loader.
     loader_dependencies: HashMap<AssetPath<'static>, AssetHash>,
     labeled_assets: HashMap<CowArc<'static, str>, LabeledAsset>,
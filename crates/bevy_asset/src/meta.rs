@@ -1,6 +1,7 @@
 use crate::{self as bevy_asset, DeserializeMetaError, VisitAssetDependencies};
 use crate::{loader::AssetLoader, processor::Process, Asset, AssetPath};
 use bevy_log::error;
+use bevy_utils::HashMap;
 use downcast_rs::{impl_downcast, Downcast};
 use ron::ser::PrettyConfig;
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,10 @@ pub struct AssetMeta<L: AssetLoader, P: Process> {
     /// The version of the meta format being used. This will change whenever a breaking change is made to
     /// the meta format.
     pub meta_format_version: String,
+    /// The version of the loader or processor settings in [`AssetMeta::asset`], see [`AssetLoader::SETTINGS_VERSION`]
+    /// and [`Process::SETTINGS_VERSION`]. Meta written for an older version is upgraded with the registered [`MetaMigrations`].
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub settings_version: u32,
     /// Information produced by the [`AssetProcessor`] _after_ processing this asset.
     /// This will only exist alongside processed versions of assets. You should not manually set it in your asset source files.
     ///
@@ -29,19 +34,46 @@ pub struct AssetMeta<L: AssetLoader, P: Process> {
 
 impl<L: AssetLoader, P: Process> AssetMeta<L, P> {
     pub fn new(asset: AssetAction<L::Settings, P::Settings>) -> Self {
+        let settings_version = match &asset {
+            AssetAction::Load { .. } => L::SETTINGS_VERSION,
+            AssetAction::Process { .. } => P::SETTINGS_VERSION,
+            AssetAction::Ignore => 0,
+        };
         Self {
             meta_format_version: META_FORMAT_VERSION.to_string(),
+            settings_version,
             processed_info: None,
             asset,
         }
     }
 
     /// Deserializes the given serialized byte representation of the asset meta.
+    ///
+    /// This fails with [`DeserializeMetaError::NoMigration`] if the settings were not written for the current settings
+    /// version of the loader or processor. Use [`MetaMigrations::migrate`] to upgrade them first.
     pub fn deserialize(bytes: &[u8]) -> Result<Self, DeserializeMetaError> {
+        let minimal: SettingsVersionMinimal =
+            ron::de::from_bytes(bytes).map_err(DeserializeMetaError::DeserializeMinimal)?;
+        let (name, current_version) = match minimal.asset {
+            AssetActionMinimal::Load { loader } => (loader, L::SETTINGS_VERSION),
+            AssetActionMinimal::Process { processor } => (processor, P::SETTINGS_VERSION),
+            AssetActionMinimal::Ignore => return Ok(ron::de::from_bytes(bytes)?),
+        };
+        if minimal.settings_version != current_version {
+            return Err(DeserializeMetaError::NoMigration {
+                name,
+                version: minimal.settings_version,
+                current_version,
+            });
+        }
         Ok(ron::de::from_bytes(bytes)?)
     }
 }
 
+fn is_zero(version: &u32) -> bool {
+    *version == 0
+}
+
 /// Configures how an asset source file should be handled by the asset system.
 #[derive(Serialize, Deserialize)]
 pub enum AssetAction<LoaderSettings, ProcessSettings> {
@@ -75,6 +107,15 @@ pub struct ProcessedInfo {
     pub full_hash: AssetHash,
     /// Information about the "process dependencies" used to process this asset.
     pub process_dependencies: Vec<ProcessDependencyInfo>,
+    /// A hash of the processed asset bytes and the processed asset .meta data (without this info). Processed
+    /// assets with the same content hash are loaded only once by the [`AssetServer`](crate::AssetServer).
+    #[serde(default)]
+    pub content_hash: Option<AssetHash>,
+    /// The path of the processed asset this asset is a duplicate of, if the [`AssetProcessor`](crate::processor::AssetProcessor) deduplicated it.
+    /// The processed asset bytes of a duplicate are empty, and it is loaded from this path instead.
+    /// See [`AssetProcessor::set_deduplicate`](crate::processor::AssetProcessor::set_deduplicate).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<AssetPath<'static>>,
 }
 
 /// Information about a dependency used to process an asset. This is used to determine whether an asset's "process dependency"
@@ -105,6 +146,14 @@ pub enum AssetActionMinimal {
     Ignore,
 }
 
+/// This is a minimal counterpart to [`AssetMeta`] that exists to read the settings version of the [`AssetAction`].
+#[derive(Serialize, Deserialize)]
+pub struct SettingsVersionMinimal {
+    #[serde(default)]
+    pub settings_version: u32,
+    pub asset: AssetActionMinimal,
+}
+
 /// This is a minimal counterpart to [`ProcessedInfo`] that exists to speed up serialization in cases where the whole [`ProcessedInfo`] isn't
 /// necessary.
 #[derive(Serialize, Deserialize)]
@@ -225,6 +274,142 @@ pub(crate) fn loader_settings_meta_transform<S: Settings>(
     })
 }
 
+type MetaMigration = Box<dyn Fn(&[u8]) -> Result<Vec<u8>, DeserializeMetaError> + Send + Sync>;
+
+/// Upgrades [`AssetMeta`] files written for older versions of [`AssetLoader::Settings`] or [`Process::Settings`].
+///
+/// Whenever the settings of a loader or processor change in a way that breaks existing meta files, increase its
+/// [`AssetLoader::SETTINGS_VERSION`] (or [`Process::SETTINGS_VERSION`]) and register a migration from the previous
+/// version. When meta with an older settings version is read, the migrations are applied one version at a time until
+/// the settings reach the current version. Every migration deserializes the old settings type, converts it and
+/// serializes the result, so old settings types only need to be kept around as long as their migration exists.
+///
+/// Migrations are registered with [`AssetApp::register_asset_loader_settings_migration`] and
+/// [`AssetApp::register_asset_processor_settings_migration`].
+///
+/// [`AssetApp::register_asset_loader_settings_migration`]: crate::AssetApp::register_asset_loader_settings_migration
+/// [`AssetApp::register_asset_processor_settings_migration`]: crate::AssetApp::register_asset_processor_settings_migration
+#[derive(Default)]
+pub struct MetaMigrations {
+    migrations: HashMap<&'static str, HashMap<u32, MetaMigration>>,
+    /// If `true`, migrated meta is written back to the [`AssetSource`](crate::io::AssetSource) it was read from.
+    /// Otherwise meta is only migrated in memory every time it is read.
+    pub rewrite: bool,
+}
+
+impl MetaMigrations {
+    /// Adds a migration of the settings of the [`AssetLoader`] `L` from `from_version` to `from_version + 1`.
+    pub fn add_loader_migration<L, From, To>(
+        &mut self,
+        from_version: u32,
+        migrate: impl Fn(From) -> To + Send + Sync + 'static,
+    ) where
+        L: AssetLoader,
+        From: for<'a> Deserialize<'a> + 'static,
+        To: Serialize + 'static,
+    {
+        self.add::<From, To>(std::any::type_name::<L>(), from_version, migrate);
+    }
+
+    /// Adds a migration of the settings of the [`Process`] implementation `P` from `from_version` to `from_version + 1`.
+    pub fn add_processor_migration<P, From, To>(
+        &mut self,
+        from_version: u32,
+        migrate: impl Fn(From) -> To + Send + Sync + 'static,
+    ) where
+        P: Process,
+        From: for<'a> Deserialize<'a> + 'static,
+        To: Serialize + 'static,
+    {
+        self.add::<From, To>(std::any::type_name::<P>(), from_version, migrate);
+    }
+
+    fn add<From, To>(
+        &mut self,
+        name: &'static str,
+        from_version: u32,
+        migrate: impl Fn(From) -> To + Send + Sync + 'static,
+    ) where
+        From: for<'a> Deserialize<'a> + 'static,
+        To: Serialize + 'static,
+    {
+        let migration = move |bytes: &[u8]| {
+            let meta: MigratedMeta<From> = ron::de::from_bytes(bytes)?;
+            let asset = match meta.asset {
+                AssetAction::Load { loader, settings } => AssetAction::Load {
+                    loader,
+                    settings: migrate(settings),
+                },
+                AssetAction::Process {
+                    processor,
+                    settings,
+                } => AssetAction::Process {
+                    processor,
+                    settings: migrate(settings),
+                },
+                AssetAction::Ignore => AssetAction::Ignore,
+            };
+            let meta = MigratedMeta {
+                meta_format_version: meta.meta_format_version,
+                settings_version: from_version + 1,
+                processed_info: meta.processed_info,
+                asset,
+            };
+            Ok(ron::ser::to_string_pretty(&meta, PrettyConfig::default())
+                .expect("type is convertible to ron")
+                .into_bytes())
+        };
+        self.migrations
+            .entry(name)
+            .or_default()
+            .insert(from_version, Box::new(migration));
+    }
+
+    /// Upgrades the settings in `meta_bytes` for the loader or processor with the type name `name` to
+    /// `current_version`. Returns `None` if the settings already have the current version.
+    pub fn migrate(
+        &self,
+        name: &str,
+        current_version: u32,
+        meta_bytes: &[u8],
+    ) -> Result<Option<Vec<u8>>, DeserializeMetaError> {
+        let minimal: SettingsVersionMinimal =
+            ron::de::from_bytes(meta_bytes).map_err(DeserializeMetaError::DeserializeMinimal)?;
+        if minimal.settings_version == current_version {
+            return Ok(None);
+        }
+        let no_migration = |version| DeserializeMetaError::NoMigration {
+            name: name.to_string(),
+            version,
+            current_version,
+        };
+        if minimal.settings_version > current_version {
+            return Err(no_migration(minimal.settings_version));
+        }
+        let mut bytes = meta_bytes.to_vec();
+        for version in minimal.settings_version..current_version {
+            let migration = self
+                .migrations
+                .get(name)
+                .and_then(|migrations| migrations.get(&version))
+                .ok_or_else(|| no_migration(version))?;
+            bytes = migration(&bytes)?;
+        }
+        Ok(Some(bytes))
+    }
+}
+
+/// [`AssetMeta`] with the same loader and processor settings type `S`, used to migrate settings.
+#[derive(Serialize, Deserialize)]
+struct MigratedMeta<S> {
+    meta_format_version: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    settings_version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    processed_info: Option<ProcessedInfo>,
+    asset: AssetAction<S, S>,
+}
+
 pub type AssetHash = [u8; 32];
 
 /// NOTE: changing the hashing logic here is a _breaking change_ that requires a [`META_FORMAT_VERSION`] bump.
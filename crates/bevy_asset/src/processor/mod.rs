@@ -1,8 +1,10 @@
 mod log;
 mod process;
+mod report;
 
 pub use log::*;
 pub use process::*;
+pub use report::*;
 
 use crate::{
     io::{
@@ -11,7 +13,7 @@ use crate::{
     },
     meta::{
         get_asset_hash, get_full_asset_hash, AssetAction, AssetActionMinimal, AssetHash, AssetMeta,
-        AssetMetaDyn, AssetMetaMinimal, ProcessedInfo, ProcessedInfoMinimal,
+        AssetMetaDyn, AssetMetaMinimal, ProcessDependencyInfo, ProcessedInfo, ProcessedInfoMinimal,
     },
     AssetLoadError, AssetMetaCheck, AssetPath, AssetServer, AssetServerMode, DeserializeMetaError,
     MissingAssetLoaderForExtensionError,
@@ -26,7 +28,10 @@ use parking_lot::RwLock;
 use std::{
     collections::VecDeque,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 use thiserror::Error;
 
@@ -57,6 +62,8 @@ pub struct AssetProcessorData {
     processors: RwLock<HashMap<&'static str, Arc<dyn ErasedProcessor>>>,
     /// Default processors for file extensions
     default_processors: RwLock<HashMap<String, &'static str>>,
+    /// Whether identical processed assets are deduplicated, see [`AssetProcessor::set_deduplicate`].
+    deduplicate: AtomicBool,
     state: async_lock::RwLock<ProcessorState>,
     sources: AssetSources,
     initialized_sender: async_broadcast::Sender<()>,
@@ -161,25 +168,110 @@ impl AssetProcessor {
     /// * Scan the unprocessed [`AssetReader`] and remove any final processed assets that are invalid or no longer exist.
     /// * For each asset in the unprocessed [`AssetReader`], kick off a new "process job", which will process the asset
     /// (if the latest version of the asset has not been processed).
+    ///
+    /// Errors that prevent processing from starting are logged.
     #[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
     pub fn process_assets(&self) {
+        if let Err(err) = self.try_process_assets() {
+            error!("Failed to process assets: {err}");
+        }
+    }
+
+    /// Processes all assets like [`AssetProcessor::process_assets`], returning the error that
+    /// prevented processing from starting, if any.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
+    fn try_process_assets(&self) -> Result<(), InitializeError> {
         let start_time = std::time::Instant::now();
         debug!("Processing Assets");
+        let mut result = Ok(());
         IoTaskPool::get().scope(|scope| {
+            let result = &mut result;
             scope.spawn(async move {
-                self.initialize().await.unwrap();
-                for source in self.sources().iter_processed() {
-                    self.process_assets_internal(scope, source, PathBuf::from(""))
-                        .await
-                        .unwrap();
+                *result = async {
+                    self.initialize().await?;
+                    for source in self.sources().iter_processed() {
+                        self.process_assets_internal(scope, source, PathBuf::from(""))
+                            .await
+                            .map_err(InitializeError::FailedToReadSourcePaths)?;
+                    }
+                    Ok(())
                 }
+                .await;
             });
         });
         // This must happen _after_ the scope resolves or it will happen "too early"
         // Don't move this into the async scope above! process_assets is a blocking/sync function this is fine
+        // Processing also finishes after an error, so that gated readers do not wait forever.
         bevy_tasks::block_on(self.finish_processing_assets());
         let end_time = std::time::Instant::now();
         debug!("Processing finished in {:?}", end_time - start_time);
+        result
+    }
+
+    /// Processes all assets once, like [`AssetProcessor::process_assets`], and returns the outcome
+    /// of every asset. This does not listen for changes afterwards, which makes it suitable for
+    /// build servers and other headless tools.
+    ///
+    /// Like any run of the processor, this uses the [`ProcessorTransactionLog`] and the processed
+    /// asset metadata to only process assets that changed since the last run, and to recover from
+    /// runs that were interrupted. If processing cannot start, for example because the processed
+    /// assets cannot be read, the error is returned in [`ProcessReport::error`].
+    #[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
+    pub fn process_once(&self) -> ProcessReport {
+        bevy_tasks::block_on(async {
+            self.data.asset_infos.write().await.report = Some(ProcessReport::default());
+        });
+        let result = self.try_process_assets();
+        let mut report =
+            bevy_tasks::block_on(async { self.data.asset_infos.write().await.report.take() })
+                .unwrap_or_default();
+        report.error = result.err();
+        report
+    }
+
+    /// Finishes building `app` and processes its assets once with [`AssetProcessor::process_once`],
+    /// without running the app.
+    ///
+    /// The app must use [`AssetMode::Processed`](crate::AssetMode::Processed), with the
+    /// `asset_processor` feature enabled, and should add the plugins registering the loaders and
+    /// processors of its assets.
+    ///
+    /// ```no_run
+    /// # use bevy_app::App;
+    /// # use bevy_asset::{processor::AssetProcessor, AssetMode, AssetPlugin};
+    /// # use bevy_core::TaskPoolPlugin;
+    /// fn main() -> std::process::ExitCode {
+    ///     let mut app = App::new();
+    ///     app.add_plugins((
+    ///         TaskPoolPlugin::default(),
+    ///         AssetPlugin {
+    ///             mode: AssetMode::Processed,
+    ///             ..Default::default()
+    ///         },
+    ///         // Plugins registering asset loaders and processors.
+    ///     ));
+    ///     let report = AssetProcessor::process_app_once(&mut app);
+    ///     println!("{report}");
+    ///     report.exit_code()
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the app does not have an [`AssetProcessor`].
+    #[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
+    pub fn process_app_once(app: &mut bevy_app::App) -> ProcessReport {
+        while app.plugins_state() == bevy_app::PluginsState::Adding {
+            bevy_tasks::tick_global_task_pools_on_main_thread();
+        }
+        app.finish();
+        app.cleanup();
+        let processor = app
+            .world
+            .get_resource::<AssetProcessor>()
+            .expect("Processing assets requires an AssetPlugin in AssetMode::Processed and the `asset_processor` feature")
+            .clone();
+        processor.process_once()
     }
 
     /// Listens for changes to assets in the source [`AssetSource`] and update state accordingly.
@@ -479,6 +571,17 @@ impl AssetProcessor {
         process_plans.insert(std::any::type_name::<P>(), Arc::new(processor));
     }
 
+    /// Sets whether processed assets whose content is identical to another processed asset are
+    /// deduplicated. This is disabled by default.
+    ///
+    /// The processed bytes of a duplicate are left empty and its processed meta points to the
+    /// original asset (see [`ProcessedInfo::duplicate_of`]), which the [`AssetServer`] loads
+    /// instead. Independently of this setting, the [`AssetServer`] loads processed assets with the
+    /// same [`ProcessedInfo::content_hash`] only once.
+    pub fn set_deduplicate(&self, deduplicate: bool) {
+        self.data.deduplicate.store(deduplicate, Ordering::Relaxed);
+    }
+
     /// Set the default processor for the given `extension`. Make sure `P` is registered with [`AssetProcessor::register_processor`].
     pub fn set_default_processor<P: Process>(&self, extension: &str) {
         let mut default_processors = self.data.default_processors.write();
@@ -698,28 +801,48 @@ impl AssetProcessor {
         // Note: we get the asset source reader first because we don't want to create meta files for assets that don't have source files
         let mut byte_reader = reader.read(path).await.map_err(reader_err)?;
 
-        let (mut source_meta, meta_bytes, processor) = match reader.read_meta_bytes(path).await {
+        let (source_meta, meta_bytes, processor) = match reader.read_meta_bytes(path).await {
             Ok(meta_bytes) => {
                 let minimal: AssetMetaMinimal = ron::de::from_bytes(&meta_bytes).map_err(|e| {
                     ProcessError::DeserializeMetaError(DeserializeMetaError::DeserializeMinimal(e))
                 })?;
-                let (meta, processor) = match minimal.asset {
+                let (meta, meta_bytes, processor) = match minimal.asset {
                     AssetActionMinimal::Load { loader } => {
                         let loader = server.get_asset_loader_with_type_name(&loader).await?;
+                        let meta_bytes = server
+                            .migrate_meta(
+                                source,
+                                path,
+                                loader.type_name(),
+                                loader.settings_version(),
+                                meta_bytes,
+                                true,
+                            )
+                            .await?;
                         let meta = loader.deserialize_meta(&meta_bytes)?;
-                        (meta, None)
+                        (meta, meta_bytes, None)
                     }
-                    AssetActionMinimal::Process { processor } => {
+                    AssetActionMinimal::Process { processor: name } => {
                         let processor = self
-                            .get_processor(&processor)
-                            .ok_or_else(|| ProcessError::MissingProcessor(processor))?;
+                            .get_processor(&name)
+                            .ok_or_else(|| ProcessError::MissingProcessor(name.clone()))?;
+                        let meta_bytes = server
+                            .migrate_meta(
+                                source,
+                                path,
+                                &name,
+                                processor.settings_version(),
+                                meta_bytes,
+                                true,
+                            )
+                            .await?;
                         let meta = processor.deserialize_meta(&meta_bytes)?;
-                        (meta, Some(processor))
+                        (meta, meta_bytes, Some(processor))
                     }
                     AssetActionMinimal::Ignore => {
                         let meta: Box<dyn AssetMetaDyn> =
                             Box::new(AssetMeta::<(), ()>::deserialize(&meta_bytes)?);
-                        (meta, None)
+                        (meta, meta_bytes, None)
                     }
                 };
                 (meta, meta_bytes, processor)
@@ -777,6 +900,8 @@ impl AssetProcessor {
             hash: new_hash,
             full_hash: new_hash,
             process_dependencies: Vec::new(),
+            content_hash: None,
+            duplicate_of: None,
         };
 
         {
@@ -815,9 +940,58 @@ impl AssetProcessor {
         // Directly writing to the asset destination in the processor necessitates this behavior
         // TODO: this class of failure can be recovered via re-processing + smarter log validation that allows for duplicate transactions in the event of failures
         self.log_begin_processing(asset_path).await;
-        if let Some(processor) = processor {
+        let has_processor = processor.is_some();
+        let mut processed_meta = if self.data.deduplicate.load(Ordering::Relaxed) {
+            // Duplicates are found by the hash of the processed asset, so it is processed in memory.
+            let (mut processed_meta, processed_bytes) = if let Some(processor) = processor {
+                let mut processed_bytes = Vec::new();
+                let processed_meta = {
+                    let mut context = ProcessContext::new(
+                        self,
+                        asset_path,
+                        &asset_bytes,
+                        &mut new_processed_info,
+                    );
+                    processor
+                        .process(&mut context, source_meta, &mut processed_bytes)
+                        .await?
+                };
+                (processed_meta, processed_bytes)
+            } else {
+                (source_meta, asset_bytes)
+            };
+
+            *processed_meta.processed_info_mut() = None;
+            let content_hash = get_asset_hash(&processed_meta.serialize(), &processed_bytes);
+            new_processed_info.content_hash = Some(content_hash);
+            if let Some((original, full_hash)) =
+                self.find_processed_original(asset_path, content_hash).await
+            {
+                debug!("{asset_path:?} is a duplicate of {original:?}");
+                // Depending on the original ensures this asset is processed again if the original changes.
+                new_processed_info
+                    .process_dependencies
+                    .push(ProcessDependencyInfo {
+                        full_hash,
+                        path: original.clone(),
+                    });
+                new_processed_info.duplicate_of = Some(original);
+            }
+
+            // The bytes of a duplicate are read from the original instead.
+            let processed_bytes = if new_processed_info.duplicate_of.is_some() {
+                &[]
+            } else {
+                &processed_bytes[..]
+            };
+            processed_writer
+                .write_bytes(path, processed_bytes)
+                .await
+                .map_err(writer_err)?;
+            processed_meta
+        } else if let Some(processor) = processor {
             let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
-            let mut processed_meta = {
+            let processed_meta = {
                 let mut context =
                     ProcessContext::new(self, asset_path, &asset_bytes, &mut new_processed_info);
                 processor
@@ -832,38 +1006,107 @@ impl AssetProcessor {
                     path: asset_path.clone(),
                     err: AssetWriterError::Io(e),
                 })?;
-
-            let full_hash = get_full_asset_hash(
+            processed_meta
+        } else {
+            processed_writer
+                .write_bytes(path, &asset_bytes)
+                .await
+                .map_err(writer_err)?;
+            source_meta
+        };
+        if has_processor || !new_processed_info.process_dependencies.is_empty() {
+            new_processed_info.full_hash = get_full_asset_hash(
                 new_hash,
                 new_processed_info
                     .process_dependencies
                     .iter()
                     .map(|i| i.full_hash),
             );
-            new_processed_info.full_hash = full_hash;
-            *processed_meta.processed_info_mut() = Some(new_processed_info.clone());
-            let meta_bytes = processed_meta.serialize();
-            processed_writer
-                .write_meta_bytes(path, &meta_bytes)
-                .await
-                .map_err(writer_err)?;
-        } else {
-            processed_writer
-                .write_bytes(path, &asset_bytes)
-                .await
-                .map_err(writer_err)?;
-            *source_meta.processed_info_mut() = Some(new_processed_info.clone());
-            let meta_bytes = source_meta.serialize();
-            processed_writer
-                .write_meta_bytes(path, &meta_bytes)
-                .await
-                .map_err(writer_err)?;
         }
+
+        *processed_meta.processed_info_mut() = Some(new_processed_info.clone());
+        let meta_bytes = processed_meta.serialize();
+        processed_writer
+            .write_meta_bytes(path, &meta_bytes)
+            .await
+            .map_err(writer_err)?;
         self.log_end_processing(asset_path).await;
 
         Ok(ProcessResult::Processed(new_processed_info))
     }
 
+    /// Returns the path and full hash of the processed asset with the given `content_hash` that
+    /// assets with the same content are deduplicated to, if one exists. Otherwise, the asset at
+    /// `asset_path` becomes that asset.
+    async fn find_processed_original(
+        &self,
+        asset_path: &AssetPath<'static>,
+        content_hash: AssetHash,
+    ) -> Option<(AssetPath<'static>, AssetHash)> {
+        let original = {
+            let mut infos = self.data.asset_infos.write().await;
+            let previous_hash = infos
+                .get(asset_path)
+                .and_then(|info| info.processed_info.as_ref())
+                .and_then(|info| info.content_hash);
+            if let Some(previous_hash) = previous_hash {
+                if infos.content_originals.get(&previous_hash) == Some(asset_path) {
+                    infos.content_originals.remove(&previous_hash);
+                }
+            }
+            let original = infos
+                .content_originals
+                .get(&content_hash)
+                .filter(|path| *path != asset_path)
+                .cloned()
+                .or_else(|| {
+                    // Assets processed by a previous run.
+                    infos
+                        .infos
+                        .iter()
+                        .filter(|(path, _)| *path != asset_path)
+                        .filter(|(_, info)| {
+                            info.processed_info.as_ref().is_some_and(|info| {
+                                info.content_hash == Some(content_hash)
+                                    && info.duplicate_of.is_none()
+                            })
+                        })
+                        .map(|(path, _)| path.clone())
+                        // The smallest path is chosen to keep the original stable between runs.
+                        .min_by_key(|path| path.to_string())
+                });
+            let original = original.unwrap_or_else(|| asset_path.clone());
+            infos
+                .content_originals
+                .insert(content_hash, original.clone());
+            original
+        };
+        if original == *asset_path {
+            return None;
+        }
+
+        // The original may still be processing.
+        let status = self.data.wait_until_processed(original.clone()).await;
+        let mut infos = self.data.asset_infos.write().await;
+        let full_hash = infos
+            .get(&original)
+            .and_then(|info| info.processed_info.as_ref())
+            .filter(|info| info.content_hash == Some(content_hash) && info.duplicate_of.is_none())
+            .map(|info| info.full_hash);
+        match full_hash {
+            Some(full_hash) if status == ProcessStatus::Processed => Some((original, full_hash)),
+            _ => {
+                // The original failed or no longer has this content.
+                if infos.content_originals.get(&content_hash) == Some(&original) {
+                    infos
+                        .content_originals
+                        .insert(content_hash, asset_path.clone());
+                }
+                None
+            }
+        }
+    }
+
     async fn validate_transaction_log_and_recover(&self) {
         if let Err(err) = ProcessorTransactionLog::validate().await {
             let state_is_valid = match err {
@@ -970,6 +1213,7 @@ impl AssetProcessorData {
             processors: Default::default(),
             asset_infos: Default::default(),
             default_processors: Default::default(),
+            deduplicate: AtomicBool::new(false),
         }
     }
 
@@ -1105,6 +1349,11 @@ pub struct ProcessorAssetInfos {
     /// check this maps for dependencies and add them. If an asset is removed, it should update the dependants here.
     non_existent_dependants: HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
     check_reprocess_queue: VecDeque<AssetPath<'static>>,
+    /// The outcomes of the current [`AssetProcessor::process_once`] run, if any.
+    report: Option<ProcessReport>,
+    /// The assets that processed assets with a given content hash are deduplicated to, see
+    /// [`AssetProcessor::set_deduplicate`].
+    content_originals: HashMap<AssetHash, AssetPath<'static>>,
 }
 
 impl ProcessorAssetInfos {
@@ -1148,6 +1397,10 @@ impl ProcessorAssetInfos {
         match result {
             Ok(ProcessResult::Processed(processed_info)) => {
                 debug!("Finished processing \"{:?}\"", asset_path);
+                self.record(
+                    &asset_path,
+                    AssetProcessOutcome::Processed(processed_info.clone()),
+                );
                 // clean up old dependants
                 let old_processed_info = self
                     .infos
@@ -1171,6 +1424,7 @@ impl ProcessorAssetInfos {
             }
             Ok(ProcessResult::SkippedNotChanged) => {
                 debug!("Skipping processing (unchanged) \"{:?}\"", asset_path);
+                self.record(&asset_path, AssetProcessOutcome::SkippedNotChanged);
                 let info = self.get_mut(&asset_path).expect("info should exist");
                 // NOTE: skipping an asset on a given pass doesn't mean it won't change in the future as a result
                 // of a dependency being re-processed. This means apps might receive an "old" (but valid) asset first.
@@ -1199,27 +1453,59 @@ impl ProcessorAssetInfos {
                 if let ProcessError::AssetLoadError(AssetLoadError::AssetLoaderError {
                     path: dependency,
                     ..
-                }) = err
+                }) = &err
                 {
                     let info = self.get_mut(&asset_path).expect("info should exist");
                     info.processed_info = Some(ProcessedInfo {
                         hash: AssetHash::default(),
                         full_hash: AssetHash::default(),
                         process_dependencies: vec![],
+                        content_hash: None,
+                        duplicate_of: None,
                     });
-                    self.add_dependant(&dependency, asset_path.to_owned());
+                    self.add_dependant(dependency, asset_path.to_owned());
                 }
 
                 let info = self.get_mut(&asset_path).expect("info should exist");
                 info.update_status(ProcessStatus::Failed).await;
+                self.record(&asset_path, AssetProcessOutcome::Failed(err));
             }
         }
     }
 
+    /// Records the outcome of processing an asset, if a [`ProcessReport`] is being collected.
+    fn record(&mut self, asset_path: &AssetPath<'static>, outcome: AssetProcessOutcome) {
+        if let Some(report) = &mut self.report {
+            report.outcomes.insert(asset_path.clone(), outcome);
+        }
+    }
+
+    /// Queues the assets that were deduplicated to `original` for reprocessing and removes them from `dependants`.
+    /// This should happen when `original` no longer exists at its path, as the duplicates are loaded from it.
+    fn reprocess_duplicates(
+        &mut self,
+        original: &AssetPath<'static>,
+        dependants: &mut HashSet<AssetPath<'static>>,
+    ) {
+        dependants.retain(|dependant| {
+            let is_duplicate = self
+                .infos
+                .get(dependant)
+                .and_then(|info| info.processed_info.as_ref())
+                .and_then(|info| info.duplicate_of.as_ref())
+                == Some(original);
+            if is_duplicate {
+                self.check_reprocess_queue.push_back(dependant.clone());
+            }
+            !is_duplicate
+        });
+    }
+
     /// Remove the info for the given path. This should only happen if an asset's source is removed / non-existent
     async fn remove(&mut self, asset_path: &AssetPath<'static>) {
         let info = self.infos.remove(asset_path);
-        if let Some(info) = info {
+        if let Some(mut info) = info {
+            self.reprocess_duplicates(asset_path, &mut info.dependants);
             if let Some(processed_info) = info.processed_info {
                 self.clear_dependencies(asset_path, processed_info);
             }
@@ -1243,6 +1529,7 @@ impl ProcessorAssetInfos {
     async fn rename(&mut self, old: &AssetPath<'static>, new: &AssetPath<'static>) {
         let info = self.infos.remove(old);
         if let Some(mut info) = info {
+            self.reprocess_duplicates(old, &mut info.dependants);
             if !info.dependants.is_empty() {
                 // TODO: We can't currently ensure "moved" folders with relative paths aren't broken because AssetPath
                 // doesn't distinguish between absolute and relative paths. We have "erased" relativeness. In the short term,
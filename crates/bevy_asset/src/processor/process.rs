@@ -25,6 +25,9 @@ pub trait Process: Send + Sync + Sized + 'static {
     type Settings: Settings + Default + Serialize + for<'a> Deserialize<'a>;
     /// The [`AssetLoader`] that will be used to load the final processed asset.
     type OutputLoader: AssetLoader;
+    /// The version of [`Process::Settings`]. Increase it whenever the settings change in a way that breaks existing
+    /// meta files, and register a migration from the previous version, see [`MetaMigrations`](crate::meta::MetaMigrations).
+    const SETTINGS_VERSION: u32 = 0;
     /// Processes the asset stored on `context` in some way using the settings stored on `meta`. The results are written to `writer`. The
     /// final written processed asset is loadable using [`Process::OutputLoader`]. This load will use the returned [`AssetLoader::Settings`].
     fn process<'a>(
@@ -258,6 +261,8 @@ pub trait ErasedProcessor: Send + Sync {
     fn deserialize_meta(&self, meta: &[u8]) -> Result<Box<dyn AssetMetaDyn>, DeserializeMetaError>;
     /// Returns the default type-erased [`AssetMeta`] for the underlying [`Process`] impl.
     fn default_meta(&self) -> Box<dyn AssetMetaDyn>;
+    /// Returns the [`Process::SETTINGS_VERSION`] of the underlying [`Process`] impl.
+    fn settings_version(&self) -> u32;
 }
 
 impl<P: Process> ErasedProcessor for P {
@@ -282,7 +287,7 @@ impl<P: Process> ErasedProcessor for P {
     }
 
     fn deserialize_meta(&self, meta: &[u8]) -> Result<Box<dyn AssetMetaDyn>, DeserializeMetaError> {
-        let meta: AssetMeta<(), P> = ron::de::from_bytes(meta)?;
+        let meta = AssetMeta::<(), P>::deserialize(meta)?;
         Ok(Box::new(meta))
     }
 
@@ -292,6 +297,10 @@ impl<P: Process> ErasedProcessor for P {
             settings: P::Settings::default(),
         }))
     }
+
+    fn settings_version(&self) -> u32 {
+        P::SETTINGS_VERSION
+    }
 }
 
 /// Provides scoped data access to the [`AssetProcessor`].
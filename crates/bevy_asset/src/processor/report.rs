@@ -0,0 +1,165 @@
+use crate::{
+    meta::ProcessedInfo,
+    processor::{InitializeError, ProcessError},
+    AssetPath,
+};
+use bevy_utils::HashMap;
+use std::fmt::Display;
+
+/// The outcome of processing a single asset, as recorded in a [`ProcessReport`].
+#[derive(Debug)]
+pub enum AssetProcessOutcome {
+    /// The asset was processed and written to its processed [`AssetSource`](crate::io::AssetSource).
+    Processed(ProcessedInfo),
+    /// The asset and its process dependencies did not change since it was last processed, so the
+    /// existing processed asset was kept.
+    SkippedNotChanged,
+    /// Processing the asset failed.
+    Failed(ProcessError),
+}
+
+/// The outcome of every asset handled by a run of the [`AssetProcessor`](crate::processor::AssetProcessor),
+/// returned by [`AssetProcessor::process_once`](crate::processor::AssetProcessor::process_once).
+///
+/// Assets that could not be processed because no loader or processor handles them are not part
+/// of the report. Its [`Display`] implementation writes one line per asset followed by a summary.
+#[derive(Debug, Default)]
+pub struct ProcessReport {
+    pub(crate) outcomes: HashMap<AssetPath<'static>, AssetProcessOutcome>,
+    pub(crate) error: Option<InitializeError>,
+}
+
+impl ProcessReport {
+    /// Returns the outcome of processing the asset at `path`, if it was handled by this run.
+    pub fn get(&self, path: &AssetPath<'static>) -> Option<&AssetProcessOutcome> {
+        self.outcomes.get(path)
+    }
+
+    /// Returns the outcome of every asset handled by this run, sorted by path.
+    pub fn iter(&self) -> impl Iterator<Item = (&AssetPath<'static>, &AssetProcessOutcome)> {
+        let mut outcomes: Vec<_> = self.outcomes.iter().collect();
+        outcomes.sort_by_cached_key(|(path, _)| path.to_string());
+        outcomes.into_iter()
+    }
+
+    /// Returns the assets that failed to process with their error, sorted by path.
+    pub fn failures(&self) -> impl Iterator<Item = (&AssetPath<'static>, &ProcessError)> {
+        self.iter().filter_map(|(path, outcome)| match outcome {
+            AssetProcessOutcome::Failed(error) => Some((path, error)),
+            _ => None,
+        })
+    }
+
+    /// Returns the error that prevented this run from processing the assets, if any. Assets that
+    /// were handled before the error are still part of the report.
+    pub fn error(&self) -> Option<&InitializeError> {
+        self.error.as_ref()
+    }
+
+    /// Returns the number of assets that were processed by this run.
+    pub fn processed_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, AssetProcessOutcome::Processed(_)))
+    }
+
+    /// Returns the number of assets that were skipped by this run because they did not change.
+    pub fn skipped_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, AssetProcessOutcome::SkippedNotChanged))
+    }
+
+    /// Returns the number of assets that failed to process.
+    pub fn failed_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, AssetProcessOutcome::Failed(_)))
+    }
+
+    /// Returns `true` if the run completed and no asset failed to process.
+    pub fn is_success(&self) -> bool {
+        self.error.is_none() && self.failed_count() == 0
+    }
+
+    /// Returns the exit code a command line tool running the processor should exit with:
+    /// [`ExitCode::FAILURE`](std::process::ExitCode::FAILURE) if the run failed or any asset failed
+    /// to process.
+    pub fn exit_code(&self) -> std::process::ExitCode {
+        if self.is_success() {
+            std::process::ExitCode::SUCCESS
+        } else {
+            std::process::ExitCode::FAILURE
+        }
+    }
+
+    fn count(&self, filter: impl Fn(&AssetProcessOutcome) -> bool) -> usize {
+        self.outcomes
+            .values()
+            .filter(|outcome| filter(outcome))
+            .count()
+    }
+}
+
+impl Display for ProcessReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (path, outcome) in self.iter() {
+            match outcome {
+                AssetProcessOutcome::Processed(_) => writeln!(f, "processed {path}")?,
+                AssetProcessOutcome::SkippedNotChanged => writeln!(f, "unchanged {path}")?,
+                AssetProcessOutcome::Failed(error) => writeln!(f, "failed    {path}: {error}")?,
+            }
+        }
+        if let Some(error) = &self.error {
+            writeln!(f, "error: {error}")?;
+        }
+        write!(
+            f,
+            "{} processed, {} unchanged, {} failed",
+            self.processed_count(),
+            self.skipped_count(),
+            self.failed_count()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::AssetHash;
+
+    #[test]
+    fn report_summarizes_outcomes() {
+        let mut report = ProcessReport::default();
+        assert!(report.is_success());
+
+        let info = ProcessedInfo {
+            hash: AssetHash::default(),
+            full_hash: AssetHash::default(),
+            process_dependencies: Vec::new(),
+            content_hash: None,
+            duplicate_of: None,
+        };
+        report.outcomes.insert(
+            AssetPath::from("b.cool.ron"),
+            AssetProcessOutcome::Processed(info),
+        );
+        report.outcomes.insert(
+            AssetPath::from("a.txt"),
+            AssetProcessOutcome::SkippedNotChanged,
+        );
+        report.outcomes.insert(
+            AssetPath::from("c.cool.ron"),
+            AssetProcessOutcome::Failed(ProcessError::MissingProcessor("Missing".to_string())),
+        );
+
+        assert!(!report.is_success());
+        assert_eq!(report.exit_code(), std::process::ExitCode::FAILURE);
+        let failures: Vec<_> = report
+            .failures()
+            .map(|(path, _)| path.to_string())
+            .collect();
+        assert_eq!(failures, vec!["c.cool.ron"]);
+        assert_eq!(
+            report.to_string(),
+            "unchanged a.txt\n\
+             processed b.cool.ron\n\
+             failed    c.cool.ron: The processor 'Missing' does not exist\n\
+             1 processed, 1 unchanged, 1 failed"
+        );
+    }
+}
@@ -1,9 +1,11 @@
 use std::any::{Any, TypeId};
 
 use bevy_ecs::world::{unsafe_world_cell::UnsafeWorldCell, World};
-use bevy_reflect::{FromReflect, FromType, Reflect};
+use bevy_reflect::{erased_serde, FromReflect, FromType, Reflect};
 
-use crate::{Asset, Assets, Handle, UntypedAssetId, UntypedHandle};
+use crate::{
+    deserialize_handle, Asset, Assets, Handle, HandleResolver, UntypedAssetId, UntypedHandle,
+};
 
 /// Type data for the [`TypeRegistry`](bevy_reflect::TypeRegistry) used to operate on reflected [`Asset`]s.
 ///
@@ -198,6 +200,10 @@ pub struct ReflectHandle {
     asset_type_id: TypeId,
     downcast_handle_untyped: fn(&dyn Any) -> Option<UntypedHandle>,
     typed: fn(UntypedHandle) -> Box<dyn Reflect>,
+    pub(crate) deserialize_path: fn(
+        &HandleResolver,
+        &mut dyn erased_serde::Deserializer,
+    ) -> Result<Box<dyn Reflect>, erased_serde::Error>,
 }
 impl ReflectHandle {
     /// The [`TypeId`] of the asset
@@ -227,6 +233,7 @@ impl<A: Asset> FromType<Handle<A>> for ReflectHandle {
                     .map(|h| h.clone().untyped())
             },
             typed: |handle: UntypedHandle| Box::new(handle.typed_debug_checked::<A>()),
+            deserialize_path: deserialize_handle::<A>,
         }
     }
 }
@@ -0,0 +1,215 @@
+use crate::{
+    io::Reader, Asset, AssetLoader, AssetPath, AssetServer, Handle, LoadContext, ReflectHandle,
+    UntypedAssetId,
+};
+use bevy_reflect::{
+    erased_serde,
+    serde::{ReflectDeserializerProcessor, TypedReflectDeserializer},
+    FromReflect, Reflect, TypeRegistration, TypeRegistryArc,
+};
+use bevy_utils::{BoxedFuture, HashSet};
+use futures_lite::AsyncReadExt;
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+use std::{any::TypeId, cell::RefCell, marker::PhantomData};
+use thiserror::Error;
+
+/// The format of a file loaded by a [`ReflectAssetLoader`].
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReflectAssetFormat {
+    /// [RON](https://github.com/ron-rs/ron), the format used for [`AssetMeta`](crate::meta::AssetMeta) files.
+    #[default]
+    Ron,
+    /// JSON.
+    Json,
+}
+
+impl ReflectAssetFormat {
+    /// Returns the format of the file at `path`: [`ReflectAssetFormat::Json`] for files ending
+    /// with `.json`, [`ReflectAssetFormat::Ron`] otherwise.
+    pub fn from_path(path: &AssetPath) -> Self {
+        match path
+            .path()
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Ron,
+        }
+    }
+}
+
+/// The settings of a [`ReflectAssetLoader`].
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ReflectAssetSettings {
+    /// The format of the file. If `None`, it is chosen from the file extension,
+    /// see [`ReflectAssetFormat::from_path`].
+    pub format: Option<ReflectAssetFormat>,
+}
+
+/// An [`AssetLoader`] that deserializes any [`Asset`] type implementing [`Reflect`] and
+/// [`FromReflect`] from RON or JSON, using the type information of its
+/// [`TypeRegistry`](bevy_reflect::TypeRegistry).
+///
+/// [`Handle`] fields are written as asset paths, which are resolved relative to the directory of the
+/// loaded file (see [`AssetPath::resolve_embed`]) and recorded as dependencies of the loaded asset.
+/// Every asset type of these handles must have [`ReflectHandle`] type data, which
+/// [`AssetApp::init_asset`](crate::AssetApp::init_asset) registers.
+///
+/// Use [`AssetApp::register_reflect_asset_loader`](crate::AssetApp::register_reflect_asset_loader)
+/// to add a loader for an asset type.
+///
+/// ```ron
+/// (
+///     name: "Sword",
+///     icon: "icons/sword.png",
+/// )
+/// ```
+pub struct ReflectAssetLoader<A> {
+    type_registry: TypeRegistryArc,
+    extensions: Vec<&'static str>,
+    marker: PhantomData<fn() -> A>,
+}
+
+impl<A: Asset + FromReflect> ReflectAssetLoader<A> {
+    /// Creates a loader for files with the given `extensions`, using the types registered in
+    /// `type_registry`.
+    pub fn new(type_registry: TypeRegistryArc, extensions: &[&'static str]) -> Self {
+        Self {
+            type_registry,
+            extensions: extensions.to_vec(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// An error that occurs when loading an asset with a [`ReflectAssetLoader`].
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ReflectAssetLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not read the asset: {0}")]
+    Io(#[from] std::io::Error),
+    /// The asset type is not registered in the [`TypeRegistry`](bevy_reflect::TypeRegistry).
+    #[error("The type {0} is not registered in the type registry")]
+    UnregisteredType(&'static str),
+    /// A [RON](ron) error.
+    #[error("Could not parse RON: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    /// A JSON error.
+    #[error("Could not parse JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The deserialized value could not be converted to the asset type.
+    #[error("The deserialized value could not be converted to {0}")]
+    FromReflect(&'static str),
+}
+
+impl<A: Asset + FromReflect> AssetLoader for ReflectAssetLoader<A> {
+    type Asset = A;
+    type Settings = ReflectAssetSettings;
+    type Error = ReflectAssetLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        settings: &'a ReflectAssetSettings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<A, ReflectAssetLoaderError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            let type_name = std::any::type_name::<A>();
+            let registry = self.type_registry.read();
+            let registration = registry
+                .get(TypeId::of::<A>())
+                .ok_or(ReflectAssetLoaderError::UnregisteredType(type_name))?;
+            let format = settings
+                .format
+                .unwrap_or_else(|| ReflectAssetFormat::from_path(load_context.asset_path()));
+
+            let resolver = HandleResolver {
+                asset_server: load_context.asset_server.clone(),
+                asset_path: load_context.asset_path().clone(),
+                should_load_dependencies: load_context.should_load_dependencies,
+                dependencies: RefCell::new(HashSet::new()),
+            };
+            let seed = TypedReflectDeserializer::with_processor(registration, &registry, &resolver);
+            let value = match format {
+                ReflectAssetFormat::Ron => {
+                    let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+                    seed.deserialize(&mut deserializer)
+                        .map_err(|error| deserializer.span_error(error))?
+                }
+                ReflectAssetFormat::Json => {
+                    let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+                    let value = seed.deserialize(&mut deserializer)?;
+                    deserializer.end()?;
+                    value
+                }
+            };
+            load_context
+                .dependencies
+                .extend(resolver.dependencies.into_inner());
+
+            A::from_reflect(&*value).ok_or(ReflectAssetLoaderError::FromReflect(type_name))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &self.extensions
+    }
+}
+
+/// Creates the [`Handle`]s deserialized by a [`ReflectAssetLoader`] from asset paths, and records
+/// them as dependencies of the loaded asset.
+pub(crate) struct HandleResolver {
+    asset_server: AssetServer,
+    asset_path: AssetPath<'static>,
+    should_load_dependencies: bool,
+    dependencies: RefCell<HashSet<UntypedAssetId>>,
+}
+
+impl HandleResolver {
+    fn load<A: Asset>(&self, path: &str) -> Result<Handle<A>, String> {
+        let path = self
+            .asset_path
+            .resolve_embed(path)
+            .map_err(|error| format!("Invalid asset path {path:?}: {error}"))?;
+        let handle = if self.should_load_dependencies {
+            self.asset_server.load(path)
+        } else {
+            self.asset_server.get_or_create_path_handle(path, None)
+        };
+        self.dependencies.borrow_mut().insert(handle.id().untyped());
+        Ok(handle)
+    }
+}
+
+impl ReflectDeserializerProcessor for HandleResolver {
+    fn handles(&self, registration: &TypeRegistration) -> bool {
+        registration.data::<ReflectHandle>().is_some()
+    }
+
+    fn deserialize(
+        &self,
+        registration: &TypeRegistration,
+        deserializer: &mut dyn erased_serde::Deserializer,
+    ) -> Result<Box<dyn Reflect>, erased_serde::Error> {
+        let reflect_handle = registration.data::<ReflectHandle>().unwrap();
+        (reflect_handle.deserialize_path)(self, deserializer)
+    }
+}
+
+/// Deserializes a [`Handle<A>`] from an asset path with the [`HandleResolver`] of a
+/// [`ReflectAssetLoader`].
+pub(crate) fn deserialize_handle<A: Asset>(
+    resolver: &HandleResolver,
+    deserializer: &mut dyn erased_serde::Deserializer,
+) -> Result<Box<dyn Reflect>, erased_serde::Error> {
+    use serde::de::Error;
+    let path = String::deserialize(deserializer)?;
+    let handle = resolver
+        .load::<A>(&path)
+        .map_err(erased_serde::Error::custom)?;
+    Ok(Box::new(handle))
+}
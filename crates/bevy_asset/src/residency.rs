@@ -0,0 +1,335 @@
+use crate::{Asset, AssetEvent, AssetServer, Assets, UntypedAssetId, UntypedHandle};
+use bevy_app::{App, Last, Plugin, Update};
+use bevy_diagnostic::{DiagnosticPath, Diagnostics, DiagnosticsStore};
+use bevy_ecs::prelude::*;
+use bevy_utils::{HashMap, HashSet, TypeIdMap};
+use std::{any::TypeId, sync::Arc};
+
+/// Reports the approximate memory used by an [`Asset`], so that its residency can be managed by
+/// [`AssetResidency`].
+///
+/// The size only has to be a reasonable estimate, such as the length of the buffers owned by the
+/// asset. It is queried whenever the asset is added or modified.
+pub trait AssetSize {
+    /// Returns the approximate number of bytes of memory used by this asset.
+    fn approximate_size(&self) -> usize;
+}
+
+/// Adds an [`AssetResidency`] manager with the given memory `budget` in bytes.
+///
+/// Asset types opt in with [`AssetApp::register_asset_residency`](crate::AssetApp::register_asset_residency),
+/// which also registers the [`AssetTypeResidency`] numbers of the type as diagnostics, at the
+/// paths returned by [`AssetResidencyPlugin::diagnostic_path`].
+pub struct AssetResidencyPlugin {
+    /// The memory budget in bytes, see [`AssetResidency::budget`].
+    pub budget: usize,
+}
+
+impl Plugin for AssetResidencyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AssetResidency::new(self.budget))
+            .init_resource::<DiagnosticsStore>()
+            .add_systems(Update, asset_residency_diagnostics)
+            .add_systems(Last, evict_unused_assets);
+    }
+}
+
+impl AssetResidencyPlugin {
+    /// The names of the diagnostics registered for every asset type, in the order of
+    /// [`AssetTypeResidency::measurements`].
+    pub const MEASUREMENTS: [&'static str; 6] = [
+        "resident_count",
+        "resident_bytes",
+        "unused_count",
+        "unused_bytes",
+        "evicted_count",
+        "evicted_bytes",
+    ];
+
+    /// Returns the path of the diagnostic named `measurement` (one of
+    /// [`AssetResidencyPlugin::MEASUREMENTS`]) of the asset type `A`, such as
+    /// `asset_residency/Image/resident_bytes`.
+    pub fn diagnostic_path<A: Asset>(measurement: &str) -> DiagnosticPath {
+        DiagnosticPath::from_components(["asset_residency", A::short_type_path(), measurement])
+    }
+}
+
+/// Keeps assets loaded from a path in memory after their last [`Handle`](crate::Handle) was
+/// dropped, and evicts the least recently used of them once the approximate memory of the resident
+/// assets exceeds the [`budget`](AssetResidency::budget).
+///
+/// Without residency management, an asset is dropped as soon as its last strong handle is dropped,
+/// and loaded again from scratch the next time it is needed. The residency manager instead holds
+/// a strong handle to every tracked asset. An asset is _unused_ while this handle is its only
+/// strong handle, and _used_ otherwise. Unused assets stay cached until the budget is exceeded,
+/// and are then evicted in least recently used order. Used assets are never evicted, but count
+/// towards the budget.
+///
+/// Evicting an asset removes its value from its [`Assets`] collection (sending an
+/// [`AssetEvent::Removed`] event) and resets its [`LoadState`](crate::LoadState) to
+/// [`NotLoaded`](crate::LoadState::NotLoaded), but keeps its id alive: loading its path again
+/// returns the same id and reloads the asset on demand.
+///
+/// Only assets of types registered with
+/// [`AssetApp::register_asset_residency`](crate::AssetApp::register_asset_residency), that have a
+/// path, are tracked. Add this resource with [`AssetResidencyPlugin`].
+#[derive(Resource)]
+pub struct AssetResidency {
+    budget: usize,
+    entries: HashMap<UntypedAssetId, ResidencyEntry>,
+    /// The ids of the evicted entries, which are not loaded again yet.
+    evicted: HashSet<UntypedAssetId>,
+    types: TypeIdMap<ResidencyType>,
+    tick: u64,
+}
+
+struct ResidencyType {
+    type_name: &'static str,
+    evict: fn(&mut World, UntypedAssetId),
+    /// The diagnostic paths of the type, in the order of [`AssetResidencyPlugin::MEASUREMENTS`].
+    diagnostics: [DiagnosticPath; 6],
+}
+
+struct ResidencyEntry {
+    /// Kept while the asset is evicted, so that its id stays alive.
+    handle: UntypedHandle,
+    /// The size of the asset when it was last resident.
+    size: usize,
+    /// The [`AssetResidency::tick`] at which the asset was last used.
+    last_used: u64,
+}
+
+impl ResidencyEntry {
+    fn is_used(&self) -> bool {
+        match &self.handle {
+            UntypedHandle::Strong(handle) => Arc::strong_count(handle) > 1,
+            UntypedHandle::Weak(_) => false,
+        }
+    }
+}
+
+/// The memory usage of one asset type tracked by an [`AssetResidency`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AssetTypeResidency {
+    /// The type name of the asset type.
+    pub type_name: &'static str,
+    /// The number of assets of this type in memory.
+    pub resident_count: usize,
+    /// The approximate memory used by the assets of this type in memory, in bytes.
+    pub resident_bytes: usize,
+    /// The number of assets of this type in memory that are unused, and may be evicted.
+    pub unused_count: usize,
+    /// The approximate memory used by the unused assets of this type in memory, in bytes.
+    pub unused_bytes: usize,
+    /// The number of assets of this type that were evicted and are not loaded again yet.
+    pub evicted_count: usize,
+    /// The approximate memory the evicted assets of this type used before they were evicted, in
+    /// bytes.
+    pub evicted_bytes: usize,
+}
+
+impl AssetTypeResidency {
+    /// Returns the numbers of this usage, in the order of [`AssetResidencyPlugin::MEASUREMENTS`].
+    pub fn measurements(&self) -> [usize; 6] {
+        [
+            self.resident_count,
+            self.resident_bytes,
+            self.unused_count,
+            self.unused_bytes,
+            self.evicted_count,
+            self.evicted_bytes,
+        ]
+    }
+}
+
+impl AssetResidency {
+    /// Creates an [`AssetResidency`] with the given memory `budget` in bytes.
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget,
+            entries: HashMap::default(),
+            evicted: HashSet::default(),
+            types: TypeIdMap::default(),
+            tick: 0,
+        }
+    }
+
+    /// Returns the memory budget in bytes. Unused assets are evicted while the approximate memory
+    /// of all resident assets exceeds it.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Sets the memory budget in bytes, see [`AssetResidency::budget`].
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+    }
+
+    /// Returns the approximate memory used by all resident tracked assets, in bytes.
+    pub fn resident_bytes(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|(id, _)| !self.evicted.contains(*id))
+            .map(|(_, entry)| entry.size)
+            .sum()
+    }
+
+    /// Returns `true` if the asset with the given `id` was evicted and is not loaded again yet.
+    pub fn is_evicted(&self, id: impl Into<UntypedAssetId>) -> bool {
+        self.evicted.contains(&id.into())
+    }
+
+    /// Returns the memory usage of every registered asset type, sorted by type name.
+    pub fn usage(&self) -> Vec<AssetTypeResidency> {
+        let mut usage: Vec<_> = self.usage_by_type().into_values().collect();
+        usage.sort_by_key(|usage| usage.type_name);
+        usage
+    }
+
+    fn usage_by_type(&self) -> TypeIdMap<AssetTypeResidency> {
+        let mut usage: TypeIdMap<AssetTypeResidency> = self
+            .types
+            .iter()
+            .map(|(type_id, residency_type)| {
+                let usage = AssetTypeResidency {
+                    type_name: residency_type.type_name,
+                    ..Default::default()
+                };
+                (*type_id, usage)
+            })
+            .collect();
+        for (id, entry) in &self.entries {
+            let Some(usage) = usage.get_mut(&id.type_id()) else {
+                continue;
+            };
+            if self.evicted.contains(id) {
+                usage.evicted_count += 1;
+                usage.evicted_bytes += entry.size;
+                continue;
+            }
+            usage.resident_count += 1;
+            usage.resident_bytes += entry.size;
+            if !entry.is_used() {
+                usage.unused_count += 1;
+                usage.unused_bytes += entry.size;
+            }
+        }
+        usage
+    }
+
+    /// Registers the asset type `A`, and returns the paths of its diagnostics.
+    pub(crate) fn register<A: Asset + AssetSize>(&mut self) -> [DiagnosticPath; 6] {
+        fn evict<A: Asset>(world: &mut World, id: UntypedAssetId) {
+            world.resource_mut::<Assets<A>>().remove(id.typed::<A>());
+            world
+                .resource::<AssetServer>()
+                .data
+                .infos
+                .write()
+                .unload(id);
+        }
+        let diagnostics = AssetResidencyPlugin::MEASUREMENTS
+            .map(|measurement| AssetResidencyPlugin::diagnostic_path::<A>(measurement));
+        let residency_type = ResidencyType {
+            type_name: std::any::type_name::<A>(),
+            evict: evict::<A>,
+            diagnostics: diagnostics.clone(),
+        };
+        self.types.insert(TypeId::of::<A>(), residency_type);
+        diagnostics
+    }
+}
+
+/// Tracks the assets of type `A` and their size in the [`AssetResidency`].
+pub(crate) fn track_asset_residency<A: Asset + AssetSize>(
+    mut events: EventReader<AssetEvent<A>>,
+    assets: Res<Assets<A>>,
+    asset_server: Res<AssetServer>,
+    mut residency: ResMut<AssetResidency>,
+) {
+    let tick = residency.tick;
+    for event in events.read() {
+        match *event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                let Some(asset) = assets.get(id) else {
+                    continue;
+                };
+                let size = asset.approximate_size();
+                let id = id.untyped();
+                if let Some(entry) = residency.entries.get_mut(&id) {
+                    entry.size = size;
+                    entry.last_used = tick;
+                    residency.evicted.remove(&id);
+                } else if asset_server.get_path(id).is_some() {
+                    // Only assets that can be loaded again from their path are cached.
+                    let Some(handle) = asset_server.get_id_handle_untyped(id) else {
+                        continue;
+                    };
+                    let entry = ResidencyEntry {
+                        handle,
+                        size,
+                        last_used: tick,
+                    };
+                    residency.entries.insert(id, entry);
+                }
+            }
+            // Evicted entries keep their handle, so that their id stays alive while they are not
+            // loaded. Assets removed by other means are forgotten.
+            AssetEvent::Removed { id } if !residency.evicted.contains(&id.untyped()) => {
+                residency.entries.remove(&id.untyped());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Evicts the least recently used unused assets while the [`AssetResidency`] exceeds its budget.
+pub(crate) fn evict_unused_assets(world: &mut World) {
+    world.resource_scope(|world, mut residency: Mut<AssetResidency>| {
+        residency.tick += 1;
+        let tick = residency.tick;
+        let mut resident_bytes = 0;
+        let mut unused = Vec::new();
+        let residency = &mut *residency;
+        for (id, entry) in &mut residency.entries {
+            if residency.evicted.contains(id) {
+                continue;
+            }
+            resident_bytes += entry.size;
+            if entry.is_used() {
+                entry.last_used = tick;
+            } else {
+                unused.push((entry.last_used, *id));
+            }
+        }
+        if resident_bytes <= residency.budget {
+            return;
+        }
+
+        unused.sort_unstable_by_key(|(last_used, _)| *last_used);
+        for (_, id) in unused {
+            if resident_bytes <= residency.budget {
+                break;
+            }
+            let Some(residency_type) = residency.types.get(&id.type_id()) else {
+                continue;
+            };
+            resident_bytes -= residency.entries[&id].size;
+            residency.evicted.insert(id);
+            (residency_type.evict)(world, id);
+        }
+    });
+}
+
+/// Records the [`AssetTypeResidency`] of every registered asset type as diagnostics.
+pub(crate) fn asset_residency_diagnostics(
+    residency: Res<AssetResidency>,
+    mut diagnostics: Diagnostics,
+) {
+    for (type_id, usage) in residency.usage_by_type() {
+        let paths = &residency.types[&type_id].diagnostics;
+        for (path, value) in paths.iter().zip(usage.measurements()) {
+            diagnostics.add_measurement(path, || value as f64);
+        }
+    }
+}
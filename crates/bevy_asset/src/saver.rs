@@ -1,9 +1,13 @@
 use crate::transformer::TransformedAsset;
-use crate::{io::Writer, meta::Settings, Asset, ErasedLoadedAsset};
+use crate::{
+    io::Writer,
+    meta::{AssetAction, AssetMeta, AssetMetaDyn, Settings},
+    Asset, ErasedLoadedAsset,
+};
 use crate::{AssetLoader, Handle, LabeledAsset, UntypedHandle};
 use bevy_utils::{BoxedFuture, CowArc, HashMap};
 use serde::{Deserialize, Serialize};
-use std::{borrow::Borrow, hash::Hash, ops::Deref};
+use std::{any::TypeId, borrow::Borrow, hash::Hash, ops::Deref};
 
 /// Saves an [`Asset`] of a given [`AssetSaver::Asset`] type. [`AssetSaver::OutputLoader`] will then be used to load the saved asset
 /// in the final deployed application. The saver should produce asset bytes in a format that [`AssetSaver::OutputLoader`] can read.
@@ -38,8 +42,25 @@ pub trait ErasedAssetSaver: Send + Sync + 'static {
         settings: &'a dyn Settings,
     ) -> BoxedFuture<'a, Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>>;
 
+    /// Saves the given runtime [`ErasedLoadedAsset`] with the default [`AssetSaver::Settings`] by writing it to a byte format
+    /// using `writer`. Returns the meta that loads the saved asset with [`AssetSaver::OutputLoader`].
+    fn save_with_meta<'a>(
+        &'a self,
+        writer: &'a mut Writer,
+        asset: &'a ErasedLoadedAsset,
+    ) -> BoxedFuture<
+        'a,
+        Result<Box<dyn AssetMetaDyn>, Box<dyn std::error::Error + Send + Sync + 'static>>,
+    >;
+
     /// The type name of the [`AssetSaver`].
     fn type_name(&self) -> &'static str;
+
+    /// The [`TypeId`] of the [`Asset`] saved by the [`AssetSaver`].
+    fn asset_type_id(&self) -> TypeId;
+
+    /// The type name of the [`AssetSaver::OutputLoader`].
+    fn output_loader_type_name(&self) -> &'static str;
 }
 
 impl<S: AssetSaver> ErasedAssetSaver for S {
@@ -60,9 +81,38 @@ impl<S: AssetSaver> ErasedAssetSaver for S {
             Ok(())
         })
     }
+    fn save_with_meta<'a>(
+        &'a self,
+        writer: &'a mut Writer,
+        asset: &'a ErasedLoadedAsset,
+    ) -> BoxedFuture<
+        'a,
+        Result<Box<dyn AssetMetaDyn>, Box<dyn std::error::Error + Send + Sync + 'static>>,
+    > {
+        Box::pin(async move {
+            let saved_asset = SavedAsset::<S::Asset>::from_loaded(asset).unwrap();
+            let settings = S::Settings::default();
+            let loader_settings = self
+                .save(writer, saved_asset, &settings)
+                .await
+                .map_err(Into::into)?;
+            let meta: Box<dyn AssetMetaDyn> =
+                Box::new(AssetMeta::<S::OutputLoader, ()>::new(AssetAction::Load {
+                    loader: self.output_loader_type_name().to_string(),
+                    settings: loader_settings,
+                }));
+            Ok(meta)
+        })
+    }
     fn type_name(&self) -> &'static str {
         std::any::type_name::<S>()
     }
+    fn asset_type_id(&self) -> TypeId {
+        TypeId::of::<S::Asset>()
+    }
+    fn output_loader_type_name(&self) -> &'static str {
+        std::any::type_name::<S::OutputLoader>()
+    }
 }
 
 /// An [`Asset`] (and any labeled "sub assets") intended to be saved.
@@ -0,0 +1,263 @@
+use crate::{
+    AssetPath, DependencyLoadState, LoadState, RecursiveDependencyLoadState, UntypedAssetId,
+};
+use bevy_utils::{HashMap, HashSet};
+use serde::Serialize;
+use std::fmt::Write;
+
+/// A snapshot of the assets tracked by an [`AssetServer`](crate::AssetServer) and of the
+/// dependencies between them, created with
+/// [`AssetServer::asset_graph`](crate::AssetServer::asset_graph).
+///
+/// This can be used to find out what an asset pulled in, why an asset is still alive, or be
+/// exported with [`AssetGraph::to_dot`] and [`AssetGraph::to_json`] to be inspected by other tools.
+#[derive(Debug, Clone, Default)]
+pub struct AssetGraph {
+    pub(crate) nodes: HashMap<UntypedAssetId, AssetNode>,
+}
+
+/// An asset in an [`AssetGraph`].
+#[derive(Debug, Clone)]
+pub struct AssetNode {
+    /// The id of the asset.
+    pub id: UntypedAssetId,
+    /// The path of the asset, if it was loaded from a path.
+    pub path: Option<AssetPath<'static>>,
+    /// The type name of the [`AssetLoader`](crate::AssetLoader) that loaded the asset, if it
+    /// was loaded directly. Labeled assets are loaded by the loader of their base asset.
+    pub loader: Option<&'static str>,
+    /// The [`LoadState`] of the asset.
+    pub load_state: LoadState,
+    /// The [`DependencyLoadState`] of the asset.
+    pub dep_load_state: DependencyLoadState,
+    /// The [`RecursiveDependencyLoadState`] of the asset.
+    pub rec_dep_load_state: RecursiveDependencyLoadState,
+    /// The number of strong [`Handle`](crate::Handle)s keeping the asset alive.
+    pub strong_handles: usize,
+    /// The assets this asset directly depends on.
+    pub dependencies: HashSet<UntypedAssetId>,
+    /// The assets that directly depend on this asset.
+    pub dependants: HashSet<UntypedAssetId>,
+    /// The labeled sub-assets of this asset.
+    pub labeled_assets: HashSet<UntypedAssetId>,
+    /// The paths of the assets read by the loader of this asset. This is only tracked when
+    /// watching for changes.
+    pub loader_dependencies: Vec<AssetPath<'static>>,
+}
+
+impl AssetGraph {
+    /// Returns the [`AssetNode`] of the asset with the given `id`.
+    pub fn get(&self, id: impl Into<UntypedAssetId>) -> Option<&AssetNode> {
+        self.nodes.get(&id.into())
+    }
+
+    /// Returns the [`AssetNode`] of every asset in the graph, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &AssetNode> {
+        self.nodes.values()
+    }
+
+    /// Returns the number of assets in the graph.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the graph contains no assets.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the assets the asset with the given `id` directly depends on.
+    pub fn dependencies(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        self.get(id)
+            .map(|node| node.dependencies.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns every asset the asset with the given `id` depends on, directly or through other
+    /// dependencies.
+    pub fn recursive_dependencies(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        self.walk(id.into(), |node| &node.dependencies)
+    }
+
+    /// Returns the assets that directly depend on the asset with the given `id`.
+    pub fn dependants(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        self.get(id)
+            .map(|node| node.dependants.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns every asset that depends on the asset with the given `id`, directly or through
+    /// other dependants.
+    pub fn recursive_dependants(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        self.walk(id.into(), |node| &node.dependants)
+    }
+
+    /// Returns the labeled sub-assets of the asset with the given `id`.
+    pub fn labeled_assets(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        self.get(id)
+            .map(|node| node.labeled_assets.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the assets reachable from `id` through `edges`, excluding `id` itself, in
+    /// breadth-first order.
+    fn walk(
+        &self,
+        id: UntypedAssetId,
+        edges: impl Fn(&AssetNode) -> &HashSet<UntypedAssetId>,
+    ) -> Vec<UntypedAssetId> {
+        let mut visited = HashSet::new();
+        visited.insert(id);
+        let mut reached = Vec::new();
+        let mut next = 0;
+        let mut current = Some(id);
+        while let Some(id) = current {
+            if let Some(node) = self.nodes.get(&id) {
+                reached.extend(edges(node).iter().filter(|id| visited.insert(**id)));
+            }
+            current = reached.get(next).copied();
+            next += 1;
+        }
+        reached
+    }
+
+    /// Returns the nodes sorted by path, so that exports are stable.
+    fn sorted_nodes(&self) -> (Vec<&AssetNode>, HashMap<UntypedAssetId, usize>) {
+        let mut nodes: Vec<&AssetNode> = self.nodes.values().collect();
+        nodes.sort_by_cached_key(|node| {
+            (
+                node.path.as_ref().map(ToString::to_string),
+                node.id.to_string(),
+            )
+        });
+        let indices = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.id, index))
+            .collect();
+        (nodes, indices)
+    }
+
+    /// Exports the graph in the [DOT](https://graphviz.org/doc/info/lang.html) format of
+    /// Graphviz.
+    ///
+    /// Every asset is a node labeled with its path, and every dependency is an edge from the
+    /// dependant to its dependency. Labeled sub-assets are linked to their base asset with a
+    /// dashed edge.
+    pub fn to_dot(&self) -> String {
+        let (nodes, indices) = self.sorted_nodes();
+        let mut dot = String::from("digraph assets {\n");
+        for (index, node) in nodes.iter().enumerate() {
+            let label = node_label(node);
+            let _ = writeln!(
+                dot,
+                "    {index} [label=\"{}\\n{:?}, {} handle(s)\"];",
+                escape(&label),
+                node.load_state,
+                node.strong_handles
+            );
+        }
+        for (index, node) in nodes.iter().enumerate() {
+            for dependency in sorted_indices(&node.dependencies, &indices) {
+                let _ = writeln!(dot, "    {index} -> {dependency};");
+            }
+            for labeled in sorted_indices(&node.labeled_assets, &indices) {
+                let _ = writeln!(dot, "    {index} -> {labeled} [style=dashed];");
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Exports the graph as JSON.
+    ///
+    /// The JSON document contains an `assets` array, where every asset is identified by its
+    /// position in the array. Dependencies, dependants and labeled sub-assets refer to other
+    /// assets by their position.
+    pub fn to_json(&self) -> String {
+        let (nodes, indices) = self.sorted_nodes();
+        let assets = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| JsonAssetNode {
+                id: index,
+                asset_id: node.id.to_string(),
+                path: node.path.as_ref(),
+                loader: node.loader,
+                load_state: node.load_state,
+                dependency_load_state: node.dep_load_state,
+                recursive_dependency_load_state: node.rec_dep_load_state,
+                strong_handles: node.strong_handles,
+                dependencies: sorted_indices(&node.dependencies, &indices),
+                dependants: sorted_indices(&node.dependants, &indices),
+                labeled_assets: sorted_indices(&node.labeled_assets, &indices),
+                loader_dependencies: &node.loader_dependencies,
+            })
+            .collect();
+        serde_json::to_string(&JsonAssetGraph { assets })
+            .expect("asset graphs only contain types that can be serialized to JSON")
+    }
+}
+
+/// The JSON export of an [`AssetGraph`].
+#[derive(Serialize)]
+struct JsonAssetGraph<'a> {
+    assets: Vec<JsonAssetNode<'a>>,
+}
+
+/// The JSON export of an [`AssetNode`], where assets are referred to by their position in
+/// [`JsonAssetGraph::assets`].
+#[derive(Serialize)]
+struct JsonAssetNode<'a> {
+    id: usize,
+    asset_id: String,
+    path: Option<&'a AssetPath<'static>>,
+    loader: Option<&'static str>,
+    load_state: LoadState,
+    dependency_load_state: DependencyLoadState,
+    recursive_dependency_load_state: RecursiveDependencyLoadState,
+    strong_handles: usize,
+    dependencies: Vec<usize>,
+    dependants: Vec<usize>,
+    labeled_assets: Vec<usize>,
+    loader_dependencies: &'a [AssetPath<'static>],
+}
+
+fn node_label(node: &AssetNode) -> String {
+    match &node.path {
+        Some(path) => path.to_string(),
+        None => node.id.to_string(),
+    }
+}
+
+/// Maps `ids` to their positions in an export, skipping ids that are not part of the graph.
+fn sorted_indices(
+    ids: &HashSet<UntypedAssetId>,
+    indices: &HashMap<UntypedAssetId, usize>,
+) -> Vec<usize> {
+    let mut ids: Vec<usize> = ids
+        .iter()
+        .filter_map(|id| indices.get(id).copied())
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+/// Escapes a string for use in a quoted DOT string.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
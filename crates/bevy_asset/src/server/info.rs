@@ -1,12 +1,13 @@
 use crate::{
     meta::{AssetHash, MetaTransform},
-    Asset, AssetHandleProvider, AssetLoadError, AssetPath, DependencyLoadState, ErasedLoadedAsset,
-    Handle, InternalAssetEvent, LoadState, RecursiveDependencyLoadState, StrongHandle,
-    UntypedAssetId, UntypedHandle,
+    Asset, AssetGraph, AssetHandleProvider, AssetLoadError, AssetNode, AssetPath,
+    DependencyLoadState, ErasedLoadedAsset, Handle, InternalAssetEvent, LoadState,
+    RecursiveDependencyLoadState, StrongHandle, UntypedAssetId, UntypedHandle,
 };
 use bevy_ecs::world::World;
 use bevy_log::warn;
-use bevy_utils::{Entry, HashMap, HashSet, TypeIdMap};
+use bevy_tasks::{ProgressReceiver, TaskPriority};
+use bevy_utils::{Entry, HashMap, HashSet, Instant, TypeIdMap};
 use crossbeam_channel::Sender;
 use std::{
     any::TypeId,
@@ -37,6 +38,23 @@ pub(crate) struct AssetInfo {
     /// The number of handle drops to skip for this asset.
     /// See usage (and comments) in get_or_create_path_handle for context.
     handle_drops_to_skip: usize,
+    /// The direct dependencies of this asset, as of its last load.
+    pub(crate) dependencies: HashSet<UntypedAssetId>,
+    /// The name of the [`AssetLoader`](crate::AssetLoader) used by the last load of this asset.
+    pub(crate) loader_name: Option<&'static str>,
+    /// The priority of the load of this asset, see [`AssetServer::load_with_priority`](crate::AssetServer::load_with_priority).
+    pub(crate) load_priority: TaskPriority,
+    /// The error that caused the last load of this asset to fail, if it failed.
+    pub(crate) load_error: Option<AssetLoadError>,
+    /// The number of times the current load of this asset has been retried, see [`RetryPolicy`](crate::io::RetryPolicy).
+    pub(crate) load_retries: u32,
+    /// The bytes read by the last load of this asset, if it was loaded by the [`AssetServer`](crate::AssetServer).
+    pub(crate) byte_progress: Option<ProgressReceiver>,
+    /// The [`ProcessedInfo::content_hash`](crate::meta::ProcessedInfo::content_hash) of the last load of this asset.
+    pub(crate) content_hash: Option<AssetHash>,
+    /// The asset whose value this asset shares, see [`Assets::shared_with`](crate::Assets::shared_with).
+    /// This keeps the shared asset alive.
+    pub(crate) shared_with: Option<UntypedHandle>,
 }
 
 impl AssetInfo {
@@ -55,6 +73,14 @@ impl AssetInfo {
             dependants_waiting_on_load: HashSet::default(),
             dependants_waiting_on_recursive_dep_load: HashSet::default(),
             handle_drops_to_skip: 0,
+            dependencies: HashSet::default(),
+            loader_name: None,
+            load_priority: TaskPriority::default(),
+            load_error: None,
+            load_retries: 0,
+            byte_progress: None,
+            content_hash: None,
+            shared_with: None,
         }
     }
 }
@@ -76,6 +102,16 @@ pub(crate) struct AssetInfos {
     pub(crate) dependency_loaded_event_sender: TypeIdMap<fn(&mut World, UntypedAssetId)>,
     pub(crate) dependency_failed_event_sender:
         TypeIdMap<fn(&mut World, UntypedAssetId, AssetPath<'static>, AssetLoadError)>,
+    /// Failed loads waiting to be retried, with the time at which they should be retried.
+    pub(crate) pending_retries: Vec<(Instant, UntypedAssetId, AssetPath<'static>)>,
+    /// The assets loaded first with a given content hash, which later assets with the same content share.
+    content_originals: HashMap<AssetHash, UntypedAssetId>,
+    /// Assets waiting for the asset they share the value of to finish loading.
+    pub(crate) pending_shares: Vec<(UntypedAssetId, UntypedHandle)>,
+    pub(crate) share_asset: TypeIdMap<fn(&mut World, UntypedAssetId, UntypedAssetId)>,
+    /// The hash of the asset and meta bytes last written by [`AssetServer::save_asset`](crate::AssetServer::save_asset)
+    /// for each path. Reloads of these paths are skipped while their content does not change.
+    pub(crate) saved_content: HashMap<AssetPath<'static>, AssetHash>,
 }
 
 impl std::fmt::Debug for AssetInfos {
@@ -327,6 +363,16 @@ impl AssetInfos {
             .filter_map(|id| self.get_id_handle(id))
     }
 
+    /// Marks the asset with the given `id` as not loaded after its value was removed, so that the next
+    /// load request for its path loads it again.
+    pub(crate) fn unload(&mut self, id: UntypedAssetId) {
+        if let Some(info) = self.infos.get_mut(&id) {
+            info.load_state = LoadState::NotLoaded;
+            info.dep_load_state = DependencyLoadState::NotLoaded;
+            info.rec_dep_load_state = RecursiveDependencyLoadState::NotLoaded;
+        }
+    }
+
     pub(crate) fn get_id_handle(&self, id: UntypedAssetId) -> Option<UntypedHandle> {
         let info = self.infos.get(&id)?;
         let strong_handle = info.weak_handle.upgrade()?;
@@ -379,7 +425,48 @@ impl AssetInfos {
         sender: &Sender<InternalAssetEvent>,
     ) {
         loaded_asset.value.insert(loaded_asset_id, world);
-        let mut loading_deps = loaded_asset.dependencies;
+        if let Some(info) = self.get_mut(loaded_asset_id) {
+            info.shared_with = None;
+        }
+        self.process_dependencies(
+            loaded_asset_id,
+            loaded_asset.dependencies,
+            loaded_asset.loader_dependencies,
+            sender,
+        );
+    }
+
+    /// Updates [`AssetInfo`] / load state for an asset that shares the value of the loaded
+    /// `original` asset (and relevant dependencies / dependants). See [`Assets::shared_with`](crate::Assets::shared_with).
+    pub(crate) fn process_asset_share(
+        &mut self,
+        id: UntypedAssetId,
+        original: UntypedHandle,
+        world: &mut World,
+        sender: &Sender<InternalAssetEvent>,
+    ) {
+        let share = self
+            .share_asset
+            .get(&id.type_id())
+            .expect("Asset share function should exist");
+        share(world, id, original.id());
+        let dependencies = HashSet::from_iter([original.id()]);
+        if let Some(info) = self.get_mut(id) {
+            info.shared_with = Some(original);
+        }
+        self.process_dependencies(id, dependencies, HashMap::default(), sender);
+    }
+
+    /// Updates the load state of a loaded asset according to the state of its `dependencies`, and
+    /// the state of its dependants.
+    fn process_dependencies(
+        &mut self,
+        loaded_asset_id: UntypedAssetId,
+        dependencies: HashSet<UntypedAssetId>,
+        loader_dependencies: HashMap<AssetPath<'static>, AssetHash>,
+        sender: &Sender<InternalAssetEvent>,
+    ) {
+        let mut loading_deps = dependencies.clone();
         let mut failed_deps = HashSet::new();
         let mut loading_rec_deps = loading_deps.clone();
         let mut failed_rec_deps = HashSet::new();
@@ -455,7 +542,7 @@ impl AssetInfos {
                     .get(&loaded_asset_id)
                     .expect("Asset info should always exist at this point");
                 if let Some(asset_path) = &info.path {
-                    for loader_dependency in loaded_asset.loader_dependencies.keys() {
+                    for loader_dependency in loader_dependencies.keys() {
                         let dependants = self
                             .loader_dependants
                             .entry(loader_dependency.clone())
@@ -467,15 +554,18 @@ impl AssetInfos {
             let info = self
                 .get_mut(loaded_asset_id)
                 .expect("Asset info should always exist at this point");
+            info.dependencies = dependencies;
             info.loading_dependencies = loading_deps;
             info.failed_dependencies = failed_deps;
             info.loading_rec_dependencies = loading_rec_deps;
             info.failed_rec_dependencies = failed_rec_deps;
             info.load_state = LoadState::Loaded;
+            info.load_error = None;
+            info.load_retries = 0;
             info.dep_load_state = dep_load_state;
             info.rec_dep_load_state = rec_dep_load_state;
             if watching_for_changes {
-                info.loader_dependencies = loaded_asset.loader_dependencies;
+                info.loader_dependencies = loader_dependencies;
             }
 
             let dependants_waiting_on_rec_load = if matches!(
@@ -582,12 +672,63 @@ impl AssetInfos {
         }
     }
 
-    pub(crate) fn process_asset_fail(&mut self, failed_id: UntypedAssetId) {
+    /// Sets the content hash of the asset with the given `id`, and returns the paths of the assets
+    /// sharing its value if its content changed, as they must be loaded again.
+    pub(crate) fn set_content_hash(
+        &mut self,
+        id: UntypedAssetId,
+        content_hash: AssetHash,
+    ) -> Vec<AssetPath<'static>> {
+        let Some(info) = self.get_mut(id) else {
+            return Vec::new();
+        };
+        let previous = info.content_hash.replace(content_hash);
+        if previous.is_none() || previous == Some(content_hash) {
+            return Vec::new();
+        }
+        self.infos
+            .values()
+            .filter(|info| {
+                info.shared_with
+                    .as_ref()
+                    .is_some_and(|original| original.id() == id)
+            })
+            .filter_map(|info| info.path.clone())
+            .collect()
+    }
+
+    /// Returns the handle of the asset other than `id` that was loaded first with the given
+    /// content hash, if it is still alive and its content did not change since.
+    pub(crate) fn get_content_original(
+        &self,
+        id: UntypedAssetId,
+        content_hash: AssetHash,
+    ) -> Option<UntypedHandle> {
+        let original = *self.content_originals.get(&content_hash)?;
+        if original == id || original.type_id() != id.type_id() {
+            return None;
+        }
+        let info = self.get(original)?;
+        if info.content_hash != Some(content_hash) || info.shared_with.is_some() {
+            return None;
+        }
+        self.get_id_handle(original)
+    }
+
+    /// Makes the asset with the given `id` the one assets loaded later with the given content
+    /// hash share their value with.
+    pub(crate) fn insert_content_original(&mut self, id: UntypedAssetId, content_hash: AssetHash) {
+        self.content_originals.insert(content_hash, id);
+    }
+
+    pub(crate) fn process_asset_fail(&mut self, failed_id: UntypedAssetId, error: AssetLoadError) {
         let (dependants_waiting_on_load, dependants_waiting_on_rec_load) = {
             let info = self
                 .get_mut(failed_id)
                 .expect("Asset info should always exist at this point");
             info.load_state = LoadState::Failed;
+            info.load_error = Some(error);
+            info.load_retries = 0;
             info.dep_load_state = DependencyLoadState::Failed;
             info.rec_dep_load_state = RecursiveDependencyLoadState::Failed;
             (
@@ -609,6 +750,78 @@ impl AssetInfos {
         }
     }
 
+    /// Creates a snapshot of every tracked asset and of the dependencies between them.
+    pub(crate) fn asset_graph(&self) -> AssetGraph {
+        let mut nodes: HashMap<UntypedAssetId, AssetNode> = self
+            .infos
+            .iter()
+            .map(|(id, info)| {
+                let mut loader_dependencies: Vec<AssetPath<'static>> =
+                    info.loader_dependencies.keys().cloned().collect();
+                loader_dependencies.sort_by_cached_key(ToString::to_string);
+                let node = AssetNode {
+                    id: *id,
+                    path: info.path.clone(),
+                    loader: info.loader_name,
+                    load_state: info.load_state,
+                    dep_load_state: info.dep_load_state,
+                    rec_dep_load_state: info.rec_dep_load_state,
+                    strong_handles: info.weak_handle.strong_count(),
+                    dependencies: info.dependencies.clone(),
+                    dependants: HashSet::default(),
+                    labeled_assets: HashSet::default(),
+                    loader_dependencies,
+                };
+                (*id, node)
+            })
+            .collect();
+        for (id, info) in &self.infos {
+            for dependency in &info.dependencies {
+                if let Some(node) = nodes.get_mut(dependency) {
+                    node.dependants.insert(*id);
+                }
+            }
+            let Some(path) = info.path.as_ref().filter(|path| path.label().is_some()) else {
+                continue;
+            };
+            let base_path = path.without_label().into_owned();
+            let Some(base_ids) = self.path_to_id.get(&base_path) else {
+                continue;
+            };
+            for base_id in base_ids.values() {
+                if let Some(node) = nodes.get_mut(base_id) {
+                    node.labeled_assets.insert(*id);
+                }
+            }
+        }
+        AssetGraph { nodes }
+    }
+
+    /// Collects the errors of `id` and of its failed dependencies (recursively) into `errors`.
+    pub(crate) fn collect_load_errors(
+        &self,
+        id: UntypedAssetId,
+        visited: &mut HashSet<UntypedAssetId>,
+        errors: &mut Vec<(UntypedAssetId, AssetLoadError)>,
+    ) {
+        if !visited.insert(id) {
+            return;
+        }
+        let Some(info) = self.get(id) else {
+            return;
+        };
+        if let Some(error) = &info.load_error {
+            errors.push((id, error.clone()));
+        }
+        for dep_id in info
+            .failed_dependencies
+            .iter()
+            .chain(info.failed_rec_dependencies.iter())
+        {
+            self.collect_load_errors(*dep_id, visited, errors);
+        }
+    }
+
     fn remove_dependants_and_labels(
         info: &AssetInfo,
         loader_dependants: &mut HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
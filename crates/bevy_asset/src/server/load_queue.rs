@@ -0,0 +1,237 @@
+use crate::UntypedAssetId;
+use bevy_tasks::TaskPriority;
+use bevy_utils::HashSet;
+use parking_lot::Mutex;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+/// Limits the number of loads running concurrently for an [`AssetSource`](crate::io::AssetSource).
+///
+/// Loads that exceed the limit wait in the queue, and are started by order of [`TaskPriority`],
+/// then by order of arrival.
+pub(crate) struct LoadQueue {
+    limit: usize,
+    state: Mutex<LoadQueueState>,
+}
+
+#[derive(Default)]
+struct LoadQueueState {
+    running: usize,
+    next_ticket: u64,
+    waiting: Vec<WaitingLoad>,
+    /// Tickets of waiting loads that have been allowed to start, but were not polled since.
+    granted: HashSet<u64>,
+}
+
+struct WaitingLoad {
+    ticket: u64,
+    id: Option<UntypedAssetId>,
+    priority: TaskPriority,
+    waker: Waker,
+}
+
+impl LoadQueueState {
+    /// Allows the waiting load with the highest priority to start, if the limit allows it.
+    fn grant_next(&mut self, limit: usize) {
+        while self.running < limit && !self.waiting.is_empty() {
+            let (index, _) = self
+                .waiting
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, load)| (load.priority, std::cmp::Reverse(load.ticket)))
+                .unwrap();
+            let load = self.waiting.swap_remove(index);
+            self.running += 1;
+            self.granted.insert(load.ticket);
+            load.waker.wake();
+        }
+    }
+}
+
+impl LoadQueue {
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            // A limit of zero would never start any load.
+            limit: limit.max(1),
+            state: Default::default(),
+        }
+    }
+
+    pub(crate) fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Waits until the load of the asset with the given `id` can start. The load counts against
+    /// the limit until the returned [`LoadPermit`] is dropped.
+    pub(crate) fn acquire(
+        &self,
+        id: Option<UntypedAssetId>,
+        priority: TaskPriority,
+    ) -> AcquireLoad<'_> {
+        AcquireLoad {
+            queue: self,
+            id,
+            priority,
+            ticket: None,
+        }
+    }
+
+    /// Changes the priority of the waiting loads of the asset with the given `id`.
+    pub(crate) fn set_priority(&self, id: UntypedAssetId, priority: TaskPriority) {
+        let mut state = self.state.lock();
+        for load in &mut state.waiting {
+            if load.id == Some(id) {
+                load.priority = priority;
+            }
+        }
+    }
+
+    /// Returns the number of running and waiting loads.
+    #[cfg(test)]
+    pub(crate) fn counts(&self) -> (usize, usize) {
+        let state = self.state.lock();
+        (state.running, state.waiting.len())
+    }
+
+    fn release(&self) {
+        let mut state = self.state.lock();
+        state.running -= 1;
+        state.grant_next(self.limit);
+    }
+}
+
+/// A [`Future`] waiting for a load to be allowed to start, see [`LoadQueue::acquire`].
+pub(crate) struct AcquireLoad<'a> {
+    queue: &'a LoadQueue,
+    id: Option<UntypedAssetId>,
+    priority: TaskPriority,
+    ticket: Option<u64>,
+}
+
+impl<'a> Future for AcquireLoad<'a> {
+    type Output = LoadPermit<'a>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let queue = self.queue;
+        let mut state = queue.state.lock();
+        match self.ticket {
+            None => {
+                if state.running < queue.limit && state.waiting.is_empty() {
+                    state.running += 1;
+                    return Poll::Ready(LoadPermit { queue });
+                }
+                let ticket = state.next_ticket;
+                state.next_ticket += 1;
+                state.waiting.push(WaitingLoad {
+                    ticket,
+                    id: self.id,
+                    priority: self.priority,
+                    waker: cx.waker().clone(),
+                });
+                drop(state);
+                self.ticket = Some(ticket);
+                Poll::Pending
+            }
+            Some(ticket) => {
+                if state.granted.remove(&ticket) {
+                    drop(state);
+                    self.ticket = None;
+                    return Poll::Ready(LoadPermit { queue });
+                }
+                if let Some(load) = state.waiting.iter_mut().find(|load| load.ticket == ticket) {
+                    load.waker.clone_from(cx.waker());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for AcquireLoad<'_> {
+    fn drop(&mut self) {
+        let Some(ticket) = self.ticket else {
+            return;
+        };
+        // The load was canceled while waiting.
+        let mut state = self.queue.state.lock();
+        if state.granted.remove(&ticket) {
+            state.running -= 1;
+            state.grant_next(self.queue.limit);
+        } else {
+            state.waiting.retain(|load| load.ticket != ticket);
+        }
+    }
+}
+
+/// Allows a load to run, see [`LoadQueue::acquire`].
+pub(crate) struct LoadPermit<'a> {
+    queue: &'a LoadQueue,
+}
+
+impl Drop for LoadPermit<'_> {
+    fn drop(&mut self) {
+        self.queue.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_tasks::futures_lite::{future::poll_once, pin};
+    use futures_lite::future::block_on;
+
+    #[test]
+    fn loads_start_by_priority() {
+        let queue = LoadQueue::new(1);
+        let running = block_on(queue.acquire(None, TaskPriority::Normal));
+
+        let low = queue.acquire(None, TaskPriority::Low);
+        let normal = queue.acquire(None, TaskPriority::Normal);
+        let bumped_id = UntypedAssetId::from(crate::AssetId::<()>::invalid());
+        let bumped = queue.acquire(Some(bumped_id), TaskPriority::Low);
+        pin!(low, normal, bumped);
+        assert!(block_on(poll_once(&mut low)).is_none());
+        assert!(block_on(poll_once(&mut normal)).is_none());
+        assert!(block_on(poll_once(&mut bumped)).is_none());
+        assert_eq!(queue.counts(), (1, 3));
+
+        queue.set_priority(bumped_id, TaskPriority::High);
+        drop(running);
+        assert!(block_on(poll_once(&mut low)).is_none());
+        assert!(block_on(poll_once(&mut normal)).is_none());
+        let permit = block_on(poll_once(&mut bumped)).unwrap();
+        drop(permit);
+        assert!(block_on(poll_once(&mut low)).is_none());
+        let permit = block_on(poll_once(&mut normal)).unwrap();
+        assert_eq!(queue.counts(), (1, 1));
+        drop(permit);
+        drop(block_on(poll_once(&mut low)).unwrap());
+        assert_eq!(queue.counts(), (0, 0));
+    }
+
+    #[test]
+    fn canceled_loads_leave_the_queue() {
+        let queue = LoadQueue::new(1);
+        let running = block_on(queue.acquire(None, TaskPriority::Normal));
+        {
+            let waiting = queue.acquire(None, TaskPriority::High);
+            pin!(waiting);
+            assert!(block_on(poll_once(&mut waiting)).is_none());
+            assert_eq!(queue.counts(), (1, 1));
+        }
+        assert_eq!(queue.counts(), (1, 0));
+
+        {
+            let granted = queue.acquire(None, TaskPriority::High);
+            pin!(granted);
+            assert!(block_on(poll_once(&mut granted)).is_none());
+            drop(running);
+            // The load was allowed to start, but is dropped before being polled again.
+            assert_eq!(queue.counts(), (1, 0));
+        }
+        assert_eq!(queue.counts(), (0, 0));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     loader::{AssetLoader, ErasedAssetLoader},
+    meta::MetaMigrations,
     path::AssetPath,
 };
 use async_broadcast::RecvError;
@@ -16,6 +17,9 @@ pub(crate) struct AssetLoaders {
     extension_to_loaders: HashMap<String, Vec<usize>>,
     type_name_to_loader: HashMap<&'static str, usize>,
     preregistered_loaders: HashMap<&'static str, usize>,
+    /// The settings migrations of loaders and processors. They are stored here so that they are shared by the
+    /// main [`AssetServer`](crate::AssetServer) and the one of the [`AssetProcessor`](crate::processor::AssetProcessor).
+    pub(crate) meta_migrations: MetaMigrations,
 }
 
 impl AssetLoaders {
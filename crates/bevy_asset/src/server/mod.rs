@@ -1,33 +1,51 @@
+mod graph;
 mod info;
+pub(crate) mod load_queue;
 mod loaders;
 
+pub use graph::*;
+
 use crate::{
     folder::LoadedFolder,
     io::{
         AssetReader, AssetReaderError, AssetSource, AssetSourceEvent, AssetSourceId, AssetSources,
-        MissingAssetSourceError, MissingProcessedAssetReaderError, Reader,
+        AssetWriterError, MissingAssetSourceError, MissingAssetWriterError,
+        MissingProcessedAssetReaderError, Reader,
     },
     loader::{AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset},
     meta::{
-        loader_settings_meta_transform, AssetActionMinimal, AssetMetaDyn, AssetMetaMinimal,
-        MetaTransform, Settings,
+        get_asset_hash, loader_settings_meta_transform, AssetActionMinimal, AssetMetaDyn,
+        AssetMetaMinimal, MetaTransform, Settings,
     },
     path::AssetPath,
+    processor::Process,
+    saver::{AssetSaver, ErasedAssetSaver},
     Asset, AssetEvent, AssetHandleProvider, AssetId, AssetLoadFailedEvent, AssetMetaCheck, Assets,
     DeserializeMetaError, ErasedLoadedAsset, Handle, LoadedUntypedAsset, UntypedAssetId,
     UntypedAssetLoadFailedEvent, UntypedHandle,
 };
 use bevy_ecs::prelude::*;
-use bevy_log::{error, info};
-use bevy_tasks::IoTaskPool;
-use bevy_utils::{CowArc, HashSet};
+use bevy_log::{error, info, warn};
+use bevy_tasks::{
+    progress_channel, IoTaskPool, Progress, ProgressReceiver, ProgressSender, TaskPriority,
+};
+use bevy_utils::{CowArc, Duration, HashSet, Instant, TypeIdMap};
 use crossbeam_channel::{Receiver, Sender};
-use futures_lite::StreamExt;
+use futures_io::AsyncRead;
+use futures_lite::{AsyncReadExt, StreamExt};
 use info::*;
 use loaders::*;
 use parking_lot::RwLock;
+use serde::Serialize;
 use std::path::PathBuf;
-use std::{any::TypeId, path::Path, sync::Arc};
+use std::{
+    any::TypeId,
+    future::Future,
+    path::Path,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
 use thiserror::Error;
 
 /// Loads and tracks the state of [`Asset`] values from a configured [`AssetReader`]. This can be used to kick off new asset loads and
@@ -53,6 +71,7 @@ pub struct AssetServer {
 pub(crate) struct AssetServerData {
     pub(crate) infos: RwLock<AssetInfos>,
     pub(crate) loaders: Arc<RwLock<AssetLoaders>>,
+    savers: RwLock<TypeIdMap<Vec<Arc<dyn ErasedAssetSaver>>>>,
     asset_event_sender: Sender<InternalAssetEvent>,
     asset_event_receiver: Receiver<InternalAssetEvent>,
     sources: AssetSources,
@@ -117,6 +136,7 @@ impl AssetServer {
                 asset_event_sender,
                 asset_event_receiver,
                 loaders,
+                savers: Default::default(),
                 infos: RwLock::new(infos),
             }),
         }
@@ -140,6 +160,94 @@ impl AssetServer {
         self.data.loaders.write().push(loader);
     }
 
+    /// Registers a new [`AssetSaver`], which [`AssetServer::save`] and [`AssetServer::save_asset`] use to save assets of
+    /// type [`AssetSaver::Asset`] at runtime.
+    pub fn register_saver<S: AssetSaver>(&self, saver: S) {
+        self.data
+            .savers
+            .write()
+            .entry(TypeId::of::<S::Asset>())
+            .or_default()
+            .push(Arc::new(saver));
+    }
+
+    /// Registers a migration of the settings of the [`AssetLoader`] `L` from `from_version` to `from_version + 1`.
+    /// See [`MetaMigrations`](crate::meta::MetaMigrations).
+    pub fn register_loader_settings_migration<L, From, To>(
+        &self,
+        from_version: u32,
+        migrate: impl Fn(From) -> To + Send + Sync + 'static,
+    ) where
+        L: AssetLoader,
+        From: for<'a> serde::Deserialize<'a> + 'static,
+        To: serde::Serialize + 'static,
+    {
+        self.data
+            .loaders
+            .write()
+            .meta_migrations
+            .add_loader_migration::<L, From, To>(from_version, migrate);
+    }
+
+    /// Registers a migration of the settings of the [`Process`] implementation `P` from `from_version` to
+    /// `from_version + 1`. See [`MetaMigrations`](crate::meta::MetaMigrations).
+    pub fn register_processor_settings_migration<P, From, To>(
+        &self,
+        from_version: u32,
+        migrate: impl Fn(From) -> To + Send + Sync + 'static,
+    ) where
+        P: Process,
+        From: for<'a> serde::Deserialize<'a> + 'static,
+        To: serde::Serialize + 'static,
+    {
+        self.data
+            .loaders
+            .write()
+            .meta_migrations
+            .add_processor_migration::<P, From, To>(from_version, migrate);
+    }
+
+    /// Sets whether meta files that were migrated to the current settings version are written back to their
+    /// [`AssetSource`]. This is disabled by default, which migrates meta files in memory every time they are read.
+    ///
+    /// Meta files are only rewritten when they are read from an unprocessed [`AssetSource`] that has a writer.
+    pub fn set_rewrite_migrated_meta(&self, rewrite: bool) {
+        self.data.loaders.write().meta_migrations.rewrite = rewrite;
+    }
+
+    /// Upgrades `meta_bytes` read for the asset at `path` from `source` to the `current_version` of the settings of
+    /// the loader or processor `name`. If enabled and `rewrite` is `true`, migrated meta is written back to `source`.
+    pub(crate) async fn migrate_meta(
+        &self,
+        source: &AssetSource,
+        path: &Path,
+        name: &str,
+        current_version: u32,
+        meta_bytes: Vec<u8>,
+        rewrite: bool,
+    ) -> Result<Vec<u8>, DeserializeMetaError> {
+        let (migrated, rewrite) = {
+            let loaders = self.data.loaders.read();
+            let migrations = &loaders.meta_migrations;
+            let migrated = migrations.migrate(name, current_version, &meta_bytes)?;
+            (migrated, rewrite && migrations.rewrite)
+        };
+        let Some(migrated) = migrated else {
+            return Ok(meta_bytes);
+        };
+        if rewrite {
+            match source.writer() {
+                Ok(writer) => {
+                    if let Err(err) = writer.write_meta_bytes(path, &migrated).await {
+                        error!("Failed to rewrite migrated meta for {path:?}: {err}");
+                    }
+                }
+                Err(err) => warn!("Cannot rewrite migrated meta for {path:?}: {err}"),
+            }
+        }
+        Ok(migrated)
+    }
+
     /// Registers a new [`Asset`] type. [`Asset`] types must be registered before assets of that type can be loaded.
     pub fn register_asset<A: Asset>(&self, assets: &Assets<A>) {
         self.register_handle_provider(assets.get_handle_provider());
@@ -163,12 +271,20 @@ impl AssetServer {
                 });
         }
 
+        fn share<A: Asset>(world: &mut World, id: UntypedAssetId, original: UntypedAssetId) {
+            world
+                .resource_mut::<Assets<A>>()
+                .share(id.typed(), original.typed());
+        }
+
         let mut infos = self.data.infos.write();
 
         infos
             .dependency_loaded_event_sender
             .insert(TypeId::of::<A>(), sender::<A>);
 
+        infos.share_asset.insert(TypeId::of::<A>(), share::<A>);
+
         infos
             .dependency_failed_event_sender
             .insert(TypeId::of::<A>(), failed_sender::<A>);
@@ -266,7 +382,51 @@ impl AssetServer {
     /// The asset load will fail and an error will be printed to the logs if the asset stored at `path` is not of type `A`.
     #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
     pub fn load<'a, A: Asset>(&self, path: impl Into<AssetPath<'a>>) -> Handle<A> {
-        self.load_with_meta_transform(path, None)
+        self.load_with_meta_transform(path, None, TaskPriority::Normal)
+    }
+
+    /// Begins loading an [`Asset`] of type `A` stored at `path` with the given `priority`. This behaves like
+    /// [`AssetServer::load`], but loads with a higher priority are started first when the [`IoTaskPool`] is busy
+    /// or when the asset source limits the number of concurrent loads (see
+    /// [`AssetSourceBuilder::with_max_concurrent_loads`](crate::io::AssetSourceBuilder::with_max_concurrent_loads)).
+    ///
+    /// If the asset is already loading with a lower priority, its priority is raised to `priority`.
+    /// See [`AssetServer::set_load_priority`] to change the priority of a load afterwards.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_priority<'a, A: Asset>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        priority: TaskPriority,
+    ) -> Handle<A> {
+        self.load_with_meta_transform(path, None, priority)
+    }
+
+    /// Changes the priority of the load of the asset with the given `id`, for example to bump the priority of an
+    /// asset that was loaded in the background once it becomes urgent.
+    ///
+    /// This only affects loads that have not started yet because the asset source limits the number of concurrent
+    /// loads, and future reloads of the asset.
+    pub fn set_load_priority(&self, id: impl Into<UntypedAssetId>, priority: TaskPriority) {
+        let id = id.into();
+        let mut infos = self.data.infos.write();
+        let Some(info) = infos.get_mut(id) else {
+            return;
+        };
+        info.load_priority = priority;
+        for source in self.data.sources.iter() {
+            if let Some(queue) = source.load_queue() {
+                queue.set_priority(id, priority);
+            }
+        }
+    }
+
+    /// Returns the priority of the load of the asset with the given `id`, if it is managed by this [`AssetServer`].
+    pub fn get_load_priority(&self, id: impl Into<UntypedAssetId>) -> Option<TaskPriority> {
+        self.data
+            .infos
+            .read()
+            .get(id.into())
+            .map(|info| info.load_priority)
     }
 
     /// Begins loading an [`Asset`] of type `A` stored at `path`. The given `settings` function will override the asset's
@@ -278,13 +438,18 @@ impl AssetServer {
         path: impl Into<AssetPath<'a>>,
         settings: impl Fn(&mut S) + Send + Sync + 'static,
     ) -> Handle<A> {
-        self.load_with_meta_transform(path, Some(loader_settings_meta_transform(settings)))
+        self.load_with_meta_transform(
+            path,
+            Some(loader_settings_meta_transform(settings)),
+            TaskPriority::Normal,
+        )
     }
 
     fn load_with_meta_transform<'a, A: Asset>(
         &self,
         path: impl Into<AssetPath<'a>>,
         meta_transform: Option<MetaTransform>,
+        priority: TaskPriority,
     ) -> Handle<A> {
         let path = path.into().into_owned();
         let (handle, should_load) = self.data.infos.write().get_or_create_path_handle::<A>(
@@ -294,15 +459,23 @@ impl AssetServer {
         );
 
         if should_load {
+            if let Some(info) = self.data.infos.write().get_mut(handle.id().untyped()) {
+                info.load_priority = priority;
+            }
             let owned_handle = Some(handle.clone().untyped());
             let server = self.clone();
             IoTaskPool::get()
-                .spawn(async move {
+                .spawn_with_priority(priority, async move {
                     if let Err(err) = server.load_internal(owned_handle, path, false, None).await {
                         error!("{}", err);
                     }
                 })
                 .detach();
+        } else if self
+            .get_load_priority(&handle)
+            .is_some_and(|current| current < priority)
+        {
+            self.set_load_priority(&handle, priority);
         }
 
         handle
@@ -363,8 +536,13 @@ impl AssetServer {
         if !should_load {
             return handle;
         }
-        let id = handle.id().untyped();
+        self.spawn_load_untyped(handle.id().untyped(), path);
+        handle
+    }
 
+    /// Loads the asset at `path` without knowing its type, and stores its handle in the
+    /// [`LoadedUntypedAsset`] with the given `id`.
+    fn spawn_load_untyped(&self, id: UntypedAssetId, path: AssetPath<'static>) {
         let server = self.clone();
         IoTaskPool::get()
             .spawn(async move {
@@ -389,8 +567,6 @@ impl AssetServer {
                 }
             })
             .detach();
-
-        handle
     }
 
     /// Performs an async asset load.
@@ -407,9 +583,24 @@ impl AssetServer {
         let asset_type_id = input_handle.as_ref().map(|handle| handle.type_id());
 
         let path = path.into_owned();
+
+        // Wait for a slot if the asset source limits the number of concurrent loads. A missing
+        // source is reported below.
+        let source = self.get_source(path.source().clone_owned()).ok();
+        let _load_permit = match source.and_then(AssetSource::load_queue) {
+            Some(queue) => {
+                let id = input_handle.as_ref().map(UntypedHandle::id);
+                let priority = id
+                    .and_then(|id| self.get_load_priority(id))
+                    .unwrap_or_default();
+                Some(queue.acquire(id, priority).await)
+            }
+            None => None,
+        };
+
         let path_clone = path.clone();
-        let (mut meta, loader, mut reader) = self
-            .get_meta_loader_and_reader(&path_clone, asset_type_id)
+        let (mut meta, loader, mut reader, byte_len) = self
+            .get_meta_loader_reader_and_byte_len(&path_clone, asset_type_id)
             .await
             .map_err(|e| {
                 // if there was an input handle, a "load" operation has already started, so we must produce a "failure" event, if
@@ -492,14 +683,69 @@ impl AssetServer {
             (handle.clone().unwrap(), path.clone())
         };
 
+        // Settings changed by a meta transform could make the loaded asset differ from the processed content.
+        if path.label().is_none() && base_handle.meta_transform().is_none() {
+            if let Some(original) = self.get_shared_original(&base_handle, &*meta, &*loader) {
+                self.send_asset_event(InternalAssetEvent::Shared {
+                    id: base_handle.id(),
+                    original,
+                });
+                return Ok(base_handle);
+            }
+        }
+
         if let Some(meta_transform) = base_handle.meta_transform() {
             (*meta_transform)(&mut *meta);
         }
 
-        match self
-            .load_with_meta_loader_and_reader(&base_path, meta, &*loader, &mut *reader, true, false)
-            .await
-        {
+        // The processed bytes of a duplicate are empty, so labeled loads and loads with a meta
+        // transform, which cannot share the value of the original, read the bytes of the original.
+        let duplicate_of = meta
+            .processed_info()
+            .as_ref()
+            .and_then(|info| info.duplicate_of.clone());
+        let mut original_reader = None;
+        if let Some(original_path) = &duplicate_of {
+            match self.get_reader_and_byte_len(original_path).await {
+                Ok(reader_and_byte_len) => original_reader = Some(reader_and_byte_len),
+                Err(err) => {
+                    self.send_asset_event(InternalAssetEvent::Failed {
+                        id: base_handle.id(),
+                        error: err.clone(),
+                        path: path.into_owned(),
+                    });
+                    return Err(err);
+                }
+            }
+        }
+        let (reader, byte_len): (&mut Reader, _) = match &mut original_reader {
+            Some((original_reader, original_byte_len)) => {
+                (&mut **original_reader, *original_byte_len)
+            }
+            None => (&mut *reader, byte_len),
+        };
+
+        let (progress_sender, progress_receiver) = progress_channel();
+        progress_sender.set_total(byte_len.unwrap_or(0));
+        if let Some(info) = self.data.infos.write().get_mut(base_handle.id()) {
+            info.loader_name = Some(loader.type_name());
+            info.byte_progress = Some(progress_receiver);
+        }
+        let mut reader = ProgressReader {
+            reader,
+            progress: progress_sender,
+            bytes_read: 0,
+        };
+
+        let result = self
+            .load_with_meta_loader_and_reader(&base_path, meta, &*loader, &mut reader, true, false)
+            .await;
+        if byte_len.is_none() {
+            // Once the asset is loaded, the bytes read so far are all of its bytes.
+            reader.progress.set_total(reader.bytes_read);
+        }
+
+        match result {
             Ok(loaded_asset) => {
                 let final_handle = if let Some(label) = path.label_cow() {
                     match loaded_asset.labeled_assets.get(&label) {
@@ -537,6 +783,70 @@ impl AssetServer {
         }
     }
 
+    /// Returns the handle of the asset that the asset being loaded with `handle` shares its value
+    /// with because their processed content is identical (see [`Assets::shared_with`]), and starts
+    /// loading it if needed.
+    fn get_shared_original(
+        &self,
+        handle: &UntypedHandle,
+        meta: &dyn AssetMetaDyn,
+        loader: &dyn ErasedAssetLoader,
+    ) -> Option<UntypedHandle> {
+        let processed_info = meta.processed_info().as_ref()?;
+        let content_hash = processed_info.content_hash?;
+        let id = handle.id();
+        let mut infos = self.data.infos.write();
+        let stale_sharers = infos.set_content_hash(id, content_hash);
+        let mut original_load = None;
+        let original = match (
+            infos.get_content_original(id, content_hash),
+            &processed_info.duplicate_of,
+        ) {
+            (Some(original), _) => Some(original),
+            (None, Some(original_path)) => {
+                let (original, should_load) = infos.get_or_create_path_handle_untyped(
+                    original_path.clone(),
+                    loader.asset_type_id(),
+                    loader.asset_type_name(),
+                    HandleLoadingMode::Request,
+                    None,
+                );
+                if should_load {
+                    let priority = infos.get(id).map(|info| info.load_priority);
+                    let priority = priority.unwrap_or_default();
+                    if let Some(info) = infos.get_mut(original.id()) {
+                        info.load_priority = priority;
+                    }
+                    original_load = Some((original.clone(), original_path.clone(), priority));
+                }
+                Some(original)
+            }
+            (None, None) => {
+                infos.insert_content_original(id, content_hash);
+                None
+            }
+        };
+        drop(infos);
+
+        for path in stale_sharers {
+            self.reload(path);
+        }
+        if let Some((original, path, priority)) = original_load {
+            let server = self.clone();
+            IoTaskPool::get()
+                .spawn_with_priority(priority, async move {
+                    if let Err(err) = server
+                        .load_internal(Some(original), path, false, None)
+                        .await
+                    {
+                        error!("{}", err);
+                    }
+                })
+                .detach();
+        }
+        original
+    }
+
     /// Sends a load event for the given `loaded_asset` and does the same recursively for all
     /// labeled assets.
     fn send_loaded_asset(&self, id: UntypedAssetId, mut loaded_asset: ErasedLoadedAsset) {
@@ -553,6 +863,10 @@ impl AssetServer {
         let path = path.into().into_owned();
         IoTaskPool::get()
             .spawn(async move {
+                if server.is_saved_content(&path).await {
+                    return;
+                }
+
                 let mut reloaded = false;
 
                 let requests = server
@@ -579,6 +893,150 @@ impl AssetServer {
             .detach();
     }
 
+    /// Returns `true` if the asset at `path` has the content it was last saved with by [`AssetServer::save_asset`],
+    /// which means that it does not need to be reloaded.
+    async fn is_saved_content(&self, path: &AssetPath<'static>) -> bool {
+        let Some(saved_hash) = self.data.infos.read().saved_content.get(path).copied() else {
+            return false;
+        };
+        let hash = async {
+            let (mut meta, _, mut reader) =
+                self.get_meta_loader_and_reader(path, None).await.ok()?;
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await.ok()?;
+            // Processed meta only differs from the saved meta by its processed info.
+            *meta.processed_info_mut() = None;
+            Some(get_asset_hash(&meta.serialize(), &bytes))
+        };
+        if hash.await == Some(saved_hash) {
+            return true;
+        }
+        self.data.infos.write().saved_content.remove(path);
+        false
+    }
+
+    /// Saves a copy of the value of `handle` in `assets` to the path of the handle, see [`AssetServer::save_asset`].
+    ///
+    /// The returned future does not borrow `assets`, so it can be spawned on the [`IoTaskPool`] to save the asset in
+    /// the background.
+    pub fn save<A: Asset + Clone>(
+        &self,
+        handle: &Handle<A>,
+        assets: &Assets<A>,
+    ) -> impl Future<Output = Result<Handle<A>, SaveAssetError>> + Send + 'static {
+        let server = self.clone();
+        let asset = match (handle.path(), assets.get(handle)) {
+            (Some(path), Some(asset)) => Ok((path.clone(), asset.clone())),
+            (None, _) => Err(SaveAssetError::MissingPath),
+            (Some(path), None) => Err(SaveAssetError::MissingAsset(path.clone())),
+        };
+        async move {
+            let (path, asset) = asset?;
+            server.save_asset(path, asset).await
+        }
+    }
+
+    /// Saves `asset` to `path` with a registered [`AssetSaver`] for its type, and writes a matching `.meta` file
+    /// that loads it with the [`AssetSaver::OutputLoader`].
+    ///
+    /// If several savers are registered for the asset type, the one whose output loader supports the extension of
+    /// `path` is used. The asset is written with the [`AssetWriter`](crate::io::AssetWriter) of the unprocessed
+    /// [`AssetSource`] of `path`.
+    ///
+    /// Afterwards, the asset becomes the loaded value of the returned path [`Handle`], so that loading `path` returns
+    /// it without reading it again. Hot reloading skips the file changes caused by the save, but still reloads the
+    /// asset when its file is changed later.
+    pub async fn save_asset<'a, A: Asset>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        asset: A,
+    ) -> Result<Handle<A>, SaveAssetError> {
+        let path = path.into().into_owned();
+        if path.label().is_some() {
+            return Err(SaveAssetError::LabeledPath(path));
+        }
+        let saver = self.get_saver::<A>(&path).await?;
+        let source = self.get_source(path.source())?;
+        let writer = source.writer()?;
+
+        let loaded_asset: ErasedLoadedAsset = LoadedAsset::from(asset).into();
+        let mut bytes = Vec::new();
+        let meta = saver
+            .save_with_meta(&mut bytes, &loaded_asset)
+            .await
+            .map_err(|error| SaveAssetError::AssetSaverError {
+                path: path.clone(),
+                saver_name: saver.type_name(),
+                error: error.into(),
+            })?;
+        let meta_bytes = meta.serialize();
+
+        // Recorded before writing, so that the file changes caused by this save do not trigger a reload.
+        self.data
+            .infos
+            .write()
+            .saved_content
+            .insert(path.clone(), get_asset_hash(&meta_bytes, &bytes));
+        let write = async {
+            writer.write_bytes(path.path(), &bytes).await?;
+            writer.write_meta_bytes(path.path(), &meta_bytes).await
+        };
+        if let Err(error) = write.await {
+            self.data.infos.write().saved_content.remove(&path);
+            return Err(SaveAssetError::AssetWriterError { path, error });
+        }
+
+        // The handle is marked as loading so that loads of the path until the saved value arrives do not read it again.
+        let (handle, _) = self.data.infos.write().get_or_create_path_handle::<A>(
+            path,
+            HandleLoadingMode::Force,
+            None,
+        );
+        self.send_loaded_asset(handle.id().untyped(), loaded_asset);
+        Ok(handle)
+    }
+
+    /// Returns the [`AssetSaver`] used to save assets of type `A` to `path`.
+    async fn get_saver<A: Asset>(
+        &self,
+        path: &AssetPath<'static>,
+    ) -> Result<Arc<dyn ErasedAssetSaver>, SaveAssetError> {
+        let savers = self
+            .data
+            .savers
+            .read()
+            .get(&TypeId::of::<A>())
+            .cloned()
+            .unwrap_or_default();
+        let file_name = path
+            .path()
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .unwrap_or_default();
+        let mut fallback = None;
+        for saver in savers.into_iter().rev() {
+            // Savers whose output could not be loaded again are skipped.
+            let Ok(loader) = self
+                .get_asset_loader_with_type_name(saver.output_loader_type_name())
+                .await
+            else {
+                continue;
+            };
+            if loader
+                .extensions()
+                .iter()
+                .any(|extension| file_name.ends_with(&format!(".{extension}")))
+            {
+                return Ok(saver);
+            }
+            fallback.get_or_insert(saver);
+        }
+        fallback.ok_or_else(|| SaveAssetError::MissingAssetSaver {
+            asset_type: std::any::type_name::<A>(),
+            path: path.clone(),
+        })
+    }
+
     /// Queues a new asset to be tracked by the [`AssetServer`] and returns a [`Handle`] to it. This can be used to track
     /// dependencies of assets created at runtime.
     ///
@@ -737,6 +1195,49 @@ impl AssetServer {
         self.data.asset_event_sender.send(event).unwrap();
     }
 
+    /// Returns the delay after which the failed load of the asset with the given `id` should be
+    /// retried, according to the [`RetryPolicy`](crate::io::RetryPolicy) of its source, or `None`
+    /// if it should not be retried.
+    fn retry_delay(
+        &self,
+        infos: &mut AssetInfos,
+        id: UntypedAssetId,
+        path: &AssetPath,
+        error: &AssetLoadError,
+    ) -> Option<Duration> {
+        let AssetLoadError::AssetReaderError(error) = error else {
+            return None;
+        };
+        let source = self.get_source(path.source()).ok()?;
+        let policy = source.retry_policy()?;
+        let info = infos.get_mut(id)?;
+        if !policy.should_retry(info.load_retries, error) {
+            return None;
+        }
+        let delay = policy.delay(info.load_retries);
+        info.load_retries += 1;
+        Some(delay)
+    }
+
+    /// Loads the asset of `handle` again after its load failed.
+    fn retry_load(&self, handle: UntypedHandle, path: AssetPath<'static>, priority: TaskPriority) {
+        let id = handle.id();
+        if id.type_id() == TypeId::of::<LoadedFolder>() {
+            self.load_folder_internal(id, path);
+        } else if id.type_id() == TypeId::of::<LoadedUntypedAsset>() {
+            self.spawn_load_untyped(id, path);
+        } else {
+            let server = self.clone();
+            IoTaskPool::get()
+                .spawn_with_priority(priority, async move {
+                    if let Err(err) = server.load_internal(Some(handle), path, true, None).await {
+                        error!("{}", err);
+                    }
+                })
+                .detach();
+        }
+    }
+
     /// Retrieves all loads states for the given asset id.
     pub fn get_load_states(
         &self,
@@ -749,6 +1250,53 @@ impl AssetServer {
             .map(|i| (i.load_state, i.dep_load_state, i.rec_dep_load_state))
     }
 
+    /// Retrieves the error that caused the last load of the asset with the given `id` to fail.
+    ///
+    /// Returns `None` if the asset is not [`LoadState::Failed`].
+    pub fn get_load_error(&self, id: impl Into<UntypedAssetId>) -> Option<AssetLoadError> {
+        self.data
+            .infos
+            .read()
+            .get(id.into())
+            .and_then(|i| i.load_error.clone())
+    }
+
+    /// Retrieves how many bytes of the asset with the given `id` have been read by its last load.
+    ///
+    /// [`Progress::total`] is `0` until the total length is known, which is the case when the
+    /// [`AssetReader`] reports it from [`AssetReader::read_with_byte_len`] or once the asset has
+    /// been read entirely. Returns `None` if the asset has not been loaded by this [`AssetServer`].
+    pub fn get_byte_progress(&self, id: impl Into<UntypedAssetId>) -> Option<Progress> {
+        self.data
+            .infos
+            .read()
+            .get(id.into())
+            .and_then(|i| i.byte_progress.as_ref().map(ProgressReceiver::progress))
+    }
+
+    /// Creates a snapshot of every asset tracked by this [`AssetServer`] and of the dependencies
+    /// between them.
+    ///
+    /// This is meant for debugging and tooling, such as finding out why an asset is still alive.
+    /// It is not cheap, as the whole graph is copied.
+    pub fn asset_graph(&self) -> AssetGraph {
+        self.data.infos.read().asset_graph()
+    }
+
+    /// Collects the errors of the asset with the given `id` and of its failed dependencies (recursively).
+    pub(crate) fn get_load_errors(
+        &self,
+        id: UntypedAssetId,
+        visited: &mut HashSet<UntypedAssetId>,
+    ) -> Vec<(UntypedAssetId, AssetLoadError)> {
+        let mut errors = Vec::new();
+        self.data
+            .infos
+            .read()
+            .collect_load_errors(id, visited, &mut errors);
+        errors
+    }
+
     /// Retrieves the main [`LoadState`] of a given asset `id`.
     ///
     /// Note that this is "just" the root asset load state. To check if an asset _and_ its recursive
@@ -914,6 +1462,40 @@ impl AssetServer {
             Box<Reader<'a>>,
         ),
         AssetLoadError,
+    > {
+        let (meta, loader, reader, _) = self
+            .get_meta_loader_reader_and_byte_len(asset_path, asset_type_id)
+            .await?;
+        Ok((meta, loader, reader))
+    }
+
+    /// Returns a reader of the asset bytes at `asset_path`, without reading its meta.
+    async fn get_reader_and_byte_len<'a>(
+        &'a self,
+        asset_path: &'a AssetPath<'_>,
+    ) -> Result<(Box<Reader<'a>>, Option<u64>), AssetLoadError> {
+        let source = self.get_source(asset_path.source())?;
+        let asset_reader = match self.data.mode {
+            AssetServerMode::Unprocessed { .. } => source.reader(),
+            AssetServerMode::Processed { .. } => source.processed_reader()?,
+        };
+        Ok(asset_reader.read_with_byte_len(asset_path.path()).await?)
+    }
+
+    /// Like [`AssetServer::get_meta_loader_and_reader`], also returning the length of the asset if
+    /// its [`AssetReader`] knows it upfront.
+    async fn get_meta_loader_reader_and_byte_len<'a>(
+        &'a self,
+        asset_path: &'a AssetPath<'_>,
+        asset_type_id: Option<TypeId>,
+    ) -> Result<
+        (
+            Box<dyn AssetMetaDyn>,
+            Arc<dyn ErasedAssetLoader>,
+            Box<Reader<'a>>,
+            Option<u64>,
+        ),
+        AssetLoadError,
     > {
         let source = self.get_source(asset_path.source())?;
         // NOTE: We grab the asset byte reader first to ensure this is transactional for AssetReaders like ProcessorGatedReader
@@ -924,7 +1506,7 @@ impl AssetServer {
             AssetServerMode::Unprocessed { .. } => source.reader(),
             AssetServerMode::Processed { .. } => source.processed_reader()?,
         };
-        let reader = asset_reader.read(asset_path.path()).await?;
+        let (reader, byte_len) = asset_reader.read_with_byte_len(asset_path.path()).await?;
         let read_meta = match &self.data.meta_check {
             AssetMetaCheck::Always => true,
             AssetMetaCheck::Paths(paths) => paths.contains(asset_path),
@@ -956,14 +1538,27 @@ impl AssetServer {
                         }
                     };
                     let loader = self.get_asset_loader_with_type_name(&loader_name).await?;
-                    let meta = loader.deserialize_meta(&meta_bytes).map_err(|e| {
-                        AssetLoadError::DeserializeMeta {
+                    let deserialize_error =
+                        |e: DeserializeMetaError| AssetLoadError::DeserializeMeta {
                             path: asset_path.clone_owned(),
                             error: e.into(),
-                        }
-                    })?;
+                        };
+                    let meta_bytes = self
+                        .migrate_meta(
+                            source,
+                            asset_path.path(),
+                            loader.type_name(),
+                            loader.settings_version(),
+                            meta_bytes,
+                            self.data.mode == AssetServerMode::Unprocessed,
+                        )
+                        .await
+                        .map_err(deserialize_error)?;
+                    let meta = loader
+                        .deserialize_meta(&meta_bytes)
+                        .map_err(deserialize_error)?;
 
-                    Ok((meta, loader, reader))
+                    Ok((meta, loader, reader, byte_len))
                 }
                 Err(AssetReaderError::NotFound(_)) => {
                     // TODO: Handle error transformation
@@ -984,7 +1579,7 @@ impl AssetServer {
                     let loader = loader.ok_or_else(error)?.get().await.map_err(|_| error())?;
 
                     let meta = loader.default_meta();
-                    Ok((meta, loader, reader))
+                    Ok((meta, loader, reader, byte_len))
                 }
                 Err(err) => Err(err.into()),
             }
@@ -1006,7 +1601,7 @@ impl AssetServer {
             let loader = loader.ok_or_else(error)?.get().await.map_err(|_| error())?;
 
             let meta = loader.default_meta();
-            Ok((meta, loader, reader))
+            Ok((meta, loader, reader, byte_len))
         }
     }
 
@@ -1048,6 +1643,9 @@ pub fn handle_internal_asset_events(world: &mut World) {
                         &server.data.asset_event_sender,
                     );
                 }
+                InternalAssetEvent::Shared { id, original } => {
+                    infos.pending_shares.push((id, original));
+                }
                 InternalAssetEvent::LoadedWithDependencies { id } => {
                     let sender = infos
                         .dependency_loaded_event_sender
@@ -1056,7 +1654,14 @@ pub fn handle_internal_asset_events(world: &mut World) {
                     sender(world, id);
                 }
                 InternalAssetEvent::Failed { id, path, error } => {
-                    infos.process_asset_fail(id);
+                    if let Some(delay) = server.retry_delay(&mut infos, id, &path, &error) {
+                        warn!("Retrying the load of {path} in {delay:?}: {error}");
+                        infos
+                            .pending_retries
+                            .push((Instant::now() + delay, id, path));
+                        continue;
+                    }
+                    infos.process_asset_fail(id, error.clone());
 
                     // Send untyped failure event
                     untyped_failures.push(UntypedAssetLoadFailedEvent {
@@ -1079,6 +1684,49 @@ pub fn handle_internal_asset_events(world: &mut World) {
             world.send_event_batch(untyped_failures);
         }
 
+        // Assets sharing the value of another asset finish loading once it is loaded.
+        for (id, mut original) in std::mem::take(&mut infos.pending_shares) {
+            let Some(path) = infos.get(id).map(|info| info.path.clone()) else {
+                continue;
+            };
+            // The original may itself share the value of an asset with the same content loaded before it.
+            while let Some(shared_with) = infos
+                .get(original.id())
+                .and_then(|info| info.shared_with.clone())
+            {
+                original = shared_with;
+            }
+            let Some(original_info) = infos.get(original.id()) else {
+                continue;
+            };
+            match original_info.load_state {
+                LoadState::Loaded => {
+                    infos.process_asset_share(id, original, world, &server.data.asset_event_sender);
+                }
+                LoadState::Failed => {
+                    if let (Some(path), Some(error)) = (path, original_info.load_error.clone()) {
+                        server.send_asset_event(InternalAssetEvent::Failed { id, path, error });
+                    }
+                }
+                LoadState::NotLoaded | LoadState::Loading => {
+                    infos.pending_shares.push((id, original));
+                }
+            }
+        }
+
+        let now = Instant::now();
+        let (due_retries, pending_retries) = std::mem::take(&mut infos.pending_retries)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(due, ..)| *due <= now);
+        infos.pending_retries = pending_retries;
+        for (_, id, path) in due_retries {
+            // Loads of assets that were dropped in the meantime are not retried.
+            if let Some(handle) = infos.get_id_handle(id) {
+                let priority = infos.get(id).map(|info| info.load_priority);
+                server.retry_load(handle, path, priority.unwrap_or_default());
+            }
+        }
+
         fn queue_ancestors(
             asset_path: &AssetPath,
             infos: &AssetInfos,
@@ -1119,8 +1767,19 @@ pub fn handle_internal_asset_events(world: &mut World) {
                     reload_parent_folders(old, &source);
                     reload_parent_folders(new, &source);
                 }
-                AssetSourceEvent::AddedAsset(path)
-                | AssetSourceEvent::RemovedAsset(path)
+                AssetSourceEvent::AddedAsset(path) => {
+                    // Assets that failed to load because they were missing are loaded again.
+                    let asset_path = AssetPath::from(path.clone()).with_source(source.clone());
+                    if infos.get_path_ids(&asset_path).any(|id| {
+                        infos
+                            .get(id)
+                            .is_some_and(|info| info.load_state == LoadState::Failed)
+                    }) {
+                        paths_to_reload.insert(asset_path);
+                    }
+                    reload_parent_folders(path, &source);
+                }
+                AssetSourceEvent::RemovedAsset(path)
                 | AssetSourceEvent::RemovedFolder(path)
                 | AssetSourceEvent::AddedFolder(path) => {
                     reload_parent_folders(path, &source);
@@ -1165,6 +1824,11 @@ pub(crate) enum InternalAssetEvent {
     LoadedWithDependencies {
         id: UntypedAssetId,
     },
+    /// The asset with the given `id` shares the value of the `original` asset.
+    Shared {
+        id: UntypedAssetId,
+        original: UntypedHandle,
+    },
     Failed {
         id: UntypedAssetId,
         path: AssetPath<'static>,
@@ -1172,8 +1836,33 @@ pub(crate) enum InternalAssetEvent {
     },
 }
 
+/// An [`AsyncRead`] reporting the bytes read from the wrapped [`Reader`] to a [`ProgressSender`].
+struct ProgressReader<'a, 'b> {
+    reader: &'a mut Reader<'b>,
+    progress: ProgressSender,
+    bytes_read: u64,
+}
+
+impl AsyncRead for ProgressReader<'_, '_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = &mut *self;
+        let result = Pin::new(&mut *this.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(read)) = result {
+            if read > 0 {
+                this.bytes_read += read as u64;
+                this.progress.add_completed(read as u64);
+            }
+        }
+        result
+    }
+}
+
 /// The load state of an asset.
-#[derive(Component, Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Component, Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
 pub enum LoadState {
     /// The asset has not started loading yet
     NotLoaded,
@@ -1186,7 +1875,7 @@ pub enum LoadState {
 }
 
 /// The load state of an asset's dependencies.
-#[derive(Component, Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Component, Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
 pub enum DependencyLoadState {
     /// The asset has not started loading yet
     NotLoaded,
@@ -1199,7 +1888,7 @@ pub enum DependencyLoadState {
 }
 
 /// The recursive load state of an asset's dependencies.
-#[derive(Component, Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Component, Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
 pub enum RecursiveDependencyLoadState {
     /// The asset has not started loading yet
     NotLoaded,
@@ -1269,6 +1958,37 @@ pub enum AssetLoadError {
     },
 }
 
+/// An error that occurs when saving an [`Asset`] with [`AssetServer::save_asset`].
+#[derive(Error, Debug)]
+pub enum SaveAssetError {
+    #[error("No `AssetSaver` that can be loaded again is registered for {asset_type}, which is saved to '{path}'")]
+    MissingAssetSaver {
+        asset_type: &'static str,
+        path: AssetPath<'static>,
+    },
+    #[error("Cannot save an asset to the labeled path '{0}'")]
+    LabeledPath(AssetPath<'static>),
+    #[error("Cannot save an asset whose handle has no path")]
+    MissingPath,
+    #[error("The asset '{0}' to save does not exist")]
+    MissingAsset(AssetPath<'static>),
+    #[error(transparent)]
+    MissingAssetSourceError(#[from] MissingAssetSourceError),
+    #[error(transparent)]
+    MissingAssetWriterError(#[from] MissingAssetWriterError),
+    #[error("Failed to save asset '{path}' with asset saver '{saver_name}': {error}")]
+    AssetSaverError {
+        path: AssetPath<'static>,
+        saver_name: &'static str,
+        error: Arc<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    #[error("Failed to write saved asset '{path}': {error}")]
+    AssetWriterError {
+        path: AssetPath<'static>,
+        error: AssetWriterError,
+    },
+}
+
 /// An error that occurs when an [`AssetLoader`] is not registered for a given extension.
 #[derive(Error, Debug, Clone)]
 #[error("no `AssetLoader` found{}", format_missing_asset_ext(.extensions))]
@@ -0,0 +1,254 @@
+use crate::{
+    AssetLoadError, AssetServer, LoadState, LoadedFolder, RecursiveDependencyLoadState,
+    UntypedAssetId, UntypedHandle,
+};
+use bevy_ecs::prelude::*;
+use bevy_utils::HashSet;
+
+/// Tracks the loading progress of a group of assets, for example to display a loading screen.
+///
+/// Add the handles of the group with [`AssetLoadTracker::add`] or [`AssetLoadTracker::add_folder`],
+/// and spawn the tracker as a component. Every frame, its [`AssetLoadProgress`] and
+/// [`failures`](AssetLoadTracker::failures) are updated from the [`AssetServer`], and an
+/// [`AssetGroupLoaded`] event is sent once every asset of the group is loaded, including its
+/// recursive dependencies.
+///
+/// ```
+/// # use bevy_asset::{AssetGroupLoaded, AssetLoadTracker, AssetServer, Handle, LoadedFolder};
+/// # use bevy_ecs::prelude::*;
+/// fn load_level(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     let folder: Handle<LoadedFolder> = asset_server.load_folder("levels/forest");
+///     commands.spawn(AssetLoadTracker::from_handles([folder]));
+/// }
+///
+/// fn display_progress(trackers: Query<&AssetLoadTracker, Changed<AssetLoadTracker>>) {
+///     for tracker in &trackers {
+///         let progress = tracker.progress();
+///         println!(
+///             "{} of {} assets, {:.0}% of bytes",
+///             progress.loaded,
+///             progress.total,
+///             progress.bytes_fraction() * 100.0
+///         );
+///         for failure in tracker.failures() {
+///             println!("{}", failure.error);
+///         }
+///     }
+/// }
+///
+/// fn start_level(mut commands: Commands, mut loaded: EventReader<AssetGroupLoaded>) {
+///     for event in loaded.read() {
+///         commands.entity(event.entity).despawn();
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(load_level);
+/// # bevy_ecs::system::assert_is_system(display_progress);
+/// # bevy_ecs::system::assert_is_system(start_level);
+/// ```
+///
+/// The tracker can also be stored elsewhere and refreshed with [`AssetLoadTracker::update`].
+#[derive(Component, Default, Debug)]
+pub struct AssetLoadTracker {
+    handles: Vec<UntypedHandle>,
+    ids: HashSet<UntypedAssetId>,
+    progress: AssetLoadProgress,
+    failures: Vec<AssetLoadFailure>,
+    notified: bool,
+}
+
+impl AssetLoadTracker {
+    /// Creates an empty [`AssetLoadTracker`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an [`AssetLoadTracker`] tracking the given handles.
+    pub fn from_handles(handles: impl IntoIterator<Item = impl Into<UntypedHandle>>) -> Self {
+        let mut tracker = Self::new();
+        for handle in handles {
+            tracker.add(handle);
+        }
+        tracker
+    }
+
+    /// Adds an asset to the group. Adding an asset that is already tracked does nothing.
+    ///
+    /// If the group was already fully loaded, an [`AssetGroupLoaded`] event is sent again once
+    /// the new asset is loaded.
+    pub fn add(&mut self, handle: impl Into<UntypedHandle>) -> &mut Self {
+        let handle = handle.into();
+        if self.ids.insert(handle.id()) {
+            self.handles.push(handle);
+            self.progress.total += 1;
+            self.notified = false;
+        }
+        self
+    }
+
+    /// Adds every asset of a [`LoadedFolder`] to the group.
+    ///
+    /// Tracking the [`Handle<LoadedFolder>`](crate::Handle) itself also waits for all of the
+    /// assets of the folder, as they are dependencies of the folder, but only counts as a single
+    /// asset until the folder is loaded.
+    pub fn add_folder(&mut self, folder: &LoadedFolder) -> &mut Self {
+        for handle in &folder.handles {
+            self.add(handle.clone());
+        }
+        self
+    }
+
+    /// Returns the handles of the tracked assets.
+    pub fn handles(&self) -> &[UntypedHandle] {
+        &self.handles
+    }
+
+    /// Returns the progress of the group as of the last [`AssetLoadTracker::update`].
+    pub fn progress(&self) -> AssetLoadProgress {
+        self.progress
+    }
+
+    /// Returns the errors of the assets of the group that failed to load, including the errors
+    /// of failed dependencies, as of the last [`AssetLoadTracker::update`].
+    pub fn failures(&self) -> &[AssetLoadFailure] {
+        &self.failures
+    }
+
+    /// Returns `true` if every tracked asset is loaded, including its recursive dependencies.
+    pub fn is_loaded(&self) -> bool {
+        self.progress.is_loaded()
+    }
+
+    /// Refreshes the progress and failures of the group from the `asset_server`.
+    pub fn update(&mut self, asset_server: &AssetServer) {
+        let mut progress = AssetLoadProgress::default();
+        let mut failures = Vec::new();
+        let mut visited = HashSet::new();
+        for handle in &self.handles {
+            let id = handle.id();
+            // Assets that are not managed by the asset server, for example because they were
+            // added to `Assets` directly, are not counted.
+            let Some((load_state, _, rec_dep_load_state)) = asset_server.get_load_states(id) else {
+                continue;
+            };
+            progress.total += 1;
+            if load_state == LoadState::Failed
+                || rec_dep_load_state == RecursiveDependencyLoadState::Failed
+            {
+                progress.failed += 1;
+                failures.extend(
+                    asset_server
+                        .get_load_errors(id, &mut visited)
+                        .into_iter()
+                        .map(|(id, error)| AssetLoadFailure { id, error }),
+                );
+            } else if load_state == LoadState::Loaded
+                && rec_dep_load_state == RecursiveDependencyLoadState::Loaded
+            {
+                progress.loaded += 1;
+            }
+            if let Some(bytes) = asset_server.get_byte_progress(id) {
+                progress.bytes_read += bytes.completed;
+                progress.bytes_total += bytes.total.max(bytes.completed);
+            }
+        }
+        self.progress = progress;
+        self.failures = failures;
+    }
+}
+
+/// The aggregated progress of an [`AssetLoadTracker`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AssetLoadProgress {
+    /// Number of tracked assets managed by the [`AssetServer`].
+    ///
+    /// Assets the [`AssetServer`] does not know about, like assets added to
+    /// [`Assets`](crate::Assets) directly, are not counted once the tracker is updated.
+    pub total: usize,
+    /// Number of tracked assets that are loaded, including their recursive dependencies.
+    pub loaded: usize,
+    /// Number of tracked assets that failed to load, or that have a dependency that failed to load.
+    pub failed: usize,
+    /// Number of bytes read by the loads of the tracked assets, excluding their dependencies.
+    pub bytes_read: u64,
+    /// Total number of bytes of the tracked assets, excluding their dependencies.
+    ///
+    /// This only accounts for the assets whose length is known, which is the case once they are
+    /// loaded or when their [`AssetReader`](crate::io::AssetReader) can report their length
+    /// upfront, so it may grow while the group is loading.
+    pub bytes_total: u64,
+}
+
+impl AssetLoadProgress {
+    /// Returns the fraction of the tracked assets that are loaded, between `0.0` and `1.0`.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.loaded as f32 / self.total as f32
+        }
+    }
+
+    /// Returns the fraction of the bytes of the tracked assets that have been read, between
+    /// `0.0` and `1.0`.
+    pub fn bytes_fraction(&self) -> f32 {
+        if self.bytes_total == 0 {
+            0.0
+        } else {
+            (self.bytes_read.min(self.bytes_total) as f64 / self.bytes_total as f64) as f32
+        }
+    }
+
+    /// Returns `true` if every tracked asset is loaded, including its recursive dependencies.
+    pub fn is_loaded(&self) -> bool {
+        self.loaded == self.total
+    }
+
+    /// Returns `true` once no tracked asset is loading anymore, either because it is loaded or
+    /// because it failed to load.
+    pub fn is_finished(&self) -> bool {
+        self.loaded + self.failed == self.total
+    }
+}
+
+/// An asset tracked by an [`AssetLoadTracker`], or one of its dependencies, that failed to load.
+#[derive(Debug, Clone)]
+pub struct AssetLoadFailure {
+    /// The asset that failed to load.
+    pub id: UntypedAssetId,
+    /// Why the asset failed to load.
+    pub error: AssetLoadError,
+}
+
+/// An event sent once every asset tracked by the [`AssetLoadTracker`] on `entity` is loaded,
+/// including its recursive dependencies.
+///
+/// This is not sent for groups where an asset failed to load, see
+/// [`AssetLoadTracker::failures`].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssetGroupLoaded {
+    /// The entity holding the [`AssetLoadTracker`].
+    pub entity: Entity,
+}
+
+/// Updates every [`AssetLoadTracker`] and sends [`AssetGroupLoaded`] events.
+pub fn update_asset_load_trackers(
+    asset_server: Res<AssetServer>,
+    mut trackers: Query<(Entity, &mut AssetLoadTracker)>,
+    mut loaded: EventWriter<AssetGroupLoaded>,
+) {
+    for (entity, mut tracker) in &mut trackers {
+        // Only trigger change detection when the progress changed.
+        let inner = tracker.bypass_change_detection();
+        let previous = (inner.progress, inner.failures.len());
+        inner.update(&asset_server);
+        let changed = previous != (inner.progress, inner.failures.len());
+        let notify = inner.is_loaded() && !inner.notified;
+        inner.notified |= notify;
+        if changed {
+            tracker.set_changed();
+        }
+        if notify {
+            loaded.send(AssetGroupLoaded { entity });
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use bevy_ecs::{
+    bundle::Bundle,
+    component::Component,
+    entity::Entity,
+    event::Event,
+    system::{CommandQueue, Commands, Query},
+    world::World,
+};
+use bevy_tasks::{block_on, futures_lite::future::poll_once, Task};
+use bevy_utils::synccell::SyncCell;
+
+type OnComplete = Box<dyn FnOnce(Entity, &mut World) + Send>;
+
+/// Type-erased part of an [`EntityTask`], so that tasks of any output type share a component.
+trait PollTask: Send + Sync {
+    /// Polls the task once, returning a function applying its output once it is complete.
+    fn poll(&mut self) -> Option<OnComplete>;
+}
+
+struct PendingTask<T, F> {
+    task: Task<T>,
+    on_complete: SyncCell<Option<F>>,
+}
+
+impl<T, F> PollTask for PendingTask<T, F>
+where
+    T: Send + 'static,
+    F: FnOnce(T, Entity, &mut World) + Send + 'static,
+{
+    fn poll(&mut self) -> Option<OnComplete> {
+        let output = block_on(poll_once(&mut self.task))?;
+        let on_complete = self.on_complete.get().take()?;
+        Some(Box::new(move |entity, world| {
+            on_complete(output, entity, world);
+        }))
+    }
+}
+
+/// A [`Task`] attached to an entity, whose output is applied to the [`World`] once it completes.
+///
+/// Every frame, [`poll_entity_tasks`] polls the task. Once it is complete, the [`EntityTask`]
+/// component is removed from the entity and the output is applied in a deferred manner, as
+/// described by the constructor used to create the component. This replaces storing the
+/// [`Task`] in a custom component and polling it by hand.
+///
+/// Since the task is stored in the component, it is canceled if the entity is despawned or the
+/// component is removed before the task completes. An entity can hold a single [`EntityTask`]
+/// at a time, spawn child entities to run several tasks for the same entity.
+///
+/// ```
+/// # use bevy_core::EntityTask;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_tasks::AsyncComputeTaskPool;
+/// #[derive(Component)]
+/// struct Path(Vec<[f32; 2]>);
+///
+/// fn request_paths(mut commands: Commands, agents: Query<Entity, Added<Agent>>) {
+///     for agent in &agents {
+///         let task = AsyncComputeTaskPool::get().spawn(async move {
+///             // An expensive computation.
+///             Path(vec![[0.0, 0.0], [1.0, 2.0]])
+///         });
+///         commands.entity(agent).insert(EntityTask::insert(task));
+///     }
+/// }
+/// # #[derive(Component)]
+/// # struct Agent;
+/// # bevy_ecs::system::assert_is_system(request_paths);
+/// ```
+#[derive(Component)]
+pub struct EntityTask(Box<dyn PollTask>);
+
+impl EntityTask {
+    /// Creates an [`EntityTask`] calling `on_complete` with the output of the task, the entity
+    /// holding the component, and the [`World`] once the task is complete.
+    pub fn new<T, F>(task: Task<T>, on_complete: F) -> Self
+    where
+        T: Send + 'static,
+        F: FnOnce(T, Entity, &mut World) + Send + 'static,
+    {
+        Self(Box::new(PendingTask {
+            task,
+            on_complete: SyncCell::new(Some(on_complete)),
+        }))
+    }
+
+    /// Creates an [`EntityTask`] inserting the bundle returned by the task on its entity.
+    ///
+    /// The bundle is discarded if the entity has been despawned in the meantime.
+    pub fn insert<B: Bundle>(task: Task<B>) -> Self {
+        Self::new(task, |bundle, entity, world| {
+            if let Some(mut entity) = world.get_entity_mut(entity) {
+                entity.insert(bundle);
+            }
+        })
+    }
+
+    /// Creates an [`EntityTask`] sending the event returned by the task.
+    ///
+    /// If the event type was not added to the app, an error is logged and the event is discarded.
+    pub fn send_event<E: Event>(task: Task<E>) -> Self {
+        Self::new(task, |event, _, world| {
+            world.send_event(event);
+        })
+    }
+
+    /// Creates an [`EntityTask`] applying the [`CommandQueue`] returned by the task.
+    pub fn apply_commands(task: Task<CommandQueue>) -> Self {
+        Self::new(task, |mut commands, _, world| {
+            commands.apply(world);
+        })
+    }
+}
+
+/// Polls every [`EntityTask`] and applies the output of completed tasks.
+///
+/// This is added to [`PreUpdate`](bevy_app::PreUpdate) by the
+/// [`TaskPoolPlugin`](crate::TaskPoolPlugin), so that outputs are visible from
+/// [`Update`](bevy_app::Update) on.
+pub fn poll_entity_tasks(mut commands: Commands, mut tasks: Query<(Entity, &mut EntityTask)>) {
+    for (entity, mut task) in &mut tasks {
+        if let Some(on_complete) = task.0.poll() {
+            // Remove the component first, so that the output may attach another task.
+            commands.entity(entity).remove::<EntityTask>();
+            commands.add(move |world: &mut World| on_complete(entity, world));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TaskPoolPlugin;
+    use bevy_app::App;
+    use bevy_ecs::event::Events;
+    use bevy_tasks::AsyncComputeTaskPool;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Output(u32);
+
+    #[derive(Event)]
+    struct Done;
+
+    /// Updates the app until no [`EntityTask`] is left.
+    fn run_tasks(app: &mut App) {
+        for _ in 0..1000 {
+            app.update();
+            let mut tasks = app.world.query::<&EntityTask>();
+            if tasks.iter(&app.world).next().is_none() {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        panic!("entity tasks did not complete");
+    }
+
+    #[test]
+    fn applies_task_output() {
+        let mut app = App::new();
+        app.add_plugins(TaskPoolPlugin::default())
+            .add_event::<Done>();
+
+        let pool = AsyncComputeTaskPool::get();
+        let inserted = app
+            .world
+            .spawn(EntityTask::insert(pool.spawn(async { Output(7) })))
+            .id();
+        let sent = app
+            .world
+            .spawn(EntityTask::send_event(pool.spawn(async { Done })))
+            .id();
+        let applied = app
+            .world
+            .spawn(EntityTask::apply_commands(pool.spawn(async {
+                let mut commands = CommandQueue::default();
+                commands.push(|world: &mut World| {
+                    world.spawn(Output(1));
+                });
+                commands
+            })))
+            .id();
+
+        run_tasks(&mut app);
+
+        assert_eq!(app.world.get::<Output>(inserted), Some(&Output(7)));
+        assert!(app.world.get::<EntityTask>(sent).is_none());
+        assert_eq!(app.world.resource::<Events<Done>>().len(), 1);
+        assert!(app.world.get::<Output>(applied).is_none());
+        let mut outputs = app.world.query::<&Output>();
+        assert_eq!(outputs.iter(&app.world).count(), 2);
+    }
+
+    #[test]
+    fn output_can_attach_another_task() {
+        let mut app = App::new();
+        app.add_plugins(TaskPoolPlugin::default());
+
+        let pool = AsyncComputeTaskPool::get();
+        let entity = app
+            .world
+            .spawn(EntityTask::new(
+                pool.spawn(async { 1 }),
+                |value, entity, world| {
+                    let next = AsyncComputeTaskPool::get().spawn(async move { Output(value + 1) });
+                    world.entity_mut(entity).insert(EntityTask::insert(next));
+                },
+            ))
+            .id();
+
+        run_tasks(&mut app);
+
+        assert_eq!(app.world.get::<Output>(entity), Some(&Output(2)));
+    }
+
+    #[test]
+    fn unregistered_event_is_discarded() {
+        let mut app = App::new();
+        app.add_plugins(TaskPoolPlugin::default());
+
+        let task = AsyncComputeTaskPool::get().spawn(async { Done });
+        let entity = app.world.spawn(EntityTask::send_event(task)).id();
+
+        run_tasks(&mut app);
+
+        assert!(app.world.get::<EntityTask>(entity).is_none());
+        assert!(!app.world.contains_resource::<Events<Done>>());
+    }
+
+    #[test]
+    fn despawning_cancels_task() {
+        let mut app = App::new();
+        app.add_plugins(TaskPoolPlugin::default())
+            .init_resource::<Events<Done>>();
+
+        let (started_tx, started_rx) = crossbeam_channel::bounded(1);
+        let (release_tx, release_rx) = crossbeam_channel::bounded::<()>(1);
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            started_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+            Done
+        });
+        let entity = app.world.spawn(EntityTask::send_event(task)).id();
+        started_rx.recv().unwrap();
+
+        app.world.despawn(entity);
+        release_tx.send(()).unwrap();
+        app.update();
+        app.update();
+
+        assert!(app.world.resource::<Events<Done>>().is_empty());
+    }
+}
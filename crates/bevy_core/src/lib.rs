@@ -1,5 +1,6 @@
 //! This crate provides core functionality for Bevy Engine.
 
+mod entity_task;
 mod name;
 #[cfg(feature = "serialize")]
 mod serde;
@@ -7,6 +8,7 @@ mod task_pool_options;
 
 use bevy_ecs::system::{ResMut, Resource};
 pub use bytemuck::{bytes_of, cast_slice, Pod, Zeroable};
+pub use entity_task::*;
 pub use name::*;
 pub use task_pool_options::*;
 
@@ -14,7 +16,8 @@ pub mod prelude {
     //! The Bevy Core Prelude.
     #[doc(hidden)]
     pub use crate::{
-        DebugName, FrameCountPlugin, Name, TaskPoolOptions, TaskPoolPlugin, TypeRegistrationPlugin,
+        DebugName, EntityTask, FrameCountPlugin, Name, TaskPoolOptions, TaskPoolPlugin,
+        TypeRegistrationPlugin,
     };
 }
 
@@ -121,12 +124,14 @@ pub struct TaskPoolPlugin {
 }
 
 impl Plugin for TaskPoolPlugin {
-    fn build(&self, _app: &mut App) {
+    fn build(&self, app: &mut App) {
         // Setup the default bevy task pools
         self.task_pool_options.create_default_pools();
 
+        app.add_systems(PreUpdate, poll_entity_tasks);
+
         #[cfg(not(target_arch = "wasm32"))]
-        _app.add_systems(Last, tick_global_task_pools);
+        app.add_systems(Last, tick_global_task_pools);
     }
 }
 /// A dummy type that is [`!Send`](Send), to force systems to run on the main thread.
@@ -14,5 +14,14 @@ pub fn derive_dynamic_plugin(input: TokenStream) -> TokenStream {
             let boxed = Box::new(object);
             Box::into_raw(boxed)
         }
+
+        #[no_mangle]
+        pub extern "C" fn _bevy_plugin_abi() -> u64 {
+            bevy::app::dynamic_plugin_abi_fingerprint(
+                ::std::any::TypeId::of::<bevy::app::App>(),
+                ::std::mem::size_of::<bevy::app::App>(),
+                ::std::mem::align_of::<bevy::app::App>(),
+            )
+        }
     })
 }
@@ -0,0 +1,74 @@
+use bevy_app::prelude::*;
+use bevy_tasks::{AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool, TaskPool};
+
+use crate::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+
+/// Adds "active tasks" and "queued tasks" diagnostics for each of the global task pools.
+///
+/// Active tasks have been spawned and are not finished yet, queued tasks are ready to run
+/// but wait for a free thread of their pool. A steadily growing queue means the pool is
+/// saturated.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct TaskPoolDiagnosticsPlugin;
+
+impl Plugin for TaskPoolDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        for path in [
+            Self::COMPUTE_ACTIVE,
+            Self::COMPUTE_QUEUED,
+            Self::ASYNC_COMPUTE_ACTIVE,
+            Self::ASYNC_COMPUTE_QUEUED,
+            Self::IO_ACTIVE,
+            Self::IO_QUEUED,
+        ] {
+            app.register_diagnostic(Diagnostic::new(path).with_smoothing_factor(0.0));
+        }
+        app.add_systems(Update, Self::diagnostic_system);
+    }
+}
+
+impl TaskPoolDiagnosticsPlugin {
+    pub const COMPUTE_ACTIVE: DiagnosticPath =
+        DiagnosticPath::const_new("task_pools/compute/active");
+    pub const COMPUTE_QUEUED: DiagnosticPath =
+        DiagnosticPath::const_new("task_pools/compute/queued");
+    pub const ASYNC_COMPUTE_ACTIVE: DiagnosticPath =
+        DiagnosticPath::const_new("task_pools/async_compute/active");
+    pub const ASYNC_COMPUTE_QUEUED: DiagnosticPath =
+        DiagnosticPath::const_new("task_pools/async_compute/queued");
+    pub const IO_ACTIVE: DiagnosticPath = DiagnosticPath::const_new("task_pools/io/active");
+    pub const IO_QUEUED: DiagnosticPath = DiagnosticPath::const_new("task_pools/io/queued");
+
+    pub fn diagnostic_system(mut diagnostics: Diagnostics) {
+        let pools: [(Option<&TaskPool>, _, _); 3] = [
+            (
+                ComputeTaskPool::try_get().map(|pool| &**pool),
+                &Self::COMPUTE_ACTIVE,
+                &Self::COMPUTE_QUEUED,
+            ),
+            (
+                AsyncComputeTaskPool::try_get().map(|pool| &**pool),
+                &Self::ASYNC_COMPUTE_ACTIVE,
+                &Self::ASYNC_COMPUTE_QUEUED,
+            ),
+            (
+                IoTaskPool::try_get().map(|pool| &**pool),
+                &Self::IO_ACTIVE,
+                &Self::IO_QUEUED,
+            ),
+        ];
+
+        for (pool, active, queued) in pools {
+            let Some(pool) = pool else {
+                continue;
+            };
+            let statistics = pool.statistics();
+            diagnostics.add_measurement(active, || statistics.active as f64);
+            diagnostics.add_measurement(queued, || statistics.queued() as f64);
+        }
+    }
+}
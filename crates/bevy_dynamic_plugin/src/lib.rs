@@ -2,5 +2,7 @@
 #![allow(missing_docs)]
 
 mod loader;
+mod reload;
 
 pub use loader::*;
+pub use reload::*;
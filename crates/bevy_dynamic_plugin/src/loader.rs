@@ -1,9 +1,11 @@
 use libloading::{Library, Symbol};
-use std::ffi::OsStr;
+use std::{ffi::OsStr, path::Path};
 use thiserror::Error;
 
 use bevy_app::{App, CreatePlugin, Plugin};
 
+use crate::{DynamicPluginId, DynamicPluginReloadError};
+
 /// Errors that can occur when loading a dynamic plugin
 #[derive(Debug, Error)]
 pub enum DynamicPluginLoadError {
@@ -51,6 +53,30 @@ pub trait DynamicPluginExt {
     ///
     /// Same as [`dynamically_load_plugin`].
     unsafe fn load_plugin<P: AsRef<OsStr>>(&mut self, path: P) -> &mut Self;
+
+    /// Loads a dynamic plugin that is reloaded whenever its library changes, keeping the state of
+    /// its reflected resources and components. See
+    /// [`DynamicPluginReloadPlugin`](crate::DynamicPluginReloadPlugin) and
+    /// [`reload_dynamic_plugin`](crate::reload_dynamic_plugin) for details.
+    ///
+    /// Hot reloading is experimental: the ABI check only catches plugins built with another
+    /// compiler or against another build of Bevy. Every version of the library stays loaded until
+    /// the program exits.
+    ///
+    /// The systems of the plugin run in schedules isolated from the app's: they run together
+    /// when the app's schedule with the same label runs, and can't be ordered relative to the
+    /// app's systems. See [`DynamicPlugins`](crate::DynamicPlugins) for what is removed when the
+    /// plugin is reloaded.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`dynamically_load_plugin`], for every version of the library. In addition, the
+    /// values whose types are defined by the plugin must only be stored in components and
+    /// resources, so that they are removed before a new version of these types is loaded.
+    unsafe fn load_reloadable_plugin<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<DynamicPluginId, DynamicPluginReloadError>;
 }
 
 impl DynamicPluginExt for App {
@@ -61,4 +87,12 @@ impl DynamicPluginExt for App {
         plugin.build(self);
         self
     }
+
+    unsafe fn load_reloadable_plugin<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<DynamicPluginId, DynamicPluginReloadError> {
+        // SAFETY: Follows the same safety requirements as `load_reloadable_plugin`.
+        unsafe { crate::reload::load_reloadable_plugin(self, path.as_ref()) }
+    }
 }
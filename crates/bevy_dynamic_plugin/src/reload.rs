@@ -0,0 +1,1004 @@
+use std::{
+    any::TypeId,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant, SystemTime},
+};
+
+use bevy_app::{
+    dynamic_plugin_abi_fingerprint, App, CreatePlugin, DynamicPluginAbi, First, Plugin,
+};
+use bevy_ecs::{
+    component::ComponentId,
+    prelude::*,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    schedule::{InternedScheduleLabel, ScheduleLabel, Schedules},
+};
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    ReflectFromReflect, TypeRegistration, TypeRegistry,
+};
+use bevy_utils::{
+    tracing::{error, info, warn},
+    HashSet,
+};
+use libloading::{Library, Symbol};
+use serde::de::DeserializeSeed;
+use thiserror::Error;
+
+use crate::DynamicPluginLoadError;
+
+/// Errors that can occur when loading or reloading a hot-reloadable dynamic plugin.
+#[derive(Debug, Error)]
+pub enum DynamicPluginReloadError {
+    #[error(transparent)]
+    Load(#[from] DynamicPluginLoadError),
+    #[error("cannot copy dynamic plugin library {path:?}: {error}")]
+    Copy {
+        path: PathBuf,
+        #[source]
+        error: std::io::Error,
+    },
+    #[error("dynamic library {path:?} does not export an ABI fingerprint, it must derive `DynamicPlugin`")]
+    MissingAbi { path: PathBuf },
+    #[error("dynamic library {path:?} was not built with the same compiler against the same Bevy build as this program (fingerprint {found:#x}, expected {expected:#x})")]
+    AbiMismatch {
+        path: PathBuf,
+        expected: u64,
+        found: u64,
+    },
+    #[error("no dynamic plugin with id {0:?} is loaded")]
+    UnknownPlugin(DynamicPluginId),
+}
+
+/// Errors that can occur while carrying the state of a dynamic plugin over a reload.
+///
+/// They don't prevent the reload: the affected values are dropped, and the reloaded plugin
+/// starts with whatever values it creates itself.
+#[derive(Debug, Error)]
+pub enum DynamicPluginStateError {
+    #[error("cannot save the state of {type_path}: {message}")]
+    Save { type_path: String, message: String },
+    #[error("{type_path} is no longer a registered {kind} in the reloaded plugin, its state was dropped")]
+    TypeRemoved {
+        type_path: String,
+        kind: &'static str,
+    },
+    #[error("the layout of {type_path} changed and its state could not be restored: {message}")]
+    LayoutChanged { type_path: String, message: String },
+}
+
+/// Identifies a plugin loaded with
+/// [`load_reloadable_plugin`](crate::DynamicPluginExt::load_reloadable_plugin).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynamicPluginId(usize);
+
+/// Sent after each attempt to reload a dynamic plugin whose library changed.
+#[derive(Event, Debug)]
+pub enum DynamicPluginEvent {
+    /// The plugin was reloaded. Values listed in `state_errors` were not carried over.
+    Reloaded {
+        id: DynamicPluginId,
+        path: PathBuf,
+        state_errors: Vec<DynamicPluginStateError>,
+    },
+    /// The new library could not be loaded. The previous version of the plugin keeps running.
+    ReloadFailed {
+        id: DynamicPluginId,
+        path: PathBuf,
+        error: DynamicPluginReloadError,
+    },
+}
+
+/// Watches the libraries of the plugins loaded with
+/// [`load_reloadable_plugin`](crate::DynamicPluginExt::load_reloadable_plugin), and reloads them
+/// when they change.
+///
+/// It is added automatically when loading the first reloadable plugin. Add it beforehand to
+/// configure it.
+///
+/// This is experimental. See [`DynamicPlugins`] for how the systems of reloadable plugins are
+/// scheduled, and what is removed when they are reloaded.
+pub struct DynamicPluginReloadPlugin {
+    /// How often the libraries are checked for changes. A library is only reloaded once it stays
+    /// unchanged for a whole interval, so that it isn't loaded while it is being written.
+    pub poll_interval: Duration,
+}
+
+impl Default for DynamicPluginReloadPlugin {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_millis(500),
+        }
+    }
+}
+
+impl Plugin for DynamicPluginReloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AppTypeRegistry>()
+            .insert_resource(DynamicPlugins::new(self.poll_interval))
+            .add_event::<DynamicPluginEvent>()
+            .add_systems(First, reload_changed_dynamic_plugins);
+    }
+}
+
+/// The schedule [`reload_changed_dynamic_plugins`] runs in.
+const RELOAD_SCHEDULE: First = First;
+
+/// The hot-reloadable dynamic plugins of an app.
+///
+/// The systems added by these plugins are kept in schedules owned by each plugin instead of the
+/// app's [`Schedules`], so that they can be removed when the plugin is unloaded. For each schedule
+/// label the plugins use, a single exclusive system is added to the app's schedule with that label
+/// to run them. Plugin systems therefore can't be ordered relative to the app's systems, and
+/// sub-apps created by plugins are ignored.
+///
+/// Before a plugin is reloaded, everything it added to the world is removed:
+/// - its systems, and the app schedules that were only created to run them;
+/// - the resources it inserted while building;
+/// - the components and resources whose types it registers with reflection, or that were first
+///   used while its code was running, along with their types, so that the reloaded plugin can
+///   change their layout;
+/// - its type registrations.
+///
+/// Libraries are never unloaded: values the app can't remove, such as the schedule labels and
+/// system sets interned by the plugin, keep pointing into the code of every version of the plugin
+/// until the program exits.
+#[derive(Resource)]
+pub struct DynamicPlugins {
+    plugins: Vec<ReloadablePlugin>,
+    wired_labels: HashSet<InternedScheduleLabel>,
+    /// The labels of the app schedules created to run the systems of plugins.
+    created_labels: HashSet<InternedScheduleLabel>,
+    poll_interval: Duration,
+    last_poll: Option<Instant>,
+}
+
+struct ReloadablePlugin {
+    id: DynamicPluginId,
+    path: PathBuf,
+    loaded_modified: Option<SystemTime>,
+    pending_modified: Option<SystemTime>,
+    loaded: Option<LoadedPlugin>,
+}
+
+/// A plugin instance along with everything referring to the code of its library.
+struct LoadedPlugin {
+    schedules: Schedules,
+    plugin: Option<Box<dyn Plugin>>,
+    /// Types registered while building the plugin.
+    registered_types: Vec<TypeId>,
+    /// Resources inserted while building the plugin, and whether they are `Send`.
+    resources: Vec<(ComponentId, bool)>,
+    /// Components and resources first initialized while the code of the plugin was running.
+    components: Vec<ComponentId>,
+    library: Option<Library>,
+    /// The copy of the library that was actually loaded.
+    library_path: Option<PathBuf>,
+}
+
+impl LoadedPlugin {
+    /// Drops the plugin and its systems, and deletes the copy of its library.
+    fn unload(mut self) {
+        self.schedules = Schedules::new();
+        self.plugin = None;
+        if let Some(library_path) = self.library_path.take() {
+            // This fails on platforms that lock loaded libraries, the copy is then left behind.
+            let _ = std::fs::remove_file(library_path);
+        }
+    }
+
+    /// Runs `f`, recording the components and resources it initializes as the plugin's.
+    fn track_components<T>(&mut self, world: &mut World, f: impl FnOnce(&mut World) -> T) -> T {
+        let before = world.components().len();
+        let result = f(world);
+        self.components
+            .extend((before..world.components().len()).map(ComponentId::new));
+        result
+    }
+}
+
+impl Drop for LoadedPlugin {
+    fn drop(&mut self) {
+        // The library is never unloaded: interned labels and other values the app can't remove
+        // keep pointing into its code.
+        if let Some(library) = self.library.take() {
+            std::mem::forget(library);
+        }
+    }
+}
+
+/// The state of a plugin saved before unloading it.
+#[derive(Default)]
+struct SavedState {
+    resources: Vec<SavedValue>,
+    components: Vec<(Entity, SavedValue)>,
+}
+
+struct SavedValue {
+    type_path: String,
+    serialized: String,
+}
+
+impl DynamicPlugins {
+    fn new(poll_interval: Duration) -> Self {
+        Self {
+            plugins: Vec::new(),
+            wired_labels: HashSet::default(),
+            created_labels: HashSet::default(),
+            poll_interval,
+            last_poll: None,
+        }
+    }
+
+    /// Returns the ids of the loaded plugins.
+    pub fn ids(&self) -> impl Iterator<Item = DynamicPluginId> + '_ {
+        self.plugins.iter().map(|plugin| plugin.id)
+    }
+
+    /// Returns the path of the library of a plugin.
+    pub fn path(&self, id: DynamicPluginId) -> Option<&Path> {
+        self.get(id).map(|plugin| plugin.path.as_path())
+    }
+
+    /// Returns the plugin instance currently loaded for `id`.
+    pub fn plugin(&self, id: DynamicPluginId) -> Option<&dyn Plugin> {
+        self.get(id)?.loaded.as_ref()?.plugin.as_deref()
+    }
+
+    fn get(&self, id: DynamicPluginId) -> Option<&ReloadablePlugin> {
+        self.plugins.iter().find(|plugin| plugin.id == id)
+    }
+
+    /// Loads the library at `path` and builds its plugin.
+    ///
+    /// # Safety
+    ///
+    /// See [`load_reloadable_plugin`](crate::DynamicPluginExt::load_reloadable_plugin).
+    unsafe fn load(
+        &mut self,
+        world: &mut World,
+        path: &Path,
+    ) -> Result<DynamicPluginId, DynamicPluginReloadError> {
+        let loaded_modified = modified_time(path);
+        // SAFETY: Caller must follow the safety requirements of `load_reloadable_plugin`.
+        let (library, library_path) = unsafe { open_library(path)? };
+        // SAFETY: The library was checked to be compatible with this program.
+        let plugin = unsafe { create_plugin(&library)? };
+
+        let id = DynamicPluginId(self.plugins.len());
+        self.plugins.push(ReloadablePlugin {
+            id,
+            path: path.to_owned(),
+            loaded_modified,
+            pending_modified: None,
+            loaded: None,
+        });
+        let state_errors = self.install(
+            world,
+            self.plugins.len() - 1,
+            plugin,
+            Some(library),
+            Some(library_path),
+        );
+        debug_assert!(state_errors.is_empty());
+        Ok(id)
+    }
+
+    /// Replaces the library of a plugin with the current content of its file, carrying its
+    /// reflected state over.
+    ///
+    /// # Safety
+    ///
+    /// See [`load_reloadable_plugin`](crate::DynamicPluginExt::load_reloadable_plugin).
+    unsafe fn reload(
+        &mut self,
+        world: &mut World,
+        id: DynamicPluginId,
+    ) -> Result<Vec<DynamicPluginStateError>, DynamicPluginReloadError> {
+        let index = self
+            .plugins
+            .iter()
+            .position(|plugin| plugin.id == id)
+            .ok_or(DynamicPluginReloadError::UnknownPlugin(id))?;
+        let path = self.plugins[index].path.clone();
+        // The new library is checked before unloading the previous one, which keeps running if
+        // it is invalid.
+        // SAFETY: Caller must follow the safety requirements of `load_reloadable_plugin`.
+        let (library, library_path) = unsafe { open_library(&path)? };
+        // SAFETY: The library was checked to be compatible with this program.
+        let plugin = unsafe { create_plugin(&library)? };
+        Ok(self.install(world, index, plugin, Some(library), Some(library_path)))
+    }
+
+    /// Unloads the current version of the plugin at `index` if any, then builds `plugin` in its
+    /// place and restores the state of the previous version.
+    fn install(
+        &mut self,
+        world: &mut World,
+        index: usize,
+        plugin: Box<dyn Plugin>,
+        library: Option<Library>,
+        library_path: Option<PathBuf>,
+    ) -> Vec<DynamicPluginStateError> {
+        let mut errors = Vec::new();
+        let saved = match self.plugins[index].loaded.take() {
+            Some(previous) => {
+                let saved = save_and_remove_state(world, &previous, &mut errors);
+                previous.unload();
+                saved
+            }
+            None => SavedState::default(),
+        };
+
+        let mut loaded = build_isolated(world, plugin);
+        loaded.library = library;
+        loaded.library_path = library_path;
+        let labels: Vec<_> = loaded
+            .schedules
+            .iter()
+            .map(|(_, schedule)| schedule.label())
+            .collect();
+        loaded.track_components(world, |world| restore_state(world, saved, &mut errors));
+        self.plugins[index].loaded = Some(loaded);
+        for label in labels {
+            self.wire_schedule(world, label);
+        }
+        self.remove_unused_schedules(world);
+        errors
+    }
+
+    /// Removes the app schedules created for labels that no plugin uses anymore.
+    fn remove_unused_schedules(&mut self, world: &mut World) {
+        let used: HashSet<InternedScheduleLabel> = self
+            .plugins
+            .iter()
+            .filter_map(|plugin| plugin.loaded.as_ref())
+            .flat_map(|loaded| {
+                loaded
+                    .schedules
+                    .iter()
+                    .map(|(_, schedule)| schedule.label())
+            })
+            .collect();
+        let unused: Vec<_> = self
+            .created_labels
+            .iter()
+            .filter(|label| !used.contains(*label))
+            .copied()
+            .collect();
+        let mut schedules = world.resource_mut::<Schedules>();
+        for label in unused {
+            schedules.remove(label);
+            self.created_labels.remove(&label);
+            self.wired_labels.remove(&label);
+        }
+    }
+
+    /// Makes sure plugin schedules with the given label are run by the app.
+    fn wire_schedule(&mut self, world: &mut World, label: InternedScheduleLabel) {
+        if self.wired_labels.contains(&label) {
+            return;
+        }
+        let mut schedules = world.resource_mut::<Schedules>();
+        if let Some(schedule) = schedules.get_mut(label) {
+            schedule.add_systems(run_dynamic_plugin_schedules(label));
+        } else if label == RELOAD_SCHEDULE.intern() {
+            // The schedule is running and was taken out of `Schedules`, try again on next reload.
+            warn!("Systems added by reloaded dynamic plugins to {label:?} will only run after the next reload");
+            return;
+        } else {
+            let mut schedule = Schedule::new(label);
+            schedule.add_systems(run_dynamic_plugin_schedules(label));
+            schedules.insert(schedule);
+            self.created_labels.insert(label);
+        }
+        self.wired_labels.insert(label);
+    }
+
+    /// Returns the plugins whose library changed and stayed unchanged for a poll interval.
+    fn poll_changes(&mut self) -> Vec<DynamicPluginId> {
+        let now = Instant::now();
+        if self
+            .last_poll
+            .is_some_and(|last_poll| now.duration_since(last_poll) < self.poll_interval)
+        {
+            return Vec::new();
+        }
+        self.last_poll = Some(now);
+
+        let mut changed = Vec::new();
+        for plugin in &mut self.plugins {
+            let modified = modified_time(&plugin.path);
+            if modified.is_none() || modified == plugin.loaded_modified {
+                plugin.pending_modified = None;
+            } else if modified == plugin.pending_modified {
+                plugin.loaded_modified = modified;
+                plugin.pending_modified = None;
+                changed.push(plugin.id);
+            } else {
+                plugin.pending_modified = modified;
+            }
+        }
+        changed
+    }
+}
+
+/// Loads the library of a hot-reloadable plugin, and checks that it is compatible with this
+/// program.
+///
+/// The library is copied to a unique path before being loaded, so that the original file can be
+/// overwritten and loaded again while the copy is in use.
+///
+/// # Safety
+///
+/// See [`load_reloadable_plugin`](crate::DynamicPluginExt::load_reloadable_plugin).
+unsafe fn open_library(path: &Path) -> Result<(Library, PathBuf), DynamicPluginReloadError> {
+    static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let library_path = std::env::temp_dir().join(format!(
+        "bevy_dynamic_plugin_{}_{}_{file_name}",
+        std::process::id(),
+        GENERATION.fetch_add(1, Ordering::Relaxed),
+    ));
+    std::fs::copy(path, &library_path).map_err(|error| DynamicPluginReloadError::Copy {
+        path: path.to_owned(),
+        error,
+    })?;
+
+    // SAFETY: Caller must follow the safety requirements of Library::new.
+    let result = unsafe { Library::new(&library_path) }
+        .map_err(|error| DynamicPluginLoadError::Library(error).into())
+        .and_then(|library| {
+            // SAFETY: `_bevy_plugin_abi` is generated by `DynamicPlugin` with this signature.
+            let abi: Symbol<DynamicPluginAbi> = unsafe { library.get(b"_bevy_plugin_abi") }
+                .map_err(|_| DynamicPluginReloadError::MissingAbi {
+                    path: path.to_owned(),
+                })?;
+            // SAFETY: The fingerprint function only hashes values known at compile time.
+            let found = unsafe { abi() };
+            let expected = dynamic_plugin_abi_fingerprint(
+                TypeId::of::<App>(),
+                std::mem::size_of::<App>(),
+                std::mem::align_of::<App>(),
+            );
+            if found != expected {
+                return Err(DynamicPluginReloadError::AbiMismatch {
+                    path: path.to_owned(),
+                    expected,
+                    found,
+                });
+            }
+            Ok(library)
+        });
+    match result {
+        Ok(library) => Ok((library, library_path)),
+        Err(error) => {
+            let _ = std::fs::remove_file(library_path);
+            Err(error)
+        }
+    }
+}
+
+/// Creates the plugin exported by `library`.
+///
+/// # Safety
+///
+/// The library must have been checked with [`open_library`].
+unsafe fn create_plugin(library: &Library) -> Result<Box<dyn Plugin>, DynamicPluginReloadError> {
+    // SAFETY: Loaded plugins are not allowed to specify `_bevy_create_plugin` symbol manually, but
+    // must instead automatically generate it through `DynamicPlugin`.
+    let func: Symbol<CreatePlugin> =
+        unsafe { library.get(b"_bevy_create_plugin") }.map_err(DynamicPluginLoadError::Plugin)?;
+    // SAFETY: `func` is automatically generated and is guaranteed to return a pointer created
+    // using `Box::into_raw`.
+    Ok(unsafe { Box::from_raw(func()) })
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Builds, finishes and cleans up `plugin` on `world` with an empty [`Schedules`], recording the
+/// schedules, types and resources it adds.
+fn build_isolated(world: &mut World, plugin: Box<dyn Plugin>) -> LoadedPlugin {
+    world.init_resource::<AppTypeRegistry>();
+    let types_before: HashSet<TypeId> = world
+        .resource::<AppTypeRegistry>()
+        .read()
+        .iter()
+        .map(TypeRegistration::type_id)
+        .collect();
+    let resources_before = resource_ids(world);
+    let components_before = world.components().len();
+
+    let main_schedules = world.remove_resource::<Schedules>().unwrap_or_default();
+    let mut app = App::empty();
+    std::mem::swap(&mut app.world, world);
+    app.world.insert_resource(Schedules::new());
+    plugin.build(&mut app);
+    plugin.finish(&mut app);
+    plugin.cleanup(&mut app);
+    std::mem::swap(&mut app.world, world);
+    let schedules = world.remove_resource::<Schedules>().unwrap_or_default();
+    world.insert_resource(main_schedules);
+
+    let registered_types = world
+        .resource::<AppTypeRegistry>()
+        .read()
+        .iter()
+        .map(TypeRegistration::type_id)
+        .filter(|type_id| !types_before.contains(type_id))
+        .collect();
+    let resources = resource_ids(world)
+        .into_iter()
+        .filter(|resource| !resources_before.contains(resource))
+        .collect();
+    let components = (components_before..world.components().len())
+        .map(ComponentId::new)
+        .collect();
+
+    LoadedPlugin {
+        schedules,
+        plugin: Some(plugin),
+        registered_types,
+        resources,
+        components,
+        library: None,
+        library_path: None,
+    }
+}
+
+fn resource_ids(world: &World) -> Vec<(ComponentId, bool)> {
+    let storages = world.storages();
+    let send = storages
+        .resources
+        .iter()
+        .filter(|(_, data)| data.is_present())
+        .map(|(id, _)| (id, true));
+    let non_send = storages
+        .non_send_resources
+        .iter()
+        .filter(|(_, data)| data.is_present())
+        .map(|(id, _)| (id, false));
+    send.chain(non_send).collect()
+}
+
+/// Serializes the reflected resources and components whose types were registered by `plugin`,
+/// then removes them from the world along with the other resources it inserted, the components
+/// and resources of its types, and its type registrations.
+fn save_and_remove_state(
+    world: &mut World,
+    plugin: &LoadedPlugin,
+    errors: &mut Vec<DynamicPluginStateError>,
+) -> SavedState {
+    let mut saved = SavedState::default();
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let mut registry = registry.write();
+
+    for type_id in &plugin.registered_types {
+        let Some(registration) = registry.get(*type_id) else {
+            continue;
+        };
+        let type_path = registration.type_info().type_path();
+        let mut save = |value: &dyn bevy_reflect::Reflect| match ron::to_string(
+            &TypedReflectSerializer::new(value, &registry),
+        ) {
+            Ok(serialized) => Some(SavedValue {
+                type_path: type_path.to_string(),
+                serialized,
+            }),
+            Err(error) => {
+                errors.push(DynamicPluginStateError::Save {
+                    type_path: type_path.to_string(),
+                    message: error.to_string(),
+                });
+                None
+            }
+        };
+
+        if let Some(reflect_resource) = registration.data::<ReflectResource>() {
+            if let Some(value) = reflect_resource.reflect(world) {
+                saved.resources.extend(save(value));
+            }
+            reflect_resource.remove(world);
+        }
+        if let Some(reflect_component) = registration.data::<ReflectComponent>() {
+            let entities: Vec<Entity> = world
+                .iter_entities()
+                .filter(|entity| reflect_component.contains(*entity))
+                .map(|entity| entity.id())
+                .collect();
+            for entity in entities {
+                if let Some(value) = reflect_component.reflect(world.entity(entity)) {
+                    saved
+                        .components
+                        .extend(save(value).map(|value| (entity, value)));
+                }
+                reflect_component.remove(&mut world.entity_mut(entity));
+            }
+        }
+    }
+
+    for (resource, send) in &plugin.resources {
+        if *send {
+            world.remove_resource_by_id(*resource);
+        } else {
+            world.remove_non_send_by_id(*resource);
+        }
+    }
+    for id in plugin_components(world, plugin, &registry) {
+        remove_component(world, id);
+        world.forget_component_type(id);
+    }
+    for type_id in &plugin.registered_types {
+        registry.remove(*type_id);
+    }
+    saved
+}
+
+/// Returns the components and resources whose types are registered by `plugin`, or were first
+/// initialized while its code was running. Types registered by the app are reflected types it
+/// knows about, so they are left alone.
+fn plugin_components(
+    world: &World,
+    plugin: &LoadedPlugin,
+    registry: &TypeRegistry,
+) -> HashSet<ComponentId> {
+    let components = world.components();
+    let registered = plugin.registered_types.iter().flat_map(|type_id| {
+        [
+            components.get_id(*type_id),
+            components.get_resource_id(*type_id),
+        ]
+    });
+    let initialized = plugin.components.iter().copied().filter(|id| {
+        let Some(type_id) = components.get_info(*id).and_then(|info| info.type_id()) else {
+            return true;
+        };
+        registry.get(type_id).is_none() || plugin.registered_types.contains(&type_id)
+    });
+    registered.flatten().chain(initialized).collect()
+}
+
+/// Removes every value of the component or resource `id` from the world.
+fn remove_component(world: &mut World, id: ComponentId) {
+    let entities: Vec<Entity> = world
+        .archetypes()
+        .iter()
+        .filter(|archetype| archetype.contains(id))
+        .flat_map(|archetype| archetype.entities().iter().map(|entity| entity.id()))
+        .collect();
+    for entity in entities {
+        world.entity_mut(entity).remove_by_id(id);
+    }
+    world.remove_resource_by_id(id);
+    world.remove_non_send_by_id(id);
+}
+
+/// Deserializes the saved state with the types registered by the reloaded plugin, and puts it back
+/// in the world. Values already created by the reloaded plugin are patched, keeping the fields the
+/// saved state doesn't have.
+fn restore_state(world: &mut World, saved: SavedState, errors: &mut Vec<DynamicPluginStateError>) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+
+    for value in saved.resources {
+        let Some((registration, reflect_resource)) =
+            registered_data::<ReflectResource>(&registry, &value.type_path)
+        else {
+            errors.push(DynamicPluginStateError::TypeRemoved {
+                type_path: value.type_path,
+                kind: "resource",
+            });
+            continue;
+        };
+        match deserialize(&value, registration, &registry) {
+            Ok(restored) if reflect_resource.reflect(world).is_some() => {
+                reflect_resource.apply(world, restored.as_ref());
+            }
+            Ok(restored) => match from_reflect(&value, registration, restored) {
+                Ok(restored) => reflect_resource.insert(world, restored.as_ref()),
+                Err(error) => errors.push(error),
+            },
+            Err(error) => errors.push(error),
+        }
+    }
+
+    for (entity, value) in saved.components {
+        let Some((registration, reflect_component)) =
+            registered_data::<ReflectComponent>(&registry, &value.type_path)
+        else {
+            errors.push(DynamicPluginStateError::TypeRemoved {
+                type_path: value.type_path,
+                kind: "component",
+            });
+            continue;
+        };
+        let Some(mut entity) = world.get_entity_mut(entity) else {
+            continue;
+        };
+        match deserialize(&value, registration, &registry) {
+            Ok(restored) if reflect_component.contains(EntityRef::from(&entity)) => {
+                reflect_component.apply(&mut entity, restored.as_ref());
+            }
+            Ok(restored) => match from_reflect(&value, registration, restored) {
+                Ok(restored) => reflect_component.insert(&mut entity, restored.as_ref(), &registry),
+                Err(error) => errors.push(error),
+            },
+            Err(error) => errors.push(error),
+        }
+    }
+}
+
+fn registered_data<'a, T: bevy_reflect::TypeData>(
+    registry: &'a TypeRegistry,
+    type_path: &str,
+) -> Option<(&'a TypeRegistration, &'a T)> {
+    let registration = registry.get_with_type_path(type_path)?;
+    Some((registration, registration.data::<T>()?))
+}
+
+fn deserialize(
+    value: &SavedValue,
+    registration: &TypeRegistration,
+    registry: &TypeRegistry,
+) -> Result<Box<dyn bevy_reflect::Reflect>, DynamicPluginStateError> {
+    ron::Deserializer::from_str(&value.serialized)
+        .map_err(|error| error.to_string())
+        .and_then(|mut deserializer| {
+            TypedReflectDeserializer::new(registration, registry)
+                .deserialize(&mut deserializer)
+                .map_err(|error| error.to_string())
+        })
+        .map_err(|message| DynamicPluginStateError::LayoutChanged {
+            type_path: value.type_path.clone(),
+            message,
+        })
+}
+
+/// Converts a deserialized value to its concrete type, which fails if the type gained fields.
+fn from_reflect(
+    value: &SavedValue,
+    registration: &TypeRegistration,
+    restored: Box<dyn bevy_reflect::Reflect>,
+) -> Result<Box<dyn bevy_reflect::Reflect>, DynamicPluginStateError> {
+    let Some(reflect_from_reflect) = registration.data::<ReflectFromReflect>() else {
+        return Ok(restored);
+    };
+    reflect_from_reflect
+        .from_reflect(restored.as_ref())
+        .ok_or_else(|| DynamicPluginStateError::LayoutChanged {
+            type_path: value.type_path.clone(),
+            message: "the saved value is missing fields".to_string(),
+        })
+}
+
+/// Creates the system running the schedules with the given label of all dynamic plugins.
+fn run_dynamic_plugin_schedules(label: InternedScheduleLabel) -> impl FnMut(&mut World) {
+    move |world: &mut World| {
+        world.resource_scope(|world, mut plugins: Mut<DynamicPlugins>| {
+            for plugin in &mut plugins.plugins {
+                let Some(loaded) = plugin.loaded.as_mut() else {
+                    continue;
+                };
+                let Some(mut schedule) = loaded.schedules.remove(label) else {
+                    continue;
+                };
+                loaded.track_components(world, |world| schedule.run(world));
+                loaded.schedules.insert(schedule);
+            }
+        });
+    }
+}
+
+/// Reloads the dynamic plugins whose library changed, and sends a [`DynamicPluginEvent`] for each.
+pub fn reload_changed_dynamic_plugins(world: &mut World) {
+    let changed = world.resource_mut::<DynamicPlugins>().poll_changes();
+    for id in changed {
+        // SAFETY: Plugins can only be added through `load_reloadable_plugin`, whose caller
+        // guarantees that reloading them is sound.
+        let result = unsafe { reload_dynamic_plugin(world, id) };
+        let path = world
+            .resource::<DynamicPlugins>()
+            .path(id)
+            .map(Path::to_owned)
+            .unwrap_or_default();
+        let event = match result {
+            Ok(state_errors) => {
+                for error in &state_errors {
+                    warn!("{error}");
+                }
+                info!("Reloaded dynamic plugin {path:?}");
+                DynamicPluginEvent::Reloaded {
+                    id,
+                    path,
+                    state_errors,
+                }
+            }
+            Err(error) => {
+                error!("Failed to reload dynamic plugin {path:?}: {error}");
+                DynamicPluginEvent::ReloadFailed { id, path, error }
+            }
+        };
+        world.send_event(event);
+    }
+}
+
+/// Reloads the library of a dynamic plugin from its file, whether it changed or not.
+///
+/// The reflected resources and components whose types the plugin registered are serialized, and
+/// everything the plugin added is removed as described in [`DynamicPlugins`]. The new library is
+/// then loaded alongside the previous ones and its plugin is built, and the saved state is
+/// deserialized with the new types and restored. Values that can't be carried over are returned
+/// as errors.
+///
+/// # Safety
+///
+/// See [`load_reloadable_plugin`](crate::DynamicPluginExt::load_reloadable_plugin).
+pub unsafe fn reload_dynamic_plugin(
+    world: &mut World,
+    id: DynamicPluginId,
+) -> Result<Vec<DynamicPluginStateError>, DynamicPluginReloadError> {
+    // SAFETY: Caller must follow the safety requirements of `load_reloadable_plugin`.
+    world.resource_scope(|world, mut plugins: Mut<DynamicPlugins>| unsafe {
+        plugins.reload(world, id)
+    })
+}
+
+/// Loads a hot-reloadable dynamic plugin into `app`. See
+/// [`load_reloadable_plugin`](crate::DynamicPluginExt::load_reloadable_plugin).
+///
+/// # Safety
+///
+/// See [`load_reloadable_plugin`](crate::DynamicPluginExt::load_reloadable_plugin).
+pub(crate) unsafe fn load_reloadable_plugin(
+    app: &mut App,
+    path: &Path,
+) -> Result<DynamicPluginId, DynamicPluginReloadError> {
+    if !app.is_plugin_added::<DynamicPluginReloadPlugin>() {
+        app.add_plugins(DynamicPluginReloadPlugin::default());
+    }
+    // SAFETY: Caller must follow the safety requirements of `load_reloadable_plugin`.
+    app.world
+        .resource_scope(|world, mut plugins: Mut<DynamicPlugins>| unsafe {
+            plugins.load(world, path)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, Plugin, Update};
+    use bevy_ecs::{
+        prelude::*,
+        schedule::{ScheduleLabel, Schedules},
+    };
+    use bevy_reflect::Reflect;
+
+    use super::{DynamicPluginReloadPlugin, DynamicPluginStateError, DynamicPlugins};
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Counter(u32);
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    fn count(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    struct CountingPlugin;
+
+    impl Plugin for CountingPlugin {
+        fn build(&self, app: &mut App) {
+            app.register_type::<Counter>()
+                .register_type::<Health>()
+                .init_resource::<Counter>()
+                .add_systems(Update, count);
+        }
+    }
+
+    #[derive(Component)]
+    struct Marker;
+
+    #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct PluginSchedule;
+
+    fn spawn_marker(mut commands: Commands) {
+        commands.spawn(Marker);
+    }
+
+    struct MarkerPlugin;
+
+    impl Plugin for MarkerPlugin {
+        fn build(&self, app: &mut App) {
+            app.add_systems(Update, spawn_marker)
+                .add_systems(PluginSchedule, spawn_marker);
+        }
+    }
+
+    struct EmptyPlugin;
+
+    impl Plugin for EmptyPlugin {
+        fn build(&self, _app: &mut App) {}
+    }
+
+    fn install(app: &mut App, plugin: impl Plugin) -> Vec<DynamicPluginStateError> {
+        app.world
+            .resource_scope(|world, mut plugins: Mut<DynamicPlugins>| {
+                if plugins.plugins.is_empty() {
+                    plugins.plugins.push(super::ReloadablePlugin {
+                        id: super::DynamicPluginId(0),
+                        path: Default::default(),
+                        loaded_modified: None,
+                        pending_modified: None,
+                        loaded: None,
+                    });
+                }
+                plugins.install(world, 0, Box::new(plugin), None, None)
+            })
+    }
+
+    #[test]
+    fn plugin_systems_run_and_are_removed() {
+        let mut app = App::new();
+        app.add_plugins(DynamicPluginReloadPlugin::default());
+        assert!(install(&mut app, CountingPlugin).is_empty());
+
+        app.update();
+        app.update();
+        assert_eq!(app.world.resource::<Counter>().0, 2);
+
+        install(&mut app, EmptyPlugin);
+        assert!(app.world.get_resource::<Counter>().is_none());
+        app.update();
+        assert!(app.world.get_resource::<Counter>().is_none());
+    }
+
+    #[test]
+    fn state_is_carried_over() {
+        let mut app = App::new();
+        app.add_plugins(DynamicPluginReloadPlugin::default());
+        install(&mut app, CountingPlugin);
+        let entity = app.world.spawn(Health(7)).id();
+        app.update();
+
+        assert!(install(&mut app, CountingPlugin).is_empty());
+        assert_eq!(app.world.resource::<Counter>().0, 1);
+        assert_eq!(app.world.get::<Health>(entity), Some(&Health(7)));
+
+        app.update();
+        assert_eq!(app.world.resource::<Counter>().0, 2);
+
+        let errors = install(&mut app, EmptyPlugin);
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .all(|error| matches!(error, DynamicPluginStateError::TypeRemoved { .. })));
+        assert_eq!(app.world.get::<Health>(entity), None);
+    }
+
+    #[test]
+    fn unregistered_values_and_schedules_are_removed() {
+        let mut app = App::new();
+        app.add_plugins(DynamicPluginReloadPlugin::default());
+        install(&mut app, MarkerPlugin);
+        app.update();
+        let entity = app
+            .world
+            .query_filtered::<Entity, With<Marker>>()
+            .single(&app.world);
+        let marker = app.world.component_id::<Marker>().unwrap();
+        assert!(app.world.resource::<Schedules>().contains(PluginSchedule));
+
+        install(&mut app, EmptyPlugin);
+        assert!(!app.world.entity(entity).contains_id(marker));
+        assert_eq!(app.world.component_id::<Marker>(), None);
+        assert!(!app.world.resource::<Schedules>().contains(PluginSchedule));
+    }
+}
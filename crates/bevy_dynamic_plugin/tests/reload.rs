@@ -0,0 +1,51 @@
+//! Builds a plugin as a real dynamic library, and reloads it into a host program.
+
+use std::{
+    env::consts::{DLL_PREFIX, DLL_SUFFIX},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn cargo(fixture: &Path, target_dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO"));
+    command
+        .current_dir(fixture)
+        .arg("--quiet")
+        .env("CARGO_TARGET_DIR", target_dir)
+        // The plugin and the host must share a single copy of Bevy and of the standard library.
+        .env("RUSTFLAGS", "-C prefer-dynamic")
+        .env_remove("CARGO_ENCODED_RUSTFLAGS");
+    command
+}
+
+fn run(command: &mut Command) {
+    let status = command.status().unwrap();
+    assert!(status.success(), "{command:?} failed with {status}");
+}
+
+#[test]
+fn reload_dynamic_library() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let fixture = manifest_dir.join("tests/reload_fixture");
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("reload_fixture");
+    let target_dir = out_dir.join("target");
+    // Build the fixture with the dependency versions of the workspace.
+    let lock_file = manifest_dir.join("../../Cargo.lock");
+    if lock_file.exists() {
+        std::fs::copy(lock_file, fixture.join("Cargo.lock")).unwrap();
+    }
+
+    let library_name = |name: &str| format!("{DLL_PREFIX}{name}{DLL_SUFFIX}");
+    let built = target_dir.join("debug").join(library_name("reload_plugin"));
+    let v1 = out_dir.join(library_name("reload_plugin"));
+    let v2 = out_dir.join(library_name("reload_plugin_v2"));
+    run(cargo(&fixture, &target_dir).args(["build", "-p", "reload_plugin", "--features", "v2"]));
+    std::fs::copy(&built, &v2).unwrap();
+    run(cargo(&fixture, &target_dir).args(["build", "-p", "reload_plugin"]));
+    std::fs::copy(&built, &v1).unwrap();
+
+    // `cargo run` makes the dynamic libraries of Bevy and of the standard library available.
+    run(cargo(&fixture, &target_dir)
+        .args(["run", "-p", "reload_host", "--"])
+        .args([&v1, &v2]));
+}
@@ -0,0 +1,7 @@
+//! The subset of Bevy used by the fixture, linked dynamically by the plugin and the host.
+
+pub use bevy_app as app;
+pub use bevy_derive::DynamicPlugin;
+pub use bevy_dynamic_plugin as dynamic_plugin;
+pub use bevy_ecs as ecs;
+pub use bevy_reflect as reflect;
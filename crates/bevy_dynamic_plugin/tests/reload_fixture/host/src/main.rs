@@ -0,0 +1,76 @@
+//! Loads the plugin library given as first argument, replaces it with the library given as second
+//! argument, and reloads it.
+
+use std::{path::PathBuf, time::Duration};
+
+use bevy::{
+    app::App,
+    dynamic_plugin::{reload_dynamic_plugin, DynamicPluginExt, DynamicPluginReloadPlugin},
+    ecs::{
+        prelude::*,
+        reflect::{AppTypeRegistry, ReflectResource},
+    },
+    reflect::ReflectRef,
+};
+
+/// Reads the `Counter` resource of the plugin through reflection.
+fn counter(world: &World) -> u32 {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let registration = registry
+        .get_with_type_path("reload_plugin::Counter")
+        .unwrap();
+    let counter = registration
+        .data::<ReflectResource>()
+        .unwrap()
+        .reflect(world)
+        .unwrap();
+    let ReflectRef::TupleStruct(counter) = counter.reflect_ref() else {
+        panic!("`Counter` is not a tuple struct");
+    };
+    *counter.field(0).unwrap().downcast_ref::<u32>().unwrap()
+}
+
+/// Counts the `Marker` components of every version of the plugin.
+fn markers(world: &World) -> usize {
+    world
+        .components()
+        .iter()
+        .filter(|info| info.name() == "reload_plugin::Marker")
+        .map(|info| {
+            world
+                .archetypes()
+                .iter()
+                .filter(|archetype| archetype.contains(info.id()))
+                .map(|archetype| archetype.len())
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1).map(PathBuf::from);
+    let (path, next_version) = (args.next().unwrap(), args.next().unwrap());
+
+    let mut app = App::new();
+    // Reloads are triggered explicitly.
+    app.add_plugins(DynamicPluginReloadPlugin {
+        poll_interval: Duration::from_secs(3600),
+    });
+    // SAFETY: The plugin derives `DynamicPlugin`, and is linked against the same Bevy library.
+    let id = unsafe { app.load_reloadable_plugin(&path) }.unwrap();
+    app.update();
+    app.update();
+    assert_eq!(counter(&app.world), 2);
+    assert_eq!(markers(&app.world), 2);
+
+    std::fs::copy(next_version, &path).unwrap();
+    // SAFETY: Same as above.
+    let errors = unsafe { reload_dynamic_plugin(&mut app.world, id) }.unwrap();
+    assert!(errors.is_empty(), "{errors:?}");
+    assert_eq!(counter(&app.world), 2);
+    assert_eq!(markers(&app.world), 0);
+
+    app.update();
+    assert_eq!(counter(&app.world), 12);
+    assert_eq!(markers(&app.world), 1);
+}
@@ -0,0 +1,44 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::prelude::*,
+    reflect::Reflect,
+    DynamicPlugin,
+};
+
+/// Carried over reloads, as it is reflected.
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+struct Counter(u32);
+
+/// Removed on reload, as it isn't reflected.
+#[cfg(not(feature = "v2"))]
+#[derive(Component)]
+struct Marker(String);
+
+#[cfg(feature = "v2")]
+#[derive(Component)]
+struct Marker(Vec<u64>, String);
+
+fn count(mut counter: ResMut<Counter>, mut commands: Commands) {
+    #[cfg(not(feature = "v2"))]
+    {
+        counter.0 += 1;
+        commands.spawn(Marker("v1".to_string()));
+    }
+    #[cfg(feature = "v2")]
+    {
+        counter.0 += 10;
+        commands.spawn(Marker(vec![2], "v2".to_string()));
+    }
+}
+
+#[derive(DynamicPlugin)]
+pub struct ReloadPlugin;
+
+impl Plugin for ReloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Counter>()
+            .init_resource::<Counter>()
+            .add_systems(Update, count);
+    }
+}
@@ -820,6 +820,14 @@ impl Bundles {
         self.bundle_ids.get(&type_id).cloned()
     }
 
+    /// Forgets the Rust types of the bundles containing the component `id`, so that initializing
+    /// these types again creates new bundles.
+    pub(crate) fn forget_component(&mut self, id: ComponentId) {
+        let bundle_infos = &self.bundle_infos;
+        self.bundle_ids
+            .retain(|_, bundle_id| !bundle_infos[bundle_id.index()].components().contains(&id));
+    }
+
     /// Initializes a new [`BundleInfo`] for a statically known type.
     pub(crate) fn init_info<'a, T: Bundle>(
         &'a mut self,
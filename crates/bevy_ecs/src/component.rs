@@ -507,6 +507,22 @@ impl Components {
         component_id
     }
 
+    /// Forgets the Rust type of the component or resource `id`, so that initializing that type
+    /// again creates a new [`ComponentId`] instead of returning `id`.
+    ///
+    /// The [`ComponentInfo`] of `id` stays valid.
+    pub(crate) fn forget_type(&mut self, id: ComponentId) {
+        let Some(type_id) = self.get_info(id).and_then(ComponentInfo::type_id) else {
+            return;
+        };
+        if self.indices.get(&type_id) == Some(&id) {
+            self.indices.remove(&type_id);
+        }
+        if self.resource_indices.get(&type_id) == Some(&id) {
+            self.resource_indices.remove(&type_id);
+        }
+    }
+
     /// Returns the number of components registered with this instance.
     #[inline]
     pub fn len(&self) -> usize {
@@ -205,6 +205,17 @@ impl World {
             .init_component_with_descriptor(&mut self.storages, descriptor)
     }
 
+    /// Forgets the Rust type of the component or resource `id`, so that initializing that type
+    /// again creates a new [`ComponentId`], with a new [`ComponentInfo`](crate::component::ComponentInfo).
+    ///
+    /// This is meant for types whose definition can change while the program runs, such as the
+    /// types of a dynamically reloaded plugin. Values already stored with `id` keep it, and are
+    /// no longer accessible through their type.
+    pub fn forget_component_type(&mut self, id: ComponentId) {
+        self.components.forget_type(id);
+        self.bundles.forget_component(id);
+    }
+
     /// Returns the [`ComponentId`] of the given [`Component`] type `T`.
     ///
     /// The returned `ComponentId` is specific to the `World` instance
@@ -2796,4 +2807,30 @@ mod tests {
         let mut world = World::new();
         world.spawn(());
     }
+
+    #[test]
+    fn forget_component_type() {
+        #[derive(Component, Debug, PartialEq)]
+        struct Foo(u32);
+
+        #[derive(Component)]
+        struct Bar;
+
+        let mut world = World::new();
+        let old = world.spawn((Foo(1), Bar)).id();
+        let old_id = world.component_id::<Foo>().unwrap();
+
+        world.forget_component_type(old_id);
+        assert_eq!(world.component_id::<Foo>(), None);
+        let new = world.spawn((Foo(2), Bar)).id();
+        let new_id = world.component_id::<Foo>().unwrap();
+        assert_ne!(new_id, old_id);
+        assert_eq!(world.get::<Foo>(old), None);
+        assert_eq!(world.get::<Foo>(new), Some(&Foo(2)));
+        assert!(world.entity(old).contains_id(old_id));
+
+        world.entity_mut(old).remove_by_id(old_id);
+        assert!(!world.entity(old).contains_id(old_id));
+        assert!(world.entity(old).contains::<Bar>());
+    }
 }
@@ -192,3 +192,9 @@ pub mod dynamic_plugin {
     //! Dynamic linking of plugins
     pub use bevy_dynamic_plugin::*;
 }
+
+#[cfg(feature = "bevy_remote")]
+pub mod remote {
+    //! A JSON-RPC protocol to inspect and modify a running app from other processes
+    pub use bevy_remote::*;
+}
@@ -43,6 +43,7 @@ pub use bevy_utils::{
 pub use tracing_subscriber;
 
 use bevy_app::{App, Plugin};
+use bevy_reflect::Reflect;
 use bevy_utils::tracing::Subscriber;
 use tracing_log::LogTracer;
 #[cfg(feature = "tracing-chrome")]
@@ -98,16 +99,23 @@ use tracing_subscriber::{prelude::*, registry::Registry, EnvFilter};
 /// This plugin should not be added multiple times in the same process. This plugin
 /// sets up global logging configuration for **all** Apps in a given process, and
 /// rerunning the same initialization multiple times will lead to a panic.
+///
+/// Only the `filter` is reflected, so it is the only field that can be overridden from settings
+/// files. It can also set levels, as in `"debug,wgpu=error"`.
+#[derive(Reflect)]
+#[reflect(from_reflect = false)]
 pub struct LogPlugin {
     /// Filters logs using the [`EnvFilter`] format
     pub filter: String,
 
     /// Filters out logs that are "less than" the given level.
     /// This can be further filtered using the `filter` setting.
+    #[reflect(ignore)]
     pub level: Level,
 
     /// Optionally apply extra transformations to the tracing subscriber.
     /// For example add [`Layers`](tracing_subscriber::layer::Layer)
+    #[reflect(ignore)]
     pub update_subscriber: Option<fn(BoxedSubscriber) -> BoxedSubscriber>,
 }
 
@@ -127,6 +135,8 @@ impl Default for LogPlugin {
 impl Plugin for LogPlugin {
     #[cfg_attr(not(feature = "tracing-chrome"), allow(unused_variables))]
     fn build(&self, app: &mut App) {
+        app.register_type::<LogPlugin>();
+
         #[cfg(feature = "trace")]
         {
             let old_handler = panic::take_hook();
@@ -226,3 +236,23 @@ impl Plugin for LogPlugin {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::settings::{Settings, SettingsFormat};
+
+    use super::LogPlugin;
+
+    #[test]
+    fn filter_can_be_overridden_from_settings() {
+        let settings = Settings::new().with_str(
+            "settings.ron",
+            SettingsFormat::Ron,
+            r#"{ "LogPlugin": (filter: "debug,wgpu=error") }"#,
+        );
+        let mut plugin = LogPlugin::default();
+        assert!(settings.apply_to(&mut plugin).unwrap());
+        assert_eq!(plugin.filter, "debug,wgpu=error");
+        assert_eq!(plugin.level, super::Level::INFO);
+    }
+}
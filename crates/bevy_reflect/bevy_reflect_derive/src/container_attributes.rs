@@ -5,6 +5,7 @@
 //! the derive helper attribute for `Reflect`, which looks like:
 //! `#[reflect(PartialEq, Default, ...)]` and `#[reflect_value(PartialEq, Default, ...)]`.
 
+use crate::custom_attributes::CustomAttributes;
 use crate::derive_data::ReflectTraitToImpl;
 use crate::utility;
 use crate::utility::terminated_parser;
@@ -223,6 +224,7 @@ pub(crate) struct ContainerAttributes {
     type_path_attrs: TypePathAttrs,
     custom_where: Option<WhereClause>,
     no_field_bounds: bool,
+    custom_attributes: CustomAttributes,
     idents: Vec<Ident>,
 }
 
@@ -257,7 +259,9 @@ impl ContainerAttributes {
         trait_: ReflectTraitToImpl,
     ) -> syn::Result<()> {
         let lookahead = input.lookahead1();
-        if lookahead.peek(Token![where]) {
+        if lookahead.peek(Token![@]) {
+            self.custom_attributes.parse_custom_attribute(input)
+        } else if lookahead.peek(Token![where]) {
             self.parse_custom_where(input)
         } else if lookahead.peek(kw::from_reflect) {
             self.parse_from_reflect(input, trait_)
@@ -531,6 +535,11 @@ impl ContainerAttributes {
         self.no_field_bounds
     }
 
+    /// The custom attributes found within `#[reflect(@...)]` attributes on this type.
+    pub fn custom_attributes(&self) -> &CustomAttributes {
+        &self.custom_attributes
+    }
+
     /// Merges the trait implementations of this [`ContainerAttributes`] with another one.
     ///
     /// An error is returned if the two [`ContainerAttributes`] have conflicting implementations.
@@ -544,6 +553,7 @@ impl ContainerAttributes {
             type_path_attrs,
             custom_where,
             no_field_bounds,
+            custom_attributes,
             idents,
         } = self;
 
@@ -557,6 +567,8 @@ impl ContainerAttributes {
 
         *no_field_bounds |= other.no_field_bounds;
 
+        custom_attributes.merge(other.custom_attributes);
+
         for ident in other.idents {
             add_unique_ident(idents, ident)?;
         }
@@ -0,0 +1,49 @@
+//! Contains code related to custom attributes for reflected types, fields and variants.
+//!
+//! A custom attribute is any expression prefixed with `@` in a `#[reflect(...)]` attribute,
+//! such as `#[reflect(@0.0..=1.0)]`. Its value is stored in the type info by its type.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{Expr, Path, Token};
+
+/// The custom attributes of a reflected type, field or variant.
+#[derive(Default, Clone)]
+pub(crate) struct CustomAttributes {
+    attributes: Vec<Expr>,
+}
+
+impl CustomAttributes {
+    /// Parse a custom attribute.
+    ///
+    /// Examples:
+    /// - `#[reflect(@Tooltip::new("A tooltip"))]`
+    /// - `#[reflect(@0.0..=1.0)]`
+    pub fn parse_custom_attribute(&mut self, input: ParseStream) -> syn::Result<()> {
+        input.parse::<Token![@]>()?;
+        self.attributes.push(input.parse::<Expr>()?);
+        Ok(())
+    }
+
+    /// Adds the custom attributes of another [`CustomAttributes`].
+    pub fn merge(&mut self, other: CustomAttributes) {
+        self.attributes.extend(other.attributes);
+    }
+
+    /// Returns a call to `with_custom_attributes` setting these attributes on an info type,
+    /// or nothing if there are none.
+    pub fn to_with_tokens(&self, bevy_reflect_path: &Path) -> Option<TokenStream> {
+        if self.attributes.is_empty() {
+            return None;
+        }
+
+        let attributes = &self.attributes;
+        Some(quote! {
+            .with_custom_attributes(
+                #bevy_reflect_path::attributes::CustomAttributes::default()
+                    #(.with_attribute(#attributes))*
+            )
+        })
+    }
+}
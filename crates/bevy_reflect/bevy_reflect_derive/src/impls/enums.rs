@@ -57,18 +57,24 @@ pub(crate) fn impl_enum(reflect_enum: &ReflectEnum) -> proc_macro2::TokenStream
             }
         });
 
+    let with_custom_attributes = reflect_enum
+        .meta()
+        .attrs()
+        .custom_attributes()
+        .to_with_tokens(bevy_reflect_path);
+
     #[cfg(feature = "documentation")]
     let info_generator = {
         let doc = reflect_enum.meta().doc();
         quote! {
-            #bevy_reflect_path::EnumInfo::new::<Self>(&variants).with_docs(#doc)
+            #bevy_reflect_path::EnumInfo::new::<Self>(&variants) #with_custom_attributes .with_docs(#doc)
         }
     };
 
     #[cfg(not(feature = "documentation"))]
     let info_generator = {
         quote! {
-            #bevy_reflect_path::EnumInfo::new::<Self>(&variants)
+            #bevy_reflect_path::EnumInfo::new::<Self>(&variants) #with_custom_attributes
         }
     };
 
@@ -360,18 +366,24 @@ fn generate_impls(reflect_enum: &ReflectEnum, ref_index: &Ident, ref_name: &Iden
         }
 
         let mut push_variant =
-            |_variant: &EnumVariant, arguments: proc_macro2::TokenStream, field_len: usize| {
+            |variant: &EnumVariant, arguments: proc_macro2::TokenStream, field_len: usize| {
                 #[cfg(feature = "documentation")]
                 let with_docs = {
-                    let doc = quote::ToTokens::to_token_stream(&_variant.doc);
+                    let doc = quote::ToTokens::to_token_stream(&variant.doc);
                     Some(quote!(.with_docs(#doc)))
                 };
                 #[cfg(not(feature = "documentation"))]
                 let with_docs: Option<proc_macro2::TokenStream> = None;
 
+                let with_custom_attributes = variant
+                    .attrs
+                    .custom_attributes
+                    .to_with_tokens(bevy_reflect_path);
+
                 variant_info.push(quote! {
                     #bevy_reflect_path::VariantInfo::#variant_type_ident(
                         #bevy_reflect_path::#variant_info_ident::new(#arguments)
+                        #with_custom_attributes
                         #with_docs
                     )
                 });
@@ -402,9 +414,15 @@ fn generate_impls(reflect_enum: &ReflectEnum, ref_index: &Ident, ref_name: &Iden
                     #[cfg(not(feature = "documentation"))]
                     let with_docs: Option<proc_macro2::TokenStream> = None;
 
+                    let with_custom_attributes = field
+                        .attrs
+                        .custom_attributes
+                        .to_with_tokens(bevy_reflect_path);
+
                     let field_ty = &field.data.ty;
                     quote! {
                         #bevy_reflect_path::UnnamedField::new::<#field_ty>(#reflect_idx)
+                        #with_custom_attributes
                         #with_docs
                     }
                 });
@@ -437,9 +455,15 @@ fn generate_impls(reflect_enum: &ReflectEnum, ref_index: &Ident, ref_name: &Iden
                     #[cfg(not(feature = "documentation"))]
                     let with_docs: Option<proc_macro2::TokenStream> = None;
 
+                    let with_custom_attributes = field
+                        .attrs
+                        .custom_attributes
+                        .to_with_tokens(bevy_reflect_path);
+
                     let field_ty = &field.data.ty;
                     quote! {
                         #bevy_reflect_path::NamedField::new::<#field_ty>(#field_name)
+                        #with_custom_attributes
                         #with_docs
                     }
                 });
@@ -46,20 +46,35 @@ pub(crate) fn impl_struct(reflect_struct: &ReflectStruct) -> proc_macro2::TokenS
             }
         });
 
+    let field_attributes = reflect_struct
+        .active_fields()
+        .map(|field| {
+            field
+                .attrs
+                .custom_attributes
+                .to_with_tokens(bevy_reflect_path)
+        })
+        .collect::<Vec<_>>();
+    let with_custom_attributes = reflect_struct
+        .meta()
+        .attrs()
+        .custom_attributes()
+        .to_with_tokens(bevy_reflect_path);
+
     #[cfg(feature = "documentation")]
     let field_generator = {
         let docs = reflect_struct
             .active_fields()
             .map(|field| ToTokens::to_token_stream(&field.doc));
         quote! {
-            #(#bevy_reflect_path::NamedField::new::<#field_types>(#field_names).with_docs(#docs) ,)*
+            #(#bevy_reflect_path::NamedField::new::<#field_types>(#field_names) #field_attributes .with_docs(#docs) ,)*
         }
     };
 
     #[cfg(not(feature = "documentation"))]
     let field_generator = {
         quote! {
-            #(#bevy_reflect_path::NamedField::new::<#field_types>(#field_names) ,)*
+            #(#bevy_reflect_path::NamedField::new::<#field_types>(#field_names) #field_attributes ,)*
         }
     };
 
@@ -67,14 +82,14 @@ pub(crate) fn impl_struct(reflect_struct: &ReflectStruct) -> proc_macro2::TokenS
     let info_generator = {
         let doc = reflect_struct.meta().doc();
         quote! {
-            #bevy_reflect_path::StructInfo::new::<Self>(&fields).with_docs(#doc)
+            #bevy_reflect_path::StructInfo::new::<Self>(&fields) #with_custom_attributes .with_docs(#doc)
         }
     };
 
     #[cfg(not(feature = "documentation"))]
     let info_generator = {
         quote! {
-            #bevy_reflect_path::StructInfo::new::<Self>(&fields)
+            #bevy_reflect_path::StructInfo::new::<Self>(&fields) #with_custom_attributes
         }
     };
 
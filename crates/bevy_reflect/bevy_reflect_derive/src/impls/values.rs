@@ -21,12 +21,17 @@ pub(crate) fn impl_value(meta: &ReflectMeta) -> proc_macro2::TokenStream {
     #[cfg(not(feature = "documentation"))]
     let with_docs: Option<proc_macro2::TokenStream> = None;
 
+    let with_custom_attributes = meta
+        .attrs()
+        .custom_attributes()
+        .to_with_tokens(bevy_reflect_path);
+
     let where_clause_options = WhereClauseOptions::new(meta);
     let typed_impl = impl_typed(
         meta,
         &where_clause_options,
         quote! {
-            let info = #bevy_reflect_path::ValueInfo::new::<Self>() #with_docs;
+            let info = #bevy_reflect_path::ValueInfo::new::<Self>() #with_custom_attributes #with_docs;
             #bevy_reflect_path::TypeInfo::Value(info)
         },
     );
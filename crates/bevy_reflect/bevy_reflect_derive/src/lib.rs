@@ -15,6 +15,7 @@
 extern crate proc_macro;
 
 mod container_attributes;
+mod custom_attributes;
 mod derive_data;
 #[cfg(feature = "documentation")]
 mod documentation;
@@ -271,6 +272,21 @@ fn match_reflect_impls(ast: DeriveInput, source: ReflectImplSource) -> TokenStre
 /// // {/* ... */}
 /// ```
 ///
+/// ## `#[reflect(@expr)]`
+///
+/// This attribute attaches the value of `expr` to the `TypeInfo` of the type as a custom attribute,
+/// which can be retrieved by its type at runtime.
+/// It can also be applied to fields and enum variants.
+///
+/// ```ignore
+/// #[derive(Reflect)]
+/// #[reflect(@Tooltip::new("The volume of the game"))]
+/// struct Volume {
+///   #[reflect(@0.0..=1.0)]
+///   master: f64,
+/// }
+/// ```
+///
 /// # Field Attributes
 ///
 /// Along with the container attributes, this macro comes with some attributes that may be applied
@@ -294,6 +310,11 @@ fn match_reflect_impls(ast: DeriveInput, source: ReflectImplSource) -> TokenStre
 /// What this does is register the `SerializationData` type within the `GetTypeRegistration` implementation,
 /// which will be used by the reflection serializers to determine whether or not the field is serializable.
 ///
+/// ## `#[reflect(@expr)]`
+///
+/// This attribute attaches the value of `expr` to the `NamedField` or `UnnamedField` info of the field
+/// (or the `VariantInfo` of an enum variant) as a custom attribute, see the container attribute above.
+///
 /// [`reflect_trait`]: macro@reflect_trait
 #[proc_macro_derive(Reflect, attributes(reflect, reflect_value, type_path, type_name))]
 pub fn derive_reflect(input: TokenStream) -> TokenStream {
@@ -0,0 +1,268 @@
+//! Custom attributes of reflected types, fields and enum variants.
+//!
+//! Any reflectable value can be attached as an attribute with the `@` prefix in a
+//! `#[reflect(...)]` attribute of the `Reflect` derive. Attributes are stored by type in the
+//! [`CustomAttributes`] of the [`TypeInfo`](crate::TypeInfo), [`NamedField`](crate::NamedField),
+//! [`UnnamedField`](crate::UnnamedField) or [`VariantInfo`](crate::VariantInfo) they annotate,
+//! so there can be only one attribute of each type on an item.
+//!
+//! ```
+//! # use bevy_reflect::{Reflect, Struct, TypeInfo, Typed};
+//! # use std::ops::RangeInclusive;
+//! /// Shown as a tooltip in an inspector.
+//! #[derive(Reflect, Debug, PartialEq)]
+//! struct Tooltip(&'static str);
+//!
+//! #[derive(Reflect)]
+//! #[reflect(@Tooltip("The volume of the game"))]
+//! struct Volume {
+//!     #[reflect(@0.0..=1.0_f32, @Tooltip("The master volume"))]
+//!     master: f32,
+//! }
+//!
+//! let TypeInfo::Struct(info) = Volume::type_info() else {
+//!     panic!("expected a struct");
+//! };
+//! assert_eq!(
+//!     info.get_attribute::<Tooltip>(),
+//!     Some(&Tooltip("The volume of the game"))
+//! );
+//!
+//! let master = info.field("master").unwrap();
+//! let range = master.get_attribute::<RangeInclusive<f32>>().unwrap();
+//! assert_eq!(range, &(0.0..=1.0));
+//! assert!(master.has_attribute::<Tooltip>());
+//! ```
+
+use crate::Reflect;
+use bevy_utils::TypeIdMap;
+use std::any::TypeId;
+use std::fmt::{Debug, Formatter};
+
+/// The custom attributes of a reflected type, field or enum variant, by type.
+///
+/// See the [module-level documentation](crate::attributes) for how to add them.
+#[derive(Default)]
+pub struct CustomAttributes {
+    attributes: TypeIdMap<Box<dyn Reflect>>,
+}
+
+impl CustomAttributes {
+    /// Adds the attribute `value`, replacing the attribute of the same type if there is one.
+    pub fn with_attribute<T: Reflect>(mut self, value: T) -> Self {
+        self.attributes.insert(TypeId::of::<T>(), Box::new(value));
+        self
+    }
+
+    /// Returns `true` if there is an attribute of the type `T`.
+    pub fn contains<T: Reflect>(&self) -> bool {
+        self.contains_by_id(TypeId::of::<T>())
+    }
+
+    /// Returns `true` if there is an attribute of the type with the given [`TypeId`].
+    pub fn contains_by_id(&self, id: TypeId) -> bool {
+        self.attributes.contains_key(&id)
+    }
+
+    /// Returns the attribute of the type `T`, if any.
+    pub fn get<T: Reflect>(&self) -> Option<&T> {
+        self.get_by_id(TypeId::of::<T>())?.downcast_ref()
+    }
+
+    /// Returns the attribute of the type with the given [`TypeId`], if any.
+    pub fn get_by_id(&self, id: TypeId) -> Option<&dyn Reflect> {
+        self.attributes.get(&id).map(|value| value.as_ref())
+    }
+
+    /// Returns an iterator over the attributes, in no particular order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (TypeId, &dyn Reflect)> {
+        self.attributes
+            .iter()
+            .map(|(id, value)| (*id, value.as_ref()))
+    }
+
+    /// Returns the number of attributes.
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    /// Returns `true` if there are no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+}
+
+impl Debug for CustomAttributes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set()
+            .entries(self.attributes.values().map(|value| value as &dyn Debug))
+            .finish()
+    }
+}
+
+/// Implements the methods accessing the [`CustomAttributes`] of an info type, stored in an
+/// `Arc<CustomAttributes>` field named `custom_attributes`.
+macro_rules! impl_custom_attribute_methods {
+    ($item:literal) => {
+        #[doc = concat!("Sets the custom attributes of this ", $item, ".")]
+        pub fn with_custom_attributes(
+            self,
+            custom_attributes: $crate::attributes::CustomAttributes,
+        ) -> Self {
+            Self {
+                custom_attributes: ::std::sync::Arc::new(custom_attributes),
+                ..self
+            }
+        }
+
+        #[doc = concat!("Returns the custom attributes of this ", $item, ".")]
+        pub fn custom_attributes(&self) -> &$crate::attributes::CustomAttributes {
+            &self.custom_attributes
+        }
+
+        #[doc = concat!("Returns the custom attribute of the type `T` of this ", $item, ", if any.")]
+        pub fn get_attribute<T: $crate::Reflect>(&self) -> Option<&T> {
+            self.custom_attributes.get::<T>()
+        }
+
+        #[doc = concat!("Returns `true` if this ", $item, " has a custom attribute of the type `T`.")]
+        pub fn has_attribute<T: $crate::Reflect>(&self) -> bool {
+            self.custom_attributes.contains::<T>()
+        }
+    };
+}
+
+pub(crate) use impl_custom_attribute_methods;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{TypeInfo, Typed, VariantInfo};
+    use std::ops::RangeInclusive;
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Tooltip(String);
+
+    impl Tooltip {
+        fn new(text: &str) -> Self {
+            Self(text.to_string())
+        }
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct ReadOnly;
+
+    #[test]
+    fn should_store_attributes_by_type() {
+        let attributes = CustomAttributes::default()
+            .with_attribute(ReadOnly)
+            .with_attribute(Tooltip::new("first"))
+            .with_attribute(Tooltip::new("second"));
+
+        assert_eq!(attributes.len(), 2);
+        assert!(attributes.contains::<ReadOnly>());
+        assert_eq!(attributes.get::<Tooltip>(), Some(&Tooltip::new("second")));
+        assert!(attributes.get::<u32>().is_none());
+        let tooltip = attributes.get_by_id(TypeId::of::<Tooltip>()).unwrap();
+        assert!(tooltip.is::<Tooltip>());
+    }
+
+    #[test]
+    fn should_derive_struct_attributes() {
+        #[derive(Reflect)]
+        #[reflect(@ReadOnly)]
+        #[reflect(@Tooltip::new("A player"))]
+        struct Player {
+            #[reflect(@0..=100_u32, @Tooltip::new("The health"))]
+            health: u32,
+            #[reflect(ignore)]
+            _cache: u32,
+            name: String,
+        }
+
+        let TypeInfo::Struct(info) = Player::type_info() else {
+            panic!("expected a struct");
+        };
+        assert!(info.has_attribute::<ReadOnly>());
+        assert_eq!(
+            info.get_attribute::<Tooltip>(),
+            Some(&Tooltip::new("A player"))
+        );
+
+        let health = info.field("health").unwrap();
+        assert_eq!(
+            health.get_attribute::<RangeInclusive<u32>>(),
+            Some(&(0..=100))
+        );
+        assert_eq!(health.custom_attributes().len(), 2);
+        assert!(info.field("name").unwrap().custom_attributes().is_empty());
+    }
+
+    #[test]
+    fn should_derive_tuple_struct_attributes() {
+        #[derive(Reflect)]
+        #[reflect(@ReadOnly)]
+        struct Meters(#[reflect(@Tooltip::new("In meters"))] f32);
+
+        let TypeInfo::TupleStruct(info) = Meters::type_info() else {
+            panic!("expected a tuple struct");
+        };
+        assert!(info.has_attribute::<ReadOnly>());
+        assert_eq!(
+            info.field_at(0).unwrap().get_attribute::<Tooltip>(),
+            Some(&Tooltip::new("In meters"))
+        );
+    }
+
+    #[test]
+    fn should_derive_enum_attributes() {
+        #[derive(Reflect)]
+        #[reflect(@Tooltip::new("A state"))]
+        enum State {
+            #[reflect(@ReadOnly)]
+            Idle,
+            Moving(#[reflect(@0.0..=10.0_f32)] f32),
+            #[reflect(@Tooltip::new("Attacking"))]
+            Attacking {
+                #[reflect(@ReadOnly)]
+                target: u32,
+            },
+        }
+
+        let TypeInfo::Enum(info) = State::type_info() else {
+            panic!("expected an enum");
+        };
+        assert_eq!(
+            info.get_attribute::<Tooltip>(),
+            Some(&Tooltip::new("A state"))
+        );
+
+        let VariantInfo::Unit(idle) = info.variant("Idle").unwrap() else {
+            panic!("expected a unit variant");
+        };
+        assert!(idle.has_attribute::<ReadOnly>());
+
+        let VariantInfo::Tuple(moving) = info.variant("Moving").unwrap() else {
+            panic!("expected a tuple variant");
+        };
+        assert!(moving.custom_attributes().is_empty());
+        assert_eq!(
+            moving
+                .field_at(0)
+                .unwrap()
+                .get_attribute::<RangeInclusive<f32>>(),
+            Some(&(0.0..=10.0))
+        );
+
+        let attacking = info.variant("Attacking").unwrap();
+        assert!(attacking.custom_attributes().contains::<Tooltip>());
+        let VariantInfo::Struct(attacking) = attacking else {
+            panic!("expected a struct variant");
+        };
+        assert!(attacking
+            .field("target")
+            .unwrap()
+            .has_attribute::<ReadOnly>());
+    }
+}
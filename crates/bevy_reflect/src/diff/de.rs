@@ -0,0 +1,320 @@
+use crate::diff::ser::{
+    LIST_CHANGE_VARIANTS, MAP_CHANGE_VARIANTS, PATCH_VARIANTS, SET_CHANGE_VARIANTS,
+};
+use crate::diff::{ListChange, MapChange, Patch, SetChange};
+use crate::serde::UntypedReflectDeserializer;
+use crate::{Reflect, TypeRegistry};
+use serde::de::{
+    DeserializeSeed, EnumAccess, Error, MapAccess, SeqAccess, Unexpected, VariantAccess, Visitor,
+};
+use serde::Deserializer;
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Deserializes a [`Patch`] serialized by a [`PatchSerializer`](crate::diff::PatchSerializer).
+///
+/// The values in the patch are deserialized by an [`UntypedReflectDeserializer`], so they are
+/// usually dynamic values, which [`apply_patch`](crate::diff::apply_patch) accepts.
+#[derive(Clone, Copy)]
+pub struct PatchDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> PatchDeserializer<'a> {
+    /// Creates a deserializer for patches containing types of the given registry.
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PatchDeserializer<'a> {
+    type Value = Patch;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Patch, D::Error> {
+        deserializer.deserialize_enum("Patch", PATCH_VARIANTS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for PatchDeserializer<'a> {
+    type Value = Patch;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a reflect patch")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Patch, A::Error> {
+        let registry = self.registry;
+        let (variant, access) = data.variant_seed(VariantSeed(PATCH_VARIANTS))?;
+        Ok(match variant {
+            0 => {
+                access.unit_variant()?;
+                Patch::NoChange
+            }
+            1 => Patch::Replace(access.newtype_variant_seed(ValueSeed(registry))?),
+            2 => Patch::Struct(access.newtype_variant_seed(FieldPatchesSeed(self))?),
+            3 => Patch::TupleStruct(access.newtype_variant_seed(SeqSeed(IndexedPatchSeed(self)))?),
+            4 => Patch::Tuple(access.newtype_variant_seed(SeqSeed(IndexedPatchSeed(self)))?),
+            5 => Patch::Array(access.newtype_variant_seed(SeqSeed(IndexedPatchSeed(self)))?),
+            6 => Patch::List(access.newtype_variant_seed(SeqSeed(ListChangeSeed(registry)))?),
+            7 => Patch::Map(access.newtype_variant_seed(SeqSeed(MapChangeSeed(registry)))?),
+            8 => Patch::Set(access.newtype_variant_seed(SeqSeed(SetChangeSeed(registry)))?),
+            9 => {
+                let (variant, fields) = access.tuple_variant(
+                    2,
+                    PairVisitor(PhantomData::<String>, FieldPatchesSeed(self)),
+                )?;
+                Patch::StructVariant {
+                    variant: Cow::Owned(variant),
+                    fields,
+                }
+            }
+            _ => {
+                let (variant, fields) = access.tuple_variant(
+                    2,
+                    PairVisitor(PhantomData::<String>, SeqSeed(IndexedPatchSeed(self))),
+                )?;
+                Patch::TupleVariant {
+                    variant: Cow::Owned(variant),
+                    fields,
+                }
+            }
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ListChangeSeed<'a>(&'a TypeRegistry);
+
+impl<'a, 'de> DeserializeSeed<'de> for ListChangeSeed<'a> {
+    type Value = ListChange;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<ListChange, D::Error> {
+        deserializer.deserialize_enum("ListChange", LIST_CHANGE_VARIANTS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for ListChangeSeed<'a> {
+    type Value = ListChange;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list change")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<ListChange, A::Error> {
+        let (variant, access) = data.variant_seed(VariantSeed(LIST_CHANGE_VARIANTS))?;
+        Ok(match variant {
+            0 => {
+                let visitor = PairVisitor(PhantomData::<usize>, ValueSeed(self.0));
+                let (index, value) = access.tuple_variant(2, visitor)?;
+                ListChange::Insert(index, value)
+            }
+            1 => ListChange::Remove(access.newtype_variant()?),
+            _ => {
+                let visitor = PairVisitor(PhantomData::<usize>, PatchDeserializer::new(self.0));
+                let (index, patch) = access.tuple_variant(2, visitor)?;
+                ListChange::Modify(index, patch)
+            }
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct MapChangeSeed<'a>(&'a TypeRegistry);
+
+impl<'a, 'de> DeserializeSeed<'de> for MapChangeSeed<'a> {
+    type Value = MapChange;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<MapChange, D::Error> {
+        deserializer.deserialize_enum("MapChange", MAP_CHANGE_VARIANTS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for MapChangeSeed<'a> {
+    type Value = MapChange;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map change")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<MapChange, A::Error> {
+        let (variant, access) = data.variant_seed(VariantSeed(MAP_CHANGE_VARIANTS))?;
+        Ok(match variant {
+            0 => {
+                let visitor = PairVisitor(ValueSeed(self.0), ValueSeed(self.0));
+                let (key, value) = access.tuple_variant(2, visitor)?;
+                MapChange::Insert(key, value)
+            }
+            1 => MapChange::Remove(access.newtype_variant_seed(ValueSeed(self.0))?),
+            _ => {
+                let visitor = PairVisitor(ValueSeed(self.0), PatchDeserializer::new(self.0));
+                let (key, patch) = access.tuple_variant(2, visitor)?;
+                MapChange::Modify(key, patch)
+            }
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct SetChangeSeed<'a>(&'a TypeRegistry);
+
+impl<'a, 'de> DeserializeSeed<'de> for SetChangeSeed<'a> {
+    type Value = SetChange;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<SetChange, D::Error> {
+        deserializer.deserialize_enum("SetChange", SET_CHANGE_VARIANTS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for SetChangeSeed<'a> {
+    type Value = SetChange;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a set change")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<SetChange, A::Error> {
+        let (variant, access) = data.variant_seed(VariantSeed(SET_CHANGE_VARIANTS))?;
+        let value = access.newtype_variant_seed(ValueSeed(self.0))?;
+        Ok(match variant {
+            0 => SetChange::Insert(value),
+            _ => SetChange::Remove(value),
+        })
+    }
+}
+
+/// Deserializes a value serialized by a [`ReflectSerializer`](crate::serde::ReflectSerializer).
+#[derive(Clone, Copy)]
+struct ValueSeed<'a>(&'a TypeRegistry);
+
+impl<'a, 'de> DeserializeSeed<'de> for ValueSeed<'a> {
+    type Value = Box<dyn Reflect>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        UntypedReflectDeserializer::new(self.0).deserialize(deserializer)
+    }
+}
+
+/// Deserializes the patches of named fields, serialized as a map.
+#[derive(Clone, Copy)]
+struct FieldPatchesSeed<'a>(PatchDeserializer<'a>);
+
+impl<'a, 'de> DeserializeSeed<'de> for FieldPatchesSeed<'a> {
+    type Value = Vec<(Cow<'static, str>, Patch)>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for FieldPatchesSeed<'a> {
+    type Value = Vec<(Cow<'static, str>, Patch)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of field patches")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut fields = Vec::with_capacity(map.size_hint().unwrap_or_default());
+        while let Some(name) = map.next_key::<String>()? {
+            fields.push((Cow::Owned(name), map.next_value_seed(self.0)?));
+        }
+        Ok(fields)
+    }
+}
+
+/// Deserializes the patch of an indexed field, serialized as a tuple of the index and the patch.
+#[derive(Clone, Copy)]
+struct IndexedPatchSeed<'a>(PatchDeserializer<'a>);
+
+impl<'a, 'de> DeserializeSeed<'de> for IndexedPatchSeed<'a> {
+    type Value = (usize, Patch);
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_tuple(2, PairVisitor(PhantomData::<usize>, self.0))
+    }
+}
+
+/// Deserializes a sequence of the values of a seed.
+#[derive(Clone, Copy)]
+struct SeqSeed<S>(S);
+
+impl<'de, S: DeserializeSeed<'de> + Copy> DeserializeSeed<'de> for SeqSeed<S> {
+    type Value = Vec<S::Value>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, S: DeserializeSeed<'de> + Copy> Visitor<'de> for SeqSeed<S> {
+    type Value = Vec<S::Value>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(value) = seq.next_element_seed(self.0)? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+/// Visits a sequence of two values, such as a tuple or the fields of a tuple variant.
+struct PairVisitor<A, B>(A, B);
+
+impl<'de, A: DeserializeSeed<'de>, B: DeserializeSeed<'de>> Visitor<'de> for PairVisitor<A, B> {
+    type Value = (A::Value, B::Value);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a pair")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let first = seq
+            .next_element_seed(self.0)?
+            .ok_or_else(|| Error::invalid_length(0, &PAIR))?;
+        let second = seq
+            .next_element_seed(self.1)?
+            .ok_or_else(|| Error::invalid_length(1, &PAIR))?;
+        Ok((first, second))
+    }
+}
+
+const PAIR: &str = "a pair";
+
+/// Deserializes the index of a variant from its name or index.
+struct VariantSeed(&'static [&'static str]);
+
+impl<'de> DeserializeSeed<'de> for VariantSeed {
+    type Value = usize;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for VariantSeed {
+    type Value = usize;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "one of the variants {:?}", self.0)
+    }
+
+    fn visit_u64<E: Error>(self, index: u64) -> Result<usize, E> {
+        match usize::try_from(index) {
+            Ok(index) if index < self.0.len() => Ok(index),
+            _ => Err(Error::invalid_value(Unexpected::Unsigned(index), &self)),
+        }
+    }
+
+    fn visit_str<E: Error>(self, name: &str) -> Result<usize, E> {
+        self.0
+            .iter()
+            .position(|variant| *variant == name)
+            .ok_or_else(|| Error::unknown_variant(name, self.0))
+    }
+}
@@ -0,0 +1,365 @@
+//! Diffing of reflected values.
+//!
+//! [`diff`] computes the [`Patch`] between two values of the same type, which only contains what
+//! changed: the fields of structs, the elements of arrays, the insertions and removals of list
+//! elements, the entries of maps, the values of sets and the variants of enums.
+//! [`apply_patch`] applies it to another value of that type, either concrete or dynamic.
+//!
+//! Patches can be serialized with a [`PatchSerializer`] and deserialized with a
+//! [`PatchDeserializer`], for example to send the changes of a value over the network.
+//!
+//! ```
+//! # use bevy_reflect::{diff::{apply_patch, diff}, Reflect};
+//! #[derive(Reflect, Clone, Debug, PartialEq)]
+//! struct Player {
+//!     name: String,
+//!     health: u32,
+//!     items: Vec<String>,
+//! }
+//!
+//! let old = Player {
+//!     name: String::from("Ferris"),
+//!     health: 100,
+//!     items: vec![String::from("sword")],
+//! };
+//! let mut new = old.clone();
+//! new.health = 80;
+//! new.items.push(String::from("shield"));
+//!
+//! let patch = diff(&old, &new).unwrap();
+//!
+//! let mut patched = old.clone();
+//! apply_patch(&mut patched, &patch).unwrap();
+//! assert_eq!(patched, new);
+//! ```
+
+mod de;
+mod patch;
+mod ser;
+
+pub use de::*;
+pub use patch::*;
+pub use ser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{DynamicList, FromReflect, Reflect, TypeRegistry};
+    use bevy_utils::{HashMap, HashSet};
+    use bincode::Options;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Player {
+        name: String,
+        state: State,
+        items: Vec<Item>,
+        stats: HashMap<String, u32>,
+        tags: HashSet<String>,
+        position: [f32; 3],
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    enum State {
+        Idle,
+        Moving { speed: f32, running: bool },
+        Attacking(u32, String),
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Item(String, u32);
+
+    fn player() -> Player {
+        Player {
+            name: String::from("Ferris"),
+            state: State::Moving {
+                speed: 1.0,
+                running: false,
+            },
+            items: vec![
+                Item(String::from("sword"), 1),
+                Item(String::from("potion"), 3),
+                Item(String::from("key"), 1),
+            ],
+            stats: HashMap::from([(String::from("strength"), 10), (String::from("agility"), 5)]),
+            tags: HashSet::from([String::from("hero"), String::from("crab")]),
+            position: [0.0, 1.0, 2.0],
+        }
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Player>();
+        registry.register::<State>();
+        registry.register::<Item>();
+        registry.register::<Vec<Item>>();
+        registry.register::<HashMap<String, u32>>();
+        registry.register::<HashSet<String>>();
+        registry.register::<[f32; 3]>();
+        registry
+    }
+
+    /// Applies the patch between `old` and `new` to `old`, and to a dynamic clone of `old`.
+    fn assert_round_trip(old: &Player, new: &Player) -> Patch {
+        let patch = diff(old, new).unwrap();
+
+        let mut patched = old.clone();
+        apply_patch(&mut patched, &patch).unwrap();
+        assert_eq!(&patched, new);
+
+        let mut dynamic = old.clone_value();
+        apply_patch(dynamic.as_mut(), &patch).unwrap();
+        assert_eq!(&Player::from_reflect(dynamic.as_ref()).unwrap(), new);
+
+        patch
+    }
+
+    #[test]
+    fn should_not_change_equal_values() {
+        let patch = diff(&player(), &player()).unwrap();
+        assert!(patch.is_no_change());
+
+        let dynamic = player().clone_value();
+        assert!(diff(&player(), dynamic.as_ref()).unwrap().is_no_change());
+    }
+
+    #[test]
+    fn should_patch_struct_fields() {
+        let old = player();
+        let mut new = player();
+        new.name = String::from("Crab");
+
+        let patch = assert_round_trip(&old, &new);
+        let Patch::Struct(fields) = patch else {
+            panic!("expected a struct patch, got {patch:?}");
+        };
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].0, "name");
+        assert!(matches!(fields[0].1, Patch::Replace(_)));
+    }
+
+    #[test]
+    fn should_patch_enums() {
+        let old = player();
+        let mut new = player();
+        new.state = State::Moving {
+            speed: 1.0,
+            running: true,
+        };
+        let patch = assert_round_trip(&old, &new);
+        let Patch::Struct(fields) = patch else {
+            panic!("expected a struct patch, got {patch:?}");
+        };
+        let Patch::StructVariant { variant, fields } = &fields[0].1 else {
+            panic!("expected a struct variant patch, got {:?}", fields[0].1);
+        };
+        assert_eq!(variant, "Moving");
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].0, "running");
+
+        new.state = State::Attacking(3, String::from("sword"));
+        assert_round_trip(&old, &new);
+
+        let mut attacking = new.clone();
+        attacking.state = State::Attacking(5, String::from("sword"));
+        assert_round_trip(&new, &attacking);
+
+        attacking.state = State::Idle;
+        assert_round_trip(&new, &attacking);
+    }
+
+    #[test]
+    fn should_patch_lists() {
+        let old = player();
+        let mut new = player();
+        new.items.remove(0);
+        new.items[0].1 = 2;
+        new.items.push(Item(String::from("shield"), 1));
+        new.items.insert(1, Item(String::from("bow"), 1));
+
+        let patch = assert_round_trip(&old, &new);
+        let Patch::Struct(fields) = patch else {
+            panic!("expected a struct patch, got {patch:?}");
+        };
+        let Patch::List(changes) = &fields[0].1 else {
+            panic!("expected a list patch, got {:?}", fields[0].1);
+        };
+        assert!(changes
+            .iter()
+            .any(|change| matches!(change, ListChange::Remove(0))));
+        assert!(changes
+            .iter()
+            .any(|change| matches!(change, ListChange::Modify(..))));
+
+        new.items.clear();
+        assert_round_trip(&old, &new);
+        assert_round_trip(&new, &old);
+    }
+
+    #[test]
+    fn should_patch_large_lists() {
+        let old: Vec<u32> = (0..100_000).collect();
+        let mut new = old.clone();
+        new[50_000] = 0;
+
+        let patch = diff(&old, &new).unwrap();
+        let Patch::List(changes) = &patch else {
+            panic!("expected a list patch, got {patch:?}");
+        };
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(changes[0], ListChange::Modify(50_000, _)));
+
+        // Lists differing in many places are patched element by element.
+        let mut reversed = old.clone();
+        reversed[1_000..9_000].reverse();
+        reversed.truncate(95_000);
+        for (old, new) in [(&old, &reversed), (&reversed, &old)] {
+            let patch = diff(old, new).unwrap();
+            let mut patched = old.clone();
+            apply_patch(&mut patched, &patch).unwrap();
+            assert_eq!(&patched, new);
+        }
+    }
+
+    #[test]
+    fn should_patch_maps() {
+        let old = player();
+        let mut new = player();
+        new.stats.remove("agility");
+        new.stats.insert(String::from("strength"), 12);
+        new.stats.insert(String::from("luck"), 1);
+
+        let patch = assert_round_trip(&old, &new);
+        let Patch::Struct(fields) = patch else {
+            panic!("expected a struct patch, got {patch:?}");
+        };
+        let Patch::Map(changes) = &fields[0].1 else {
+            panic!("expected a map patch, got {:?}", fields[0].1);
+        };
+        assert_eq!(changes.len(), 3);
+    }
+
+    #[test]
+    fn should_patch_sets() {
+        let old = player();
+        let mut new = player();
+        new.tags.remove("crab");
+        new.tags.insert(String::from("villain"));
+
+        let patch = assert_round_trip(&old, &new);
+        let Patch::Struct(fields) = patch else {
+            panic!("expected a struct patch, got {patch:?}");
+        };
+        let Patch::Set(changes) = &fields[0].1 else {
+            panic!("expected a set patch, got {:?}", fields[0].1);
+        };
+        assert_eq!(changes.len(), 2);
+        let SetChange::Insert(inserted) = &changes[0] else {
+            panic!("expected an insertion, got {:?}", changes[0]);
+        };
+        assert_eq!(
+            inserted.reflect_partial_eq(&String::from("villain")),
+            Some(true)
+        );
+        assert!(matches!(&changes[1], SetChange::Remove(_)));
+    }
+
+    #[test]
+    fn should_patch_arrays() {
+        let old = player();
+        let mut new = player();
+        new.position[1] = 5.0;
+
+        let patch = assert_round_trip(&old, &new);
+        let Patch::Struct(fields) = patch else {
+            panic!("expected a struct patch, got {patch:?}");
+        };
+        let Patch::Array(elements) = &fields[0].1 else {
+            panic!("expected an array patch, got {:?}", fields[0].1);
+        };
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0].0, 1);
+    }
+
+    #[test]
+    fn should_round_trip_serialized_patches() {
+        let registry = registry();
+        let old = player();
+        let mut new = player();
+        new.name = String::from("Crab");
+        new.state = State::Attacking(3, String::from("sword"));
+        new.items.remove(1);
+        new.items[1].1 = 4;
+        new.items.push(Item(String::from("shield"), 1));
+        new.stats.remove("agility");
+        new.stats.insert(String::from("strength"), 12);
+        new.stats.insert(String::from("luck"), 1);
+        new.tags.remove("hero");
+        new.tags.insert(String::from("villain"));
+        new.position[2] = 3.0;
+        let patch = diff(&old, &new).unwrap();
+        let serializer = PatchSerializer::new(&patch, &registry);
+
+        let json = serde_json::to_string(&serializer).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let deserialized = PatchDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        let mut patched = old.clone();
+        apply_patch(&mut patched, &deserialized).unwrap();
+        assert_eq!(patched, new);
+
+        let ron = ron::to_string(&serializer).unwrap();
+        let mut deserializer = ron::Deserializer::from_str(&ron).unwrap();
+        let deserialized = PatchDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        let mut patched = old.clone();
+        apply_patch(&mut patched, &deserialized).unwrap();
+        assert_eq!(patched, new);
+
+        let bytes = bincode::serialize(&serializer).unwrap();
+        let deserialized = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(PatchDeserializer::new(&registry), &bytes)
+            .unwrap();
+        let mut patched = old.clone();
+        apply_patch(&mut patched, &deserialized).unwrap();
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn should_fail_on_mismatched_values() {
+        let result = diff(&player(), &Item(String::from("sword"), 1));
+        assert!(matches!(result, Err(DiffError::TypeMismatch { .. })));
+
+        let patch = diff(&vec![1, 2, 3], &vec![1, 2]).unwrap();
+        let mut item = Item(String::from("sword"), 1);
+        assert_eq!(
+            apply_patch(&mut item, &patch),
+            Err(ApplyPatchError::KindMismatch {
+                expected: crate::ReflectKind::List,
+                received: crate::ReflectKind::TupleStruct,
+            })
+        );
+
+        let mut short = DynamicList::default();
+        short.push(1);
+        assert_eq!(
+            apply_patch(&mut short, &patch),
+            Err(ApplyPatchError::OutOfBounds(2))
+        );
+
+        let mut state = State::Idle;
+        let patch = diff(
+            &State::Attacking(1, String::new()),
+            &State::Attacking(2, String::new()),
+        )
+        .unwrap();
+        assert!(matches!(
+            apply_patch(&mut state, &patch),
+            Err(ApplyPatchError::VariantMismatch { .. })
+        ));
+    }
+}
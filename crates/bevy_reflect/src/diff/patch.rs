@@ -0,0 +1,660 @@
+use crate::{
+    Array, Enum, Reflect, ReflectKind, ReflectMut, ReflectRef, Tuple, TupleStruct, VariantType,
+};
+use std::borrow::Cow;
+use thiserror::Error;
+
+/// The difference between two values of the same type, computed by [`diff`] and applied by
+/// [`apply_patch`].
+///
+/// Patches only contain the parts of the values that changed: unchanged fields, elements and
+/// entries are left out.
+#[derive(Debug)]
+pub enum Patch {
+    /// The values are equal.
+    NoChange,
+    /// The value is replaced by another one, for [value types](ReflectKind::Value) and enums whose
+    /// variant changed.
+    Replace(Box<dyn Reflect>),
+    /// The patches of the changed fields of a struct, by name.
+    Struct(Vec<(Cow<'static, str>, Patch)>),
+    /// The patches of the changed fields of a tuple struct, by index.
+    TupleStruct(Vec<(usize, Patch)>),
+    /// The patches of the changed fields of a tuple, by index.
+    Tuple(Vec<(usize, Patch)>),
+    /// The patches of the changed elements of an array, by index.
+    Array(Vec<(usize, Patch)>),
+    /// The changes to the elements of a list, applied in order.
+    List(Vec<ListChange>),
+    /// The changes to the entries of a map.
+    Map(Vec<MapChange>),
+    /// The changes to the values of a set.
+    Set(Vec<SetChange>),
+    /// The patches of the changed fields of an enum in a struct variant, by name.
+    StructVariant {
+        /// The name of the variant.
+        variant: Cow<'static, str>,
+        /// The patches of the changed fields.
+        fields: Vec<(Cow<'static, str>, Patch)>,
+    },
+    /// The patches of the changed fields of an enum in a tuple variant, by index.
+    TupleVariant {
+        /// The name of the variant.
+        variant: Cow<'static, str>,
+        /// The patches of the changed fields.
+        fields: Vec<(usize, Patch)>,
+    },
+}
+
+impl Patch {
+    /// Returns `true` if applying the patch doesn't change anything.
+    pub fn is_no_change(&self) -> bool {
+        matches!(self, Self::NoChange)
+    }
+}
+
+impl Clone for Patch {
+    fn clone(&self) -> Self {
+        match self {
+            Self::NoChange => Self::NoChange,
+            Self::Replace(value) => Self::Replace(value.clone_value()),
+            Self::Struct(fields) => Self::Struct(fields.clone()),
+            Self::TupleStruct(fields) => Self::TupleStruct(fields.clone()),
+            Self::Tuple(fields) => Self::Tuple(fields.clone()),
+            Self::Array(elements) => Self::Array(elements.clone()),
+            Self::List(changes) => Self::List(changes.clone()),
+            Self::Map(changes) => Self::Map(changes.clone()),
+            Self::Set(changes) => Self::Set(changes.clone()),
+            Self::StructVariant { variant, fields } => Self::StructVariant {
+                variant: variant.clone(),
+                fields: fields.clone(),
+            },
+            Self::TupleVariant { variant, fields } => Self::TupleVariant {
+                variant: variant.clone(),
+                fields: fields.clone(),
+            },
+        }
+    }
+}
+
+/// A change to a list in a [`Patch::List`].
+///
+/// Indices refer to the list as left by the previous changes.
+#[derive(Debug)]
+pub enum ListChange {
+    /// Inserts an element at the index, shifting the following ones.
+    Insert(usize, Box<dyn Reflect>),
+    /// Removes the element at the index, shifting the following ones.
+    Remove(usize),
+    /// Patches the element at the index.
+    Modify(usize, Patch),
+}
+
+impl Clone for ListChange {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Insert(index, value) => Self::Insert(*index, value.clone_value()),
+            Self::Remove(index) => Self::Remove(*index),
+            Self::Modify(index, patch) => Self::Modify(*index, patch.clone()),
+        }
+    }
+}
+
+/// A change to a map in a [`Patch::Map`].
+#[derive(Debug)]
+pub enum MapChange {
+    /// Inserts an entry, replacing the entry with the same key if there is one.
+    Insert(Box<dyn Reflect>, Box<dyn Reflect>),
+    /// Removes the entry with the key.
+    Remove(Box<dyn Reflect>),
+    /// Patches the value of the entry with the key.
+    Modify(Box<dyn Reflect>, Patch),
+}
+
+impl Clone for MapChange {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Insert(key, value) => Self::Insert(key.clone_value(), value.clone_value()),
+            Self::Remove(key) => Self::Remove(key.clone_value()),
+            Self::Modify(key, patch) => Self::Modify(key.clone_value(), patch.clone()),
+        }
+    }
+}
+
+/// A change to a set in a [`Patch::Set`].
+#[derive(Debug)]
+pub enum SetChange {
+    /// Inserts the value.
+    Insert(Box<dyn Reflect>),
+    /// Removes the value.
+    Remove(Box<dyn Reflect>),
+}
+
+impl Clone for SetChange {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Insert(value) => Self::Insert(value.clone_value()),
+            Self::Remove(value) => Self::Remove(value.clone_value()),
+        }
+    }
+}
+
+/// An error that occurs when computing a [`Patch`] with [`diff`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DiffError {
+    /// The values have different types.
+    #[error("cannot diff `{old}` with `{new}`, they are different types")]
+    TypeMismatch {
+        /// The type path of the old value.
+        old: Cow<'static, str>,
+        /// The type path of the new value.
+        new: Cow<'static, str>,
+    },
+    /// The values have the same type but different fields, which can happen with dynamic types.
+    #[error("cannot diff values of `{type_path}` with different fields")]
+    FieldMismatch {
+        /// The type path of the values.
+        type_path: Cow<'static, str>,
+    },
+}
+
+/// An error that occurs when applying a [`Patch`] with [`apply_patch`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ApplyPatchError {
+    /// The patch is meant for another kind of value.
+    #[error("cannot apply a patch for a {expected} to a {received}")]
+    KindMismatch {
+        /// The kind of value the patch is meant for.
+        expected: ReflectKind,
+        /// The kind of the patched value.
+        received: ReflectKind,
+    },
+    /// The patch replaces a value with a value of another type.
+    #[error("cannot replace a value of `{expected}` with a value of `{received}`")]
+    TypeMismatch {
+        /// The type path of the patched value.
+        expected: Cow<'static, str>,
+        /// The type path of the value in the patch.
+        received: Cow<'static, str>,
+    },
+    /// The patch is meant for another variant of the enum.
+    #[error("cannot apply a patch for the variant `{expected}` to the variant `{received}`")]
+    VariantMismatch {
+        /// The variant the patch is meant for.
+        expected: Cow<'static, str>,
+        /// The variant of the patched value.
+        received: Cow<'static, str>,
+    },
+    /// The patch changes a field that does not exist.
+    #[error("the field `{0}` does not exist")]
+    MissingField(Cow<'static, str>),
+    /// The patch changes an element or field at an index that is out of bounds.
+    #[error("the index {0} is out of bounds")]
+    OutOfBounds(usize),
+    /// The patch changes a map entry or removes a set value that does not exist.
+    #[error("the map entry with the key or the set value `{0}` does not exist")]
+    MissingEntry(String),
+}
+
+/// Computes the [`Patch`] that turns `old` into `new`.
+///
+/// Both values must be of the same type, but either can be a dynamic value representing it.
+/// Values are compared with [`Reflect::reflect_partial_eq`], value types that can't be compared
+/// are always replaced.
+///
+/// List changes are computed from the longest common subsequence of the two lists, so that
+/// insertions and removals in the middle of a list don't change the following elements.
+pub fn diff(old: &dyn Reflect, new: &dyn Reflect) -> Result<Patch, DiffError> {
+    if let (Some(old_info), Some(new_info)) = (
+        old.get_represented_type_info(),
+        new.get_represented_type_info(),
+    ) {
+        if old_info.type_id() != new_info.type_id() {
+            return Err(type_mismatch(old, new));
+        }
+    }
+
+    match (old.reflect_ref(), new.reflect_ref()) {
+        (ReflectRef::Struct(old_struct), ReflectRef::Struct(new_struct)) => {
+            if old_struct.field_len() != new_struct.field_len() {
+                return Err(field_mismatch(new));
+            }
+            let mut fields = Vec::new();
+            for (index, new_field) in new_struct.iter_fields().enumerate() {
+                let name = new_struct.name_at(index).unwrap();
+                let old_field = old_struct.field(name).ok_or_else(|| field_mismatch(new))?;
+                let patch = diff(old_field, new_field)?;
+                if !patch.is_no_change() {
+                    fields.push((Cow::Owned(name.to_string()), patch));
+                }
+            }
+            Ok(patch_or_no_change(fields, Patch::Struct))
+        }
+        (ReflectRef::TupleStruct(old_struct), ReflectRef::TupleStruct(new_struct)) => {
+            if old_struct.field_len() != new_struct.field_len() {
+                return Err(field_mismatch(new));
+            }
+            let fields = diff_indexed(old_struct.iter_fields(), new_struct.iter_fields())?;
+            Ok(patch_or_no_change(fields, Patch::TupleStruct))
+        }
+        (ReflectRef::Tuple(old_tuple), ReflectRef::Tuple(new_tuple)) => {
+            if old_tuple.field_len() != new_tuple.field_len() {
+                return Err(field_mismatch(new));
+            }
+            let fields = diff_indexed(old_tuple.iter_fields(), new_tuple.iter_fields())?;
+            Ok(patch_or_no_change(fields, Patch::Tuple))
+        }
+        (ReflectRef::Array(old_array), ReflectRef::Array(new_array)) => {
+            if old_array.len() != new_array.len() {
+                return Err(field_mismatch(new));
+            }
+            let elements = diff_indexed(old_array.iter(), new_array.iter())?;
+            Ok(patch_or_no_change(elements, Patch::Array))
+        }
+        (ReflectRef::List(old_list), ReflectRef::List(new_list)) => {
+            let old_elements: Vec<&dyn Reflect> = old_list.iter().collect();
+            let new_elements: Vec<&dyn Reflect> = new_list.iter().collect();
+            let changes = diff_list(&old_elements, &new_elements)?;
+            Ok(patch_or_no_change(changes, Patch::List))
+        }
+        (ReflectRef::Map(old_map), ReflectRef::Map(new_map)) => {
+            let mut changes = Vec::new();
+            for (key, new_value) in new_map.iter() {
+                match old_map.get(key) {
+                    Some(old_value) => {
+                        let patch = diff(old_value, new_value)?;
+                        if !patch.is_no_change() {
+                            changes.push(MapChange::Modify(key.clone_value(), patch));
+                        }
+                    }
+                    None => {
+                        changes.push(MapChange::Insert(
+                            key.clone_value(),
+                            new_value.clone_value(),
+                        ));
+                    }
+                }
+            }
+            for (key, _) in old_map.iter() {
+                if new_map.get(key).is_none() {
+                    changes.push(MapChange::Remove(key.clone_value()));
+                }
+            }
+            Ok(patch_or_no_change(changes, Patch::Map))
+        }
+        (ReflectRef::Set(old_set), ReflectRef::Set(new_set)) => {
+            let inserted = new_set
+                .iter()
+                .filter(|value| !old_set.contains(*value))
+                .map(|value| SetChange::Insert(value.clone_value()));
+            let removed = old_set
+                .iter()
+                .filter(|value| !new_set.contains(*value))
+                .map(|value| SetChange::Remove(value.clone_value()));
+            Ok(patch_or_no_change(
+                inserted.chain(removed).collect(),
+                Patch::Set,
+            ))
+        }
+        (ReflectRef::Enum(old_enum), ReflectRef::Enum(new_enum)) => {
+            if old_enum.variant_name() != new_enum.variant_name()
+                || old_enum.field_len() != new_enum.field_len()
+            {
+                return Ok(Patch::Replace(new.clone_value()));
+            }
+            let variant = Cow::Owned(new_enum.variant_name().to_string());
+            match new_enum.variant_type() {
+                VariantType::Unit => Ok(Patch::NoChange),
+                VariantType::Tuple => {
+                    let old_fields = old_enum.iter_fields().map(|field| field.value());
+                    let new_fields = new_enum.iter_fields().map(|field| field.value());
+                    let fields = diff_indexed(old_fields, new_fields)?;
+                    Ok(patch_or_no_change(fields, |fields| Patch::TupleVariant {
+                        variant,
+                        fields,
+                    }))
+                }
+                VariantType::Struct => {
+                    let mut fields = Vec::new();
+                    for field in new_enum.iter_fields() {
+                        let name = field.name().unwrap();
+                        let old_field = old_enum.field(name).ok_or_else(|| field_mismatch(new))?;
+                        let patch = diff(old_field, field.value())?;
+                        if !patch.is_no_change() {
+                            fields.push((Cow::Owned(name.to_string()), patch));
+                        }
+                    }
+                    Ok(patch_or_no_change(fields, |fields| Patch::StructVariant {
+                        variant,
+                        fields,
+                    }))
+                }
+            }
+        }
+        (ReflectRef::Value(old_value), ReflectRef::Value(new_value)) => {
+            if old_value.type_id() != new_value.type_id() {
+                return Err(type_mismatch(old, new));
+            }
+            match old_value.reflect_partial_eq(new_value) {
+                Some(true) => Ok(Patch::NoChange),
+                _ => Ok(Patch::Replace(new_value.clone_value())),
+            }
+        }
+        _ => Err(type_mismatch(old, new)),
+    }
+}
+
+/// Applies a [`Patch`] computed by [`diff`] to `target`, which can be a concrete or a dynamic
+/// value of the type the patch was computed for.
+///
+/// Changes are applied in order, and stop at the first error, leaving `target` partially patched.
+///
+/// # Panics
+///
+/// Like [`Reflect::apply`] and [`List::insert`](crate::List::insert), this panics if a value in
+/// the patch can't be converted into the type of the value it replaces or is inserted into.
+pub fn apply_patch(target: &mut dyn Reflect, patch: &Patch) -> Result<(), ApplyPatchError> {
+    match (patch, target.reflect_mut()) {
+        (Patch::NoChange, _) => Ok(()),
+        (Patch::Replace(value), _) => {
+            if target.reflect_kind() != value.reflect_kind() {
+                return Err(ApplyPatchError::KindMismatch {
+                    expected: value.reflect_kind(),
+                    received: target.reflect_kind(),
+                });
+            }
+            if target.reflect_kind() == ReflectKind::Value && target.type_id() != value.type_id() {
+                return Err(ApplyPatchError::TypeMismatch {
+                    expected: Cow::Owned(target.reflect_type_path().to_string()),
+                    received: Cow::Owned(value.reflect_type_path().to_string()),
+                });
+            }
+            target.apply(value.as_ref());
+            Ok(())
+        }
+        (Patch::Struct(fields), ReflectMut::Struct(target)) => {
+            for (name, patch) in fields {
+                let field = target
+                    .field_mut(name)
+                    .ok_or_else(|| ApplyPatchError::MissingField(name.clone()))?;
+                apply_patch(field, patch)?;
+            }
+            Ok(())
+        }
+        (Patch::TupleStruct(fields), ReflectMut::TupleStruct(target)) => {
+            apply_indexed(fields, target, TupleStruct::field_mut)
+        }
+        (Patch::Tuple(fields), ReflectMut::Tuple(target)) => {
+            apply_indexed(fields, target, Tuple::field_mut)
+        }
+        (Patch::Array(elements), ReflectMut::Array(target)) => {
+            apply_indexed(elements, target, Array::get_mut)
+        }
+        (Patch::List(changes), ReflectMut::List(target)) => {
+            for change in changes {
+                match change {
+                    ListChange::Insert(index, value) => {
+                        if *index > target.len() {
+                            return Err(ApplyPatchError::OutOfBounds(*index));
+                        }
+                        target.insert(*index, value.clone_value());
+                    }
+                    ListChange::Remove(index) => {
+                        if *index >= target.len() {
+                            return Err(ApplyPatchError::OutOfBounds(*index));
+                        }
+                        target.remove(*index);
+                    }
+                    ListChange::Modify(index, patch) => {
+                        let element = target
+                            .get_mut(*index)
+                            .ok_or(ApplyPatchError::OutOfBounds(*index))?;
+                        apply_patch(element, patch)?;
+                    }
+                }
+            }
+            Ok(())
+        }
+        (Patch::Map(changes), ReflectMut::Map(target)) => {
+            for change in changes {
+                match change {
+                    MapChange::Insert(key, value) => {
+                        target.insert_boxed(key.clone_value(), value.clone_value());
+                    }
+                    MapChange::Remove(key) => {
+                        target
+                            .remove(key.as_ref())
+                            .ok_or_else(|| missing_entry(key.as_ref()))?;
+                    }
+                    MapChange::Modify(key, patch) => {
+                        let value = target
+                            .get_mut(key.as_ref())
+                            .ok_or_else(|| missing_entry(key.as_ref()))?;
+                        apply_patch(value, patch)?;
+                    }
+                }
+            }
+            Ok(())
+        }
+        (Patch::Set(changes), ReflectMut::Set(target)) => {
+            for change in changes {
+                match change {
+                    SetChange::Insert(value) => {
+                        target.insert_boxed(value.clone_value());
+                    }
+                    SetChange::Remove(value) => {
+                        if !target.remove(value.as_ref()) {
+                            return Err(missing_entry(value.as_ref()));
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+        (Patch::StructVariant { variant, fields }, ReflectMut::Enum(target)) => {
+            check_variant(variant, target.variant_name())?;
+            for (name, patch) in fields {
+                let field = target
+                    .field_mut(name)
+                    .ok_or_else(|| ApplyPatchError::MissingField(name.clone()))?;
+                apply_patch(field, patch)?;
+            }
+            Ok(())
+        }
+        (Patch::TupleVariant { variant, fields }, ReflectMut::Enum(target)) => {
+            check_variant(variant, target.variant_name())?;
+            apply_indexed(fields, target, Enum::field_at_mut)
+        }
+        (patch, target) => Err(ApplyPatchError::KindMismatch {
+            expected: patch_kind(patch),
+            received: target.kind(),
+        }),
+    }
+}
+
+fn diff_indexed<'a>(
+    old: impl Iterator<Item = &'a dyn Reflect>,
+    new: impl Iterator<Item = &'a dyn Reflect>,
+) -> Result<Vec<(usize, Patch)>, DiffError> {
+    let mut patches = Vec::new();
+    for (index, (old, new)) in old.zip(new).enumerate() {
+        let patch = diff(old, new)?;
+        if !patch.is_no_change() {
+            patches.push((index, patch));
+        }
+    }
+    Ok(patches)
+}
+
+/// The largest product of the lengths of the differing parts of two lists for which [`diff_list`]
+/// computes their longest common subsequence, which takes time and memory proportional to it.
+/// Larger lists are diffed element by element instead.
+const MAX_LIST_DIFF_SIZE: usize = 1 << 16;
+
+/// Computes the changes turning `old` into `new`, keeping their longest common subsequence.
+fn diff_list(old: &[&dyn Reflect], new: &[&dyn Reflect]) -> Result<Vec<ListChange>, DiffError> {
+    let equal = |a: &dyn Reflect, b: &dyn Reflect| a.reflect_partial_eq(b).unwrap_or(false);
+
+    // The common prefix and suffix are left unchanged
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(old, new)| equal(**old, **new))
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| equal(**old, **new))
+        .count();
+    let old = &old[prefix..old.len() - suffix];
+    let new = &new[prefix..new.len() - suffix];
+
+    if old.len().saturating_mul(new.len()) > MAX_LIST_DIFF_SIZE {
+        return diff_list_by_index(prefix, old, new);
+    }
+
+    // `lengths[i][j]` is the length of the longest common subsequence of `old[i..]` and `new[j..]`
+    let mut lengths = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if equal(old[i], new[j]) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    // The index in the list being patched
+    let mut index = prefix;
+    // The element of `old` removed by the last change, if it was a removal
+    let mut removed = None;
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && equal(old[i], new[j]) {
+            i += 1;
+            j += 1;
+            index += 1;
+            removed = None;
+        } else if i < old.len() && (j == new.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            changes.push(ListChange::Remove(index));
+            removed = Some(old[i]);
+            i += 1;
+        } else {
+            // An element replaced by another one is patched instead
+            match removed.take().map(|removed| diff(removed, new[j])) {
+                Some(Ok(patch)) => {
+                    changes.pop();
+                    if !patch.is_no_change() {
+                        changes.push(ListChange::Modify(index, patch));
+                    }
+                }
+                _ => changes.push(ListChange::Insert(index, new[j].clone_value())),
+            }
+            j += 1;
+            index += 1;
+        }
+    }
+    Ok(changes)
+}
+
+/// Computes the changes turning `old` into `new` by patching the elements at the same index, then
+/// inserting or removing the remaining ones. `offset` is the index of the first element in the list
+/// being patched.
+fn diff_list_by_index(
+    offset: usize,
+    old: &[&dyn Reflect],
+    new: &[&dyn Reflect],
+) -> Result<Vec<ListChange>, DiffError> {
+    let mut changes = Vec::new();
+    for (index, (old, new)) in old.iter().zip(new).enumerate() {
+        let index = offset + index;
+        match diff(*old, *new) {
+            Ok(patch) if patch.is_no_change() => {}
+            Ok(patch) => changes.push(ListChange::Modify(index, patch)),
+            Err(_) => {
+                changes.push(ListChange::Remove(index));
+                changes.push(ListChange::Insert(index, new.clone_value()));
+            }
+        }
+    }
+    let common = old.len().min(new.len());
+    for (index, new) in new.iter().enumerate().skip(common) {
+        changes.push(ListChange::Insert(offset + index, new.clone_value()));
+    }
+    for _ in common..old.len() {
+        changes.push(ListChange::Remove(offset + common));
+    }
+    Ok(changes)
+}
+
+fn apply_indexed<T: ?Sized>(
+    patches: &[(usize, Patch)],
+    target: &mut T,
+    field: fn(&mut T, usize) -> Option<&mut dyn Reflect>,
+) -> Result<(), ApplyPatchError> {
+    for (index, patch) in patches {
+        let field = field(target, *index).ok_or(ApplyPatchError::OutOfBounds(*index))?;
+        apply_patch(field, patch)?;
+    }
+    Ok(())
+}
+
+fn patch_or_no_change<T>(changes: Vec<T>, patch: impl FnOnce(Vec<T>) -> Patch) -> Patch {
+    if changes.is_empty() {
+        Patch::NoChange
+    } else {
+        patch(changes)
+    }
+}
+
+fn check_variant(expected: &str, received: &str) -> Result<(), ApplyPatchError> {
+    if expected != received {
+        return Err(ApplyPatchError::VariantMismatch {
+            expected: Cow::Owned(expected.to_string()),
+            received: Cow::Owned(received.to_string()),
+        });
+    }
+    Ok(())
+}
+
+fn patch_kind(patch: &Patch) -> ReflectKind {
+    match patch {
+        Patch::NoChange => ReflectKind::Value,
+        Patch::Replace(value) => value.reflect_kind(),
+        Patch::Struct(_) => ReflectKind::Struct,
+        Patch::TupleStruct(_) => ReflectKind::TupleStruct,
+        Patch::Tuple(_) => ReflectKind::Tuple,
+        Patch::Array(_) => ReflectKind::Array,
+        Patch::List(_) => ReflectKind::List,
+        Patch::Map(_) => ReflectKind::Map,
+        Patch::Set(_) => ReflectKind::Set,
+        Patch::StructVariant { .. } | Patch::TupleVariant { .. } => ReflectKind::Enum,
+    }
+}
+
+fn missing_entry(key: &dyn Reflect) -> ApplyPatchError {
+    ApplyPatchError::MissingEntry(format!("{key:?}"))
+}
+
+fn type_mismatch(old: &dyn Reflect, new: &dyn Reflect) -> DiffError {
+    DiffError::TypeMismatch {
+        old: Cow::Owned(type_path(old).to_string()),
+        new: Cow::Owned(type_path(new).to_string()),
+    }
+}
+
+fn field_mismatch(value: &dyn Reflect) -> DiffError {
+    DiffError::FieldMismatch {
+        type_path: Cow::Owned(type_path(value).to_string()),
+    }
+}
+
+fn type_path(value: &dyn Reflect) -> &str {
+    value
+        .get_represented_type_info()
+        .map(|info| info.type_path())
+        .unwrap_or_else(|| value.reflect_type_path())
+}
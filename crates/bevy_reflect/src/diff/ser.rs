@@ -0,0 +1,230 @@
+use crate::diff::{ListChange, MapChange, Patch, SetChange};
+use crate::serde::ReflectSerializer;
+use crate::TypeRegistry;
+use serde::ser::{SerializeMap, SerializeSeq, SerializeTupleVariant};
+use serde::{Serialize, Serializer};
+use std::borrow::Cow;
+
+pub(super) const PATCH_VARIANTS: &[&str] = &[
+    "NoChange",
+    "Replace",
+    "Struct",
+    "TupleStruct",
+    "Tuple",
+    "Array",
+    "List",
+    "Map",
+    "Set",
+    "StructVariant",
+    "TupleVariant",
+];
+pub(super) const LIST_CHANGE_VARIANTS: &[&str] = &["Insert", "Remove", "Modify"];
+pub(super) const MAP_CHANGE_VARIANTS: &[&str] = &["Insert", "Remove", "Modify"];
+pub(super) const SET_CHANGE_VARIANTS: &[&str] = &["Insert", "Remove"];
+
+/// Serializes a [`Patch`], with the values it contains serialized by a [`ReflectSerializer`].
+///
+/// The result can be deserialized with a [`PatchDeserializer`](crate::diff::PatchDeserializer).
+pub struct PatchSerializer<'a> {
+    patch: &'a Patch,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> PatchSerializer<'a> {
+    /// Creates a serializer for the given patch.
+    pub fn new(patch: &'a Patch, registry: &'a TypeRegistry) -> Self {
+        Self { patch, registry }
+    }
+}
+
+impl<'a> Serialize for PatchSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let variant = |index: u32| (index, PATCH_VARIANTS[index as usize]);
+        let registry = self.registry;
+        match self.patch {
+            Patch::NoChange => {
+                let (index, name) = variant(0);
+                serializer.serialize_unit_variant("Patch", index, name)
+            }
+            Patch::Replace(value) => {
+                let (index, name) = variant(1);
+                let value = ReflectSerializer::new(value.as_ref(), registry);
+                serializer.serialize_newtype_variant("Patch", index, name, &value)
+            }
+            Patch::Struct(fields) => {
+                let (index, name) = variant(2);
+                let fields = FieldPatchesSerializer(fields, registry);
+                serializer.serialize_newtype_variant("Patch", index, name, &fields)
+            }
+            Patch::TupleStruct(fields) | Patch::Tuple(fields) | Patch::Array(fields) => {
+                let (index, name) = variant(match self.patch {
+                    Patch::TupleStruct(_) => 3,
+                    Patch::Tuple(_) => 4,
+                    _ => 5,
+                });
+                let fields = IndexedPatchesSerializer(fields, registry);
+                serializer.serialize_newtype_variant("Patch", index, name, &fields)
+            }
+            Patch::List(changes) => {
+                let (index, name) = variant(6);
+                let changes = ListChangesSerializer(changes, registry);
+                serializer.serialize_newtype_variant("Patch", index, name, &changes)
+            }
+            Patch::Map(changes) => {
+                let (index, name) = variant(7);
+                let changes = MapChangesSerializer(changes, registry);
+                serializer.serialize_newtype_variant("Patch", index, name, &changes)
+            }
+            Patch::Set(changes) => {
+                let (index, name) = variant(8);
+                let changes = SetChangesSerializer(changes, registry);
+                serializer.serialize_newtype_variant("Patch", index, name, &changes)
+            }
+            Patch::StructVariant { variant: v, fields } => {
+                let (index, name) = variant(9);
+                let mut state = serializer.serialize_tuple_variant("Patch", index, name, 2)?;
+                state.serialize_field(v)?;
+                state.serialize_field(&FieldPatchesSerializer(fields, registry))?;
+                state.end()
+            }
+            Patch::TupleVariant { variant: v, fields } => {
+                let (index, name) = variant(10);
+                let mut state = serializer.serialize_tuple_variant("Patch", index, name, 2)?;
+                state.serialize_field(v)?;
+                state.serialize_field(&IndexedPatchesSerializer(fields, registry))?;
+                state.end()
+            }
+        }
+    }
+}
+
+struct FieldPatchesSerializer<'a>(&'a [(Cow<'static, str>, Patch)], &'a TypeRegistry);
+
+impl<'a> Serialize for FieldPatchesSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, patch) in self.0 {
+            state.serialize_entry(name, &PatchSerializer::new(patch, self.1))?;
+        }
+        state.end()
+    }
+}
+
+struct IndexedPatchesSerializer<'a>(&'a [(usize, Patch)], &'a TypeRegistry);
+
+impl<'a> Serialize for IndexedPatchesSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_seq(Some(self.0.len()))?;
+        for (index, patch) in self.0 {
+            state.serialize_element(&(index, PatchSerializer::new(patch, self.1)))?;
+        }
+        state.end()
+    }
+}
+
+struct ListChangesSerializer<'a>(&'a [ListChange], &'a TypeRegistry);
+
+impl<'a> Serialize for ListChangesSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_seq(Some(self.0.len()))?;
+        for change in self.0 {
+            state.serialize_element(&ListChangeSerializer(change, self.1))?;
+        }
+        state.end()
+    }
+}
+
+struct ListChangeSerializer<'a>(&'a ListChange, &'a TypeRegistry);
+
+impl<'a> Serialize for ListChangeSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let names = LIST_CHANGE_VARIANTS;
+        match self.0 {
+            ListChange::Insert(index, value) => {
+                let mut state = serializer.serialize_tuple_variant("ListChange", 0, names[0], 2)?;
+                state.serialize_field(index)?;
+                state.serialize_field(&ReflectSerializer::new(value.as_ref(), self.1))?;
+                state.end()
+            }
+            ListChange::Remove(index) => {
+                serializer.serialize_newtype_variant("ListChange", 1, names[1], index)
+            }
+            ListChange::Modify(index, patch) => {
+                let mut state = serializer.serialize_tuple_variant("ListChange", 2, names[2], 2)?;
+                state.serialize_field(index)?;
+                state.serialize_field(&PatchSerializer::new(patch, self.1))?;
+                state.end()
+            }
+        }
+    }
+}
+
+struct MapChangesSerializer<'a>(&'a [MapChange], &'a TypeRegistry);
+
+impl<'a> Serialize for MapChangesSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_seq(Some(self.0.len()))?;
+        for change in self.0 {
+            state.serialize_element(&MapChangeSerializer(change, self.1))?;
+        }
+        state.end()
+    }
+}
+
+struct MapChangeSerializer<'a>(&'a MapChange, &'a TypeRegistry);
+
+impl<'a> Serialize for MapChangeSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let names = MAP_CHANGE_VARIANTS;
+        let registry = self.1;
+        match self.0 {
+            MapChange::Insert(key, value) => {
+                let mut state = serializer.serialize_tuple_variant("MapChange", 0, names[0], 2)?;
+                state.serialize_field(&ReflectSerializer::new(key.as_ref(), registry))?;
+                state.serialize_field(&ReflectSerializer::new(value.as_ref(), registry))?;
+                state.end()
+            }
+            MapChange::Remove(key) => serializer.serialize_newtype_variant(
+                "MapChange",
+                1,
+                names[1],
+                &ReflectSerializer::new(key.as_ref(), registry),
+            ),
+            MapChange::Modify(key, patch) => {
+                let mut state = serializer.serialize_tuple_variant("MapChange", 2, names[2], 2)?;
+                state.serialize_field(&ReflectSerializer::new(key.as_ref(), registry))?;
+                state.serialize_field(&PatchSerializer::new(patch, registry))?;
+                state.end()
+            }
+        }
+    }
+}
+
+struct SetChangesSerializer<'a>(&'a [SetChange], &'a TypeRegistry);
+
+impl<'a> Serialize for SetChangesSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_seq(Some(self.0.len()))?;
+        for change in self.0 {
+            state.serialize_element(&SetChangeSerializer(change, self.1))?;
+        }
+        state.end()
+    }
+}
+
+struct SetChangeSerializer<'a>(&'a SetChange, &'a TypeRegistry);
+
+impl<'a> Serialize for SetChangeSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (index, value) = match self.0 {
+            SetChange::Insert(value) => (0, value),
+            SetChange::Remove(value) => (1, value),
+        };
+        serializer.serialize_newtype_variant(
+            "SetChange",
+            index,
+            SET_CHANGE_VARIANTS[index as usize],
+            &ReflectSerializer::new(value.as_ref(), self.1),
+        )
+    }
+}
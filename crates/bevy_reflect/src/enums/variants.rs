@@ -1,6 +1,8 @@
+use crate::attributes::{impl_custom_attribute_methods, CustomAttributes};
 use crate::{NamedField, UnnamedField};
 use bevy_utils::HashMap;
 use std::slice::Iter;
+use std::sync::Arc;
 
 /// Describes the form of an enum variant.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -82,6 +84,15 @@ impl VariantInfo {
             Self::Unit(info) => info.docs(),
         }
     }
+
+    /// The custom attributes of the underlying variant.
+    pub fn custom_attributes(&self) -> &CustomAttributes {
+        match self {
+            Self::Struct(info) => info.custom_attributes(),
+            Self::Tuple(info) => info.custom_attributes(),
+            Self::Unit(info) => info.custom_attributes(),
+        }
+    }
 }
 
 /// Type info for struct variants.
@@ -91,6 +102,7 @@ pub struct StructVariantInfo {
     fields: Box<[NamedField]>,
     field_names: Box<[&'static str]>,
     field_indices: HashMap<&'static str, usize>,
+    custom_attributes: Arc<CustomAttributes>,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -105,6 +117,7 @@ impl StructVariantInfo {
             fields: fields.to_vec().into_boxed_slice(),
             field_names,
             field_indices,
+            custom_attributes: Arc::new(CustomAttributes::default()),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -116,6 +129,8 @@ impl StructVariantInfo {
         Self { docs, ..self }
     }
 
+    impl_custom_attribute_methods!("variant");
+
     /// The name of this variant.
     pub fn name(&self) -> &'static str {
         self.name
@@ -173,6 +188,7 @@ impl StructVariantInfo {
 pub struct TupleVariantInfo {
     name: &'static str,
     fields: Box<[UnnamedField]>,
+    custom_attributes: Arc<CustomAttributes>,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -183,6 +199,7 @@ impl TupleVariantInfo {
         Self {
             name,
             fields: fields.to_vec().into_boxed_slice(),
+            custom_attributes: Arc::new(CustomAttributes::default()),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -194,6 +211,8 @@ impl TupleVariantInfo {
         Self { docs, ..self }
     }
 
+    impl_custom_attribute_methods!("variant");
+
     /// The name of this variant.
     pub fn name(&self) -> &'static str {
         self.name
@@ -225,6 +244,7 @@ impl TupleVariantInfo {
 #[derive(Clone, Debug)]
 pub struct UnitVariantInfo {
     name: &'static str,
+    custom_attributes: Arc<CustomAttributes>,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -234,6 +254,7 @@ impl UnitVariantInfo {
     pub fn new(name: &'static str) -> Self {
         Self {
             name,
+            custom_attributes: Arc::new(CustomAttributes::default()),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -245,6 +266,8 @@ impl UnitVariantInfo {
         Self { docs, ..self }
     }
 
+    impl_custom_attribute_methods!("variant");
+
     /// The name of this variant.
     pub fn name(&self) -> &'static str {
         self.name
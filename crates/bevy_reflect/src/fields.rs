@@ -1,5 +1,7 @@
+use crate::attributes::{impl_custom_attribute_methods, CustomAttributes};
 use crate::{Reflect, TypePath, TypePathTable};
 use std::any::{Any, TypeId};
+use std::sync::Arc;
 
 /// The named field of a reflected struct.
 #[derive(Clone, Debug)]
@@ -7,6 +9,7 @@ pub struct NamedField {
     name: &'static str,
     type_path: TypePathTable,
     type_id: TypeId,
+    custom_attributes: Arc<CustomAttributes>,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -18,6 +21,7 @@ impl NamedField {
             name,
             type_path: TypePathTable::of::<T>(),
             type_id: TypeId::of::<T>(),
+            custom_attributes: Arc::new(CustomAttributes::default()),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -29,6 +33,8 @@ impl NamedField {
         Self { docs, ..self }
     }
 
+    impl_custom_attribute_methods!("field");
+
     /// The name of the field.
     pub fn name(&self) -> &'static str {
         self.name
@@ -74,6 +80,7 @@ pub struct UnnamedField {
     index: usize,
     type_path: TypePathTable,
     type_id: TypeId,
+    custom_attributes: Arc<CustomAttributes>,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -84,6 +91,7 @@ impl UnnamedField {
             index,
             type_path: TypePathTable::of::<T>(),
             type_id: TypeId::of::<T>(),
+            custom_attributes: Arc::new(CustomAttributes::default()),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -95,6 +103,8 @@ impl UnnamedField {
         Self { docs, ..self }
     }
 
+    impl_custom_attribute_methods!("field");
+
     /// Returns the index of the field.
     pub fn index(&self) -> usize {
         self.index
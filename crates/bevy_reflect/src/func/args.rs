@@ -0,0 +1,294 @@
+use crate::func::ArgError;
+use crate::{FromReflect, Reflect, TypeInfo, Typed};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::fmt;
+
+/// How an argument is passed to a [`DynamicFunction`](crate::func::DynamicFunction).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ownership {
+    /// The argument is passed by value.
+    Owned,
+    /// The argument is passed by shared reference.
+    Ref,
+    /// The argument is passed by mutable reference.
+    Mut,
+}
+
+impl fmt::Display for Ownership {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Owned => write!(f, "owned"),
+            Self::Ref => write!(f, "borrowed"),
+            Self::Mut => write!(f, "mutably borrowed"),
+        }
+    }
+}
+
+/// An argument passed to a [`DynamicFunction`](crate::func::DynamicFunction).
+#[derive(Debug)]
+pub enum Arg<'a> {
+    /// An owned value, for parameters taken by value.
+    Owned(Box<dyn Reflect>),
+    /// A shared reference, for parameters taken by `&T`.
+    Ref(&'a dyn Reflect),
+    /// A mutable reference, for parameters taken by `&T` or `&mut T`.
+    Mut(&'a mut dyn Reflect),
+}
+
+impl<'a> Arg<'a> {
+    /// Returns how this argument is passed.
+    pub fn ownership(&self) -> Ownership {
+        match self {
+            Self::Owned(_) => Ownership::Owned,
+            Self::Ref(_) => Ownership::Ref,
+            Self::Mut(_) => Ownership::Mut,
+        }
+    }
+
+    /// Returns the value of this argument.
+    pub fn value(&self) -> &dyn Reflect {
+        match self {
+            Self::Owned(value) => value.as_ref(),
+            Self::Ref(value) => *value,
+            Self::Mut(value) => *value,
+        }
+    }
+}
+
+/// The ordered list of [`Arg`]s a [`DynamicFunction`](crate::func::DynamicFunction) is called with.
+///
+/// ```
+/// # use bevy_reflect::func::ArgList;
+/// let name = String::from("a");
+/// let mut value = 2_i32;
+/// let args = ArgList::new()
+///     .with_owned(1_i32)
+///     .with_ref(&name)
+///     .with_mut(&mut value);
+/// assert_eq!(args.len(), 3);
+/// ```
+#[derive(Debug, Default)]
+pub struct ArgList<'a> {
+    args: Vec<Arg<'a>>,
+}
+
+impl<'a> ArgList<'a> {
+    /// Creates an empty [`ArgList`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the given [`Arg`] to the list.
+    pub fn push(&mut self, arg: Arg<'a>) {
+        self.args.push(arg);
+    }
+
+    /// Appends an owned argument to the list.
+    pub fn with_owned<T: Reflect>(self, value: T) -> Self {
+        self.with_boxed(Box::new(value))
+    }
+
+    /// Appends a boxed owned argument to the list.
+    pub fn with_boxed(mut self, value: Box<dyn Reflect>) -> Self {
+        self.push(Arg::Owned(value));
+        self
+    }
+
+    /// Appends an argument passed by shared reference to the list.
+    pub fn with_ref(mut self, value: &'a dyn Reflect) -> Self {
+        self.push(Arg::Ref(value));
+        self
+    }
+
+    /// Appends an argument passed by mutable reference to the list.
+    pub fn with_mut(mut self, value: &'a mut dyn Reflect) -> Self {
+        self.push(Arg::Mut(value));
+        self
+    }
+
+    /// Returns the number of arguments in the list.
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    /// Returns `true` if the list contains no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    /// Returns an iterator over the arguments in the list.
+    pub fn iter(&self) -> impl Iterator<Item = &Arg<'a>> {
+        self.args.iter()
+    }
+}
+
+impl<'a> FromIterator<Arg<'a>> for ArgList<'a> {
+    fn from_iter<I: IntoIterator<Item = Arg<'a>>>(iter: I) -> Self {
+        Self {
+            args: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for ArgList<'a> {
+    type Item = Arg<'a>;
+    type IntoIter = std::vec::IntoIter<Arg<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.args.into_iter()
+    }
+}
+
+/// Information about a parameter of a [`DynamicFunction`](crate::func::DynamicFunction).
+#[derive(Debug, Clone)]
+pub struct ArgInfo {
+    index: usize,
+    name: Option<Cow<'static, str>>,
+    ownership: Ownership,
+    type_info: &'static TypeInfo,
+}
+
+impl ArgInfo {
+    /// Creates the [`ArgInfo`] of the parameter at `index` of type `T`.
+    pub fn new<T: Typed>(index: usize, ownership: Ownership) -> Self {
+        Self {
+            index,
+            name: None,
+            ownership,
+            type_info: T::type_info(),
+        }
+    }
+
+    /// Sets the name of the parameter.
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// The index of the parameter in the function signature.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The name of the parameter, if it was given one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// How the parameter is passed.
+    pub fn ownership(&self) -> Ownership {
+        self.ownership
+    }
+
+    /// The [`TypeInfo`] of the parameter type, without the reference.
+    pub fn type_info(&self) -> &'static TypeInfo {
+        self.type_info
+    }
+
+    /// The [type path] of the parameter type, without the reference.
+    ///
+    /// [type path]: crate::TypePath::type_path
+    pub fn type_path(&self) -> &'static str {
+        self.type_info.type_path()
+    }
+
+    /// The [`TypeId`] of the parameter type, without the reference.
+    pub fn type_id(&self) -> TypeId {
+        self.type_info.type_id()
+    }
+}
+
+/// Marks parameters taken by value, see [`FromArg`].
+pub struct OwnedArg;
+
+/// Marks parameters taken by shared reference, see [`FromArg`].
+pub struct RefArg;
+
+/// Marks parameters taken by mutable reference, see [`FromArg`].
+pub struct MutArg;
+
+/// A parameter type of a function that can be turned into a
+/// [`DynamicFunction`](crate::func::DynamicFunction).
+///
+/// This is implemented for every `T`, `&T` and `&mut T` where `T` is [`Typed`], and for `T` also
+/// [`FromReflect`]. The `Marker` distinguishes these implementations.
+pub trait FromArg<Marker> {
+    /// The parameter type for an argument borrowed for `'a`.
+    type Item<'a>;
+
+    /// Returns the [`ArgInfo`] of the parameter at `index`.
+    fn arg_info(index: usize) -> ArgInfo;
+
+    /// Extracts the parameter value for the parameter at `index` from `arg`.
+    fn from_arg(arg: Arg<'_>, index: usize) -> Result<Self::Item<'_>, ArgError>;
+}
+
+impl<T: FromReflect + Typed> FromArg<OwnedArg> for T {
+    type Item<'a> = T;
+
+    fn arg_info(index: usize) -> ArgInfo {
+        ArgInfo::new::<T>(index, Ownership::Owned)
+    }
+
+    fn from_arg(arg: Arg<'_>, index: usize) -> Result<T, ArgError> {
+        match arg {
+            Arg::Owned(value) => T::take_from_reflect(value)
+                .map_err(|value| ArgError::unexpected_type::<T>(index, value.as_ref())),
+            arg => Err(ArgError::InvalidOwnership {
+                index,
+                expected: Ownership::Owned,
+                received: arg.ownership(),
+            }),
+        }
+    }
+}
+
+impl<T: Typed> FromArg<RefArg> for &'static T {
+    type Item<'a> = &'a T;
+
+    fn arg_info(index: usize) -> ArgInfo {
+        ArgInfo::new::<T>(index, Ownership::Ref)
+    }
+
+    fn from_arg(arg: Arg<'_>, index: usize) -> Result<&T, ArgError> {
+        let value: &dyn Reflect = match arg {
+            Arg::Ref(value) => value,
+            Arg::Mut(value) => value,
+            Arg::Owned(_) => {
+                return Err(ArgError::InvalidOwnership {
+                    index,
+                    expected: Ownership::Ref,
+                    received: Ownership::Owned,
+                })
+            }
+        };
+        value
+            .downcast_ref()
+            .ok_or_else(|| ArgError::unexpected_type::<T>(index, value))
+    }
+}
+
+impl<T: Typed> FromArg<MutArg> for &'static mut T {
+    type Item<'a> = &'a mut T;
+
+    fn arg_info(index: usize) -> ArgInfo {
+        ArgInfo::new::<T>(index, Ownership::Mut)
+    }
+
+    fn from_arg(arg: Arg<'_>, index: usize) -> Result<&mut T, ArgError> {
+        match arg {
+            Arg::Mut(value) => {
+                if !value.is::<T>() {
+                    return Err(ArgError::unexpected_type::<T>(index, value));
+                }
+                Ok(value.downcast_mut().unwrap())
+            }
+            arg => Err(ArgError::InvalidOwnership {
+                index,
+                expected: Ownership::Mut,
+                received: arg.ownership(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::func::Ownership;
+use crate::{Reflect, TypePath};
+use std::borrow::Cow;
+use thiserror::Error;
+
+/// An error that occurs when converting an [`Arg`](crate::func::Arg) into a parameter value.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ArgError {
+    /// The argument has a different type than the parameter.
+    #[error("expected `{expected}` but received `{received}` for argument {index}")]
+    UnexpectedType {
+        /// The index of the argument.
+        index: usize,
+        /// The type path of the parameter.
+        expected: Cow<'static, str>,
+        /// The type path of the argument.
+        received: Cow<'static, str>,
+    },
+    /// The argument is passed with an ownership the parameter does not accept.
+    #[error("expected {expected} value but received {received} value for argument {index}")]
+    InvalidOwnership {
+        /// The index of the argument.
+        index: usize,
+        /// How the parameter is passed.
+        expected: Ownership,
+        /// How the argument is passed.
+        received: Ownership,
+    },
+}
+
+impl ArgError {
+    pub(crate) fn unexpected_type<T: TypePath>(index: usize, value: &dyn Reflect) -> Self {
+        Self::UnexpectedType {
+            index,
+            expected: Cow::Borrowed(T::type_path()),
+            received: Cow::Owned(value.reflect_type_path().to_string()),
+        }
+    }
+}
+
+/// An error that occurs when calling a [`DynamicFunction`](crate::func::DynamicFunction).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FunctionError {
+    /// The function was called with the wrong number of arguments.
+    #[error("expected {expected} arguments but received {received}")]
+    ArgCount {
+        /// The number of parameters of the function.
+        expected: usize,
+        /// The number of arguments passed.
+        received: usize,
+    },
+    /// An argument could not be converted into its parameter value.
+    #[error(transparent)]
+    Arg(#[from] ArgError),
+}
+
+/// An error that occurs when registering a function in a [`TypeRegistry`](crate::TypeRegistry).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FunctionRegistrationError {
+    /// A function or method with the same name is already registered.
+    #[error("a function named `{0}` is already registered")]
+    DuplicateName(Cow<'static, str>),
+    /// A method was registered for a type that is not registered.
+    #[error("cannot register a method for `{0}` because the type is not registered")]
+    MissingRegistration(&'static str),
+}
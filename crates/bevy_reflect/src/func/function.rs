@@ -0,0 +1,307 @@
+use crate::func::{ArgInfo, ArgList, FromArg, FunctionError};
+use crate::{Reflect, TypeInfo, Typed};
+use bevy_utils::all_tuples;
+use bevy_utils::HashMap;
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+/// The result of calling a [`DynamicFunction`]: its return value, or a [`FunctionError`].
+pub type FunctionResult = Result<Box<dyn Reflect>, FunctionError>;
+
+/// Information about the signature of a [`DynamicFunction`].
+#[derive(Debug, Clone)]
+pub struct FunctionInfo {
+    name: Option<Cow<'static, str>>,
+    args: Vec<ArgInfo>,
+    return_info: ReturnInfo,
+}
+
+impl FunctionInfo {
+    /// Creates a [`FunctionInfo`] for a function with the given parameters and return type `R`.
+    pub fn new<R: Typed>(args: Vec<ArgInfo>) -> Self {
+        Self {
+            name: None,
+            args,
+            return_info: ReturnInfo::new::<R>(),
+        }
+    }
+
+    /// Sets the name of the function.
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// The name of the function, if it has one.
+    ///
+    /// Functions created with [`IntoFunction`] are named after their [type name](std::any::type_name),
+    /// which is the path of function items but not very useful for closures.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The parameters of the function.
+    pub fn args(&self) -> &[ArgInfo] {
+        &self.args
+    }
+
+    /// The number of parameters of the function.
+    pub fn arg_count(&self) -> usize {
+        self.args.len()
+    }
+
+    /// Information about the return type of the function.
+    pub fn return_info(&self) -> &ReturnInfo {
+        &self.return_info
+    }
+}
+
+/// Information about the return type of a [`DynamicFunction`].
+#[derive(Debug, Clone)]
+pub struct ReturnInfo {
+    type_info: &'static TypeInfo,
+}
+
+impl ReturnInfo {
+    /// Creates the [`ReturnInfo`] of the return type `R`.
+    pub fn new<R: Typed>() -> Self {
+        Self {
+            type_info: R::type_info(),
+        }
+    }
+
+    /// The [`TypeInfo`] of the return type.
+    pub fn type_info(&self) -> &'static TypeInfo {
+        self.type_info
+    }
+
+    /// The [type path] of the return type.
+    ///
+    /// [type path]: crate::TypePath::type_path
+    pub fn type_path(&self) -> &'static str {
+        self.type_info.type_path()
+    }
+
+    /// The [`TypeId`] of the return type.
+    pub fn type_id(&self) -> TypeId {
+        self.type_info.type_id()
+    }
+}
+
+/// A type-erased function that is called with an [`ArgList`] of [`Reflect`] values and returns a
+/// boxed [`Reflect`] value.
+///
+/// It is usually created from an ordinary Rust function or closure with [`IntoFunction`].
+///
+/// ```
+/// # use bevy_reflect::func::{ArgList, IntoFunction};
+/// fn add(a: i32, b: &i32) -> i32 {
+///     a + *b
+/// }
+///
+/// let function = add.into_function();
+/// assert_eq!(function.info().arg_count(), 2);
+///
+/// let args = ArgList::new().with_owned(25_i32).with_ref(&75_i32);
+/// let value = function.call(args).unwrap();
+/// assert_eq!(value.downcast_ref::<i32>(), Some(&100));
+/// ```
+#[derive(Clone)]
+pub struct DynamicFunction {
+    info: FunctionInfo,
+    func: Arc<dyn for<'a> Fn(ArgList<'a>) -> FunctionResult + Send + Sync>,
+}
+
+impl DynamicFunction {
+    /// Creates a [`DynamicFunction`] from the type-erased `func` with the signature `info`.
+    ///
+    /// `func` is only called with as many arguments as `info` has parameters.
+    pub fn new(
+        func: impl for<'a> Fn(ArgList<'a>) -> FunctionResult + Send + Sync + 'static,
+        info: FunctionInfo,
+    ) -> Self {
+        Self {
+            info,
+            func: Arc::new(func),
+        }
+    }
+
+    /// Sets the name of the function.
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.info = self.info.with_name(name);
+        self
+    }
+
+    /// Sets the names of the parameters of the function, in order.
+    pub fn with_arg_names<N: Into<Cow<'static, str>>>(
+        mut self,
+        names: impl IntoIterator<Item = N>,
+    ) -> Self {
+        for (arg, name) in self.info.args.iter_mut().zip(names) {
+            *arg = arg.clone().with_name(name);
+        }
+        self
+    }
+
+    /// Returns information about the signature of the function.
+    pub fn info(&self) -> &FunctionInfo {
+        &self.info
+    }
+
+    /// Returns the name of the function, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.info.name()
+    }
+
+    /// Calls the function with the given `args`.
+    ///
+    /// Returns [`FunctionError::ArgCount`] if the number of arguments does not match the number of
+    /// parameters, and [`FunctionError::Arg`] if an argument has the wrong type or ownership.
+    pub fn call(&self, args: ArgList) -> FunctionResult {
+        let expected = self.info.arg_count();
+        if args.len() != expected {
+            return Err(FunctionError::ArgCount {
+                expected,
+                received: args.len(),
+            });
+        }
+        (self.func)(args)
+    }
+}
+
+impl fmt::Debug for DynamicFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicFunction")
+            .field("info", &self.info)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Converts a function or closure into a [`DynamicFunction`].
+///
+/// This is implemented for functions and closures with up to 12 parameters, where every parameter
+/// implements [`FromArg`] (any [`Typed`] `T` by value if it is also [`FromReflect`](crate::FromReflect),
+/// or by `&T` or `&mut T`), and the return type is [`Typed`]. Closures must be `Send`, `Sync` and
+/// `'static`.
+pub trait IntoFunction<Marker> {
+    /// Converts `self` into a [`DynamicFunction`].
+    fn into_function(self) -> DynamicFunction;
+}
+
+impl IntoFunction<()> for DynamicFunction {
+    fn into_function(self) -> DynamicFunction {
+        self
+    }
+}
+
+macro_rules! impl_into_function {
+    ($(($Arg:ident, $Marker:ident)),*) => {
+        #[allow(
+            non_snake_case,
+            unused_mut,
+            unused_variables,
+            unused_assignments,
+            clippy::unused_unit
+        )]
+        impl<F, R, $($Arg, $Marker),*> IntoFunction<(fn($($Arg),*) -> R, ($($Marker,)*))> for F
+        where
+            F: Fn($($Arg),*) -> R + for<'a> Fn($($Arg::Item<'a>),*) -> R + Send + Sync + 'static,
+            $($Arg: FromArg<$Marker>,)*
+            R: Reflect + Typed,
+        {
+            fn into_function(self) -> DynamicFunction {
+                let mut index = 0;
+                let args = vec![$({
+                    index += 1;
+                    $Arg::arg_info(index - 1)
+                }),*];
+                let info = FunctionInfo::new::<R>(args).with_name(std::any::type_name::<F>());
+                DynamicFunction::new(
+                    move |args: ArgList| {
+                        let mut args = args.into_iter();
+                        let mut index = 0;
+                        $(
+                            let $Arg = $Arg::from_arg(args.next().unwrap(), index)?;
+                            index += 1;
+                        )*
+                        Ok(Box::new((self)($($Arg),*)))
+                    },
+                    info,
+                )
+            }
+        }
+    };
+}
+
+all_tuples!(impl_into_function, 0, 12, A, M);
+
+/// Type data with the methods registered for a type with
+/// [`TypeRegistry::register_method`](crate::TypeRegistry::register_method).
+///
+/// ```
+/// # use bevy_reflect::{func::{ArgList, ReflectMethods}, Reflect, TypeRegistry};
+/// #[derive(Reflect)]
+/// struct Counter(u32);
+///
+/// impl Counter {
+///     fn increment(&mut self, amount: u32) {
+///         self.0 += amount;
+///     }
+/// }
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<Counter>();
+/// registry
+///     .register_method::<Counter, _, _>("increment", Counter::increment)
+///     .unwrap();
+///
+/// let mut counter = Counter(1);
+/// let methods = registry.get_type_data::<ReflectMethods>(std::any::TypeId::of::<Counter>());
+/// let increment = methods.unwrap().get("increment").unwrap();
+/// increment
+///     .call(ArgList::new().with_mut(&mut counter).with_owned(2_u32))
+///     .unwrap();
+/// assert_eq!(counter.0, 3);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReflectMethods {
+    methods: HashMap<Cow<'static, str>, DynamicFunction>,
+}
+
+impl ReflectMethods {
+    /// Returns the method with the given `name`, if it is registered.
+    pub fn get(&self, name: &str) -> Option<&DynamicFunction> {
+        self.methods.get(name)
+    }
+
+    /// Returns an iterator over the names and functions of all registered methods.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &DynamicFunction)> {
+        self.methods
+            .iter()
+            .map(|(name, method)| (name.as_ref(), method))
+    }
+
+    /// Returns the number of registered methods.
+    pub fn len(&self) -> usize {
+        self.methods.len()
+    }
+
+    /// Returns `true` if no methods are registered.
+    pub fn is_empty(&self) -> bool {
+        self.methods.is_empty()
+    }
+
+    pub(crate) fn insert(
+        &mut self,
+        name: Cow<'static, str>,
+        method: DynamicFunction,
+    ) -> Result<(), crate::func::FunctionRegistrationError> {
+        if self.methods.contains_key(&name) {
+            return Err(crate::func::FunctionRegistrationError::DuplicateName(name));
+        }
+        self.methods.insert(name, method);
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+//! Reflection of functions and methods.
+//!
+//! Ordinary Rust functions and closures can be converted into a [`DynamicFunction`] with
+//! [`IntoFunction`], as long as their parameters and return type are reflectable.
+//! A [`DynamicFunction`] is called with an [`ArgList`] of [`Reflect`](crate::Reflect) values,
+//! and describes its signature with a [`FunctionInfo`].
+//!
+//! Functions can be registered by name in the [`TypeRegistry`](crate::TypeRegistry) with
+//! [`register_function`](crate::TypeRegistry::register_function), and methods of registered types
+//! with [`register_method`](crate::TypeRegistry::register_method), which stores them in the
+//! [`ReflectMethods`] type data of the type.
+//!
+//! ```
+//! # use bevy_reflect::{func::ArgList, TypeRegistry};
+//! fn greet(name: String, excited: &bool) -> String {
+//!     format!("Hello, {name}{}", if *excited { "!" } else { "." })
+//! }
+//!
+//! let mut registry = TypeRegistry::new();
+//! registry.register_function("greet", greet).unwrap();
+//!
+//! let greet = registry.get_function("greet").unwrap();
+//! let args = ArgList::new()
+//!     .with_owned(String::from("world"))
+//!     .with_ref(&true);
+//! let greeting = greet.call(args).unwrap();
+//! assert_eq!(greeting.downcast_ref::<String>().unwrap(), "Hello, world!");
+//! ```
+//!
+//! # Limitations
+//!
+//! Return values are always owned, so functions returning references can't be reflected.
+//! Generic functions have to be converted once for every set of type parameters.
+
+mod args;
+mod error;
+mod function;
+
+pub use args::*;
+pub use error::*;
+pub use function::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{Reflect, TypeRegistry};
+    use std::any::TypeId;
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Player {
+        name: String,
+        health: u32,
+    }
+
+    impl Player {
+        fn damage(&mut self, amount: u32) -> bool {
+            self.health = self.health.saturating_sub(amount);
+            self.health == 0
+        }
+
+        fn name(&self) -> String {
+            self.name.clone()
+        }
+    }
+
+    #[test]
+    fn should_call_functions_and_closures() {
+        fn add(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        let function = add.into_function();
+        let result = function
+            .call(ArgList::new().with_owned(1_i32).with_owned(2_i32))
+            .unwrap();
+        assert_eq!(result.downcast_ref::<i32>(), Some(&3));
+
+        let offset = 10_i32;
+        let function = (move |value: &i32| value + offset).into_function();
+        let result = function.call(ArgList::new().with_ref(&5_i32)).unwrap();
+        assert_eq!(result.downcast_ref::<i32>(), Some(&15));
+
+        let function = (|| {}).into_function();
+        let result = function.call(ArgList::new()).unwrap();
+        assert!(result.is::<()>());
+    }
+
+    #[test]
+    fn should_describe_signature() {
+        let function = Player::damage
+            .into_function()
+            .with_arg_names(["self", "amount"]);
+        let info = function.info();
+        assert!(info.name().unwrap().ends_with("Player::damage"));
+        assert_eq!(info.arg_count(), 2);
+        assert_eq!(info.args()[0].name(), Some("self"));
+        assert_eq!(info.args()[0].ownership(), Ownership::Mut);
+        assert_eq!(info.args()[0].type_id(), TypeId::of::<Player>());
+        assert_eq!(info.args()[1].index(), 1);
+        assert_eq!(info.args()[1].ownership(), Ownership::Owned);
+        assert_eq!(info.args()[1].type_path(), "u32");
+        assert_eq!(info.return_info().type_path(), "bool");
+    }
+
+    #[test]
+    fn should_report_mismatched_arguments() {
+        let function = Player::damage.into_function();
+        let mut player = Player {
+            name: String::from("Alice"),
+            health: 10,
+        };
+
+        let result = function.call(ArgList::new().with_mut(&mut player));
+        assert_eq!(
+            result.unwrap_err(),
+            FunctionError::ArgCount {
+                expected: 2,
+                received: 1
+            }
+        );
+
+        let args = ArgList::new().with_mut(&mut player).with_owned(5_i32);
+        assert_eq!(
+            function.call(args).unwrap_err(),
+            FunctionError::Arg(ArgError::UnexpectedType {
+                index: 1,
+                expected: "u32".into(),
+                received: "i32".into(),
+            })
+        );
+
+        let args = ArgList::new().with_ref(&player).with_owned(5_u32);
+        assert_eq!(
+            function.call(args).unwrap_err(),
+            FunctionError::Arg(ArgError::InvalidOwnership {
+                index: 0,
+                expected: Ownership::Mut,
+                received: Ownership::Ref,
+            })
+        );
+        assert_eq!(player.health, 10);
+    }
+
+    #[test]
+    fn should_take_owned_arguments_from_dynamic_values() {
+        let function = (|player: Player| player.health).into_function();
+        let player = Player {
+            name: String::from("Bob"),
+            health: 7,
+        };
+        let result = function
+            .call(ArgList::new().with_boxed(player.clone_value()))
+            .unwrap();
+        assert_eq!(result.downcast_ref::<u32>(), Some(&7));
+    }
+
+    #[test]
+    fn should_register_functions_and_methods() {
+        let mut registry = TypeRegistry::new();
+        registry
+            .register_function("max", |a: u32, b: u32| a.max(b))
+            .unwrap();
+        assert_eq!(
+            registry.register_function("max", |a: u32, b: u32| a.min(b)),
+            Err(FunctionRegistrationError::DuplicateName("max".into()))
+        );
+        assert_eq!(registry.get_function("max").unwrap().name(), Some("max"));
+        assert_eq!(registry.functions().count(), 1);
+
+        assert_eq!(
+            registry.register_method::<Player, _, _>("damage", Player::damage),
+            Err(FunctionRegistrationError::MissingRegistration(
+                "bevy_reflect::func::tests::Player"
+            ))
+        );
+        registry.register::<Player>();
+        registry
+            .register_method::<Player, _, _>("damage", Player::damage)
+            .unwrap();
+        registry
+            .register_method::<Player, _, _>("name", Player::name)
+            .unwrap();
+
+        let methods = registry
+            .get_type_data::<ReflectMethods>(TypeId::of::<Player>())
+            .unwrap();
+        assert_eq!(methods.len(), 2);
+        let mut player = Player {
+            name: String::from("Carol"),
+            health: 3,
+        };
+        let name = methods.get("name").unwrap();
+        let result = name.call(ArgList::new().with_ref(&player)).unwrap();
+        assert_eq!(result.downcast_ref::<String>().unwrap(), "Carol");
+        let damage = methods.get("damage").unwrap();
+        let args = ArgList::new().with_mut(&mut player).with_owned(5_u32);
+        let result = damage.call(args).unwrap();
+        assert_eq!(result.downcast_ref::<bool>(), Some(&true));
+        assert_eq!(player.health, 0);
+    }
+}
@@ -68,6 +68,7 @@
 //! * [`Array`]
 //! * [`List`]
 //! * [`Map`]
+//! * [`Set`]
 //! * [`Struct`]
 //! * [`TupleStruct`]
 //! * [`Enum`]
@@ -128,6 +129,7 @@
 //! * [`DynamicArray`]
 //! * [`DynamicList`]
 //! * [`DynamicMap`]
+//! * [`DynamicSet`]
 //! * [`DynamicStruct`]
 //! * [`DynamicTupleStruct`]
 //! * [`DynamicEnum`]
@@ -402,8 +404,9 @@
 //! Another limitation is the inability to fully reflect functions and methods.
 //! Most languages offer some way of calling methods dynamically,
 //! but Rust makes this very difficult to do.
-//! For non-generic methods, this can be done by registering custom [type data] that
-//! contains function pointers.
+//! Non-generic functions and methods can be converted into a [`DynamicFunction`](func::DynamicFunction)
+//! and registered by name, see the [`func`] module,
+//! but they can't be discovered automatically and can't return references.
 //! For generic methods, the same can be done but will typically require manual monomorphization
 //! (i.e. manually specifying the types the generic method can take).
 //!
@@ -440,6 +443,17 @@
 //! This can be useful for generating documentation for scripting language interop or
 //! for displaying tooltips in an editor.
 //!
+//! ## `json_schema`
+//!
+//! | Default | Dependencies     |
+//! | :-----: | :--------------: |
+//! | ❌      | [`serde_json`]   |
+//!
+//! This feature enables the [`JsonSchemaExporter`](serde::JsonSchemaExporter), which exports
+//! [JSON Schemas] of the values written by the [`ReflectSerializer`] and [`TypedReflectSerializer`],
+//! so that files edited outside of Bevy can be validated.
+//! With the `documentation` feature, doc comments are included as descriptions.
+//!
 //! [Reflection]: https://en.wikipedia.org/wiki/Reflective_programming
 //! [Bevy]: https://bevyengine.org/
 //! [limitations]: #limitations
@@ -466,6 +480,8 @@
 //! [`smallvec`]: https://docs.rs/smallvec/latest/smallvec/
 //! [orphan rule]: https://doc.rust-lang.org/book/ch10-02-traits.html#implementing-a-trait-on-a-type:~:text=But%20we%20can%E2%80%99t,implementation%20to%20use.
 //! [`bevy_reflect_derive/documentation`]: bevy_reflect_derive
+//! [`serde_json`]: https://docs.rs/serde_json/latest/serde_json/
+//! [JSON Schemas]: https://json-schema.org/
 //! [derive `Reflect`]: derive@crate::Reflect
 
 mod array;
@@ -475,6 +491,7 @@ mod list;
 mod map;
 mod path;
 mod reflect;
+mod set;
 mod struct_trait;
 mod tuple;
 mod tuple_struct;
@@ -499,7 +516,10 @@ mod impls {
     mod uuid;
 }
 
+pub mod attributes;
+pub mod diff;
 mod enums;
+pub mod func;
 pub mod serde;
 pub mod std_traits;
 pub mod utility;
@@ -522,6 +542,7 @@ pub use list::*;
 pub use map::*;
 pub use path::*;
 pub use reflect::*;
+pub use set::*;
 pub use struct_trait::*;
 pub use tuple::*;
 pub use tuple_struct::*;
@@ -543,7 +564,7 @@ pub mod __macro_exports {
 #[allow(clippy::disallowed_types, clippy::approx_constant)]
 mod tests {
     use ::serde::{de::DeserializeSeed, Deserialize, Serialize};
-    use bevy_utils::HashMap;
+    use bevy_utils::{HashMap, HashSet};
     use ron::{
         ser::{to_string_pretty, PrettyConfig},
         Deserializer,
@@ -1115,6 +1136,44 @@ mod tests {
         let fields = map_value.drain();
         assert!(fields[0].0.reflect_partial_eq(&123_i32).unwrap_or_default());
         assert!(fields[0].1.reflect_partial_eq(&321_i32).unwrap_or_default());
+
+        let set_value: Box<dyn Set> = Box::new(HashSet::from([123_i32]));
+        let fields = set_value.drain();
+        assert!(fields[0].reflect_partial_eq(&123_i32).unwrap_or_default());
+    }
+
+    #[test]
+    fn reflect_sets() {
+        type MySet = std::collections::BTreeSet<u32>;
+
+        let info = MySet::type_info();
+        if let TypeInfo::Set(info) = info {
+            assert!(info.is::<MySet>());
+            assert!(info.value_is::<u32>());
+            assert_eq!(MySet::type_path(), info.type_path());
+            assert_eq!(u32::type_path(), info.value_type_path_table().path());
+        } else {
+            panic!("Expected `TypeInfo::Set`");
+        }
+
+        let mut set = MySet::from([1, 2]);
+        let mut dynamic_set = DynamicSet::default();
+        dynamic_set.insert(2_u32);
+        dynamic_set.insert(3_u32);
+        set.apply(&dynamic_set);
+        assert_eq!(set, MySet::from([1, 2, 3]));
+
+        let set_value: &mut dyn Set = &mut set;
+        assert!(set_value.contains(&3_u32));
+        assert!(set_value.remove(&1_u32));
+        assert!(!set_value.insert_boxed(Box::new(2_u32)));
+        assert!(set_value
+            .reflect_partial_eq(&HashSet::from([2_u32, 3]))
+            .unwrap());
+
+        let from_reflect = <std::collections::HashSet<u32>>::from_reflect(&dynamic_set).unwrap();
+        assert_eq!(from_reflect, std::collections::HashSet::from([2, 3]));
+        assert_eq!(format!("{:?}", &MySet::from([1]) as &dyn Reflect), "{1}");
     }
 
     #[test]
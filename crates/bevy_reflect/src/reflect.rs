@@ -1,7 +1,7 @@
 use crate::{
-    array_debug, enum_debug, list_debug, map_debug, serde::Serializable, struct_debug, tuple_debug,
-    tuple_struct_debug, Array, DynamicTypePath, Enum, List, Map, Struct, Tuple, TupleStruct,
-    TypeInfo, TypePath, Typed, ValueInfo,
+    array_debug, enum_debug, list_debug, map_debug, serde::Serializable, set_debug, struct_debug,
+    tuple_debug, tuple_struct_debug, Array, DynamicTypePath, Enum, List, Map, Set, Struct, Tuple,
+    TupleStruct, TypeInfo, TypePath, Typed, ValueInfo,
 };
 use std::{
     any::{Any, TypeId},
@@ -22,6 +22,7 @@ macro_rules! impl_reflect_enum {
                     Self::List(_) => ReflectKind::List,
                     Self::Array(_) => ReflectKind::Array,
                     Self::Map(_) => ReflectKind::Map,
+                    Self::Set(_) => ReflectKind::Set,
                     Self::Enum(_) => ReflectKind::Enum,
                     Self::Value(_) => ReflectKind::Value,
                 }
@@ -37,6 +38,7 @@ macro_rules! impl_reflect_enum {
                     $name::List(_) => Self::List,
                     $name::Array(_) => Self::Array,
                     $name::Map(_) => Self::Map,
+                    $name::Set(_) => Self::Set,
                     $name::Enum(_) => Self::Enum,
                     $name::Value(_) => Self::Value,
                 }
@@ -58,6 +60,7 @@ pub enum ReflectRef<'a> {
     List(&'a dyn List),
     Array(&'a dyn Array),
     Map(&'a dyn Map),
+    Set(&'a dyn Set),
     Enum(&'a dyn Enum),
     Value(&'a dyn Reflect),
 }
@@ -76,6 +79,7 @@ pub enum ReflectMut<'a> {
     List(&'a mut dyn List),
     Array(&'a mut dyn Array),
     Map(&'a mut dyn Map),
+    Set(&'a mut dyn Set),
     Enum(&'a mut dyn Enum),
     Value(&'a mut dyn Reflect),
 }
@@ -94,6 +98,7 @@ pub enum ReflectOwned {
     List(Box<dyn List>),
     Array(Box<dyn Array>),
     Map(Box<dyn Map>),
+    Set(Box<dyn Set>),
     Enum(Box<dyn Enum>),
     Value(Box<dyn Reflect>),
 }
@@ -111,6 +116,7 @@ pub enum ReflectKind {
     List,
     Array,
     Map,
+    Set,
     Enum,
     Value,
 }
@@ -124,6 +130,7 @@ impl std::fmt::Display for ReflectKind {
             ReflectKind::List => f.pad("list"),
             ReflectKind::Array => f.pad("array"),
             ReflectKind::Map => f.pad("map"),
+            ReflectKind::Set => f.pad("set"),
             ReflectKind::Enum => f.pad("enum"),
             ReflectKind::Value => f.pad("value"),
         }
@@ -197,17 +204,20 @@ pub trait Reflect: DynamicTypePath + Any + Send + Sync {
     /// - If `T` is a [`Map`], then for each key in `value`, the associated
     ///   value is applied to the value associated with the same key in `self`.
     ///   Keys which are not present in `self` are inserted.
+    /// - If `T` is a [`Set`], then each value of `value` which is not present in
+    ///   `self` is inserted.
     /// - If `T` is none of these, then `value` is downcast to `T`, cloned, and
     ///   assigned to `self`.
     ///
-    /// Note that `Reflect` must be implemented manually for [`List`]s and
-    /// [`Map`]s in order to achieve the correct semantics, as derived
+    /// Note that `Reflect` must be implemented manually for [`List`]s,
+    /// [`Map`]s and [`Set`]s in order to achieve the correct semantics, as derived
     /// implementations will have the semantics for [`Struct`], [`TupleStruct`], [`Enum`]
-    /// or none of the above depending on the kind of type. For lists and maps, use the
-    /// [`list_apply`] and [`map_apply`] helper functions when implementing this method.
+    /// or none of the above depending on the kind of type. For lists, maps and sets, use the
+    /// [`list_apply`], [`map_apply`] and [`set_apply`] helper functions when implementing this method.
     ///
     /// [`list_apply`]: crate::list_apply
     /// [`map_apply`]: crate::map_apply
+    /// [`set_apply`]: crate::set_apply
     ///
     /// # Panics
     ///
@@ -285,6 +295,7 @@ pub trait Reflect: DynamicTypePath + Any + Send + Sync {
             ReflectRef::List(dyn_list) => list_debug(dyn_list, f),
             ReflectRef::Array(dyn_array) => array_debug(dyn_array, f),
             ReflectRef::Map(dyn_map) => map_debug(dyn_map, f),
+            ReflectRef::Set(dyn_set) => set_debug(dyn_set, f),
             ReflectRef::Enum(dyn_enum) => enum_debug(dyn_enum, f),
             _ => write!(f, "Reflect({})", self.reflect_type_path()),
         }
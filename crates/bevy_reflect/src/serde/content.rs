@@ -0,0 +1,313 @@
+use serde::de::{
+    self,
+    value::{MapDeserializer, SeqDeserializer},
+    Deserialize, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use std::{fmt, marker::PhantomData};
+
+/// A value of a self-describing format, read with [`Deserializer::deserialize_any`] so that it can
+/// be deserialized once its type is known, with [`ContentDeserializer`].
+#[derive(Debug)]
+pub(super) enum Content {
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Char(char),
+    String(String),
+    Bytes(Vec<u8>),
+    None,
+    Some(Box<Content>),
+    Unit,
+    Newtype(Box<Content>),
+    Seq(Vec<Content>),
+    Map(Vec<(Content, Content)>),
+}
+
+impl<'de> Deserialize<'de> for Content {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ContentVisitor)
+    }
+}
+
+struct ContentVisitor;
+
+impl<'de> Visitor<'de> for ContentVisitor {
+    type Value = Content;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Content, E> {
+        Ok(Content::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Content, E> {
+        Ok(Content::I64(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Content, E> {
+        Ok(Content::U64(value))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Content, E> {
+        Ok(Content::F64(value))
+    }
+
+    fn visit_char<E>(self, value: char) -> Result<Content, E> {
+        Ok(Content::Char(value))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Content, E> {
+        Ok(Content::String(value.to_owned()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Content, E> {
+        Ok(Content::String(value))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Content, E> {
+        Ok(Content::Bytes(value.to_owned()))
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Content, E> {
+        Ok(Content::Bytes(value))
+    }
+
+    fn visit_none<E>(self) -> Result<Content, E> {
+        Ok(Content::None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Content, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Content::deserialize(deserializer).map(|content| Content::Some(Box::new(content)))
+    }
+
+    fn visit_unit<E>(self) -> Result<Content, E> {
+        Ok(Content::Unit)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Content, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Content::deserialize(deserializer).map(|content| Content::Newtype(Box::new(content)))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Content, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+        Ok(Content::Seq(elements))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Content, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(Content::Map(entries))
+    }
+}
+
+/// Deserializes a buffered [`Content`], with the error type of the deserializer it was read from.
+pub(super) struct ContentDeserializer<E> {
+    content: Content,
+    marker: PhantomData<E>,
+}
+
+impl<E> ContentDeserializer<E> {
+    pub(super) fn new(content: Content) -> Self {
+        Self {
+            content,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, E: de::Error> IntoDeserializer<'de, E> for Content {
+    type Deserializer = ContentDeserializer<E>;
+
+    fn into_deserializer(self) -> ContentDeserializer<E> {
+        ContentDeserializer::new(self)
+    }
+}
+
+impl<'de, E: de::Error> Deserializer<'de> for ContentDeserializer<E> {
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Bool(value) => visitor.visit_bool(value),
+            Content::I64(value) => visitor.visit_i64(value),
+            Content::U64(value) => visitor.visit_u64(value),
+            Content::F64(value) => visitor.visit_f64(value),
+            Content::Char(value) => visitor.visit_char(value),
+            Content::String(value) => visitor.visit_string(value),
+            Content::Bytes(value) => visitor.visit_byte_buf(value),
+            Content::None => visitor.visit_none(),
+            Content::Some(value) => visitor.visit_some(ContentDeserializer::new(*value)),
+            Content::Unit => visitor.visit_unit(),
+            Content::Newtype(value) => {
+                visitor.visit_newtype_struct(ContentDeserializer::new(*value))
+            }
+            Content::Seq(elements) => {
+                let mut seq = SeqDeserializer::new(elements.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Content::Map(entries) => {
+                let mut map = MapDeserializer::new(entries.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::None | Content::Unit => visitor.visit_none(),
+            Content::Some(value) => visitor.visit_some(ContentDeserializer::new(*value)),
+            content => visitor.visit_some(ContentDeserializer::new(content)),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content {
+            Content::Newtype(value) => {
+                visitor.visit_newtype_struct(ContentDeserializer::new(*value))
+            }
+            content => visitor.visit_newtype_struct(ContentDeserializer::new(content)),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        let (variant, value) = match self.content {
+            // A unit variant, in formats writing enums as strings.
+            content @ Content::String(_) => (content, None),
+            // A variant with a value, in formats writing enums as single entry maps.
+            Content::Map(entries) if entries.len() == 1 => {
+                let (variant, value) = entries.into_iter().next().unwrap();
+                (variant, Some(value))
+            }
+            content => {
+                return Err(de::Error::custom(format_args!(
+                    "expected an enum, found {content:?}"
+                )))
+            }
+        };
+        visitor.visit_enum(EnumDeserializer {
+            variant,
+            value,
+            marker: PhantomData,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct EnumDeserializer<E> {
+    variant: Content,
+    value: Option<Content>,
+    marker: PhantomData<E>,
+}
+
+impl<'de, E: de::Error> EnumAccess<'de> for EnumDeserializer<E> {
+    type Error = E;
+    type Variant = VariantDeserializer<E>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, VariantDeserializer<E>), E>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(ContentDeserializer::new(self.variant))?;
+        let value = VariantDeserializer {
+            value: self.value,
+            marker: PhantomData,
+        };
+        Ok((variant, value))
+    }
+}
+
+struct VariantDeserializer<E> {
+    value: Option<Content>,
+    marker: PhantomData<E>,
+}
+
+impl<E: de::Error> VariantDeserializer<E> {
+    fn into_value(self) -> Result<ContentDeserializer<E>, E> {
+        self.value
+            .map(ContentDeserializer::new)
+            .ok_or_else(|| de::Error::custom("expected a variant with a value"))
+    }
+}
+
+impl<'de, E: de::Error> VariantAccess<'de> for VariantDeserializer<E> {
+    type Error = E;
+
+    fn unit_variant(self) -> Result<(), E> {
+        match self.value {
+            None | Some(Content::Unit) => Ok(()),
+            Some(content) => Err(de::Error::custom(format_args!(
+                "expected a unit variant, found {content:?}"
+            ))),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, E>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.into_value()?)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        self.into_value()?.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        self.into_value()?.deserialize_any(visitor)
+    }
+}
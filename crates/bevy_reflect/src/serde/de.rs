@@ -1,9 +1,13 @@
-use crate::serde::SerializationData;
+use crate::serde::{
+    content::{Content, ContentDeserializer},
+    ReflectMigrations, SerializationData, SerializedField, VERSION_FIELD,
+};
 use crate::{
-    ArrayInfo, DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
-    DynamicTupleStruct, DynamicVariant, EnumInfo, ListInfo, Map, MapInfo, NamedField, Reflect,
-    ReflectDeserialize, StructInfo, StructVariantInfo, TupleInfo, TupleStructInfo,
-    TupleVariantInfo, TypeInfo, TypeRegistration, TypeRegistry, UnnamedField, VariantInfo,
+    ArrayInfo, DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicSet, DynamicStruct,
+    DynamicTuple, DynamicTupleStruct, DynamicVariant, EnumInfo, ListInfo, Map, MapInfo, NamedField,
+    Reflect, ReflectDeserialize, Set, SetInfo, StructInfo, StructVariantInfo, TupleInfo,
+    TupleStructInfo, TupleVariantInfo, TypeInfo, TypeRegistration, TypeRegistry, UnnamedField,
+    VariantInfo,
 };
 use erased_serde::Deserializer;
 use serde::de::{
@@ -407,6 +411,7 @@ impl<'a, 'de> Visitor<'de> for UntypedReflectDeserializerVisitor<'a> {
         let value = map.next_value_seed(TypedReflectDeserializer {
             registration,
             registry: self.registry,
+            processor: None,
         })?;
 
         if map.next_key::<IgnoredAny>()?.is_some() {
@@ -435,6 +440,7 @@ impl<'a, 'de> Visitor<'de> for UntypedReflectDeserializerVisitor<'a> {
 pub struct TypedReflectDeserializer<'a> {
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    processor: Option<&'a dyn ReflectDeserializerProcessor>,
 }
 
 impl<'a> TypedReflectDeserializer<'a> {
@@ -442,8 +448,40 @@ impl<'a> TypedReflectDeserializer<'a> {
         Self {
             registration,
             registry,
+            processor: None,
         }
     }
+
+    /// Creates a deserializer where the values of the types handled by `processor`, including
+    /// nested values, are deserialized by the `processor` instead.
+    pub fn with_processor(
+        registration: &'a TypeRegistration,
+        registry: &'a TypeRegistry,
+        processor: &'a dyn ReflectDeserializerProcessor,
+    ) -> Self {
+        Self {
+            registration,
+            registry,
+            processor: Some(processor),
+        }
+    }
+}
+
+/// Overrides how a [`TypedReflectDeserializer`] deserializes the values of some types.
+///
+/// Unlike [`ReflectDeserialize`] type data, a processor is passed to a single deserializer, so it
+/// can hold the state of that deserialization, like the context the values are loaded in.
+pub trait ReflectDeserializerProcessor {
+    /// Returns `true` if values of the type of `registration` are deserialized by this processor.
+    fn handles(&self, registration: &TypeRegistration) -> bool;
+
+    /// Deserializes a value of the type of `registration`, which this processor
+    /// [handles](ReflectDeserializerProcessor::handles).
+    fn deserialize(
+        &self,
+        registration: &TypeRegistration,
+        deserializer: &mut dyn Deserializer,
+    ) -> Result<Box<dyn Reflect>, erased_serde::Error>;
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
@@ -455,6 +493,16 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
     {
         let type_path = self.registration.type_info().type_path();
 
+        if let Some(processor) = self
+            .processor
+            .filter(|processor| processor.handles(self.registration))
+        {
+            let mut erased = <dyn Deserializer>::erase(deserializer);
+            return processor
+                .deserialize(self.registration, &mut erased)
+                .map_err(D::Error::custom);
+        }
+
         // Handle both Value case and types that have a custom `ReflectDeserialize`
         if let Some(deserialize_reflect) = self.registration.data::<ReflectDeserialize>() {
             let value = deserialize_reflect.deserialize(deserializer)?;
@@ -463,13 +511,19 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
 
         match self.registration.type_info() {
             TypeInfo::Struct(struct_info) => {
+                let migrations = self.registration.data::<ReflectMigrations>();
                 let mut dynamic_struct = deserializer.deserialize_struct(
                     struct_info.type_path_table().ident().unwrap(),
-                    struct_info.field_names(),
+                    match migrations {
+                        Some(_) => ReflectMigrations::field_names(struct_info),
+                        None => struct_info.field_names(),
+                    },
                     StructVisitor {
                         struct_info,
                         registration: self.registration,
                         registry: self.registry,
+                        processor: self.processor,
+                        migrations,
                     },
                 )?;
                 dynamic_struct.set_represented_type(Some(self.registration.type_info()));
@@ -482,6 +536,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                     TupleStructVisitor {
                         tuple_struct_info,
                         registry: self.registry,
+                        processor: self.processor,
                         registration: self.registration,
                     },
                 )?;
@@ -492,6 +547,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                 let mut dynamic_list = deserializer.deserialize_seq(ListVisitor {
                     list_info,
                     registry: self.registry,
+                    processor: self.processor,
                 })?;
                 dynamic_list.set_represented_type(Some(self.registration.type_info()));
                 Ok(Box::new(dynamic_list))
@@ -502,6 +558,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                     ArrayVisitor {
                         array_info,
                         registry: self.registry,
+                        processor: self.processor,
                     },
                 )?;
                 dynamic_array.set_represented_type(Some(self.registration.type_info()));
@@ -511,10 +568,20 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                 let mut dynamic_map = deserializer.deserialize_map(MapVisitor {
                     map_info,
                     registry: self.registry,
+                    processor: self.processor,
                 })?;
                 dynamic_map.set_represented_type(Some(self.registration.type_info()));
                 Ok(Box::new(dynamic_map))
             }
+            TypeInfo::Set(set_info) => {
+                let mut dynamic_set = deserializer.deserialize_seq(SetVisitor {
+                    set_info,
+                    registry: self.registry,
+                    processor: self.processor,
+                })?;
+                dynamic_set.set_represented_type(Some(self.registration.type_info()));
+                Ok(Box::new(dynamic_set))
+            }
             TypeInfo::Tuple(tuple_info) => {
                 let mut dynamic_tuple = deserializer.deserialize_tuple(
                     tuple_info.field_len(),
@@ -522,6 +589,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                         tuple_info,
                         registration: self.registration,
                         registry: self.registry,
+                        processor: self.processor,
                     },
                 )?;
                 dynamic_tuple.set_represented_type(Some(self.registration.type_info()));
@@ -535,6 +603,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                     deserializer.deserialize_option(OptionVisitor {
                         enum_info,
                         registry: self.registry,
+                        processor: self.processor,
                     })?
                 } else {
                     deserializer.deserialize_enum(
@@ -544,6 +613,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                             enum_info,
                             registration: self.registration,
                             registry: self.registry,
+                            processor: self.processor,
                         },
                     )?
                 };
@@ -564,6 +634,8 @@ struct StructVisitor<'a> {
     struct_info: &'static StructInfo,
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    processor: Option<&'a dyn ReflectDeserializerProcessor>,
+    migrations: Option<&'a ReflectMigrations>,
 }
 
 impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
@@ -577,20 +649,55 @@ impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
     where
         A: SeqAccess<'de>,
     {
-        visit_struct_seq(&mut seq, self.struct_info, self.registration, self.registry)
+        if let Some(migrations) = self.migrations {
+            let version = seq
+                .next_element::<u32>()?
+                .ok_or_else(|| Error::invalid_length(0, &"a version"))?;
+            if version != migrations.version() {
+                return Err(Error::custom(format_args!(
+                    "cannot migrate `{}` from version {version} to version {}, older versions can only be migrated from structs serialized as maps",
+                    self.struct_info.type_path(),
+                    migrations.version(),
+                )));
+            }
+        }
+        visit_struct_seq(
+            &mut seq,
+            self.struct_info,
+            self.registration,
+            self.registry,
+            self.processor,
+        )
     }
 
     fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
     where
         V: MapAccess<'de>,
     {
-        visit_struct(&mut map, self.struct_info, self.registration, self.registry)
+        match self.migrations {
+            Some(migrations) => visit_versioned_struct(
+                &mut map,
+                self.struct_info,
+                self.registration,
+                self.registry,
+                self.processor,
+                migrations,
+            ),
+            None => visit_struct(
+                &mut map,
+                self.struct_info,
+                self.registration,
+                self.registry,
+                self.processor,
+            ),
+        }
     }
 }
 
 struct TupleStructVisitor<'a> {
     tuple_struct_info: &'static TupleStructInfo,
     registry: &'a TypeRegistry,
+    processor: Option<&'a dyn ReflectDeserializerProcessor>,
     registration: &'a TypeRegistration,
 }
 
@@ -610,6 +717,7 @@ impl<'a, 'de> Visitor<'de> for TupleStructVisitor<'a> {
             self.tuple_struct_info,
             self.registration,
             self.registry,
+            self.processor,
         )
         .map(DynamicTupleStruct::from)
     }
@@ -619,6 +727,7 @@ struct TupleVisitor<'a> {
     tuple_info: &'static TupleInfo,
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    processor: Option<&'a dyn ReflectDeserializerProcessor>,
 }
 
 impl<'a, 'de> Visitor<'de> for TupleVisitor<'a> {
@@ -632,13 +741,20 @@ impl<'a, 'de> Visitor<'de> for TupleVisitor<'a> {
     where
         V: SeqAccess<'de>,
     {
-        visit_tuple(&mut seq, self.tuple_info, self.registration, self.registry)
+        visit_tuple(
+            &mut seq,
+            self.tuple_info,
+            self.registration,
+            self.registry,
+            self.processor,
+        )
     }
 }
 
 struct ArrayVisitor<'a> {
     array_info: &'static ArrayInfo,
     registry: &'a TypeRegistry,
+    processor: Option<&'a dyn ReflectDeserializerProcessor>,
 }
 
 impl<'a, 'de> Visitor<'de> for ArrayVisitor<'a> {
@@ -661,6 +777,7 @@ impl<'a, 'de> Visitor<'de> for ArrayVisitor<'a> {
         while let Some(value) = seq.next_element_seed(TypedReflectDeserializer {
             registration,
             registry: self.registry,
+            processor: self.processor,
         })? {
             vec.push(value);
         }
@@ -679,6 +796,7 @@ impl<'a, 'de> Visitor<'de> for ArrayVisitor<'a> {
 struct ListVisitor<'a> {
     list_info: &'static ListInfo,
     registry: &'a TypeRegistry,
+    processor: Option<&'a dyn ReflectDeserializerProcessor>,
 }
 
 impl<'a, 'de> Visitor<'de> for ListVisitor<'a> {
@@ -701,6 +819,7 @@ impl<'a, 'de> Visitor<'de> for ListVisitor<'a> {
         while let Some(value) = seq.next_element_seed(TypedReflectDeserializer {
             registration,
             registry: self.registry,
+            processor: self.processor,
         })? {
             list.push_box(value);
         }
@@ -711,6 +830,7 @@ impl<'a, 'de> Visitor<'de> for ListVisitor<'a> {
 struct MapVisitor<'a> {
     map_info: &'static MapInfo,
     registry: &'a TypeRegistry,
+    processor: Option<&'a dyn ReflectDeserializerProcessor>,
 }
 
 impl<'a, 'de> Visitor<'de> for MapVisitor<'a> {
@@ -738,10 +858,12 @@ impl<'a, 'de> Visitor<'de> for MapVisitor<'a> {
         while let Some(key) = map.next_key_seed(TypedReflectDeserializer {
             registration: key_registration,
             registry: self.registry,
+            processor: self.processor,
         })? {
             let value = map.next_value_seed(TypedReflectDeserializer {
                 registration: value_registration,
                 registry: self.registry,
+                processor: self.processor,
             })?;
             dynamic_map.insert_boxed(key, value);
         }
@@ -750,10 +872,46 @@ impl<'a, 'de> Visitor<'de> for MapVisitor<'a> {
     }
 }
 
+struct SetVisitor<'a> {
+    set_info: &'static SetInfo,
+    registry: &'a TypeRegistry,
+    processor: Option<&'a dyn ReflectDeserializerProcessor>,
+}
+
+impl<'a, 'de> Visitor<'de> for SetVisitor<'a> {
+    type Value = DynamicSet;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("reflected set value")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut dynamic_set = DynamicSet::default();
+        let registration = get_registration(
+            self.set_info.value_type_id(),
+            self.set_info.value_type_path_table().path(),
+            self.registry,
+        )?;
+        while let Some(value) = seq.next_element_seed(TypedReflectDeserializer {
+            registration,
+            registry: self.registry,
+            processor: self.processor,
+        })? {
+            dynamic_set.insert_boxed(value);
+        }
+
+        Ok(dynamic_set)
+    }
+}
+
 struct EnumVisitor<'a> {
     enum_info: &'static EnumInfo,
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    processor: Option<&'a dyn ReflectDeserializerProcessor>,
 }
 
 impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
@@ -781,6 +939,7 @@ impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
                         struct_info,
                         registration: self.registration,
                         registry: self.registry,
+                        processor: self.processor,
                     },
                 )?
                 .into(),
@@ -789,6 +948,7 @@ impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
                 let value = variant.newtype_variant_seed(TypedReflectDeserializer {
                     registration,
                     registry: self.registry,
+                    processor: self.processor,
                 })?;
                 let mut dynamic_tuple = DynamicTuple::default();
                 dynamic_tuple.insert_boxed(value);
@@ -801,6 +961,7 @@ impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
                         tuple_info,
                         registration: self.registration,
                         registry: self.registry,
+                        processor: self.processor,
                     },
                 )?
                 .into(),
@@ -867,6 +1028,7 @@ struct StructVariantVisitor<'a> {
     struct_info: &'static StructVariantInfo,
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    processor: Option<&'a dyn ReflectDeserializerProcessor>,
 }
 
 impl<'a, 'de> Visitor<'de> for StructVariantVisitor<'a> {
@@ -880,14 +1042,26 @@ impl<'a, 'de> Visitor<'de> for StructVariantVisitor<'a> {
     where
         A: SeqAccess<'de>,
     {
-        visit_struct_seq(&mut seq, self.struct_info, self.registration, self.registry)
+        visit_struct_seq(
+            &mut seq,
+            self.struct_info,
+            self.registration,
+            self.registry,
+            self.processor,
+        )
     }
 
     fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
     where
         V: MapAccess<'de>,
     {
-        visit_struct(&mut map, self.struct_info, self.registration, self.registry)
+        visit_struct(
+            &mut map,
+            self.struct_info,
+            self.registration,
+            self.registry,
+            self.processor,
+        )
     }
 }
 
@@ -895,6 +1069,7 @@ struct TupleVariantVisitor<'a> {
     tuple_info: &'static TupleVariantInfo,
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    processor: Option<&'a dyn ReflectDeserializerProcessor>,
 }
 
 impl<'a, 'de> Visitor<'de> for TupleVariantVisitor<'a> {
@@ -908,13 +1083,20 @@ impl<'a, 'de> Visitor<'de> for TupleVariantVisitor<'a> {
     where
         V: SeqAccess<'de>,
     {
-        visit_tuple(&mut seq, self.tuple_info, self.registration, self.registry)
+        visit_tuple(
+            &mut seq,
+            self.tuple_info,
+            self.registration,
+            self.registry,
+            self.processor,
+        )
     }
 }
 
 struct OptionVisitor<'a> {
     enum_info: &'static EnumInfo,
     registry: &'a TypeRegistry,
+    processor: Option<&'a dyn ReflectDeserializerProcessor>,
 }
 
 impl<'a, 'de> Visitor<'de> for OptionVisitor<'a> {
@@ -947,6 +1129,7 @@ impl<'a, 'de> Visitor<'de> for OptionVisitor<'a> {
                 let de = TypedReflectDeserializer {
                     registration,
                     registry: self.registry,
+                    processor: self.processor,
                 };
                 let mut value = DynamicTuple::default();
                 value.insert_boxed(de.deserialize(deserializer)?);
@@ -967,6 +1150,7 @@ fn visit_struct<'de, T, V>(
     info: &'static T,
     registration: &TypeRegistration,
     registry: &TypeRegistry,
+    processor: Option<&dyn ReflectDeserializerProcessor>,
 ) -> Result<DynamicStruct, V::Error>
 where
     T: StructLikeInfo,
@@ -986,10 +1170,159 @@ where
         let value = map.next_value_seed(TypedReflectDeserializer {
             registration,
             registry,
+            processor,
         })?;
         dynamic_struct.insert_boxed(&key, value);
     }
 
+    insert_skipped_fields(&mut dynamic_struct, info, registration);
+    Ok(dynamic_struct)
+}
+
+/// A field of a struct with [`ReflectMigrations`] read before its version.
+enum PendingField {
+    /// The field has the same type in every version.
+    Value(Box<dyn Reflect>),
+    /// The field is removed in every version it exists in.
+    Ignored,
+    /// The type of the field depends on the version.
+    Buffered(Content),
+}
+
+/// Visits a struct with [`ReflectMigrations`], whose fields are migrated from the serialized
+/// version to the current one.
+fn visit_versioned_struct<'de, V>(
+    map: &mut V,
+    info: &'static StructInfo,
+    registration: &TypeRegistration,
+    registry: &TypeRegistry,
+    processor: Option<&dyn ReflectDeserializerProcessor>,
+    migrations: &ReflectMigrations,
+) -> Result<DynamicStruct, V::Error>
+where
+    V: MapAccess<'de>,
+{
+    // The version is serialized first, but formats sorting the keys of maps can move it after
+    // other fields. Their values are buffered until the version is known if their type depends
+    // on it.
+    let mut pending = Vec::new();
+    let mut versions: Option<Vec<_>> = None;
+    let mut version = 0;
+    while let Some(Ident(name)) = map.next_key::<Ident>()? {
+        if name == VERSION_FIELD {
+            version = map.next_value::<u32>()?;
+            break;
+        }
+        let versions = versions.get_or_insert_with(|| {
+            (0..=migrations.version())
+                .map(|version| migrations.serialized_fields(info, registration, version))
+                .collect()
+        });
+        let mut fields = versions
+            .iter()
+            .flatten()
+            .filter(|(field_name, _)| *field_name == name)
+            .map(|(_, field)| field);
+        let field = match fields.next() {
+            Some(first) if fields.all(|field| field == first) => Some(first),
+            _ => None,
+        };
+        let field = match field {
+            Some(SerializedField::Typed { type_id, type_path }) => {
+                let registration = get_registration(*type_id, type_path, registry)?;
+                PendingField::Value(map.next_value_seed(TypedReflectDeserializer {
+                    registration,
+                    registry,
+                    processor,
+                })?)
+            }
+            Some(SerializedField::Ignored) => {
+                map.next_value::<IgnoredAny>()?;
+                PendingField::Ignored
+            }
+            None => PendingField::Buffered(map.next_value::<Content>()?),
+        };
+        pending.push((name, field));
+    }
+    if version > migrations.version() {
+        return Err(Error::custom(format_args!(
+            "cannot deserialize version {version} of `{}`, the current version is {}",
+            info.type_path(),
+            migrations.version(),
+        )));
+    }
+
+    let serialized_fields = migrations.serialized_fields(info, registration, version);
+    let find_field = |name: &str| {
+        serialized_fields
+            .iter()
+            .find(|(field_name, _)| *field_name == name)
+            .ok_or_else(|| {
+                let fields = serialized_fields.iter().map(|(name, _)| name.as_ref());
+                Error::custom(format_args!(
+                    "unknown field `{}` in version {version} of `{}`, expected one of {:?}",
+                    name,
+                    info.type_path(),
+                    ExpectedValues(fields.collect())
+                ))
+            })
+    };
+    let mut fields = Vec::with_capacity(serialized_fields.len());
+    for (name, pending_field) in pending {
+        let (name, field) = find_field(&name)?;
+        match (pending_field, field) {
+            (PendingField::Value(value), _) => fields.push((name.clone(), value)),
+            (PendingField::Buffered(content), SerializedField::Typed { type_id, type_path }) => {
+                let registration = get_registration(*type_id, type_path, registry)?;
+                let value = TypedReflectDeserializer {
+                    registration,
+                    registry,
+                    processor,
+                }
+                .deserialize(ContentDeserializer::<V::Error>::new(content))?;
+                fields.push((name.clone(), value));
+            }
+            _ => {}
+        }
+    }
+    while let Some(Ident(name)) = map.next_key()? {
+        let (name, field) = find_field(&name)?;
+        match field {
+            SerializedField::Typed { type_id, type_path } => {
+                let registration = get_registration(*type_id, type_path, registry)?;
+                let value = map.next_value_seed(TypedReflectDeserializer {
+                    registration,
+                    registry,
+                    processor,
+                })?;
+                fields.push((name.clone(), value));
+            }
+            SerializedField::Ignored => {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+    }
+
+    migrations.migrate(version, &mut fields).map_err(|error| {
+        Error::custom(format_args!(
+            "cannot migrate `{}` from version {version}: {error}",
+            info.type_path()
+        ))
+    })?;
+
+    let mut dynamic_struct = DynamicStruct::default();
+    for (name, value) in fields {
+        dynamic_struct.insert_boxed(name, value);
+    }
+    insert_skipped_fields(&mut dynamic_struct, info, registration);
+    Ok(dynamic_struct)
+}
+
+fn insert_skipped_fields<T: StructLikeInfo>(
+    dynamic_struct: &mut DynamicStruct,
+    info: &T,
+    registration: &TypeRegistration,
+) {
     if let Some(serialization_data) = registration.data::<SerializationData>() {
         for (skipped_index, skipped_field) in serialization_data.iter_skipped() {
             let Some(field) = info.field_at(*skipped_index) else {
@@ -998,8 +1331,6 @@ where
             dynamic_struct.insert_boxed(field.name(), skipped_field.generate_default());
         }
     }
-
-    Ok(dynamic_struct)
 }
 
 fn visit_tuple<'de, T, V>(
@@ -1007,6 +1338,7 @@ fn visit_tuple<'de, T, V>(
     info: &T,
     registration: &TypeRegistration,
     registry: &TypeRegistry,
+    processor: Option<&dyn ReflectDeserializerProcessor>,
 ) -> Result<DynamicTuple, V::Error>
 where
     T: TupleLikeInfo + Container,
@@ -1033,6 +1365,7 @@ where
             .next_element_seed(TypedReflectDeserializer {
                 registration: info.get_field_registration(index, registry)?,
                 registry,
+                processor,
             })?
             .ok_or_else(|| Error::invalid_length(index, &len.to_string().as_str()))?;
         tuple.insert_boxed(value);
@@ -1046,6 +1379,7 @@ fn visit_struct_seq<'de, T, V>(
     info: &T,
     registration: &TypeRegistration,
     registry: &TypeRegistry,
+    processor: Option<&dyn ReflectDeserializerProcessor>,
 ) -> Result<DynamicStruct, V::Error>
 where
     T: StructLikeInfo + Container,
@@ -1079,6 +1413,7 @@ where
             .next_element_seed(TypedReflectDeserializer {
                 registration: info.get_field_registration(index, registry)?,
                 registry,
+                processor,
             })?
             .ok_or_else(|| Error::invalid_length(index, &len.to_string().as_str()))?;
         dynamic_struct.insert_boxed(name, value);
@@ -1110,8 +1445,13 @@ mod tests {
     use bevy_utils::HashMap;
 
     use crate as bevy_reflect;
-    use crate::serde::{TypedReflectDeserializer, UntypedReflectDeserializer};
-    use crate::{DynamicEnum, FromReflect, Reflect, ReflectDeserialize, TypeRegistry};
+    use crate::serde::{
+        ReflectDeserializerProcessor, TypedReflectDeserializer, UntypedReflectDeserializer,
+    };
+    use crate::{
+        erased_serde, DynamicEnum, FromReflect, Reflect, ReflectDeserialize, TypeRegistration,
+        TypeRegistry,
+    };
 
     #[derive(Reflect, Debug, PartialEq)]
     struct MyStruct {
@@ -1350,6 +1690,55 @@ mod tests {
         assert_eq!(expected, output);
     }
 
+    #[test]
+    fn should_deserialize_with_processor() {
+        #[derive(Reflect, Debug, PartialEq)]
+        struct Foo {
+            bar: i32,
+            baz: String,
+        }
+
+        struct DoubleI32;
+
+        impl ReflectDeserializerProcessor for DoubleI32 {
+            fn handles(&self, registration: &TypeRegistration) -> bool {
+                registration.type_id() == TypeId::of::<i32>()
+            }
+
+            fn deserialize(
+                &self,
+                _registration: &TypeRegistration,
+                deserializer: &mut dyn erased_serde::Deserializer,
+            ) -> Result<Box<dyn Reflect>, erased_serde::Error> {
+                let value = i32::deserialize(deserializer)?;
+                Ok(Box::new(value * 2))
+            }
+        }
+
+        let expected = Foo {
+            bar: 246,
+            baz: String::from("unchanged"),
+        };
+
+        let input = r#"(
+            bar: 123,
+            baz: "unchanged",
+        )"#;
+
+        let mut registry = get_registry();
+        registry.register::<Foo>();
+        let registration = registry.get(TypeId::of::<Foo>()).unwrap();
+        let reflect_deserializer =
+            TypedReflectDeserializer::with_processor(registration, &registry, &DoubleI32);
+        let mut ron_deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let dynamic_output = reflect_deserializer
+            .deserialize(&mut ron_deserializer)
+            .unwrap();
+
+        let output = <Foo as FromReflect>::from_reflect(dynamic_output.as_ref()).unwrap();
+        assert_eq!(expected, output);
+    }
+
     #[test]
     fn should_deserialize_option() {
         #[derive(Reflect, Debug, PartialEq)]
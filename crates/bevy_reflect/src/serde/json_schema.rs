@@ -0,0 +1,635 @@
+use crate::{
+    serde::{ReflectMigrations, SerializationData, VERSION_FIELD},
+    NamedField, ReflectSerialize, TypeInfo, TypeRegistration, TypeRegistry, UnnamedField,
+    VariantInfo,
+};
+use bevy_utils::HashMap;
+use serde_json::{json, Map, Value};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// The JSON Schema dialect of the exported schemas.
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// An error that occurs when exporting a JSON Schema.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum JsonSchemaError {
+    /// The type, or the type of one of its fields, is not registered.
+    #[error("the type `{0}` is not registered")]
+    Unregistered(Cow<'static, str>),
+    /// The type is a value type without [`ReflectSerialize`], which can't be serialized.
+    #[error("the type `{0}` is not serializable, it did not register `ReflectSerialize`")]
+    Unserializable(Cow<'static, str>),
+}
+
+/// Exports JSON Schemas describing the JSON values produced by
+/// [`TypedReflectSerializer`](crate::serde::TypedReflectSerializer) and
+/// [`ReflectSerializer`](crate::serde::ReflectSerializer) with `serde_json`.
+///
+/// Every type is described by a definition in `$defs`, keyed by its [type path](crate::TypePath),
+/// and referenced from the definitions of the types containing it.
+/// Fields skipped with `#[reflect(skip_serializing)]` are left out, and doc comments become
+/// `description`s when the `documentation` feature is enabled.
+///
+/// Types registering [`ReflectSerialize`] are serialized with their own [`Serialize`] implementation,
+/// which can't be inspected: primitives and strings are described, but other types accept any
+/// value unless they are given a schema with [`with_schema`](Self::with_schema).
+///
+/// ```
+/// # use bevy_reflect::{serde::JsonSchemaExporter, Reflect, TypePath, TypeRegistry};
+/// # use serde_json::json;
+/// #[derive(Reflect)]
+/// struct Player {
+///     name: String,
+///     lives: u8,
+/// }
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<Player>();
+///
+/// let schema = JsonSchemaExporter::new(&registry).typed_schema::<Player>().unwrap();
+/// let player = &schema["$defs"][Player::type_path()];
+/// assert_eq!(player["required"], json!(["name", "lives"]));
+/// assert_eq!(player["properties"]["lives"], json!({ "$ref": "#/$defs/u8" }));
+/// ```
+///
+/// [`Serialize`]: ::serde::Serialize
+pub struct JsonSchemaExporter<'a> {
+    registry: &'a TypeRegistry,
+    schemas: HashMap<TypeId, Value>,
+}
+
+impl<'a> JsonSchemaExporter<'a> {
+    /// Creates an exporter for the types of the given registry.
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self {
+            registry,
+            schemas: HashMap::default(),
+        }
+    }
+
+    /// Uses `schema` for the type with the given [`TypeId`] instead of deriving it from its
+    /// [`TypeInfo`], such as for types with a custom [`Serialize`](::serde::Serialize)
+    /// implementation.
+    pub fn with_schema(mut self, type_id: TypeId, schema: Value) -> Self {
+        self.schemas.insert(type_id, schema);
+        self
+    }
+
+    /// Returns the schema of the values of `T` serialized by
+    /// [`TypedReflectSerializer`](crate::serde::TypedReflectSerializer).
+    pub fn typed_schema<T: 'static>(&self) -> Result<Value, JsonSchemaError> {
+        self.typed_schema_by_id(TypeId::of::<T>())
+    }
+
+    /// Returns the schema of the values of the type with the given [`TypeId`] serialized by
+    /// [`TypedReflectSerializer`](crate::serde::TypedReflectSerializer).
+    pub fn typed_schema_by_id(&self, type_id: TypeId) -> Result<Value, JsonSchemaError> {
+        let mut definitions = Definitions {
+            exporter: self,
+            defs: Map::new(),
+        };
+        let reference = definitions.reference(type_id, || format!("{type_id:?}").into())?;
+        Ok(document(reference, definitions.defs))
+    }
+
+    /// Returns the schema of the values serialized by [`ReflectSerializer`](crate::serde::ReflectSerializer):
+    /// objects with a single property, whose key is the type path of a registered type and whose
+    /// value is serialized by [`TypedReflectSerializer`](crate::serde::TypedReflectSerializer).
+    ///
+    /// Registered types that can't be serialized are left out.
+    pub fn reflect_schema(&self) -> Value {
+        let mut definitions = Definitions {
+            exporter: self,
+            defs: Map::new(),
+        };
+        let mut registrations: Vec<&TypeRegistration> = self.registry.iter().collect();
+        registrations.sort_unstable_by_key(|registration| registration.type_info().type_path());
+
+        let mut variants = Vec::new();
+        for registration in registrations {
+            let type_path = registration.type_info().type_path();
+            let defs = definitions.defs.clone();
+            match definitions.reference(registration.type_id(), || type_path.into()) {
+                Ok(reference) => variants.push(json!({
+                    "type": "object",
+                    "properties": { type_path: reference },
+                    "required": [type_path],
+                    "additionalProperties": false,
+                })),
+                // Leaves out the definitions of the fields that could be serialized
+                Err(_) => definitions.defs = defs,
+            }
+        }
+        document(json!({ "oneOf": variants }), definitions.defs)
+    }
+}
+
+fn document(schema: Value, defs: Map<String, Value>) -> Value {
+    let mut document = Map::new();
+    document.insert("$schema".into(), JSON_SCHEMA_DIALECT.into());
+    if let Value::Object(schema) = schema {
+        document.extend(schema);
+    }
+    document.insert("$defs".into(), Value::Object(defs));
+    Value::Object(document)
+}
+
+/// The definitions of the types in a document, created as they are referenced.
+struct Definitions<'a, 'r> {
+    exporter: &'a JsonSchemaExporter<'r>,
+    defs: Map<String, Value>,
+}
+
+impl<'a, 'r> Definitions<'a, 'r> {
+    /// Returns a reference to the definition of the type, adding the definition if needed.
+    fn reference(
+        &mut self,
+        type_id: TypeId,
+        type_path: impl FnOnce() -> Cow<'static, str>,
+    ) -> Result<Value, JsonSchemaError> {
+        let registration = self
+            .exporter
+            .registry
+            .get(type_id)
+            .ok_or_else(|| JsonSchemaError::Unregistered(type_path()))?;
+        let type_path = registration.type_info().type_path();
+        if !self.defs.contains_key(type_path) {
+            // Inserted first so recursive types refer to it instead of being defined endlessly
+            self.defs.insert(type_path.into(), Value::Bool(true));
+            match self.definition(registration) {
+                Ok(definition) => {
+                    self.defs.insert(type_path.into(), definition);
+                }
+                Err(err) => {
+                    self.defs.remove(type_path);
+                    return Err(err);
+                }
+            }
+        }
+        Ok(json!({ "$ref": format!("#/$defs/{}", encode_pointer(type_path)) }))
+    }
+
+    fn definition(&mut self, registration: &TypeRegistration) -> Result<Value, JsonSchemaError> {
+        let type_info = registration.type_info();
+        let mut schema = if let Some(schema) = self.exporter.schemas.get(&type_info.type_id()) {
+            schema.clone()
+        } else if registration.data::<ReflectSerialize>().is_some() {
+            serialize_schema(type_info.type_id())
+        } else {
+            self.type_info_schema(registration)?
+        };
+        #[cfg(feature = "documentation")]
+        with_description(&mut schema, type_info.docs());
+        #[cfg(not(feature = "documentation"))]
+        let _ = &mut schema;
+        Ok(schema)
+    }
+
+    fn type_info_schema(
+        &mut self,
+        registration: &TypeRegistration,
+    ) -> Result<Value, JsonSchemaError> {
+        let serialization_data = registration.data::<SerializationData>();
+        let is_skipped = |index: usize| {
+            serialization_data
+                .map(|data| data.is_field_skipped(index))
+                .unwrap_or(false)
+        };
+        Ok(match registration.type_info() {
+            TypeInfo::Struct(info) => {
+                let fields = info
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| !is_skipped(*index))
+                    .map(|(_, field)| field);
+                let mut schema = self.named_fields_schema(fields)?;
+                if let Some(migrations) = registration.data::<ReflectMigrations>() {
+                    let version = json!({ "const": migrations.version() });
+                    schema["properties"][VERSION_FIELD] = version;
+                    schema["required"]
+                        .as_array_mut()
+                        .unwrap()
+                        .insert(0, VERSION_FIELD.into());
+                }
+                schema
+            }
+            TypeInfo::TupleStruct(info) => {
+                let fields = info.iter().filter(|field| !is_skipped(field.index()));
+                self.unnamed_fields_schema(fields)?
+            }
+            TypeInfo::Tuple(info) => self.unnamed_fields_schema(info.iter())?,
+            TypeInfo::List(info) => json!({
+                "type": "array",
+                "items": self.reference(info.item_type_id(), || info.item_type_path_table().path().into())?,
+            }),
+            TypeInfo::Array(info) => json!({
+                "type": "array",
+                "items": self.reference(info.item_type_id(), || info.item_type_path_table().path().into())?,
+                "minItems": info.capacity(),
+                "maxItems": info.capacity(),
+            }),
+            TypeInfo::Map(info) => {
+                let values = self.reference(info.value_type_id(), || {
+                    info.value_type_path_table().path().into()
+                })?;
+                // serde_json writes integer keys as strings
+                let keys = match serialize_schema(info.key_type_id())["type"].as_str() {
+                    Some("integer") => json!({ "pattern": "^-?[0-9]+$" }),
+                    _ => json!({ "type": "string" }),
+                };
+                json!({
+                    "type": "object",
+                    "propertyNames": keys,
+                    "additionalProperties": values,
+                })
+            }
+            TypeInfo::Set(info) => json!({
+                "type": "array",
+                "items": self.reference(info.value_type_id(), || info.value_type_path_table().path().into())?,
+                "uniqueItems": true,
+            }),
+            TypeInfo::Enum(info) => {
+                let type_path_table = info.type_path_table();
+                if type_path_table.module_path() == Some("core::option")
+                    && type_path_table.ident() == Some("Option")
+                {
+                    let some = match info.variant("Some") {
+                        Some(VariantInfo::Tuple(some)) => some.field_at(0),
+                        _ => None,
+                    };
+                    let some = some.ok_or_else(|| {
+                        JsonSchemaError::Unregistered(type_path_table.path().into())
+                    })?;
+                    let some = self.reference(some.type_id(), || some.type_path().into())?;
+                    return Ok(json!({ "oneOf": [{ "type": "null" }, some] }));
+                }
+
+                // Variants are externally tagged, as serde does by default
+                let mut variants = Vec::new();
+                for variant in info.iter() {
+                    let mut schema = match variant {
+                        VariantInfo::Unit(_) => json!({ "const": variant.name() }),
+                        VariantInfo::Tuple(variant_info) => {
+                            let fields = if variant_info.field_len() == 1 {
+                                let field = variant_info.field_at(0).unwrap();
+                                self.field_schema(field.type_id(), field.type_path(), None)?
+                            } else {
+                                self.unnamed_fields_schema(variant_info.iter())?
+                            };
+                            tagged(variant.name(), fields)
+                        }
+                        VariantInfo::Struct(variant_info) => {
+                            let fields = self.named_fields_schema(variant_info.iter())?;
+                            tagged(variant.name(), fields)
+                        }
+                    };
+                    #[cfg(feature = "documentation")]
+                    with_description(&mut schema, variant.docs());
+                    #[cfg(not(feature = "documentation"))]
+                    let _ = &mut schema;
+                    variants.push(schema);
+                }
+                json!({ "oneOf": variants })
+            }
+            TypeInfo::Value(info) => {
+                return Err(JsonSchemaError::Unserializable(info.type_path().into()))
+            }
+        })
+    }
+
+    fn named_fields_schema<'f>(
+        &mut self,
+        fields: impl Iterator<Item = &'f NamedField>,
+    ) -> Result<Value, JsonSchemaError> {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for field in fields {
+            #[cfg(feature = "documentation")]
+            let docs = field.docs();
+            #[cfg(not(feature = "documentation"))]
+            let docs = None;
+            let schema = self.field_schema(field.type_id(), field.type_path(), docs)?;
+            properties.insert(field.name().into(), schema);
+            required.push(field.name());
+        }
+        Ok(json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        }))
+    }
+
+    /// Fields without names are serialized as arrays, even if there is only one.
+    fn unnamed_fields_schema<'f>(
+        &mut self,
+        fields: impl Iterator<Item = &'f UnnamedField>,
+    ) -> Result<Value, JsonSchemaError> {
+        let mut items = Vec::new();
+        for field in fields {
+            #[cfg(feature = "documentation")]
+            let docs = field.docs();
+            #[cfg(not(feature = "documentation"))]
+            let docs = None;
+            items.push(self.field_schema(field.type_id(), field.type_path(), docs)?);
+        }
+        Ok(json!({
+            "type": "array",
+            "prefixItems": items,
+            "items": false,
+            "minItems": items.len(),
+            "maxItems": items.len(),
+        }))
+    }
+
+    fn field_schema(
+        &mut self,
+        type_id: TypeId,
+        type_path: &'static str,
+        docs: Option<&'static str>,
+    ) -> Result<Value, JsonSchemaError> {
+        let mut schema = self.reference(type_id, || type_path.into())?;
+        with_description(&mut schema, docs);
+        Ok(schema)
+    }
+}
+
+/// Returns the schema of an object with the variant name as its only key.
+fn tagged(variant: &str, schema: Value) -> Value {
+    json!({
+        "type": "object",
+        "properties": { variant: schema },
+        "required": [variant],
+        "additionalProperties": false,
+    })
+}
+
+fn with_description(schema: &mut Value, docs: Option<&str>) {
+    if let (Value::Object(schema), Some(docs)) = (schema, docs) {
+        schema.insert("description".into(), docs.trim().into());
+    }
+}
+
+/// Returns the schema of the primitive types serialized by their [`Serialize`](::serde::Serialize)
+/// implementation, or a schema accepting anything for other types.
+fn serialize_schema(type_id: TypeId) -> Value {
+    macro_rules! integer_schema {
+        ($($ty:ty),*) => {
+            $(if type_id == TypeId::of::<$ty>() {
+                return json!({ "type": "integer", "minimum": <$ty>::MIN, "maximum": <$ty>::MAX });
+            })*
+        };
+    }
+    integer_schema!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+    if type_id == TypeId::of::<u128>() || type_id == TypeId::of::<i128>() {
+        json!({ "type": "integer" })
+    } else if type_id == TypeId::of::<f32>() || type_id == TypeId::of::<f64>() {
+        json!({ "type": "number" })
+    } else if type_id == TypeId::of::<bool>() {
+        json!({ "type": "boolean" })
+    } else if type_id == TypeId::of::<char>() {
+        json!({ "type": "string", "minLength": 1, "maxLength": 1 })
+    } else if type_id == TypeId::of::<String>()
+        || type_id == TypeId::of::<&'static str>()
+        || type_id == TypeId::of::<Cow<'static, str>>()
+        || type_id == TypeId::of::<PathBuf>()
+    {
+        json!({ "type": "string" })
+    } else {
+        json!({})
+    }
+}
+
+/// Escapes a type path for use in a JSON pointer within a URI fragment.
+fn encode_pointer(type_path: &str) -> String {
+    let mut encoded = String::with_capacity(type_path.len());
+    for c in type_path.chars() {
+        match c {
+            '~' => encoded.push_str("~0"),
+            '/' => encoded.push_str("~1"),
+            'a'..='z'
+            | 'A'..='Z'
+            | '0'..='9'
+            | ':'
+            | '_'
+            | '-'
+            | '.'
+            | '('
+            | ')'
+            | ','
+            | ';'
+            | '&'
+            | '\''
+            | '*'
+            | '+'
+            | '='
+            | '!'
+            | '$'
+            | '@' => encoded.push(c),
+            c => {
+                let mut bytes = [0; 4];
+                for byte in c.encode_utf8(&mut bytes).bytes() {
+                    encoded.push_str(&format!("%{byte:02X}"));
+                }
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{serde::TypedReflectSerializer, Reflect};
+
+    #[derive(Reflect)]
+    struct Player {
+        name: String,
+        #[reflect(skip_serializing)]
+        cooldown: f32,
+        position: (f32, f32),
+        items: Vec<Item>,
+        stats: Stats,
+        slots: [Option<Item>; 2],
+    }
+
+    #[derive(Reflect)]
+    struct Stats(u8);
+
+    #[derive(Reflect)]
+    enum Item {
+        Empty,
+        Coins(u32),
+        Potion { strength: f32 },
+        Pair(u8, u8),
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Player>();
+        registry.register::<Stats>();
+        registry.register::<Item>();
+        registry.register::<(f32, f32)>();
+        registry.register::<Vec<Item>>();
+        registry.register::<Option<Item>>();
+        registry.register::<[Option<Item>; 2]>();
+        registry
+    }
+
+    #[test]
+    fn should_describe_structs_and_fields() {
+        let registry = registry();
+        let schema = JsonSchemaExporter::new(&registry)
+            .typed_schema::<Player>()
+            .unwrap();
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DIALECT);
+        assert_eq!(
+            schema["$ref"],
+            "#/$defs/bevy_reflect::serde::json_schema::tests::Player"
+        );
+
+        let defs = &schema["$defs"];
+        let player = &defs["bevy_reflect::serde::json_schema::tests::Player"];
+        assert_eq!(
+            player["required"],
+            json!(["name", "position", "items", "stats", "slots"])
+        );
+        assert!(player["properties"].get("cooldown").is_none());
+        assert_eq!(
+            player["properties"]["items"],
+            json!({ "$ref": "#/$defs/alloc::vec::Vec%3Cbevy_reflect::serde::json_schema::tests::Item%3E" })
+        );
+        assert_eq!(defs["f32"], json!({ "type": "number" }));
+        assert_eq!(defs["alloc::string::String"], json!({ "type": "string" }));
+        assert_eq!(defs["(f32, f32)"]["minItems"], 2);
+        assert_eq!(
+            defs["bevy_reflect::serde::json_schema::tests::Stats"]["prefixItems"],
+            json!([{ "$ref": "#/$defs/u8" }])
+        );
+        assert_eq!(
+            defs["[core::option::Option<bevy_reflect::serde::json_schema::tests::Item>; 2]"]
+                ["maxItems"],
+            2
+        );
+        assert_eq!(
+            defs["core::option::Option<bevy_reflect::serde::json_schema::tests::Item>"]["oneOf"][0],
+            json!({ "type": "null" })
+        );
+    }
+
+    #[test]
+    fn should_describe_enum_variants() {
+        let registry = registry();
+        let schema = JsonSchemaExporter::new(&registry)
+            .typed_schema::<Item>()
+            .unwrap();
+        let variants = &schema["$defs"]["bevy_reflect::serde::json_schema::tests::Item"]["oneOf"];
+        assert_eq!(variants[0], json!({ "const": "Empty" }));
+        assert_eq!(
+            variants[1]["properties"]["Coins"],
+            json!({ "$ref": "#/$defs/u32" })
+        );
+        assert_eq!(variants[2]["required"], json!(["Potion"]));
+        assert_eq!(
+            variants[2]["properties"]["Potion"]["required"],
+            json!(["strength"])
+        );
+        assert_eq!(variants[3]["properties"]["Pair"]["minItems"], 2);
+    }
+
+    #[test]
+    fn should_match_serialized_values() {
+        let registry = registry();
+        let player = Player {
+            name: String::from("Alice"),
+            cooldown: 1.0,
+            position: (1.0, 2.0),
+            items: vec![Item::Empty, Item::Coins(3), Item::Potion { strength: 0.5 }],
+            stats: Stats(7),
+            slots: [None, Some(Item::Pair(1, 2))],
+        };
+        let value = serde_json::to_value(TypedReflectSerializer::new(&player, &registry)).unwrap();
+        let schema = JsonSchemaExporter::new(&registry)
+            .typed_schema::<Player>()
+            .unwrap();
+        let defs = &schema["$defs"];
+        let player_schema = &defs["bevy_reflect::serde::json_schema::tests::Player"];
+
+        let properties = player_schema["properties"].as_object().unwrap();
+        let keys: Vec<&String> = value.as_object().unwrap().keys().collect();
+        assert_eq!(keys, properties.keys().collect::<Vec<_>>());
+        assert_eq!(value["stats"], json!([7]));
+        assert_eq!(value["items"][0], json!("Empty"));
+        assert_eq!(value["items"][1], json!({ "Coins": 3 }));
+        assert_eq!(value["slots"], json!([null, { "Pair": [1, 2] }]));
+    }
+
+    #[cfg(feature = "documentation")]
+    #[test]
+    fn should_include_documentation() {
+        /// A point.
+        #[derive(Reflect)]
+        struct Point {
+            /// The horizontal position.
+            x: f32,
+        }
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<Point>();
+        let schema = JsonSchemaExporter::new(&registry)
+            .typed_schema::<Point>()
+            .unwrap();
+        let point = &schema["$defs"]["bevy_reflect::serde::json_schema::tests::Point"];
+        assert_eq!(point["description"], "A point.");
+        assert_eq!(
+            point["properties"]["x"],
+            json!({ "$ref": "#/$defs/f32", "description": "The horizontal position." })
+        );
+    }
+
+    #[test]
+    fn should_export_registry_and_report_errors() {
+        #[derive(Reflect)]
+        struct Unregistered;
+
+        #[derive(Reflect)]
+        struct Invalid(Unregistered);
+
+        let mut registry = registry();
+        registry.register::<Invalid>();
+        let exporter = JsonSchemaExporter::new(&registry);
+        assert_eq!(
+            exporter.typed_schema::<Invalid>(),
+            Err(JsonSchemaError::Unregistered(
+                "bevy_reflect::serde::json_schema::tests::Unregistered".into()
+            ))
+        );
+
+        let schema = exporter.reflect_schema();
+        let variants = schema["oneOf"].as_array().unwrap();
+        assert_eq!(variants.len(), registry.iter().count() - 1);
+        let player = variants
+            .iter()
+            .find_map(|variant| {
+                variant["properties"].get("bevy_reflect::serde::json_schema::tests::Player")
+            })
+            .unwrap();
+        assert_eq!(
+            player,
+            &json!({ "$ref": "#/$defs/bevy_reflect::serde::json_schema::tests::Player" })
+        );
+
+        let exporter = JsonSchemaExporter::new(&registry)
+            .with_schema(TypeId::of::<Stats>(), json!({ "type": "integer" }));
+        let schema = exporter.typed_schema::<Stats>().unwrap();
+        assert_eq!(
+            schema["$defs"]["bevy_reflect::serde::json_schema::tests::Stats"],
+            json!({ "type": "integer" })
+        );
+    }
+}
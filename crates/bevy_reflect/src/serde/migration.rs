@@ -0,0 +1,614 @@
+use crate::serde::SerializationData;
+use crate::{FromReflect, Reflect, StructInfo, TypeRegistration};
+use bevy_utils::HashMap;
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+use thiserror::Error;
+
+/// The name of the field holding the version of a struct with [`ReflectMigrations`].
+///
+/// It is serialized before the other fields, and data without it is read as version 0. It is also
+/// read after other fields, as in formats sorting the keys of maps: the fields whose type depends
+/// on the version are then buffered until it is known, which only works for values that
+/// [`deserialize_any`](serde::Deserializer::deserialize_any) describes fully, so not for RON
+/// enums.
+pub const VERSION_FIELD: &str = "__version";
+
+/// Type data that versions the serialized layout of a struct and migrates older layouts to the
+/// current one during deserialization.
+///
+/// Each [`Migration`] turns the fields of a version into the fields of the next one, so the
+/// current version is the number of migrations. Data serialized before the type had migrations
+/// doesn't contain a version and is read as version 0.
+///
+/// The [`TypedReflectDeserializer`](crate::serde::TypedReflectDeserializer) reads the fields of an
+/// older version with the types they had in that version, and runs the migrations from that
+/// version on them, so the deserialized value has the current layout and can be converted with
+/// [`FromReflect`]. Older versions can only be read from formats serializing structs as maps,
+/// such as RON and JSON.
+///
+/// Only structs with named fields are versioned: the migrations of tuple structs, enums and other
+/// types are ignored.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{Reflect, FromReflect, TypeRegistry};
+/// # use bevy_reflect::serde::{Migration, ReflectMigrations, TypedReflectDeserializer};
+/// # use serde::de::DeserializeSeed;
+/// # use std::any::TypeId;
+/// #[derive(Reflect, Debug, PartialEq)]
+/// struct Player {
+///     health: f32,
+///     mana: u32,
+/// }
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register::<Player>();
+/// registry.register_migrations::<Player>(
+///     ReflectMigrations::new().with_migration(
+///         Migration::new()
+///             .rename_field("hp", "health")
+///             .convert_field("health", |hp: u32| hp as f32)
+///             .add_field("mana", 100u32),
+///     ),
+/// );
+///
+/// // Saved at version 0, before the migration existed.
+/// let data = "(hp: 80)";
+///
+/// let registration = registry.get(TypeId::of::<Player>()).unwrap();
+/// let mut deserializer = ron::Deserializer::from_str(data).unwrap();
+/// let value = TypedReflectDeserializer::new(registration, &registry)
+///     .deserialize(&mut deserializer)
+///     .unwrap();
+/// let player = Player::from_reflect(value.as_ref()).unwrap();
+/// assert_eq!(player, Player { health: 80.0, mana: 100 });
+/// ```
+#[derive(Clone, Default)]
+pub struct ReflectMigrations {
+    migrations: Vec<Migration>,
+}
+
+impl ReflectMigrations {
+    /// Creates the migrations of a type at version 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a migration from the current version to the next one.
+    pub fn with_migration(mut self, migration: Migration) -> Self {
+        self.migrations.push(migration);
+        self
+    }
+
+    /// Returns the current version of the type.
+    pub fn version(&self) -> u32 {
+        self.migrations.len() as u32
+    }
+
+    /// Returns the migrations, where the migration at index `n` migrates version `n` to `n + 1`.
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    /// Migrates the `fields` of a value serialized at `version` to the current version.
+    pub fn migrate(
+        &self,
+        version: u32,
+        fields: &mut Vec<(Cow<'static, str>, Box<dyn Reflect>)>,
+    ) -> Result<(), MigrationError> {
+        let migrations =
+            self.migrations
+                .get(version as usize..)
+                .ok_or(MigrationError::UnknownVersion {
+                    version,
+                    current: self.version(),
+                })?;
+        for migration in migrations {
+            migration.apply(fields)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the names and types of the fields of `info` serialized at `version`, found by
+    /// undoing the migrations from the current layout.
+    pub(super) fn serialized_fields(
+        &self,
+        info: &'static StructInfo,
+        registration: &TypeRegistration,
+        version: u32,
+    ) -> Vec<(Cow<'static, str>, SerializedField)> {
+        let serialization_data = registration.data::<SerializationData>();
+        let mut fields: Vec<_> = info
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| match serialization_data {
+                Some(data) => !data.is_field_skipped(*index),
+                None => true,
+            })
+            .map(|(_, field)| {
+                let field_type = SerializedField::Typed {
+                    type_id: field.type_id(),
+                    type_path: field.type_path(),
+                };
+                (Cow::Borrowed(field.name()), field_type)
+            })
+            .collect();
+
+        for migration in self.migrations.iter().skip(version as usize).rev() {
+            for step in migration.steps.iter().rev() {
+                let position = fields.iter().position(|(name, _)| *name == *step.target());
+                match (step, position) {
+                    (MigrationStep::Rename { from, .. }, Some(position)) => {
+                        fields[position].0 = from.clone();
+                    }
+                    (MigrationStep::Add { .. }, Some(position)) => {
+                        fields.remove(position);
+                    }
+                    (MigrationStep::Remove { name }, None) => {
+                        fields.push((name.clone(), SerializedField::Ignored));
+                    }
+                    (
+                        MigrationStep::Convert {
+                            type_id, type_path, ..
+                        },
+                        Some(position),
+                    ) => {
+                        fields[position].1 = SerializedField::Typed {
+                            type_id: *type_id,
+                            type_path,
+                        };
+                    }
+                    _ => {}
+                }
+            }
+        }
+        fields
+    }
+
+    /// Returns the names of the serialized fields of `info` at the current version, which are
+    /// passed to [`Deserializer::deserialize_struct`](serde::Deserializer::deserialize_struct).
+    /// They are leaked once per type.
+    pub(super) fn field_names(info: &'static StructInfo) -> &'static [&'static str] {
+        static FIELD_NAMES: OnceLock<RwLock<HashMap<TypeId, &'static [&'static str]>>> =
+            OnceLock::new();
+        let field_names = FIELD_NAMES.get_or_init(Default::default);
+        if let Some(names) = field_names
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&info.type_id())
+        {
+            return names;
+        }
+        field_names
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(info.type_id())
+            .or_insert_with(|| {
+                let names =
+                    std::iter::once(VERSION_FIELD).chain(info.field_names().iter().copied());
+                Vec::leak(names.collect())
+            })
+    }
+}
+
+impl fmt::Debug for ReflectMigrations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReflectMigrations")
+            .field("version", &self.version())
+            .finish()
+    }
+}
+
+/// How a field of an older version is deserialized.
+#[derive(PartialEq)]
+pub(super) enum SerializedField {
+    /// The field is deserialized as the type with this id.
+    Typed {
+        type_id: TypeId,
+        type_path: &'static str,
+    },
+    /// The field is removed by a migration, its value is ignored.
+    Ignored,
+}
+
+/// The changes between two consecutive versions of a struct, see [`ReflectMigrations`].
+///
+/// The steps are applied in the order they are added.
+#[derive(Clone, Default)]
+pub struct Migration {
+    steps: Vec<MigrationStep>,
+}
+
+impl Migration {
+    /// Creates a migration without any step.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renames the field `from` to `to`.
+    pub fn rename_field(
+        mut self,
+        from: impl Into<Cow<'static, str>>,
+        to: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.steps.push(MigrationStep::Rename {
+            from: from.into(),
+            to: to.into(),
+        });
+        self
+    }
+
+    /// Adds the field `name` with the `default` value.
+    pub fn add_field<T: Reflect>(mut self, name: impl Into<Cow<'static, str>>, default: T) -> Self {
+        self.steps.push(MigrationStep::Add {
+            name: name.into(),
+            default: Arc::new(default),
+        });
+        self
+    }
+
+    /// Removes the field `name`.
+    pub fn remove_field(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.steps.push(MigrationStep::Remove { name: name.into() });
+        self
+    }
+
+    /// Converts the field `name` from the type `Old` to the type `New`.
+    ///
+    /// `Old` must be registered in the [`TypeRegistry`](crate::TypeRegistry) used to deserialize
+    /// older versions.
+    pub fn convert_field<Old, New>(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        convert: impl Fn(Old) -> New + Send + Sync + 'static,
+    ) -> Self
+    where
+        Old: FromReflect,
+        New: Reflect,
+    {
+        self.steps.push(MigrationStep::Convert {
+            name: name.into(),
+            type_id: TypeId::of::<Old>(),
+            type_path: std::any::type_name::<Old>(),
+            convert: Arc::new(move |value| {
+                let value = Old::from_reflect(value)?;
+                Some(Box::new(convert(value)))
+            }),
+        });
+        self
+    }
+
+    /// Applies the steps of this migration to the `fields` of a value.
+    pub fn apply(
+        &self,
+        fields: &mut Vec<(Cow<'static, str>, Box<dyn Reflect>)>,
+    ) -> Result<(), MigrationError> {
+        for step in &self.steps {
+            let position = fields.iter().position(|(name, _)| *name == *step.field());
+            match (step, position) {
+                (MigrationStep::Rename { to, .. }, Some(position)) => {
+                    fields[position].0 = to.clone();
+                }
+                (MigrationStep::Add { name, default }, None) => {
+                    fields.push((name.clone(), default.clone_value()));
+                }
+                (MigrationStep::Remove { .. }, Some(position)) => {
+                    fields.remove(position);
+                }
+                (
+                    MigrationStep::Convert {
+                        name,
+                        type_path,
+                        convert,
+                        ..
+                    },
+                    Some(position),
+                ) => {
+                    fields[position].1 = convert(fields[position].1.as_ref()).ok_or_else(|| {
+                        MigrationError::Conversion {
+                            field: name.clone(),
+                            type_path,
+                        }
+                    })?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.steps).finish()
+    }
+}
+
+/// A step of a [`Migration`].
+#[derive(Clone)]
+enum MigrationStep {
+    Rename {
+        from: Cow<'static, str>,
+        to: Cow<'static, str>,
+    },
+    Add {
+        name: Cow<'static, str>,
+        default: Arc<dyn Reflect>,
+    },
+    Remove {
+        name: Cow<'static, str>,
+    },
+    Convert {
+        name: Cow<'static, str>,
+        type_id: TypeId,
+        type_path: &'static str,
+        #[allow(clippy::type_complexity)]
+        convert: Arc<dyn Fn(&dyn Reflect) -> Option<Box<dyn Reflect>> + Send + Sync>,
+    },
+}
+
+impl MigrationStep {
+    /// Returns the name of the field changed by this step, in the layout it is applied to.
+    fn field(&self) -> &str {
+        match self {
+            Self::Rename { from, .. } => from,
+            Self::Add { name, .. } | Self::Remove { name } | Self::Convert { name, .. } => name,
+        }
+    }
+
+    /// Returns the name of the field changed by this step, in the layout it produces.
+    fn target(&self) -> &str {
+        match self {
+            Self::Rename { to, .. } => to,
+            _ => self.field(),
+        }
+    }
+}
+
+impl fmt::Debug for MigrationStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rename { from, to } => write!(f, "Rename({from:?} -> {to:?})"),
+            Self::Add { name, .. } => write!(f, "Add({name:?})"),
+            Self::Remove { name } => write!(f, "Remove({name:?})"),
+            Self::Convert {
+                name, type_path, ..
+            } => write!(f, "Convert({name:?} from {type_path})"),
+        }
+    }
+}
+
+/// An error that occurs when migrating a value with [`ReflectMigrations`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MigrationError {
+    /// The value was serialized by a newer version of the type.
+    #[error("unknown version {version}, the current version is {current}")]
+    UnknownVersion {
+        /// The version of the value.
+        version: u32,
+        /// The current version of the type.
+        current: u32,
+    },
+    /// A field could not be converted from the type it had in an older version.
+    #[error("cannot convert the field `{field}` from `{type_path}`")]
+    Conversion {
+        /// The name of the field.
+        field: Cow<'static, str>,
+        /// The type of the field in the older version.
+        type_path: &'static str,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+    use crate::TypeRegistry;
+    use bincode::Options;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Player {
+        name: String,
+        health: f32,
+        mana: u32,
+        #[reflect(skip_serializing)]
+        cache: u32,
+    }
+
+    fn migrations() -> ReflectMigrations {
+        ReflectMigrations::new()
+            .with_migration(
+                // Version 0: `(name: String, hp: u32, level: u32)`.
+                Migration::new()
+                    .rename_field("hp", "health")
+                    .remove_field("level"),
+            )
+            .with_migration(
+                // Version 1: `(name: String, health: u32)`.
+                Migration::new()
+                    .convert_field("health", |health: u32| health as f32)
+                    .add_field("mana", 100u32),
+            )
+    }
+
+    fn registry(migrations: ReflectMigrations) -> TypeRegistry {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Player>();
+        registry.register_migrations::<Player>(migrations);
+        registry
+    }
+
+    fn player() -> Player {
+        Player {
+            name: String::from("Ferris"),
+            health: 80.0,
+            mana: 100,
+            cache: 0,
+        }
+    }
+
+    fn deserialize_ron(registry: &TypeRegistry, data: &str) -> Result<Player, ron::Error> {
+        let registration = registry.get(TypeId::of::<Player>()).unwrap();
+        let mut deserializer = ron::Deserializer::from_str(data).unwrap();
+        let value =
+            TypedReflectDeserializer::new(registration, registry).deserialize(&mut deserializer)?;
+        Ok(Player::from_reflect(value.as_ref()).unwrap())
+    }
+
+    #[test]
+    fn should_migrate_older_versions() {
+        let registry = registry(migrations());
+
+        let version_0 = r#"(name: "Ferris", level: 3, hp: 80)"#;
+        assert_eq!(deserialize_ron(&registry, version_0).unwrap(), player());
+
+        let version_1 = r#"(__version: 1, name: "Ferris", health: 80)"#;
+        assert_eq!(deserialize_ron(&registry, version_1).unwrap(), player());
+
+        let version_2 = r#"(__version: 2, name: "Ferris", health: 80.0, mana: 100)"#;
+        assert_eq!(deserialize_ron(&registry, version_2).unwrap(), player());
+    }
+
+    #[test]
+    fn should_migrate_from_json() {
+        let registry = registry(migrations());
+        let registration = registry.get(TypeId::of::<Player>()).unwrap();
+
+        let data = r#"{"name": "Ferris", "hp": 80, "level": {"current": 3}}"#;
+        let mut deserializer = serde_json::Deserializer::from_str(data);
+        let value = TypedReflectDeserializer::new(registration, &registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(Player::from_reflect(value.as_ref()).unwrap(), player());
+    }
+
+    #[test]
+    fn should_read_version_after_other_fields() {
+        #[derive(Reflect, Debug, PartialEq)]
+        enum Class {
+            Warrior,
+            Mage { spells: u32 },
+        }
+
+        #[derive(Reflect, Debug, PartialEq)]
+        struct Hero {
+            name: String,
+            class: Class,
+            level: Option<u32>,
+        }
+
+        #[derive(Reflect, Debug, PartialEq)]
+        struct Sidekick {
+            level: Option<u32>,
+        }
+
+        let mut registry = registry(migrations());
+        registry.register::<Hero>();
+        registry.register::<Sidekick>();
+        registry.register::<Class>();
+        registry.register::<Option<u32>>();
+        let level_migrations =
+            ReflectMigrations::new().with_migration(Migration::new().rename_field("lvl", "level"));
+        registry.register_migrations::<Hero>(level_migrations.clone());
+        registry.register_migrations::<Sidekick>(level_migrations);
+
+        // Maps of `serde_json::Value` sort their keys.
+        let data = r#"{"health": 80, "name": "Ferris", "__version": 1}"#;
+        let registration = registry.get(TypeId::of::<Player>()).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(data);
+        let value = TypedReflectDeserializer::new(registration, &registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(Player::from_reflect(value.as_ref()).unwrap(), player());
+
+        let hero = Hero {
+            name: String::from("Ferris"),
+            class: Class::Mage { spells: 3 },
+            level: Some(2),
+        };
+        let registration = registry.get(TypeId::of::<Hero>()).unwrap();
+        let data = r#"{"class": {"Mage": {"spells": 3}}, "lvl": 2, "name": "Ferris"}"#;
+        let mut deserializer = serde_json::Deserializer::from_str(data);
+        let value = TypedReflectDeserializer::new(registration, &registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(Hero::from_reflect(value.as_ref()).unwrap(), hero);
+
+        let data = r#"(class: Mage(spells: 3), level: Some(2), name: "Ferris", __version: 1)"#;
+        let mut deserializer = ron::Deserializer::from_str(data).unwrap();
+        let value = TypedReflectDeserializer::new(registration, &registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(Hero::from_reflect(value.as_ref()).unwrap(), hero);
+
+        // Types sharing cloned migrations are deserialized with their own field names.
+        let sidekick = Sidekick { level: Some(1) };
+        for value in [&hero as &dyn Reflect, &sidekick] {
+            let bytes = bincode::serialize(&TypedReflectSerializer::new(value, &registry)).unwrap();
+            let registration = registry.get(value.type_id()).unwrap();
+            let deserialized = bincode::DefaultOptions::new()
+                .with_fixint_encoding()
+                .deserialize_seed(
+                    TypedReflectDeserializer::new(registration, &registry),
+                    &bytes,
+                )
+                .unwrap();
+            assert!(value.reflect_partial_eq(deserialized.as_ref()).unwrap());
+        }
+    }
+
+    #[test]
+    fn should_serialize_version() {
+        let registry = registry(migrations());
+        let registration = registry.get(TypeId::of::<Player>()).unwrap();
+        let player = player();
+        let serializer = TypedReflectSerializer::new(&player, &registry);
+
+        let ron = ron::to_string(&serializer).unwrap();
+        assert_eq!(ron, r#"(__version:2,name:"Ferris",health:80.0,mana:100)"#);
+        assert_eq!(deserialize_ron(&registry, &ron).unwrap(), player);
+
+        let bytes = bincode::serialize(&serializer).unwrap();
+        let value = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(
+                TypedReflectDeserializer::new(registration, &registry),
+                &bytes,
+            )
+            .unwrap();
+        assert_eq!(Player::from_reflect(value.as_ref()).unwrap(), player);
+    }
+
+    #[test]
+    fn should_fail_to_migrate_invalid_data() {
+        let older = registry(ReflectMigrations::new().with_migration(Migration::new()));
+        let registry = registry(migrations());
+
+        let newer = r#"(__version: 3, name: "Ferris", health: 80.0, mana: 100)"#;
+        assert!(deserialize_ron(&registry, newer).is_err());
+
+        let unknown_field = r#"(name: "Ferris", health: 80)"#;
+        let error = deserialize_ron(&registry, unknown_field).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("unknown field `health` in version 0"));
+
+        // Structs serialized as sequences can only be read at the current version.
+        let player = player();
+        let bytes = bincode::serialize(&TypedReflectSerializer::new(&player, &older)).unwrap();
+        let registration = registry.get(TypeId::of::<Player>()).unwrap();
+        let result = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(
+                TypedReflectDeserializer::new(registration, &registry),
+                &bytes,
+            );
+        assert!(result.is_err());
+    }
+}
@@ -1,8 +1,15 @@
+mod content;
 mod de;
+#[cfg(feature = "json_schema")]
+mod json_schema;
+mod migration;
 mod ser;
 mod type_data;
 
 pub use de::*;
+#[cfg(feature = "json_schema")]
+pub use json_schema::*;
+pub use migration::*;
 pub use ser::*;
 pub use type_data::*;
 
@@ -181,4 +188,38 @@ mod tests {
 
         assert!(expected.reflect_partial_eq(&result).unwrap());
     }
+
+    #[test]
+    fn should_roundtrip_sets() {
+        #[derive(Reflect, Debug, PartialEq)]
+        struct TestStruct {
+            tags: bevy_utils::HashSet<String>,
+            ids: std::collections::BTreeSet<u32>,
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<TestStruct>();
+        registry.register::<bevy_utils::HashSet<String>>();
+        registry.register::<std::collections::BTreeSet<u32>>();
+
+        let value = TestStruct {
+            tags: bevy_utils::HashSet::from([String::from("player")]),
+            ids: std::collections::BTreeSet::from([3, 1, 2]),
+        };
+
+        let serializer = ReflectSerializer::new(&value, &registry);
+        let expected =
+            r#"{"bevy_reflect::serde::tests::TestStruct":(tags:["player"],ids:[1,2,3])}"#;
+        let result = ron::ser::to_string(&serializer).unwrap();
+        assert_eq!(expected, result);
+
+        let mut deserializer = ron::de::Deserializer::from_str(&result).unwrap();
+        let reflect_deserializer = UntypedReflectDeserializer::new(&registry);
+        let deserialized = reflect_deserializer.deserialize(&mut deserializer).unwrap();
+
+        assert_eq!(
+            value,
+            TestStruct::from_reflect(deserialized.as_ref()).unwrap()
+        );
+    }
 }
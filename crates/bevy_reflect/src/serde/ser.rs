@@ -1,5 +1,5 @@
 use crate::{
-    Array, Enum, List, Map, Reflect, ReflectRef, ReflectSerialize, Struct, Tuple, TupleStruct,
+    Array, Enum, List, Map, Reflect, ReflectRef, ReflectSerialize, Set, Struct, Tuple, TupleStruct,
     TypeInfo, TypeRegistry, VariantInfo, VariantType,
 };
 use serde::ser::{
@@ -11,7 +11,7 @@ use serde::{
     Serialize,
 };
 
-use super::SerializationData;
+use super::{ReflectMigrations, SerializationData, VERSION_FIELD};
 
 pub enum Serializable<'a> {
     Owned(Box<dyn erased_serde::Serialize + 'a>),
@@ -145,6 +145,11 @@ impl<'a> Serialize for TypedReflectSerializer<'a> {
                 registry: self.registry,
             }
             .serialize(serializer),
+            ReflectRef::Set(value) => SetSerializer {
+                set: value,
+                registry: self.registry,
+            }
+            .serialize(serializer),
             ReflectRef::Enum(value) => EnumSerializer {
                 enum_value: value,
                 registry: self.registry,
@@ -200,16 +205,21 @@ impl<'a> Serialize for StructSerializer<'a> {
             }
         };
 
-        let serialization_data = self
-            .registry
-            .get(type_info.type_id())
-            .and_then(|registration| registration.data::<SerializationData>());
+        let registration = self.registry.get(type_info.type_id());
+        let serialization_data =
+            registration.and_then(|registration| registration.data::<SerializationData>());
+        let migrations =
+            registration.and_then(|registration| registration.data::<ReflectMigrations>());
         let ignored_len = serialization_data.map(|data| data.len()).unwrap_or(0);
         let mut state = serializer.serialize_struct(
             struct_info.type_path_table().ident().unwrap(),
-            self.struct_value.field_len() - ignored_len,
+            self.struct_value.field_len() - ignored_len + usize::from(migrations.is_some()),
         )?;
 
+        if let Some(migrations) = migrations {
+            state.serialize_field(VERSION_FIELD, &migrations.version())?;
+        }
+
         for (index, value) in self.struct_value.iter_fields().enumerate() {
             if serialization_data
                 .map(|data| data.is_field_skipped(index))
@@ -425,6 +435,24 @@ impl<'a> Serialize for MapSerializer<'a> {
     }
 }
 
+pub struct SetSerializer<'a> {
+    pub set: &'a dyn Set,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for SetSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.set.len()))?;
+        for value in self.set.iter() {
+            state.serialize_element(&TypedReflectSerializer::new(value, self.registry))?;
+        }
+        state.end()
+    }
+}
+
 pub struct ListSerializer<'a> {
     pub list: &'a dyn List,
     pub registry: &'a TypeRegistry,
@@ -1,3 +1,4 @@
+use crate::attributes::{impl_custom_attribute_methods, CustomAttributes};
 use crate::{
     self as bevy_reflect, NamedField, Reflect, ReflectKind, ReflectMut, ReflectOwned, ReflectRef,
     TypeInfo, TypePath, TypePathTable,
@@ -5,6 +6,7 @@ use crate::{
 use bevy_reflect_derive::impl_type_path;
 use bevy_utils::HashMap;
 use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
 use std::{
     any::{Any, TypeId},
     borrow::Cow,
@@ -81,6 +83,7 @@ pub struct StructInfo {
     fields: Box<[NamedField]>,
     field_names: Box<[&'static str]>,
     field_indices: HashMap<&'static str, usize>,
+    custom_attributes: Arc<CustomAttributes>,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -107,6 +110,7 @@ impl StructInfo {
             fields: fields.to_vec().into_boxed_slice(),
             field_names,
             field_indices,
+            custom_attributes: Arc::new(CustomAttributes::default()),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -118,6 +122,8 @@ impl StructInfo {
         Self { docs, ..self }
     }
 
+    impl_custom_attribute_methods!("struct");
+
     /// A slice containing the names of all fields in order.
     pub fn field_names(&self) -> &[&'static str] {
         &self.field_names
@@ -1,9 +1,11 @@
+use crate::attributes::{impl_custom_attribute_methods, CustomAttributes};
 use crate::{
-    ArrayInfo, EnumInfo, ListInfo, MapInfo, Reflect, StructInfo, TupleInfo, TupleStructInfo,
-    TypePath, TypePathTable,
+    ArrayInfo, EnumInfo, ListInfo, MapInfo, Reflect, SetInfo, StructInfo, TupleInfo,
+    TupleStructInfo, TypePath, TypePathTable,
 };
 use std::any::{Any, TypeId};
 use std::fmt::Debug;
+use std::sync::Arc;
 
 /// A static accessor to compile-time type information.
 ///
@@ -106,6 +108,7 @@ pub enum TypeInfo {
     List(ListInfo),
     Array(ArrayInfo),
     Map(MapInfo),
+    Set(SetInfo),
     Enum(EnumInfo),
     Value(ValueInfo),
 }
@@ -120,6 +123,7 @@ impl TypeInfo {
             Self::List(info) => info.type_id(),
             Self::Array(info) => info.type_id(),
             Self::Map(info) => info.type_id(),
+            Self::Set(info) => info.type_id(),
             Self::Enum(info) => info.type_id(),
             Self::Value(info) => info.type_id(),
         }
@@ -136,6 +140,7 @@ impl TypeInfo {
             Self::List(info) => info.type_path_table(),
             Self::Array(info) => info.type_path_table(),
             Self::Map(info) => info.type_path_table(),
+            Self::Set(info) => info.type_path_table(),
             Self::Enum(info) => info.type_path_table(),
             Self::Value(info) => info.type_path_table(),
         }
@@ -166,6 +171,7 @@ impl TypeInfo {
             Self::List(info) => info.docs(),
             Self::Array(info) => info.docs(),
             Self::Map(info) => info.docs(),
+            Self::Set(info) => info.docs(),
             Self::Enum(info) => info.docs(),
             Self::Value(info) => info.docs(),
         }
@@ -184,6 +190,7 @@ impl TypeInfo {
 pub struct ValueInfo {
     type_path: TypePathTable,
     type_id: TypeId,
+    custom_attributes: Arc<CustomAttributes>,
     #[cfg(feature = "documentation")]
     docs: Option<&'static str>,
 }
@@ -193,6 +200,7 @@ impl ValueInfo {
         Self {
             type_path: TypePathTable::of::<T>(),
             type_id: TypeId::of::<T>(),
+            custom_attributes: Arc::new(CustomAttributes::default()),
             #[cfg(feature = "documentation")]
             docs: None,
         }
@@ -204,6 +212,8 @@ impl ValueInfo {
         Self { docs: doc, ..self }
     }
 
+    impl_custom_attribute_methods!("value");
+
     /// A representation of the type path of the value.
     ///
     /// Provides dynamic access to all methods on [`TypePath`].
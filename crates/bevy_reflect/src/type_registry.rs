@@ -1,10 +1,15 @@
-use crate::{serde::Serializable, Reflect, TypeInfo, TypePath, Typed};
+use crate::{
+    func::{DynamicFunction, FunctionRegistrationError, IntoFunction, ReflectMethods},
+    serde::{ReflectMigrations, Serializable},
+    Reflect, TypeInfo, TypePath, Typed,
+};
 use bevy_ptr::{Ptr, PtrMut};
 use bevy_utils::{HashMap, HashSet, TypeIdMap};
 use downcast_rs::{impl_downcast, Downcast};
 use serde::Deserialize;
 use std::{
     any::TypeId,
+    borrow::Cow,
     fmt::Debug,
     sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
@@ -26,6 +31,7 @@ pub struct TypeRegistry {
     short_path_to_id: HashMap<&'static str, TypeId>,
     type_path_to_id: HashMap<&'static str, TypeId>,
     ambiguous_names: HashSet<&'static str>,
+    functions: HashMap<Cow<'static, str>, DynamicFunction>,
 }
 
 // TODO:  remove this wrapper once we migrate to Atelier Assets and the Scene AssetLoader doesn't
@@ -74,6 +80,7 @@ impl TypeRegistry {
             short_path_to_id: Default::default(),
             type_path_to_id: Default::default(),
             ambiguous_names: Default::default(),
+            functions: Default::default(),
         }
     }
 
@@ -135,6 +142,58 @@ impl TypeRegistry {
             .insert(registration.type_id(), registration);
     }
 
+    /// Removes the registration of the type with the given [`TypeId`], returning it if it was
+    /// registered.
+    ///
+    /// This is mostly useful before unloading code that registered types, such as a dynamically
+    /// loaded library, since registrations refer to data owned by that code.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::any::TypeId;
+    /// # use bevy_reflect::{Reflect, TypeRegistry};
+    /// #[derive(Reflect)]
+    /// struct Foo;
+    ///
+    /// let mut type_registry = TypeRegistry::default();
+    /// type_registry.register::<Foo>();
+    /// assert!(type_registry.remove(TypeId::of::<Foo>()).is_some());
+    /// assert!(type_registry.get_with_short_type_path("Foo").is_none());
+    /// ```
+    pub fn remove(&mut self, type_id: TypeId) -> Option<TypeRegistration> {
+        let registration = self.registrations.remove(&type_id)?;
+        let type_path_table = registration.type_info().type_path_table();
+        self.type_path_to_id.remove(type_path_table.path());
+
+        let short_name = type_path_table.short_path();
+        if self.short_path_to_id.get(short_name) == Some(&type_id) {
+            self.short_path_to_id.remove(short_name);
+        } else if self.ambiguous_names.remove(short_name) {
+            // the name may now refer to a single type, and must not borrow from the removed one
+            let remaining: Vec<_> = self
+                .registrations
+                .values()
+                .map(|registration| {
+                    (
+                        registration.type_info().type_path_table().short_path(),
+                        registration.type_id(),
+                    )
+                })
+                .filter(|(name, _)| *name == short_name)
+                .collect();
+            match remaining.as_slice() {
+                [] => {}
+                [(name, type_id)] => {
+                    self.short_path_to_id.insert(name, *type_id);
+                }
+                [(name, _), ..] => {
+                    self.ambiguous_names.insert(name);
+                }
+            }
+        }
+        Some(registration)
+    }
+
     /// Registers the type data `D` for type `T`.
     ///
     /// Most of the time [`TypeRegistry::register`] can be used instead to register a type you derived [`Reflect`] for.
@@ -162,6 +221,81 @@ impl TypeRegistry {
         data.insert(D::from_type());
     }
 
+    /// Inserts the [`ReflectMigrations`] of the type `T`, which versions its serialized layout.
+    /// Only structs with named fields are versioned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is not registered.
+    pub fn register_migrations<T: Reflect + TypePath>(&mut self, migrations: ReflectMigrations) {
+        let data = self.get_mut(TypeId::of::<T>()).unwrap_or_else(|| {
+            panic!(
+                "attempted to call `TypeRegistry::register_migrations` for type `{T}` without registering `{T}` first",
+                T = T::type_path(),
+            )
+        });
+        data.insert(migrations);
+    }
+
+    /// Registers the given `function` under `name`, see the [`func`](crate::func) module.
+    ///
+    /// Returns an error if a function with the same name is already registered.
+    pub fn register_function<F, Marker>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        function: F,
+    ) -> Result<(), FunctionRegistrationError>
+    where
+        F: IntoFunction<Marker>,
+    {
+        let name = name.into();
+        if self.functions.contains_key(&name) {
+            return Err(FunctionRegistrationError::DuplicateName(name));
+        }
+        let function = function.into_function().with_name(name.clone());
+        self.functions.insert(name, function);
+        Ok(())
+    }
+
+    /// Returns the function registered under `name` with [`TypeRegistry::register_function`].
+    pub fn get_function(&self, name: &str) -> Option<&DynamicFunction> {
+        self.functions.get(name)
+    }
+
+    /// Returns an iterator over all functions registered with [`TypeRegistry::register_function`].
+    pub fn functions(&self) -> impl Iterator<Item = &DynamicFunction> {
+        self.functions.values()
+    }
+
+    /// Registers the given `method` under `name` for the type `T`, in its [`ReflectMethods`] type data.
+    ///
+    /// The receiver is the first parameter of the method, such as `&self` for `T::method`.
+    /// Returns an error if `T` is not registered, or already has a method with the same name.
+    pub fn register_method<T, F, Marker>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        method: F,
+    ) -> Result<(), FunctionRegistrationError>
+    where
+        T: TypePath,
+        F: IntoFunction<Marker>,
+    {
+        let Some(registration) = self.get_mut(TypeId::of::<T>()) else {
+            return Err(FunctionRegistrationError::MissingRegistration(
+                T::type_path(),
+            ));
+        };
+        let name = name.into();
+        let method = method.into_function().with_name(name.clone());
+        if registration.data::<ReflectMethods>().is_none() {
+            registration.insert(ReflectMethods::default());
+        }
+        registration
+            .data_mut::<ReflectMethods>()
+            .unwrap()
+            .insert(name, method)
+    }
+
     /// Returns a reference to the [`TypeRegistration`] of the type with the
     /// given [`TypeId`].
     ///
@@ -630,6 +764,43 @@ mod test {
     use crate as bevy_reflect;
     use crate::Reflect;
 
+    #[test]
+    fn remove_restores_unambiguous_short_path() {
+        mod foo {
+            use crate as bevy_reflect;
+            use crate::Reflect;
+
+            #[derive(Reflect)]
+            pub struct MyType;
+        }
+        mod bar {
+            use crate as bevy_reflect;
+            use crate::Reflect;
+
+            #[derive(Reflect)]
+            pub struct MyType;
+        }
+
+        let mut registry = crate::TypeRegistry::empty();
+        registry.register::<foo::MyType>();
+        registry.register::<bar::MyType>();
+        assert!(registry.is_ambiguous("MyType"));
+
+        assert!(registry
+            .remove(std::any::TypeId::of::<foo::MyType>())
+            .is_some());
+        assert!(!registry.is_ambiguous("MyType"));
+        assert_eq!(
+            registry
+                .get_with_short_type_path("MyType")
+                .map(|registration| registration.type_id()),
+            Some(std::any::TypeId::of::<bar::MyType>())
+        );
+        assert!(registry
+            .remove(std::any::TypeId::of::<foo::MyType>())
+            .is_none());
+    }
+
     #[test]
     fn test_reflect_from_ptr() {
         #[derive(Reflect)]
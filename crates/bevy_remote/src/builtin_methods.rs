@@ -0,0 +1,377 @@
+//! The methods the [`RemotePlugin`](crate::RemotePlugin) provides out of the box.
+
+use crate::{error_codes, RemoteError, RemoteMethods};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    world::{EntityRef, World},
+};
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    Reflect, TypeRegistration, TypeRegistry,
+};
+use serde::{de::DeserializeOwned, de::DeserializeSeed, Deserialize};
+use serde_json::{json, Map, Value};
+
+/// The method listing all entities.
+pub const LIST_ENTITIES_METHOD: &str = "bevy/list_entities";
+/// The method listing the reflected components of an entity, or all registered components.
+pub const LIST_COMPONENTS_METHOD: &str = "bevy/list_components";
+/// The method getting components of an entity.
+pub const GET_METHOD: &str = "bevy/get";
+/// The method inserting components into an entity.
+pub const INSERT_METHOD: &str = "bevy/insert";
+/// The method removing components from an entity.
+pub const REMOVE_METHOD: &str = "bevy/remove";
+/// The method spawning an entity.
+pub const SPAWN_METHOD: &str = "bevy/spawn";
+/// The method despawning an entity.
+pub const DESPAWN_METHOD: &str = "bevy/despawn";
+/// The method querying the entities with a set of components.
+pub const QUERY_METHOD: &str = "bevy/query";
+/// The method listing the reflected resources in the world.
+pub const LIST_RESOURCES_METHOD: &str = "bevy/list_resources";
+/// The method getting a resource.
+pub const GET_RESOURCE_METHOD: &str = "bevy/get_resource";
+/// The method inserting a resource.
+pub const INSERT_RESOURCE_METHOD: &str = "bevy/insert_resource";
+/// The method removing a resource.
+pub const REMOVE_RESOURCE_METHOD: &str = "bevy/remove_resource";
+
+pub(crate) fn register(methods: &mut RemoteMethods) {
+    methods.insert(LIST_ENTITIES_METHOD, list_entities);
+    methods.insert(LIST_COMPONENTS_METHOD, list_components);
+    methods.insert(GET_METHOD, get);
+    methods.insert(INSERT_METHOD, insert);
+    methods.insert(REMOVE_METHOD, remove);
+    methods.insert(SPAWN_METHOD, spawn);
+    methods.insert(DESPAWN_METHOD, despawn);
+    methods.insert(QUERY_METHOD, query);
+    methods.insert(LIST_RESOURCES_METHOD, list_resources);
+    methods.insert(GET_RESOURCE_METHOD, get_resource);
+    methods.insert(INSERT_RESOURCE_METHOD, insert_resource);
+    methods.insert(REMOVE_RESOURCE_METHOD, remove_resource);
+}
+
+#[derive(Deserialize, Default)]
+struct ListComponentsParams {
+    entity: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ComponentsParams {
+    entity: u64,
+    components: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct InsertParams {
+    entity: u64,
+    components: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct SpawnParams {
+    components: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct EntityParams {
+    entity: u64,
+}
+
+#[derive(Deserialize)]
+struct QueryParams {
+    components: Vec<String>,
+    #[serde(default)]
+    with: Vec<String>,
+    #[serde(default)]
+    without: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ResourceParams {
+    resource: String,
+}
+
+#[derive(Deserialize)]
+struct InsertResourceParams {
+    resource: String,
+    value: Value,
+}
+
+/// Returns the bits of every entity, see [`Entity::to_bits`].
+fn list_entities(_params: Option<Value>, world: &mut World) -> Result<Value, RemoteError> {
+    let entities: Vec<u64> = world.iter_entities().map(|e| e.id().to_bits()).collect();
+    Ok(json!(entities))
+}
+
+/// Returns the type paths of the reflected components of `entity`, or of all registered components
+/// if no entity is given.
+fn list_components(params: Option<Value>, world: &mut World) -> Result<Value, RemoteError> {
+    let ListComponentsParams { entity } = match params {
+        Some(params) => parse(Some(params))?,
+        None => ListComponentsParams::default(),
+    };
+    let entity = entity.map(|entity| get_entity(world, entity)).transpose()?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let mut components: Vec<&str> = registry
+        .iter()
+        .filter(
+            |registration| match (registration.data::<ReflectComponent>(), entity) {
+                (Some(reflect_component), Some(entity)) => reflect_component.contains(entity),
+                (reflect_component, None) => reflect_component.is_some(),
+                (None, Some(_)) => false,
+            },
+        )
+        .map(|registration| registration.type_info().type_path())
+        .collect();
+    components.sort_unstable();
+    Ok(json!(components))
+}
+
+/// Returns the given components of an entity, by type path.
+fn get(params: Option<Value>, world: &mut World) -> Result<Value, RemoteError> {
+    let ComponentsParams { entity, components } = parse(params)?;
+    let entity = get_entity(world, entity)?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    serialize_components(entity, &components, &registry).map(Value::Object)
+}
+
+/// Inserts the given components into an entity, replacing existing ones.
+fn insert(params: Option<Value>, world: &mut World) -> Result<Value, RemoteError> {
+    let InsertParams { entity, components } = parse(params)?;
+    let entity = get_entity(world, entity)?.id();
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let components = deserialize_components(components, &registry)?;
+    let mut entity = world.entity_mut(entity);
+    for (reflect_component, value) in components {
+        reflect_component.insert(&mut entity, value.as_ref(), &registry);
+    }
+    Ok(Value::Null)
+}
+
+/// Removes the given components from an entity.
+fn remove(params: Option<Value>, world: &mut World) -> Result<Value, RemoteError> {
+    let ComponentsParams { entity, components } = parse(params)?;
+    let entity = get_entity(world, entity)?.id();
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let components = components
+        .iter()
+        .map(|path| get_component(path, &registry).map(|(_, c)| c.clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut entity = world.entity_mut(entity);
+    for reflect_component in components {
+        reflect_component.remove(&mut entity);
+    }
+    Ok(Value::Null)
+}
+
+/// Spawns an entity with the given components, and returns its bits.
+fn spawn(params: Option<Value>, world: &mut World) -> Result<Value, RemoteError> {
+    let SpawnParams { components } = parse(params)?;
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let components = deserialize_components(components, &registry)?;
+    let mut entity = world.spawn_empty();
+    for (reflect_component, value) in components {
+        reflect_component.insert(&mut entity, value.as_ref(), &registry);
+    }
+    Ok(json!({ "entity": entity.id().to_bits() }))
+}
+
+/// Despawns an entity.
+fn despawn(params: Option<Value>, world: &mut World) -> Result<Value, RemoteError> {
+    let EntityParams { entity } = parse(params)?;
+    let entity = get_entity(world, entity)?.id();
+    world.despawn(entity);
+    Ok(Value::Null)
+}
+
+/// Returns the given components of every entity that has all of them and the `with` components,
+/// but none of the `without` components.
+fn query(params: Option<Value>, world: &mut World) -> Result<Value, RemoteError> {
+    let QueryParams {
+        components,
+        with,
+        without,
+    } = parse(params)?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let get_components = |paths: &[String]| {
+        paths
+            .iter()
+            .map(|path| get_component(path, &registry).map(|(_, c)| c))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let required = get_components(&components)?;
+    let with = get_components(&with)?;
+    let without = get_components(&without)?;
+
+    let mut results = Vec::new();
+    for entity in world.iter_entities() {
+        if !required.iter().chain(&with).all(|c| c.contains(entity))
+            || without.iter().any(|c| c.contains(entity))
+        {
+            continue;
+        }
+        results.push(json!({
+            "entity": entity.id().to_bits(),
+            "components": serialize_components(entity, &components, &registry)?,
+        }));
+    }
+    Ok(Value::Array(results))
+}
+
+/// Returns the type paths of the reflected resources that exist in the world.
+fn list_resources(_params: Option<Value>, world: &mut World) -> Result<Value, RemoteError> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let mut resources: Vec<&str> = registry
+        .iter()
+        .filter(|registration| {
+            registration
+                .data::<ReflectResource>()
+                .is_some_and(|reflect_resource| reflect_resource.reflect(world).is_some())
+        })
+        .map(|registration| registration.type_info().type_path())
+        .collect();
+    resources.sort_unstable();
+    Ok(json!(resources))
+}
+
+/// Returns the value of a resource.
+fn get_resource(params: Option<Value>, world: &mut World) -> Result<Value, RemoteError> {
+    let ResourceParams { resource } = parse(params)?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let (_, reflect_resource) = get_resource_registration(&resource, &registry)?;
+    let value = reflect_resource.reflect(world).ok_or_else(|| {
+        RemoteError::new(
+            error_codes::RESOURCE_NOT_PRESENT,
+            format!("the resource `{resource}` does not exist in the world"),
+        )
+    })?;
+    serialize(value, &registry)
+}
+
+/// Inserts a resource, replacing the existing one.
+fn insert_resource(params: Option<Value>, world: &mut World) -> Result<Value, RemoteError> {
+    let InsertResourceParams { resource, value } = parse(params)?;
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let (registration, reflect_resource) = get_resource_registration(&resource, &registry)?;
+    let value = deserialize(registration, value, &registry)?;
+    reflect_resource.insert(world, value.as_ref());
+    Ok(Value::Null)
+}
+
+/// Removes a resource.
+fn remove_resource(params: Option<Value>, world: &mut World) -> Result<Value, RemoteError> {
+    let ResourceParams { resource } = parse(params)?;
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let (_, reflect_resource) = get_resource_registration(&resource, &registry)?;
+    reflect_resource.remove(world);
+    Ok(Value::Null)
+}
+
+/// Deserializes the parameters of a method.
+pub(crate) fn parse<T: DeserializeOwned>(params: Option<Value>) -> Result<T, RemoteError> {
+    serde_json::from_value(params.unwrap_or(Value::Null)).map_err(RemoteError::invalid_params)
+}
+
+/// Returns the entity with the given [bits](Entity::to_bits), if it exists.
+pub(crate) fn get_entity(world: &World, bits: u64) -> Result<EntityRef<'_>, RemoteError> {
+    Entity::try_from_bits(bits)
+        .ok()
+        .and_then(|entity| world.get_entity(entity))
+        .ok_or_else(|| {
+            RemoteError::new(
+                error_codes::ENTITY_NOT_FOUND,
+                format!("the entity {bits} does not exist"),
+            )
+        })
+}
+
+/// Returns the registration of the reflected component with the given type path.
+pub(crate) fn get_component<'r>(
+    path: &str,
+    registry: &'r TypeRegistry,
+) -> Result<(&'r TypeRegistration, &'r ReflectComponent), RemoteError> {
+    registry
+        .get_with_type_path(path)
+        .and_then(|registration| Some((registration, registration.data::<ReflectComponent>()?)))
+        .ok_or_else(|| {
+            RemoteError::new(
+                error_codes::COMPONENT_ERROR,
+                format!("`{path}` is not a registered, reflected component"),
+            )
+        })
+}
+
+fn get_resource_registration<'r>(
+    path: &str,
+    registry: &'r TypeRegistry,
+) -> Result<(&'r TypeRegistration, &'r ReflectResource), RemoteError> {
+    registry
+        .get_with_type_path(path)
+        .and_then(|registration| Some((registration, registration.data::<ReflectResource>()?)))
+        .ok_or_else(|| {
+            RemoteError::new(
+                error_codes::RESOURCE_ERROR,
+                format!("`{path}` is not a registered, reflected resource"),
+            )
+        })
+}
+
+/// Serializes the given components of `entity` into a map from type paths to values.
+pub(crate) fn serialize_components(
+    entity: EntityRef,
+    components: &[String],
+    registry: &TypeRegistry,
+) -> Result<Map<String, Value>, RemoteError> {
+    let mut values = Map::new();
+    for path in components {
+        let (_, reflect_component) = get_component(path, registry)?;
+        let value = reflect_component.reflect(entity).ok_or_else(|| {
+            RemoteError::new(
+                error_codes::COMPONENT_NOT_PRESENT,
+                format!(
+                    "the entity {} does not have the component `{path}`",
+                    entity.id().to_bits()
+                ),
+            )
+        })?;
+        values.insert(path.clone(), serialize(value, registry)?);
+    }
+    Ok(values)
+}
+
+fn deserialize_components(
+    components: Map<String, Value>,
+    registry: &TypeRegistry,
+) -> Result<Vec<(ReflectComponent, Box<dyn Reflect>)>, RemoteError> {
+    components
+        .into_iter()
+        .map(|(path, value)| {
+            let (registration, reflect_component) = get_component(&path, registry)?;
+            let value = deserialize(registration, value, registry)?;
+            Ok((reflect_component.clone(), value))
+        })
+        .collect()
+}
+
+fn serialize(value: &dyn Reflect, registry: &TypeRegistry) -> Result<Value, RemoteError> {
+    serde_json::to_value(TypedReflectSerializer::new(value, registry))
+        .map_err(|err| RemoteError::new(error_codes::INTERNAL_ERROR, err.to_string()))
+}
+
+fn deserialize(
+    registration: &TypeRegistration,
+    value: Value,
+    registry: &TypeRegistry,
+) -> Result<Box<dyn Reflect>, RemoteError> {
+    TypedReflectDeserializer::new(registration, registry)
+        .deserialize(value)
+        .map_err(RemoteError::invalid_params)
+}
@@ -0,0 +1,560 @@
+//! A JSON-RPC protocol to inspect and modify a running Bevy app from other processes, such as
+//! editors and debugging tools.
+//!
+//! The [`RemotePlugin`] listens on a local TCP port (`127.0.0.1:15702` by default). Clients either
+//! keep a connection open and exchange [JSON-RPC 2.0](https://www.jsonrpc.org/specification)
+//! messages separated by newlines, or send a single request as the body of an HTTP `POST` request.
+//! HTTP requests must have a local `Host`, the `application/json` content type and no `Origin`,
+//! so that web pages can't send requests to the app.
+//! Requests are processed by the app at the end of every frame, in [`Last`].
+//!
+//! Entities are identified by their [bits](bevy_ecs::entity::Entity::to_bits), and components and
+//! resources by their [type path](bevy_reflect::TypePath). Values are serialized with the
+//! reflection serializers of `bevy_reflect`, so components and resources have to be registered in
+//! the [`AppTypeRegistry`](bevy_ecs::reflect::AppTypeRegistry) and reflect
+//! [`ReflectComponent`](bevy_ecs::reflect::ReflectComponent) or
+//! [`ReflectResource`](bevy_ecs::reflect::ReflectResource).
+//!
+//! | Method                 | Parameters                                   | Result                          |
+//! |------------------------|----------------------------------------------|---------------------------------|
+//! | `bevy/list_entities`   |                                              | the entities                    |
+//! | `bevy/list_components` | `entity` (optional)                          | the type paths of components    |
+//! | `bevy/get`             | `entity`, `components` (type paths)          | the values by type path         |
+//! | `bevy/insert`          | `entity`, `components` (values by type path) |                                 |
+//! | `bevy/remove`          | `entity`, `components` (type paths)          |                                 |
+//! | `bevy/spawn`           | `components` (values by type path)           | the new `entity`                |
+//! | `bevy/despawn`         | `entity`                                     |                                 |
+//! | `bevy/query`           | `components`, `with`, `without` (type paths) | the `entity` and `components`   |
+//! | `bevy/list_resources`  |                                              | the type paths of resources     |
+//! | `bevy/get_resource`    | `resource` (type path)                       | the value                       |
+//! | `bevy/insert_resource` | `resource` (type path), `value`              |                                 |
+//! | `bevy/remove_resource` | `resource` (type path)                       |                                 |
+//! | `bevy/watch`           | `entity`, `components` (type paths)          | the `watch` id and `components` |
+//! | `bevy/unwatch`         | `watch`                                      |                                 |
+//!
+//! A watch sends a `bevy/changed` notification to the client with the `components` that changed
+//! and the ones that were `removed` at the end of every frame where this happened, or once with
+//! `despawned` set when the entity is despawned.
+//!
+//! ```text
+//! --> {"jsonrpc": "2.0", "id": 1, "method": "bevy/get", "params": {"entity": 4294967296, "components": ["bevy_transform::components::transform::Transform"]}}
+//! <-- {"jsonrpc": "2.0", "id": 1, "result": {"bevy_transform::components::transform::Transform": {"translation": [0.0, 1.0, 0.0], ...}}}
+//! ```
+//!
+//! Additional methods can be added with [`RemotePlugin::with_method`].
+
+mod builtin_methods;
+mod protocol;
+mod server;
+mod watch;
+
+pub use builtin_methods::*;
+pub use protocol::*;
+pub use watch::{CHANGED_NOTIFICATION, UNWATCH_METHOD, WATCH_METHOD};
+
+use bevy_app::{App, Last, Plugin};
+use bevy_ecs::{schedule::IntoSystemConfigs, system::Resource, world::World};
+use bevy_log::error;
+use bevy_utils::HashMap;
+use crossbeam_channel::Receiver;
+use serde_json::Value;
+use server::{IncomingRequest, RemoteClient};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener};
+use watch::RemoteWatches;
+
+/// The address the [`RemotePlugin`] listens on by default, which only accepts local connections.
+pub const DEFAULT_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+/// The port the [`RemotePlugin`] listens on by default.
+pub const DEFAULT_PORT: u16 = 15702;
+
+/// The largest body of an HTTP request or TCP message the [`RemotePlugin`] accepts by default, in
+/// bytes.
+pub const DEFAULT_MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// A method of the remote protocol, called with the parameters of the request.
+pub type RemoteMethod = fn(Option<Value>, &mut World) -> Result<Value, RemoteError>;
+
+/// Adds a server for the remote protocol to an app.
+///
+/// See the [crate level documentation](crate) for the protocol and its methods.
+pub struct RemotePlugin {
+    address: IpAddr,
+    port: u16,
+    max_body_size: usize,
+    methods: Vec<(String, RemoteMethod)>,
+}
+
+impl Default for RemotePlugin {
+    fn default() -> Self {
+        Self {
+            address: DEFAULT_ADDRESS,
+            port: DEFAULT_PORT,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            methods: Vec::new(),
+        }
+    }
+}
+
+impl RemotePlugin {
+    /// Sets the address the server listens on.
+    ///
+    /// Anyone who can connect to the server can modify the app, so beware of listening on other
+    /// addresses than the local one. HTTP requests are only accepted with a local `Host`, so
+    /// remote clients have to use TCP messages.
+    pub fn with_address(mut self, address: impl Into<IpAddr>) -> Self {
+        self.address = address.into();
+        self
+    }
+
+    /// Sets the port the server listens on. With port `0`, the operating system picks a free
+    /// one, which is returned by [`RemoteServer::local_addr`].
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Sets the largest body of an HTTP request or TCP message the server accepts, in bytes.
+    /// Larger HTTP requests are answered with `413 Payload Too Large`, and TCP clients sending
+    /// larger messages are disconnected.
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Adds a method to the protocol, replacing the built-in method with the same name if there
+    /// is one.
+    pub fn with_method(mut self, name: impl Into<String>, method: RemoteMethod) -> Self {
+        self.methods.push((name.into(), method));
+        self
+    }
+}
+
+impl Plugin for RemotePlugin {
+    fn build(&self, app: &mut App) {
+        let listener = match TcpListener::bind((self.address, self.port)) {
+            Ok(listener) => listener,
+            Err(err) => {
+                error!(
+                    "Failed to start the remote protocol server on {}:{}: {err}",
+                    self.address, self.port
+                );
+                return;
+            }
+        };
+        let local_addr = listener
+            .local_addr()
+            .unwrap_or(SocketAddr::new(self.address, self.port));
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        server::start_server(listener, sender, self.max_body_size);
+
+        let mut methods = RemoteMethods::default();
+        builtin_methods::register(&mut methods);
+        for (name, method) in &self.methods {
+            methods.insert(name.clone(), *method);
+        }
+
+        app.insert_resource(RemoteServer {
+            local_addr,
+            receiver,
+        })
+        .insert_resource(methods)
+        .init_resource::<RemoteWatches>()
+        .add_systems(
+            Last,
+            (process_remote_requests, watch::process_remote_watches).chain(),
+        );
+    }
+}
+
+/// The server of the remote protocol, added by the [`RemotePlugin`].
+#[derive(Resource)]
+pub struct RemoteServer {
+    local_addr: SocketAddr,
+    receiver: Receiver<IncomingRequest>,
+}
+
+impl RemoteServer {
+    /// Returns the address the server listens on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+/// The methods of the remote protocol, by name.
+///
+/// Methods can be added or replaced at runtime, except for `bevy/watch` and `bevy/unwatch`.
+#[derive(Resource, Default)]
+pub struct RemoteMethods(HashMap<String, RemoteMethod>);
+
+impl RemoteMethods {
+    /// Adds a method, replacing the method with the same name if there is one.
+    pub fn insert(&mut self, name: impl Into<String>, method: RemoteMethod) {
+        self.0.insert(name.into(), method);
+    }
+
+    /// Returns the method with the given name.
+    pub fn get(&self, name: &str) -> Option<RemoteMethod> {
+        self.0.get(name).copied()
+    }
+
+    /// Removes the method with the given name.
+    pub fn remove(&mut self, name: &str) -> Option<RemoteMethod> {
+        self.0.remove(name)
+    }
+
+    /// Returns an iterator over the names of all methods.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+}
+
+/// Calls the methods of the requests received since the last frame, and answers them.
+fn process_remote_requests(world: &mut World) {
+    let Some(server) = world.get_resource::<RemoteServer>() else {
+        return;
+    };
+    let requests: Vec<IncomingRequest> = server.receiver.try_iter().collect();
+    for IncomingRequest { request, client } in requests {
+        let result = call_method(world, request.method, request.params, &client);
+        if let Some(id) = request.id {
+            client.send(&RemoteResponse::new(id, result));
+        }
+    }
+}
+
+fn call_method(
+    world: &mut World,
+    method: String,
+    params: Option<Value>,
+    client: &RemoteClient,
+) -> Result<Value, RemoteError> {
+    match method.as_str() {
+        WATCH_METHOD => watch::watch(params, world, client),
+        UNWATCH_METHOD => watch::unwatch(params, world),
+        _ => match world.resource::<RemoteMethods>().get(&method) {
+            Some(method) => method(params, world),
+            None => Err(RemoteError::new(
+                error_codes::METHOD_NOT_FOUND,
+                format!("the method `{method}` does not exist"),
+            )),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        component::Component,
+        entity::Entity,
+        reflect::{ReflectComponent, ReflectResource},
+    };
+    use bevy_reflect::Reflect;
+    use serde_json::json;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpStream,
+        time::Duration,
+    };
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Frozen;
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    const POSITION: &str = "bevy_remote::tests::Position";
+    const FROZEN: &str = "bevy_remote::tests::Frozen";
+    const SCORE: &str = "bevy_remote::tests::Score";
+
+    fn create_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(RemotePlugin::default().with_port(0))
+            .register_type::<Position>()
+            .register_type::<Frozen>()
+            .register_type::<Score>()
+            .init_resource::<Score>();
+        app
+    }
+
+    /// A client connected over TCP, which updates the app until it receives a message.
+    struct Client {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+        next_id: u64,
+    }
+
+    impl Client {
+        fn connect(app: &App) -> Self {
+            let writer =
+                TcpStream::connect(app.world.resource::<RemoteServer>().local_addr()).unwrap();
+            writer
+                .set_read_timeout(Some(Duration::from_millis(10)))
+                .unwrap();
+            Self {
+                reader: BufReader::new(writer.try_clone().unwrap()),
+                writer,
+                next_id: 0,
+            }
+        }
+
+        fn receive(&mut self, app: &mut App) -> Value {
+            let mut line = String::new();
+            for _ in 0..500 {
+                app.update();
+                let _ = self.reader.read_line(&mut line);
+                if line.ends_with('\n') {
+                    return serde_json::from_str(&line).unwrap();
+                }
+            }
+            panic!("no message received");
+        }
+
+        fn call(
+            &mut self,
+            app: &mut App,
+            method: &str,
+            params: Value,
+        ) -> Result<Value, RemoteError> {
+            let id = self.next_id;
+            self.next_id += 1;
+            let request = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+            writeln!(self.writer, "{request}").unwrap();
+            let response: RemoteResponse = serde_json::from_value(self.receive(app)).unwrap();
+            assert_eq!(response.id, json!(id));
+            match response.error {
+                Some(error) => Err(error),
+                None => Ok(response.result.unwrap_or_default()),
+            }
+        }
+    }
+
+    #[test]
+    fn components_and_resources() {
+        let mut app = create_app();
+        let entity = app.world.spawn(Position { x: 1.0, y: 2.0 }).id();
+        app.world.spawn((Position { x: 3.0, y: 4.0 }, Frozen));
+        let mut client = Client::connect(&app);
+        let bits = entity.to_bits();
+
+        let result = client.call(
+            &mut app,
+            GET_METHOD,
+            json!({ "entity": bits, "components": [POSITION] }),
+        );
+        assert_eq!(result.unwrap(), json!({ POSITION: { "x": 1.0, "y": 2.0 } }));
+
+        let params = json!({ "entity": bits, "components": { POSITION: { "x": 5.0, "y": 6.0 } } });
+        client.call(&mut app, INSERT_METHOD, params).unwrap();
+        assert_eq!(
+            app.world.get::<Position>(entity),
+            Some(&Position { x: 5.0, y: 6.0 })
+        );
+
+        let result = client.call(
+            &mut app,
+            QUERY_METHOD,
+            json!({ "components": [POSITION], "without": [FROZEN] }),
+        );
+        assert_eq!(
+            result.unwrap(),
+            json!([{ "entity": bits, "components": { POSITION: { "x": 5.0, "y": 6.0 } } }])
+        );
+
+        let result = client.call(
+            &mut app,
+            SPAWN_METHOD,
+            json!({ "components": { FROZEN: {} } }),
+        );
+        let spawned = result.unwrap()["entity"].as_u64().unwrap();
+        let spawned = Entity::from_bits(spawned);
+        assert!(app.world.entity(spawned).contains::<Frozen>());
+        let result = client.call(
+            &mut app,
+            LIST_COMPONENTS_METHOD,
+            json!({ "entity": spawned.to_bits() }),
+        );
+        assert_eq!(result.unwrap(), json!([FROZEN]));
+
+        client
+            .call(
+                &mut app,
+                REMOVE_METHOD,
+                json!({ "entity": bits, "components": [POSITION] }),
+            )
+            .unwrap();
+        assert!(!app.world.entity(entity).contains::<Position>());
+        let result = client.call(
+            &mut app,
+            GET_METHOD,
+            json!({ "entity": bits, "components": [POSITION] }),
+        );
+        assert_eq!(result.unwrap_err().code, error_codes::COMPONENT_NOT_PRESENT);
+
+        client
+            .call(
+                &mut app,
+                INSERT_RESOURCE_METHOD,
+                json!({ "resource": SCORE, "value": [10] }),
+            )
+            .unwrap();
+        assert_eq!(app.world.resource::<Score>().0, 10);
+        let result = client.call(&mut app, GET_RESOURCE_METHOD, json!({ "resource": SCORE }));
+        assert_eq!(result.unwrap(), json!([10]));
+
+        client
+            .call(&mut app, DESPAWN_METHOD, json!({ "entity": bits }))
+            .unwrap();
+        let result = client.call(
+            &mut app,
+            GET_METHOD,
+            json!({ "entity": bits, "components": [POSITION] }),
+        );
+        assert_eq!(result.unwrap_err().code, error_codes::ENTITY_NOT_FOUND);
+        let result = client.call(&mut app, "bevy/unknown", Value::Null);
+        assert_eq!(result.unwrap_err().code, error_codes::METHOD_NOT_FOUND);
+        let result = client.call(&mut app, GET_METHOD, json!({ "entity": "none" }));
+        assert_eq!(result.unwrap_err().code, error_codes::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn watch_components() {
+        let mut app = create_app();
+        let entity = app.world.spawn(Position { x: 1.0, y: 2.0 }).id();
+        let mut client = Client::connect(&app);
+        let bits = entity.to_bits();
+
+        let params = json!({ "entity": bits, "components": [POSITION, FROZEN] });
+        let result = client.call(&mut app, WATCH_METHOD, params).unwrap();
+        assert_eq!(
+            result["components"],
+            json!({ POSITION: { "x": 1.0, "y": 2.0 } })
+        );
+        let watch = result["watch"].clone();
+
+        app.world.get_mut::<Position>(entity).unwrap().x = 7.0;
+        let notification: RemoteNotification =
+            serde_json::from_value(client.receive(&mut app)).unwrap();
+        assert_eq!(notification.method, CHANGED_NOTIFICATION);
+        assert_eq!(notification.params["watch"], watch);
+        assert_eq!(
+            notification.params["components"],
+            json!({ POSITION: { "x": 7.0, "y": 2.0 } })
+        );
+
+        app.world
+            .entity_mut(entity)
+            .remove::<Position>()
+            .insert(Frozen);
+        let notification = client.receive(&mut app);
+        assert_eq!(notification["params"]["components"], json!({ FROZEN: {} }));
+        assert_eq!(notification["params"]["removed"], json!([POSITION]));
+
+        app.world.despawn(entity);
+        let notification = client.receive(&mut app);
+        assert_eq!(notification["params"]["despawned"], json!(true));
+
+        let result = client.call(&mut app, UNWATCH_METHOD, json!({ "watch": watch }));
+        assert_eq!(result.unwrap_err().code, error_codes::WATCH_NOT_FOUND);
+    }
+
+    #[test]
+    fn http_request() {
+        let mut app = create_app();
+        let address = app.world.resource::<RemoteServer>().local_addr();
+        let client = std::thread::spawn(move || {
+            let body = json!({ "jsonrpc": "2.0", "id": "a", "method": GET_RESOURCE_METHOD, "params": { "resource": SCORE } }).to_string();
+            let mut stream = TcpStream::connect(address).unwrap();
+            write!(
+                stream,
+                "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+        while !client.is_finished() {
+            app.update();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let response = client.join().unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        let response: RemoteResponse = serde_json::from_str(body).unwrap();
+        assert_eq!(response.id, json!("a"));
+        assert_eq!(response.result, Some(json!([0])));
+    }
+
+    #[test]
+    fn http_request_too_large() {
+        let mut app = App::new();
+        app.add_plugins(RemotePlugin::default().with_port(0).with_max_body_size(16));
+        let address = app.world.resource::<RemoteServer>().local_addr();
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: 1000000000\r\n\r\n{{}}"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 413 Payload Too Large\r\n"));
+
+        // TCP clients sending larger messages are disconnected.
+        let mut stream = TcpStream::connect(address).unwrap();
+        writeln!(stream, "{}", " ".repeat(64)).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.is_empty());
+    }
+
+    #[test]
+    fn http_cross_origin_requests_are_rejected() {
+        let mut app = App::new();
+        app.add_plugins(RemotePlugin::default().with_port(0));
+        let address = app.world.resource::<RemoteServer>().local_addr();
+
+        for (headers, status) in [
+            (
+                "Host: localhost\r\nOrigin: https://example.com\r\nContent-Type: application/json",
+                "403 Forbidden",
+            ),
+            (
+                "Host: attacker.example:15702\r\nContent-Type: application/json",
+                "403 Forbidden",
+            ),
+            ("Content-Type: application/json", "403 Forbidden"),
+            (
+                "Host: 127.0.0.1:15702\r\nContent-Type: text/plain",
+                "415 Unsupported Media Type",
+            ),
+        ] {
+            let mut stream = TcpStream::connect(address).unwrap();
+            write!(
+                stream,
+                "POST / HTTP/1.1\r\n{headers}\r\nContent-Length: 2\r\n\r\n{{}}"
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            assert!(
+                response.starts_with(&format!("HTTP/1.1 {status}\r\n")),
+                "{headers}: {response}"
+            );
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// The version of JSON-RPC implemented by the remote protocol.
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// A JSON-RPC request sent by a client.
+///
+/// Requests without an `id` are notifications, which are not answered.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteRequest {
+    /// The JSON-RPC version, always `"2.0"`.
+    pub jsonrpc: String,
+    /// The name of the method to call, such as `bevy/get`.
+    pub method: String,
+    /// The id the response to this request will have.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    /// The parameters of the method.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+/// A JSON-RPC response to a [`RemoteRequest`], with either a `result` or an `error`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteResponse {
+    /// The JSON-RPC version, always `"2.0"`.
+    pub jsonrpc: String,
+    /// The id of the request, or `null` if it could not be parsed.
+    pub id: Value,
+    /// The return value of the method, if it succeeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    /// The error of the method, if it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RemoteError>,
+}
+
+impl RemoteResponse {
+    /// Creates the response to the request with the given `id`.
+    pub fn new(id: Value, result: Result<Value, RemoteError>) -> Self {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+/// A JSON-RPC notification sent by the app to a client, such as the changes reported for a
+/// `bevy/watch` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteNotification {
+    /// The JSON-RPC version, always `"2.0"`.
+    pub jsonrpc: String,
+    /// The kind of the notification, such as `bevy/changed`.
+    pub method: String,
+    /// The content of the notification.
+    pub params: Value,
+}
+
+impl RemoteNotification {
+    /// Creates a notification of the given kind.
+    pub fn new(method: impl Into<String>, params: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            method: method.into(),
+            params,
+        }
+    }
+}
+
+/// The error of a failed [`RemoteRequest`].
+///
+/// See [`error_codes`] for the codes used by the built-in methods.
+#[derive(Error, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[error("{message} (code {code})")]
+pub struct RemoteError {
+    /// The kind of the error.
+    pub code: i16,
+    /// A short description of the error.
+    pub message: String,
+    /// Additional information about the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RemoteError {
+    /// Creates an error with the given `code` and `message`.
+    pub fn new(code: i16, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    /// Creates an [`error_codes::INVALID_PARAMS`] error.
+    pub fn invalid_params(error: impl std::fmt::Display) -> Self {
+        Self::new(error_codes::INVALID_PARAMS, error.to_string())
+    }
+}
+
+/// The error codes of the remote protocol.
+///
+/// Codes from `-32768` to `-32000` are defined by JSON-RPC, the others are specific to Bevy.
+pub mod error_codes {
+    /// The request is not valid JSON.
+    pub const PARSE_ERROR: i16 = -32700;
+    /// The request is not a valid JSON-RPC request.
+    pub const INVALID_REQUEST: i16 = -32600;
+    /// The method does not exist.
+    pub const METHOD_NOT_FOUND: i16 = -32601;
+    /// The parameters of the method are invalid.
+    pub const INVALID_PARAMS: i16 = -32602;
+    /// The method failed for an internal reason.
+    pub const INTERNAL_ERROR: i16 = -32603;
+
+    /// The entity does not exist.
+    pub const ENTITY_NOT_FOUND: i16 = -23401;
+    /// The type path does not belong to a registered, reflected component.
+    pub const COMPONENT_ERROR: i16 = -23402;
+    /// The entity does not have the component.
+    pub const COMPONENT_NOT_PRESENT: i16 = -23403;
+    /// The type path does not belong to a registered, reflected resource.
+    pub const RESOURCE_ERROR: i16 = -23404;
+    /// The resource does not exist in the world.
+    pub const RESOURCE_NOT_PRESENT: i16 = -23405;
+    /// The watch does not exist.
+    pub const WATCH_NOT_FOUND: i16 = -23406;
+}
@@ -0,0 +1,261 @@
+use crate::{error_codes, RemoteError, RemoteRequest, RemoteResponse, JSONRPC_VERSION};
+use bevy_log::{debug, warn};
+use crossbeam_channel::Sender;
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+};
+
+/// A request received from a client, along with the connection to answer it on.
+pub(crate) struct IncomingRequest {
+    pub(crate) request: RemoteRequest,
+    pub(crate) client: RemoteClient,
+}
+
+/// The connection to a client. Messages sent to it are written to the client in order.
+#[derive(Clone)]
+pub(crate) struct RemoteClient(Sender<String>);
+
+impl RemoteClient {
+    /// Sends `message` to the client. Returns `false` if the client disconnected.
+    pub(crate) fn send(&self, message: &impl Serialize) -> bool {
+        match serde_json::to_string(message) {
+            Ok(message) => self.0.send(message).is_ok(),
+            Err(err) => {
+                warn!("Failed to serialize a remote protocol message: {err}");
+                true
+            }
+        }
+    }
+}
+
+/// Accepts connections on `listener` in a background thread, and forwards the requests of every
+/// client to `requests`. HTTP request bodies and TCP messages larger than `max_body_size` bytes
+/// are rejected.
+pub(crate) fn start_server(
+    listener: TcpListener,
+    requests: Sender<IncomingRequest>,
+    max_body_size: usize,
+) {
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    warn!("Failed to accept a remote protocol connection: {err}");
+                    continue;
+                }
+            };
+            let requests = requests.clone();
+            std::thread::spawn(move || {
+                if let Err(err) = handle_connection(stream, requests, max_body_size) {
+                    debug!("Remote protocol connection closed: {err}");
+                }
+            });
+        }
+    });
+}
+
+/// Handles a client that either sends newline-delimited JSON-RPC messages over TCP, or a single
+/// HTTP `POST` request with a JSON-RPC body.
+fn handle_connection(
+    stream: TcpStream,
+    requests: Sender<IncomingRequest>,
+    max_body_size: usize,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    let len = read_line(&mut reader, &mut line, max_body_size.max(MAX_HEADER_LENGTH))?;
+    if len == 0 {
+        return Ok(());
+    }
+    if line.starts_with("POST ") {
+        return handle_http(reader, stream, requests, max_body_size);
+    }
+    if len > max_body_size {
+        return Err(message_too_large(max_body_size));
+    }
+
+    let (sender, receiver) = crossbeam_channel::unbounded::<String>();
+    let client = RemoteClient(sender);
+    let mut writer = stream;
+    // Writes responses and notifications until the client disconnects and nothing can be sent
+    // to it anymore.
+    std::thread::spawn(move || {
+        for message in receiver {
+            if writeln!(writer, "{message}").is_err() {
+                break;
+            }
+        }
+    });
+
+    loop {
+        if !line.trim().is_empty() {
+            if let Some(request) = parse_request(&line, &client) {
+                let client = client.clone();
+                if requests.send(IncomingRequest { request, client }).is_err() {
+                    break;
+                }
+            }
+        }
+        line.clear();
+        if read_line(&mut reader, &mut line, max_body_size)? == 0 {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// The longest request line or header of an HTTP request the server accepts, in bytes.
+const MAX_HEADER_LENGTH: usize = 8 * 1024;
+
+fn message_too_large(max_len: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("message larger than {max_len} bytes"),
+    )
+}
+
+/// Reads a line like [`BufRead::read_line`], failing if it is longer than `max_len` bytes.
+fn read_line(
+    reader: &mut BufReader<TcpStream>,
+    line: &mut String,
+    max_len: usize,
+) -> io::Result<usize> {
+    let len = reader.take(max_len as u64 + 1).read_line(line)?;
+    if len > max_len {
+        return Err(message_too_large(max_len));
+    }
+    Ok(len)
+}
+
+/// Returns `true` if the value of a `Host` header names the local host, with or without a port.
+fn is_local_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    name.eq_ignore_ascii_case("localhost") || name == "127.0.0.1" || name == "::1"
+}
+
+/// Answers an HTTP `POST` request with a JSON-RPC body.
+///
+/// Browsers let any web page send simple cross-origin requests to local servers, so requests with
+/// an `Origin` header, a `Host` other than the local host (as sent after a DNS rebinding), or a
+/// content type other than `application/json` are rejected.
+fn handle_http(
+    mut reader: BufReader<TcpStream>,
+    mut stream: TcpStream,
+    requests: Sender<IncomingRequest>,
+    max_body_size: usize,
+) -> io::Result<()> {
+    let mut content_length = 0;
+    let mut has_origin = false;
+    let mut local_host = false;
+    let mut json = false;
+    loop {
+        let mut header = String::new();
+        if read_line(&mut reader, &mut header, MAX_HEADER_LENGTH)? == 0 {
+            return Ok(());
+        }
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("origin") {
+                has_origin = true;
+            } else if name.eq_ignore_ascii_case("host") {
+                local_host = is_local_host(value);
+            } else if name.eq_ignore_ascii_case("content-type") {
+                let media_type = value.split(';').next().unwrap_or_default().trim();
+                json = media_type.eq_ignore_ascii_case("application/json");
+            }
+        }
+    }
+    if has_origin || !local_host {
+        return write!(
+            stream,
+            "HTTP/1.1 403 Forbidden\r\nConnection: close\r\n\r\n"
+        );
+    }
+    if !json {
+        return write!(
+            stream,
+            "HTTP/1.1 415 Unsupported Media Type\r\nConnection: close\r\n\r\n"
+        );
+    }
+    if content_length > max_body_size {
+        return write!(
+            stream,
+            "HTTP/1.1 413 Payload Too Large\r\nConnection: close\r\n\r\n"
+        );
+    }
+    let mut body = Vec::new();
+    reader.take(content_length as u64).read_to_end(&mut body)?;
+    if body.len() < content_length {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    let (sender, receiver) = crossbeam_channel::unbounded::<String>();
+    let client = RemoteClient(sender);
+    if let Some(request) = parse_request(&String::from_utf8_lossy(&body), &client) {
+        let answered = request.id.is_some();
+        let client = client.clone();
+        if requests.send(IncomingRequest { request, client }).is_err() || !answered {
+            return write!(
+                stream,
+                "HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n"
+            );
+        }
+    }
+    drop(client);
+
+    // The first message is the response, later ones are notifications of watches, which need a
+    // TCP connection.
+    let Ok(response) = receiver.recv() else {
+        return write!(
+            stream,
+            "HTTP/1.1 500 Internal Server Error\r\nConnection: close\r\n\r\n"
+        );
+    };
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        response.len()
+    )
+}
+
+/// Parses a JSON-RPC request, answering the client with an error if it is invalid.
+fn parse_request(text: &str, client: &RemoteClient) -> Option<RemoteRequest> {
+    let value: Value = match serde_json::from_str(text) {
+        Ok(value) => value,
+        Err(err) => {
+            let error = RemoteError::new(error_codes::PARSE_ERROR, err.to_string());
+            client.send(&RemoteResponse::new(Value::Null, Err(error)));
+            return None;
+        }
+    };
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    match serde_json::from_value::<RemoteRequest>(value) {
+        Ok(request) if request.jsonrpc == JSONRPC_VERSION => Some(request),
+        Ok(request) => {
+            let error = RemoteError::new(
+                error_codes::INVALID_REQUEST,
+                format!("unsupported JSON-RPC version `{}`", request.jsonrpc),
+            );
+            client.send(&RemoteResponse::new(id, Err(error)));
+            None
+        }
+        Err(err) => {
+            let error = RemoteError::new(error_codes::INVALID_REQUEST, err.to_string());
+            client.send(&RemoteResponse::new(id, Err(error)));
+            None
+        }
+    }
+}
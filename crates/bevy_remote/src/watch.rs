@@ -0,0 +1,172 @@
+//! Watching the components of an entity, with `bevy/watch` and `bevy/unwatch`.
+
+use crate::{
+    builtin_methods::{get_component, get_entity, parse, serialize_components},
+    error_codes, RemoteClient, RemoteError, RemoteNotification,
+};
+use bevy_ecs::{
+    component::Tick,
+    entity::Entity,
+    reflect::AppTypeRegistry,
+    system::Resource,
+    world::{EntityRef, Mut, World},
+};
+use bevy_reflect::TypeRegistry;
+use bevy_utils::{HashMap, HashSet};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+/// The method starting to watch components of an entity.
+pub const WATCH_METHOD: &str = "bevy/watch";
+/// The method stopping a watch.
+pub const UNWATCH_METHOD: &str = "bevy/unwatch";
+/// The notification sent when watched components change.
+pub const CHANGED_NOTIFICATION: &str = "bevy/changed";
+
+struct Watch {
+    client: RemoteClient,
+    entity: Entity,
+    components: Vec<String>,
+    /// The watched components the entity had when the watch was last checked.
+    present: HashSet<String>,
+    last_run: Tick,
+}
+
+/// The active watches of all clients.
+#[derive(Resource, Default)]
+pub(crate) struct RemoteWatches {
+    next_id: u64,
+    watches: HashMap<u64, Watch>,
+}
+
+#[derive(Deserialize)]
+struct WatchParams {
+    entity: u64,
+    components: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct UnwatchParams {
+    watch: u64,
+}
+
+/// Starts watching components of an entity, and returns the id of the watch along with the current
+/// values of the components.
+pub(crate) fn watch(
+    params: Option<Value>,
+    world: &mut World,
+    client: &RemoteClient,
+) -> Result<Value, RemoteError> {
+    let WatchParams { entity, components } = parse(params)?;
+    let entity = get_entity(world, entity)?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let (values, _) = changed_components(world, entity, &components, None, &registry)?;
+    let entity = entity.id();
+    drop(registry);
+
+    // Changes made from now on, including by later requests of this frame, are newer than this.
+    let last_run = world.increment_change_tick();
+    let mut watches = world.resource_mut::<RemoteWatches>();
+    let id = watches.next_id;
+    watches.next_id += 1;
+    watches.watches.insert(
+        id,
+        Watch {
+            client: client.clone(),
+            entity,
+            present: values.keys().cloned().collect(),
+            components,
+            last_run,
+        },
+    );
+    Ok(json!({ "watch": id, "components": values }))
+}
+
+/// Stops a watch.
+pub(crate) fn unwatch(params: Option<Value>, world: &mut World) -> Result<Value, RemoteError> {
+    let UnwatchParams { watch } = parse(params)?;
+    match world.resource_mut::<RemoteWatches>().watches.remove(&watch) {
+        Some(_) => Ok(Value::Null),
+        None => Err(RemoteError::new(
+            error_codes::WATCH_NOT_FOUND,
+            format!("the watch {watch} does not exist"),
+        )),
+    }
+}
+
+/// Sends a [`CHANGED_NOTIFICATION`] for every watch whose components were changed, inserted or
+/// removed since it was last checked, or whose entity was despawned.
+///
+/// Watches are dropped once their client disconnects or their entity is despawned.
+pub(crate) fn process_remote_watches(world: &mut World) {
+    let this_run = world.increment_change_tick();
+    world.resource_scope(|world, mut watches: Mut<RemoteWatches>| {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        watches.watches.retain(|&id, watch| {
+            let Ok(entity) = get_entity(world, watch.entity.to_bits()) else {
+                watch.client.send(&RemoteNotification::new(
+                    CHANGED_NOTIFICATION,
+                    json!({ "watch": id, "entity": watch.entity.to_bits(), "despawned": true }),
+                ));
+                return false;
+            };
+            let since = Some((&watch.present, watch.last_run, this_run));
+            let Ok((changed, present)) =
+                changed_components(world, entity, &watch.components, since, &registry)
+            else {
+                return true;
+            };
+            let removed: Vec<&String> = watch.present.difference(&present).collect();
+            let mut connected = true;
+            if !changed.is_empty() || !removed.is_empty() {
+                connected = watch.client.send(&RemoteNotification::new(
+                    CHANGED_NOTIFICATION,
+                    json!({
+                        "watch": id,
+                        "entity": watch.entity.to_bits(),
+                        "components": changed,
+                        "removed": removed,
+                    }),
+                ));
+            }
+            watch.present = present;
+            watch.last_run = this_run;
+            connected
+        });
+    });
+}
+
+/// Serializes the watched `components` the entity has, skipping those that were already present
+/// and unchanged `since` the last check, and returns them along with the set of present ones.
+fn changed_components(
+    world: &World,
+    entity: EntityRef,
+    components: &[String],
+    since: Option<(&HashSet<String>, Tick, Tick)>,
+    registry: &TypeRegistry,
+) -> Result<(Map<String, Value>, HashSet<String>), RemoteError> {
+    let mut changed = Vec::new();
+    let mut present = HashSet::new();
+    for path in components {
+        let (registration, _) = get_component(path, registry)?;
+        let Some(ticks) = world
+            .components()
+            .get_id(registration.type_id())
+            .and_then(|id| entity.get_change_ticks_by_id(id))
+        else {
+            continue;
+        };
+        present.insert(path.clone());
+        let is_changed = match since {
+            Some((was_present, last_run, this_run)) => {
+                !was_present.contains(path) || ticks.is_changed(last_run, this_run)
+            }
+            None => true,
+        };
+        if is_changed {
+            changed.push(path.clone());
+        }
+    }
+    let values = serialize_components(entity, &changed, registry)?;
+    Ok((values, present))
+}
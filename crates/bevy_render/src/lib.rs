@@ -68,6 +68,7 @@ use crate::{
 use bevy_app::{App, AppLabel, Plugin, SubApp};
 use bevy_asset::{load_internal_asset, AssetApp, AssetServer, Handle};
 use bevy_ecs::{prelude::*, schedule::ScheduleLabel, system::SystemState};
+use bevy_reflect::Reflect;
 use bevy_utils::tracing::debug;
 use std::{
     ops::{Deref, DerefMut},
@@ -82,8 +83,12 @@ use std::{
 /// Rendering can be executed between iterations of the main schedule,
 /// or it can be executed in parallel with main schedule when
 /// [`PipelinedRenderingPlugin`](pipelined_rendering::PipelinedRenderingPlugin) is enabled.
-#[derive(Default)]
+///
+/// `render_creation` holds wgpu types and isn't reflected, so it can't be overridden from settings
+/// files.
+#[derive(Default, Reflect)]
 pub struct RenderPlugin {
+    #[reflect(ignore)]
     pub render_creation: RenderCreation,
     /// If `true`, disables asynchronous pipeline compilation.
     /// This has no effect on macOS, Wasm, or without the `multi-threaded` feature.
@@ -228,7 +233,8 @@ pub const MATHS_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(106653563
 impl Plugin for RenderPlugin {
     /// Initializes the renderer, sets up the [`RenderSet`] and creates the rendering sub-app.
     fn build(&self, app: &mut App) {
-        app.init_resource::<DeterministicRenderingConfig>();
+        app.register_type::<RenderPlugin>()
+            .init_resource::<DeterministicRenderingConfig>();
 
         app.init_asset::<Shader>()
             .init_asset_loader::<ShaderLoader>();
@@ -505,6 +505,7 @@ mod tests {
     use bevy_ecs::query::{With, Without};
     use bevy_ecs::reflect::{AppTypeRegistry, ReflectMapEntities};
     use bevy_ecs::world::FromWorld;
+    use bevy_reflect::serde::{Migration, ReflectMigrations};
     use bevy_reflect::{Reflect, ReflectSerialize};
     use bincode::Options;
     use serde::de::DeserializeSeed;
@@ -539,6 +540,13 @@ mod tests {
         },
     }
 
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health {
+        current: f32,
+        max: f32,
+    }
+
     #[derive(Resource, Reflect, Default)]
     #[reflect(Resource)]
     struct MyResource {
@@ -578,6 +586,7 @@ mod tests {
             registry.register::<MyEntityRef>();
             registry.register::<Entity>();
             registry.register::<MyResource>();
+            registry.register::<Health>();
         }
         world.insert_resource(registry);
         world
@@ -693,6 +702,75 @@ mod tests {
         assert_eq!(1, dst_world.query::<&Baz>().iter(&dst_world).count());
     }
 
+    #[test]
+    fn should_migrate_older_component_versions() {
+        let world = create_world();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register_migrations::<Health>(
+                ReflectMigrations::new().with_migration(
+                    Migration::new()
+                        .rename_field("hp", "current")
+                        .convert_field("current", |hp: u32| hp as f32)
+                        .add_field("max", 100.0f32),
+                ),
+            );
+
+        let input = r#"(
+  resources: {},
+  entities: {
+    4294967296: (
+      components: {
+        "bevy_scene::serde::tests::Health": (
+          hp: 80,
+        ),
+      },
+    ),
+    4294967297: (
+      components: {
+        "bevy_scene::serde::tests::Health": (
+          __version: 1,
+          current: 50.0,
+          max: 60.0,
+        ),
+      },
+    ),
+  },
+)"#;
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &world.resource::<AppTypeRegistry>().read(),
+        };
+        let scene = scene_deserializer.deserialize(&mut deserializer).unwrap();
+
+        let mut dst_world = World::new();
+        dst_world.insert_resource(world.resource::<AppTypeRegistry>().clone());
+        scene
+            .write_to_world(&mut dst_world, &mut EntityHashMap::default())
+            .unwrap();
+        let mut health: Vec<_> = dst_world.query::<&Health>().iter(&dst_world).collect();
+        health.sort_by(|a, b| a.current.total_cmp(&b.current));
+        assert_eq!(
+            health,
+            [
+                &Health {
+                    current: 50.0,
+                    max: 60.0
+                },
+                &Health {
+                    current: 80.0,
+                    max: 100.0
+                },
+            ]
+        );
+
+        let output = scene
+            .serialize_ron(&world.resource::<AppTypeRegistry>().0)
+            .unwrap();
+        assert!(output.contains("__version: 1"));
+    }
+
     #[test]
     fn should_roundtrip_with_later_generations_and_obsolete_references() {
         let mut world = create_world();
@@ -0,0 +1,195 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, Weak,
+    },
+    task::{Context, Poll, Waker},
+};
+
+use futures_lite::FutureExt;
+
+/// A token used to cooperatively cancel tasks.
+///
+/// Dropping a [`Task`](crate::Task) cancels it at its next `.await` point, but the task has no
+/// way to react to it. With a [`CancellationToken`] the task itself checks whether it should
+/// stop, which lets it clean up or return a partial result first.
+///
+/// Tokens form a tree: cancelling a token also cancels all tokens created from it with
+/// [`CancellationToken::child_token`], but cancelling a child leaves its parent untouched.
+/// Hand a child token to every task spawned by a task to cancel the whole group at once.
+///
+/// ```
+/// use bevy_tasks::{block_on, CancellationToken};
+///
+/// let token = CancellationToken::new();
+/// let child = token.child_token();
+/// let work = async move {
+///     let mut processed = 0;
+///     for _chunk in 0..100 {
+///         if child.is_cancelled() {
+///             break;
+///         }
+///         processed += 1;
+///     }
+///     processed
+/// };
+///
+/// token.cancel();
+/// assert_eq!(block_on(work), 0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<TokenInner>,
+}
+
+#[derive(Debug, Default)]
+struct TokenInner {
+    cancelled: AtomicBool,
+    state: Mutex<TokenState>,
+}
+
+#[derive(Debug, Default)]
+struct TokenState {
+    wakers: Vec<Waker>,
+    children: Vec<Weak<TokenInner>>,
+}
+
+impl TokenInner {
+    fn cancel(&self) {
+        if self.cancelled.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let state = std::mem::take(&mut *self.state.lock().unwrap());
+        for waker in state.wakers {
+            waker.wake();
+        }
+        for child in state.children.iter().filter_map(Weak::upgrade) {
+            child.cancel();
+        }
+    }
+}
+
+impl CancellationToken {
+    /// Creates a new token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a token that is cancelled when this token is cancelled.
+    ///
+    /// If this token is already cancelled, the returned token is cancelled too.
+    pub fn child_token(&self) -> Self {
+        let child = Self::new();
+        {
+            let mut state = self.inner.state.lock().unwrap();
+            if !self.is_cancelled() {
+                // Forget about children that have been dropped in the meantime.
+                state.children.retain(|child| child.strong_count() > 0);
+                state.children.push(Arc::downgrade(&child.inner));
+                return child;
+            }
+        }
+        child.cancel();
+        child
+    }
+
+    /// Cancels this token and all of its children.
+    ///
+    /// Cancelling an already cancelled token does nothing.
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    /// Returns `true` if this token, or one of its parents, has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Returns a future that completes once this token is cancelled.
+    pub fn cancelled(&self) -> Cancelled<'_> {
+        Cancelled { token: self }
+    }
+
+    /// Runs `future` until it completes or this token is cancelled, whichever comes first.
+    ///
+    /// Returns [`None`] if the token was cancelled before `future` completed.
+    pub async fn run_until_cancelled<F: Future>(&self, future: F) -> Option<F::Output> {
+        async { Some(future.await) }
+            .or(async {
+                self.cancelled().await;
+                None
+            })
+            .await
+    }
+}
+
+/// Future returned by [`CancellationToken::cancelled`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Cancelled<'a> {
+    token: &'a CancellationToken,
+}
+
+impl Future for Cancelled<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+        let mut state = self.token.inner.state.lock().unwrap();
+        // The token might have been cancelled while waiting for the lock.
+        if self.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+        if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            state.wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{block_on, poll_once};
+
+    #[test]
+    fn cancel_propagates_to_children() {
+        let parent = CancellationToken::new();
+        let child = parent.child_token();
+        let grandchild = child.child_token();
+        let sibling = parent.child_token();
+
+        child.cancel();
+        assert!(!parent.is_cancelled());
+        assert!(!sibling.is_cancelled());
+        assert!(grandchild.is_cancelled());
+
+        parent.cancel();
+        assert!(sibling.is_cancelled());
+        assert!(parent.child_token().is_cancelled());
+    }
+
+    #[test]
+    fn cancelled_future_completes_on_cancel() {
+        let token = CancellationToken::new();
+        let mut cancelled = Box::pin(token.cancelled());
+        assert_eq!(block_on(poll_once(&mut cancelled)), None);
+
+        token.cancel();
+        assert_eq!(block_on(poll_once(&mut cancelled)), Some(()));
+    }
+
+    #[test]
+    fn run_until_cancelled() {
+        let token = CancellationToken::new();
+        assert_eq!(block_on(token.run_until_cancelled(async { 1 })), Some(1));
+
+        token.cancel();
+        let never = futures_lite::future::pending::<()>();
+        assert_eq!(block_on(token.run_until_cancelled(never)), None);
+    }
+}
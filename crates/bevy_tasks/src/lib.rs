@@ -6,6 +6,15 @@ pub use slice::{ParallelSlice, ParallelSliceMut};
 mod task;
 pub use task::Task;
 
+mod cancellation;
+pub use cancellation::{CancellationToken, Cancelled};
+
+mod priority;
+pub use priority::{TaskPoolStatistics, TaskPriority};
+
+mod progress;
+pub use progress::{progress_channel, Progress, ProgressReceiver, ProgressSender};
+
 #[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
 mod task_pool;
 #[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
@@ -42,7 +51,9 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         block_on,
+        cancellation::CancellationToken,
         iter::ParallelIterator,
+        priority::TaskPriority,
         slice::{ParallelSlice, ParallelSliceMut},
         usages::{AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool},
     };
@@ -0,0 +1,115 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// The priority of a task spawned with `TaskPool::spawn_with_priority`.
+///
+/// Whenever a thread of the pool picks up work, it runs the ready task with the highest
+/// priority first. Tasks with the same priority run in the order they were woken up.
+/// Priorities are not preemptive: a long running poll of a low priority task still has to
+/// finish before the thread can pick up another task.
+///
+/// Tasks spawned with `TaskPool::spawn` use [`TaskPriority::Normal`]. Scoped and local tasks
+/// don't have a priority, and run in the order they are woken up.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TaskPriority {
+    /// Background work, such as speculative asset decoding. It only runs once no
+    /// [`TaskPriority::Normal`] or [`TaskPriority::High`] task of the pool is ready.
+    Low,
+    /// The default priority.
+    #[default]
+    Normal,
+    /// Latency sensitive work, such as pathfinding requests the game is waiting on.
+    High,
+}
+
+impl TaskPriority {
+    /// The number of priority levels.
+    pub const COUNT: usize = 3;
+
+    /// All priority levels, from the highest to the lowest.
+    pub const DESCENDING: [TaskPriority; TaskPriority::COUNT] =
+        [TaskPriority::High, TaskPriority::Normal, TaskPriority::Low];
+
+    #[cfg_attr(
+        any(target_arch = "wasm32", not(feature = "multi-threaded")),
+        allow(dead_code)
+    )]
+    pub(crate) fn index(self) -> usize {
+        match self {
+            TaskPriority::High => 0,
+            TaskPriority::Normal => 1,
+            TaskPriority::Low => 2,
+        }
+    }
+}
+
+/// A snapshot of the activity of a `TaskPool`, returned by `TaskPool::statistics`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TaskPoolStatistics {
+    /// Number of spawned tasks that have neither completed nor been canceled yet.
+    ///
+    /// This includes queued tasks and tasks waiting on some external event.
+    pub active: usize,
+    queued: [usize; TaskPriority::COUNT],
+}
+
+impl TaskPoolStatistics {
+    /// Number of tasks that are ready to run but wait for a free thread.
+    pub fn queued(&self) -> usize {
+        self.queued.iter().sum()
+    }
+
+    /// Number of tasks with the given `priority` that are ready to run but wait for a free thread.
+    pub fn queued_with_priority(&self, priority: TaskPriority) -> usize {
+        self.queued[priority.index()]
+    }
+}
+
+/// Counters shared between a `TaskPool` and the tasks it spawned.
+#[derive(Debug, Default)]
+pub(crate) struct TaskCounters {
+    active: AtomicUsize,
+    queued: [AtomicUsize; TaskPriority::COUNT],
+}
+
+impl TaskCounters {
+    #[cfg_attr(
+        any(target_arch = "wasm32", not(feature = "multi-threaded")),
+        allow(dead_code)
+    )]
+    pub(crate) fn queued(&self, priority: TaskPriority) -> &AtomicUsize {
+        &self.queued[priority.index()]
+    }
+
+    pub(crate) fn statistics(&self) -> TaskPoolStatistics {
+        TaskPoolStatistics {
+            active: self.active.load(Ordering::Relaxed),
+            queued: [
+                self.queued[0].load(Ordering::Relaxed),
+                self.queued[1].load(Ordering::Relaxed),
+                self.queued[2].load(Ordering::Relaxed),
+            ],
+        }
+    }
+}
+
+/// Counts a task as active for as long as it is alive.
+///
+/// This is moved into the spawned future, so it is dropped both when the task completes and
+/// when it is canceled.
+pub(crate) struct ActiveTaskGuard(Arc<TaskCounters>);
+
+impl ActiveTaskGuard {
+    pub(crate) fn new(counters: Arc<TaskCounters>) -> Self {
+        counters.active.fetch_add(1, Ordering::Relaxed);
+        Self(counters)
+    }
+}
+
+impl Drop for ActiveTaskGuard {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,204 @@
+use std::sync::{
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+    Arc,
+};
+
+/// The progress of a task, as reported through a [`ProgressSender`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Progress {
+    /// Number of units of work that have been completed.
+    pub completed: u64,
+    /// Total number of units of work, or `0` if it is not known yet.
+    pub total: u64,
+}
+
+impl Progress {
+    /// Returns the completed fraction of the work, between `0.0` and `1.0`.
+    ///
+    /// Returns `0.0` if the total is not known yet.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (self.completed.min(self.total) as f64 / self.total as f64) as f32
+        }
+    }
+
+    /// Returns `true` once all of the work has been completed.
+    pub fn is_complete(&self) -> bool {
+        self.total > 0 && self.completed >= self.total
+    }
+}
+
+#[derive(Debug, Default)]
+struct ProgressState {
+    completed: AtomicU64,
+    total: AtomicU64,
+    /// Incremented on every update, used by receivers to detect changes.
+    version: AtomicU64,
+    senders: AtomicUsize,
+}
+
+/// Creates a new progress channel.
+///
+/// The [`ProgressSender`] is moved into a task to report how far along it is, while the
+/// [`ProgressReceiver`] is usually stored in a component or resource so that a system can
+/// read the latest progress every frame, for example to display a loading bar. Reading the
+/// progress never blocks and intermediate updates are coalesced.
+///
+/// ```
+/// use bevy_tasks::progress_channel;
+///
+/// let (sender, receiver) = progress_channel();
+/// sender.set_total(4);
+/// sender.add_completed(1);
+///
+/// assert_eq!(receiver.progress().fraction(), 0.25);
+/// assert!(receiver.has_changed());
+/// assert!(!receiver.has_changed());
+/// ```
+pub fn progress_channel() -> (ProgressSender, ProgressReceiver) {
+    let state = Arc::new(ProgressState {
+        senders: AtomicUsize::new(1),
+        ..Default::default()
+    });
+    (
+        ProgressSender {
+            state: state.clone(),
+        },
+        ProgressReceiver {
+            state,
+            seen_version: AtomicU64::new(0),
+        },
+    )
+}
+
+/// The sending half of a [`progress_channel`].
+///
+/// Senders can be cloned to report progress from several tasks at once.
+#[derive(Debug)]
+pub struct ProgressSender {
+    state: Arc<ProgressState>,
+}
+
+impl ProgressSender {
+    /// Sets the total number of units of work.
+    pub fn set_total(&self, total: u64) {
+        self.state.total.store(total, Ordering::Release);
+        self.notify();
+    }
+
+    /// Sets the number of completed units of work.
+    pub fn set_completed(&self, completed: u64) {
+        self.state.completed.store(completed, Ordering::Release);
+        self.notify();
+    }
+
+    /// Adds `amount` to the number of completed units of work.
+    pub fn add_completed(&self, amount: u64) {
+        self.state.completed.fetch_add(amount, Ordering::AcqRel);
+        self.notify();
+    }
+
+    /// Adds `amount` to the total number of units of work, for work discovered on the way.
+    pub fn add_total(&self, amount: u64) {
+        self.state.total.fetch_add(amount, Ordering::AcqRel);
+        self.notify();
+    }
+
+    fn notify(&self) {
+        self.state.version.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+impl Clone for ProgressSender {
+    fn clone(&self) -> Self {
+        self.state.senders.fetch_add(1, Ordering::AcqRel);
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl Drop for ProgressSender {
+    fn drop(&mut self) {
+        self.state.senders.fetch_sub(1, Ordering::AcqRel);
+        self.notify();
+    }
+}
+
+/// The receiving half of a [`progress_channel`].
+#[derive(Debug)]
+pub struct ProgressReceiver {
+    state: Arc<ProgressState>,
+    seen_version: AtomicU64,
+}
+
+impl ProgressReceiver {
+    /// Returns the latest reported progress.
+    pub fn progress(&self) -> Progress {
+        Progress {
+            completed: self.state.completed.load(Ordering::Acquire),
+            total: self.state.total.load(Ordering::Acquire),
+        }
+    }
+
+    /// Returns `true` if the progress has been updated since the last call to this method.
+    pub fn has_changed(&self) -> bool {
+        let version = self.state.version.load(Ordering::Acquire);
+        self.seen_version.swap(version, Ordering::AcqRel) != version
+    }
+
+    /// Returns `true` once every [`ProgressSender`] of this channel has been dropped, which
+    /// usually means that the task reporting the progress has finished or was canceled.
+    pub fn is_closed(&self) -> bool {
+        self.state.senders.load(Ordering::Acquire) == 0
+    }
+}
+
+impl Clone for ProgressReceiver {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            seen_version: AtomicU64::new(self.seen_version.load(Ordering::Acquire)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_updates_are_visible() {
+        let (sender, receiver) = progress_channel();
+        assert_eq!(receiver.progress(), Progress::default());
+        assert!(!receiver.has_changed());
+
+        sender.set_total(10);
+        sender.add_completed(3);
+        sender.add_completed(2);
+        assert!(receiver.has_changed());
+        assert_eq!(
+            receiver.progress(),
+            Progress {
+                completed: 5,
+                total: 10
+            }
+        );
+        assert_eq!(receiver.progress().fraction(), 0.5);
+
+        sender.set_completed(10);
+        assert!(receiver.progress().is_complete());
+    }
+
+    #[test]
+    fn closed_once_all_senders_are_dropped() {
+        let (sender, receiver) = progress_channel();
+        let other = sender.clone();
+        drop(sender);
+        assert!(!receiver.is_closed());
+        drop(other);
+        assert!(receiver.is_closed());
+    }
+}
@@ -1,6 +1,11 @@
 use std::sync::Arc;
 use std::{cell::RefCell, future::Future, marker::PhantomData, mem, rc::Rc};
 
+use crate::{
+    priority::{ActiveTaskGuard, TaskCounters},
+    TaskPoolStatistics, TaskPriority,
+};
+
 thread_local! {
     static LOCAL_EXECUTOR: async_executor::LocalExecutor<'static> = async_executor::LocalExecutor::new();
 }
@@ -53,7 +58,9 @@ impl TaskPoolBuilder {
 /// A thread pool for executing tasks. Tasks are futures that are being automatically driven by
 /// the pool on threads owned by the pool. In this case - main thread only.
 #[derive(Debug, Default, Clone)]
-pub struct TaskPool {}
+pub struct TaskPool {
+    counters: Arc<TaskCounters>,
+}
 
 impl TaskPool {
     /// Just create a new `ThreadExecutor` for wasm
@@ -68,7 +75,7 @@ impl TaskPool {
 
     #[allow(unused_variables)]
     fn new_internal() -> Self {
-        Self {}
+        Self::default()
     }
 
     /// Return the number of threads owned by the task pool
@@ -142,6 +149,12 @@ impl TaskPool {
     where
         T: 'static,
     {
+        let guard = ActiveTaskGuard::new(self.counters.clone());
+        let future = async move {
+            let _guard = guard;
+            future.await
+        };
+
         #[cfg(target_arch = "wasm32")]
         wasm_bindgen_futures::spawn_local(async move {
             future.await;
@@ -159,6 +172,25 @@ impl TaskPool {
         FakeTask
     }
 
+    /// Spawns a static future onto the thread pool. There is only a single thread, which runs
+    /// tasks in the order they are woken up, so the priority is ignored. This is exactly the
+    /// same as [`TaskPool::spawn`].
+    pub fn spawn_with_priority<T>(
+        &self,
+        _priority: TaskPriority,
+        future: impl Future<Output = T> + 'static,
+    ) -> FakeTask
+    where
+        T: 'static,
+    {
+        self.spawn(future)
+    }
+
+    /// Returns a snapshot of the tasks currently spawned on this pool.
+    pub fn statistics(&self) -> TaskPoolStatistics {
+        self.counters.statistics()
+    }
+
     /// Spawns a static future on the JS event loop. This is exactly the same as [`TaskPool::spawn`].
     pub fn spawn_local<T>(&self, future: impl Future<Output = T> + 'static) -> FakeTask
     where
@@ -3,18 +3,19 @@ use std::{
     marker::PhantomData,
     mem,
     panic::AssertUnwindSafe,
-    sync::Arc,
+    sync::{atomic::Ordering, Arc},
     thread::{self, JoinHandle},
 };
 
-use async_task::FallibleTask;
+use async_task::{FallibleTask, Runnable};
 use concurrent_queue::ConcurrentQueue;
 use futures_lite::FutureExt;
 
 use crate::{
     block_on,
+    priority::{ActiveTaskGuard, TaskCounters},
     thread_executor::{ThreadExecutor, ThreadExecutorTicker},
-    Task,
+    Task, TaskPoolStatistics, TaskPriority,
 };
 
 struct CallOnDrop(Option<Arc<dyn Fn() + Send + Sync + 'static>>);
@@ -27,6 +28,57 @@ impl Drop for CallOnDrop {
     }
 }
 
+/// Runnables of tasks spawned with a [`TaskPriority`], waiting for a thread of the pool.
+///
+/// Every time a runnable is pushed, a small "pump" task is spawned on the pool's executor.
+/// Once it runs, the pump picks the runnable with the highest priority, which is not
+/// necessarily the one that caused it to be spawned.
+#[derive(Debug)]
+struct PriorityQueues {
+    queues: [ConcurrentQueue<Runnable>; TaskPriority::COUNT],
+    counters: Arc<TaskCounters>,
+}
+
+impl PriorityQueues {
+    fn new() -> Self {
+        Self {
+            queues: [
+                ConcurrentQueue::unbounded(),
+                ConcurrentQueue::unbounded(),
+                ConcurrentQueue::unbounded(),
+            ],
+            counters: Arc::default(),
+        }
+    }
+
+    fn push(&self, priority: TaskPriority, runnable: Runnable) {
+        let queued = self.counters.queued(priority);
+        queued.fetch_add(1, Ordering::Relaxed);
+        // The queues are unbounded and only closed once the pool is dropped. In that case the
+        // runnable is dropped as well, which cancels its task.
+        if self.queues[priority.index()].push(runnable).is_err() {
+            queued.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    fn pop(&self) -> Option<Runnable> {
+        TaskPriority::DESCENDING.into_iter().find_map(|priority| {
+            let runnable = self.queues[priority.index()].pop().ok()?;
+            self.counters
+                .queued(priority)
+                .fetch_sub(1, Ordering::Relaxed);
+            Some(runnable)
+        })
+    }
+
+    fn close(&self) {
+        for queue in &self.queues {
+            queue.close();
+            while queue.pop().is_ok() {}
+        }
+    }
+}
+
 /// Used to create a [`TaskPool`]
 #[derive(Default)]
 #[must_use]
@@ -113,6 +165,9 @@ pub struct TaskPool {
     /// the `Vec<Task<T>>` contained within `TaskPoolInner`
     executor: Arc<async_executor::Executor<'static>>,
 
+    /// Runnables of tasks spawned through [`TaskPool::spawn_with_priority`]
+    priority_queues: Arc<PriorityQueues>,
+
     /// Inner state of the pool
     threads: Vec<JoinHandle<()>>,
     shutdown_tx: async_channel::Sender<()>,
@@ -193,6 +248,7 @@ impl TaskPool {
 
         Self {
             executor,
+            priority_queues: Arc::new(PriorityQueues::new()),
             threads,
             shutdown_tx,
         }
@@ -530,13 +586,73 @@ impl TaskPool {
     /// any case, the pool will execute the task even without polling by the
     /// end-user.
     ///
+    /// The task is spawned with [`TaskPriority::Normal`], see
+    /// [`TaskPool::spawn_with_priority`] to change this.
+    ///
     /// If the provided future is non-`Send`, [`TaskPool::spawn_local`] should
     /// be used instead.
     pub fn spawn<T>(&self, future: impl Future<Output = T> + Send + 'static) -> Task<T>
     where
         T: Send + 'static,
     {
-        Task::new(self.executor.spawn(future))
+        self.spawn_with_priority(TaskPriority::Normal, future)
+    }
+
+    /// Spawns a static future onto the thread pool with the given [`TaskPriority`].
+    ///
+    /// Whenever a thread of the pool is free, it runs the ready task with the highest
+    /// priority, so a burst of [`TaskPriority::Low`] tasks doesn't delay tasks with a
+    /// higher priority spawned after them. See [`TaskPool::spawn`] for more details.
+    ///
+    /// ```
+    /// use bevy_tasks::{block_on, TaskPool, TaskPriority};
+    ///
+    /// let pool = TaskPool::new();
+    /// let task = pool.spawn_with_priority(TaskPriority::High, async { 1 + 1 });
+    /// assert_eq!(block_on(task), 2);
+    /// ```
+    pub fn spawn_with_priority<T>(
+        &self,
+        priority: TaskPriority,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> Task<T>
+    where
+        T: Send + 'static,
+    {
+        let guard = ActiveTaskGuard::new(self.priority_queues.counters.clone());
+        let future = async move {
+            let _guard = guard;
+            future.await
+        };
+
+        let executor = Arc::downgrade(&self.executor);
+        let queues = Arc::downgrade(&self.priority_queues);
+        let schedule = move |runnable: Runnable| {
+            // If the pool is gone, dropping the runnable cancels the task.
+            let (Some(executor), Some(queues)) = (executor.upgrade(), queues.upgrade()) else {
+                return;
+            };
+            queues.push(priority, runnable);
+            executor
+                .spawn(async move {
+                    if let Some(runnable) = queues.pop() {
+                        runnable.run();
+                    }
+                })
+                .detach();
+        };
+
+        let (runnable, task) = async_task::spawn(future, schedule);
+        runnable.schedule();
+        Task::new(task)
+    }
+
+    /// Returns a snapshot of the tasks currently spawned on this pool.
+    ///
+    /// Only tasks spawned with [`TaskPool::spawn`] or [`TaskPool::spawn_with_priority`]
+    /// are counted, scoped and local tasks are not.
+    pub fn statistics(&self) -> TaskPoolStatistics {
+        self.priority_queues.counters.statistics()
     }
 
     /// Spawns a static future on the thread-local async executor for the
@@ -585,6 +701,7 @@ impl Default for TaskPool {
 impl Drop for TaskPool {
     fn drop(&mut self) {
         self.shutdown_tx.close();
+        self.priority_queues.close();
 
         let panicking = thread::panicking();
         for join_handle in self.threads.drain(..) {
@@ -943,4 +1060,64 @@ mod tests {
 
         assert_eq!(count.load(Ordering::Acquire), 1);
     }
+
+    #[test]
+    fn test_spawn_with_priority() {
+        let pool = TaskPoolBuilder::new().num_threads(1).build();
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+
+        // Keep the only thread busy until all other tasks have been queued.
+        let blocker = pool.spawn(async move {
+            started_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+        });
+        started_rx.recv().unwrap();
+
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut tasks = Vec::new();
+        for priority in [
+            TaskPriority::Low,
+            TaskPriority::Normal,
+            TaskPriority::Low,
+            TaskPriority::Normal,
+            TaskPriority::High,
+        ] {
+            let order = order.clone();
+            let future = async move {
+                order.lock().unwrap().push(priority);
+            };
+            tasks.push(if priority == TaskPriority::Normal {
+                pool.spawn(future)
+            } else {
+                pool.spawn_with_priority(priority, future)
+            });
+        }
+
+        // Tasks of every priority are queued, so a high priority task doesn't wait behind
+        // normal ones.
+        let statistics = pool.statistics();
+        assert_eq!(statistics.active, 6);
+        assert_eq!(statistics.queued(), 5);
+        assert_eq!(statistics.queued_with_priority(TaskPriority::Normal), 2);
+        assert_eq!(statistics.queued_with_priority(TaskPriority::Low), 2);
+
+        release_tx.send(()).unwrap();
+        block_on(blocker);
+        for task in tasks {
+            block_on(task);
+        }
+
+        assert_eq!(
+            *order.lock().unwrap(),
+            [
+                TaskPriority::High,
+                TaskPriority::Normal,
+                TaskPriority::Normal,
+                TaskPriority::Low,
+                TaskPriority::Low
+            ]
+        );
+        assert_eq!(pool.statistics(), TaskPoolStatistics::default());
+    }
 }
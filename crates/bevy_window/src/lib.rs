@@ -31,6 +31,7 @@ pub mod prelude {
 }
 
 use bevy_app::prelude::*;
+use bevy_reflect::Reflect;
 use std::path::PathBuf;
 
 impl Default for WindowPlugin {
@@ -44,6 +45,7 @@ impl Default for WindowPlugin {
 }
 
 /// A [`Plugin`] that defines an interface for windowing support in Bevy.
+#[derive(Reflect)]
 pub struct WindowPlugin {
     /// Settings for the primary window.
     ///
@@ -165,11 +167,16 @@ impl Plugin for WindowPlugin {
 
         // Register `PathBuf` as it's used by `FileDragAndDrop`
         app.register_type::<PathBuf>();
+
+        // Register the plugin itself so its settings can be overridden
+        app.register_type::<WindowPlugin>()
+            .register_type::<ExitCondition>()
+            .register_type::<Option<Window>>();
     }
 }
 
 /// Defines the specific conditions the application should exit on
-#[derive(Clone)]
+#[derive(Clone, Reflect)]
 pub enum ExitCondition {
     /// Close application when the primary window is closed
     ///
@@ -0,0 +1,73 @@
+//! Packs a folder of assets into a single archive, and loads assets out of it.
+//!
+//! The packing step doubles as a command line tool for release builds: run
+//! `cargo run --example asset_packing -- <folder> <archive>` to pack `<folder>`, for example the
+//! `imported_assets/Default` output of the asset processor, into `<archive>`. Patches can be
+//! shipped as additional archives layered on top of the base archive with
+//! [`ArchiveAssetReader::open_layered`].
+
+use bevy::{
+    asset::io::{
+        archive::{ArchiveAssetReader, ArchiveBuilder},
+        AssetSource,
+    },
+    prelude::*,
+};
+use std::path::PathBuf;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let folder = args.next().unwrap_or_else(|| "assets/branding".to_string());
+    let archive = args
+        .next()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::temp_dir().join("branding.pak"));
+
+    let mut builder = ArchiveBuilder::new();
+    builder
+        .add_directory(&folder)
+        .expect("failed to collect the assets to pack");
+    builder
+        .write(&archive)
+        .expect("failed to write the archive");
+    let packed = PackedArchive {
+        files: builder.len(),
+        folder,
+        archive: archive.clone(),
+    };
+
+    let reader = ArchiveAssetReader::open(&archive).expect("failed to open the archive");
+    App::new()
+        // Asset sources must be registered before `AssetPlugin`, which is part of `DefaultPlugins`.
+        // Registering the reader as `AssetSourceId::Default` instead would serve every asset
+        // out of the archive.
+        .register_asset_source(
+            "pak",
+            AssetSource::build().with_reader(move || Box::new(reader.clone())),
+        )
+        .add_plugins(DefaultPlugins)
+        .insert_resource(packed)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+/// What was packed, reported once logging is set up by `DefaultPlugins`.
+#[derive(Resource)]
+struct PackedArchive {
+    files: usize,
+    folder: String,
+    archive: PathBuf,
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, packed: Res<PackedArchive>) {
+    info!(
+        "Packed {} files from {} into {:?}",
+        packed.files, packed.folder, packed.archive
+    );
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn(SpriteBundle {
+        // Paths are relative to the packed folder.
+        texture: asset_server.load("pak://bevy_bird_dark.png"),
+        ..default()
+    });
+}
@@ -4,21 +4,38 @@ use bevy::{
     asset::{
         embedded_asset,
         io::{Reader, Writer},
-        processor::LoadTransformAndSave,
+        processor::{AssetProcessor, LoadTransformAndSave},
         ron,
         saver::{AssetSaver, SavedAsset},
         transformer::{AssetTransformer, TransformedAsset},
         AssetLoader, AsyncReadExt, AsyncWriteExt, LoadContext,
     },
+    log::LogPlugin,
     prelude::*,
     reflect::TypePath,
     utils::{thiserror, BoxedFuture},
 };
 use serde::{Deserialize, Serialize};
-use std::convert::Infallible;
+use std::{convert::Infallible, process::ExitCode};
 use thiserror::Error;
 
-fn main() {
+fn main() -> ExitCode {
+    // Running this example with `-- --process-only` processes the assets once without opening a window,
+    // prints the outcome of every asset, and exits with a failure code if any asset failed to process.
+    // This is how assets can be processed on a build server.
+    if std::env::args().any(|arg| arg == "--process-only") {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            LogPlugin::default(),
+            asset_plugin(),
+            TextPlugin,
+        ));
+        let report = AssetProcessor::process_app_once(&mut app);
+        println!("{report}");
+        return report.exit_code();
+    }
+
     App::new()
         // Using the "processed" mode will configure the AssetPlugin to use asset processing.
         // If you also enable the `asset_processor` cargo feature, this will run the AssetProcessor
@@ -28,21 +45,22 @@ fn main() {
         //
         // The AssetProcessor will create `.meta` files automatically for assets in the `assets` folder,
         // which can then be used to configure how the asset will be processed.
-        .add_plugins((
-            DefaultPlugins.set(AssetPlugin {
-                mode: AssetMode::Processed,
-                // This is just overriding the default paths to scope this to the correct example folder
-                // You can generally skip this in your own projects
-                file_path: "examples/asset/processing/assets".to_string(),
-                processed_file_path: "examples/asset/processing/imported_assets/Default"
-                    .to_string(),
-                ..default()
-            }),
-            TextPlugin,
-        ))
+        .add_plugins((DefaultPlugins.set(asset_plugin()), TextPlugin))
         .add_systems(Startup, setup)
         .add_systems(Update, print_text)
         .run();
+    ExitCode::SUCCESS
+}
+
+fn asset_plugin() -> AssetPlugin {
+    AssetPlugin {
+        mode: AssetMode::Processed,
+        // This is just overriding the default paths to scope this to the correct example folder
+        // You can generally skip this in your own projects
+        file_path: "examples/asset/processing/assets".to_string(),
+        processed_file_path: "examples/asset/processing/imported_assets/Default".to_string(),
+        ..default()
+    }
 }
 
 /// This [`TextPlugin`] defines two assets types:
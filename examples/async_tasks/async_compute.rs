@@ -4,7 +4,7 @@
 use bevy::{
     ecs::system::{CommandQueue, SystemState},
     prelude::*,
-    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+    tasks::AsyncComputeTaskPool,
 };
 use rand::Rng;
 use std::{thread, time::Duration};
@@ -13,7 +13,6 @@ fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_systems(Startup, (setup_env, add_assets, spawn_tasks))
-        .add_systems(Update, handle_tasks)
         .run();
 }
 
@@ -42,13 +41,10 @@ fn add_assets(
     commands.insert_resource(BoxMaterialHandle(box_material_handle));
 }
 
-#[derive(Component)]
-struct ComputeTransform(Task<CommandQueue>);
-
 /// This system generates tasks simulating computationally intensive
-/// work that potentially spans multiple frames/ticks. A separate
-/// system, [`handle_tasks`], will poll the spawned tasks on subsequent
-/// frames/ticks, and use the results to spawn cubes
+/// work that potentially spans multiple frames/ticks. The tasks are
+/// attached to entities with an [`EntityTask`], which polls them on
+/// subsequent frames/ticks and applies their results to spawn cubes
 fn spawn_tasks(mut commands: Commands) {
     let thread_pool = AsyncComputeTaskPool::get();
     for x in 0..NUM_CUBES {
@@ -92,34 +88,23 @@ fn spawn_tasks(mut commands: Commands) {
                                 material: box_material_handle,
                                 transform,
                                 ..default()
-                            })
-                            // Task is complete, so remove task component from entity
-                            .remove::<ComputeTransform>();
+                            });
                     });
 
                     command_queue
                 });
 
-                // Spawn new entity and add our new task as a component
-                commands.entity(entity).insert(ComputeTransform(task));
+                // Attach the task to our new entity. Once the task is complete, the
+                // `EntityTask` component is removed and the returned command queue
+                // is applied. Despawning the entity before that cancels the task.
+                commands
+                    .entity(entity)
+                    .insert(EntityTask::apply_commands(task));
             }
         }
     }
 }
 
-/// This system queries for entities that have our Task<Transform> component. It polls the
-/// tasks to see if they're complete. If the task is complete it takes the result, adds a
-/// new [`PbrBundle`] of components to the entity using the result from the task's work, and
-/// removes the task component from the entity.
-fn handle_tasks(mut commands: Commands, mut transform_tasks: Query<&mut ComputeTransform>) {
-    for mut task in &mut transform_tasks {
-        if let Some(mut commands_queue) = block_on(future::poll_once(&mut task.0)) {
-            // append the returned command queue to have it execute later
-            commands.append(&mut commands_queue);
-        }
-    }
-}
-
 /// This system is only used to setup light and camera for the environment
 fn setup_env(mut commands: Commands) {
     // Used to center camera on spawned cubes
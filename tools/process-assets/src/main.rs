@@ -0,0 +1,60 @@
+//! Tool used to process the assets of a Bevy project once, without running the project.
+//!
+//! This processes the `assets` folder of the project into its `imported_assets/Default` folder
+//! with the loaders and processors of Bevy's built-in plugins, prints the outcome of every asset,
+//! and exits with a failure code if any asset failed to process. Like the processor of a running
+//! app, it only processes the assets that changed since the last run.
+//!
+//! Projects with their own loaders or processors should instead call
+//! `AssetProcessor::process_app_once` from a binary adding their plugins.
+
+use std::{path::PathBuf, process::ExitCode};
+
+use bevy::{
+    asset::processor::AssetProcessor, core::TaskPoolPlugin, prelude::*,
+    render::texture::ImagePlugin,
+};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[arg(long, default_value = ".")]
+    /// Root folder of the project, where the processor keeps its transaction log
+    root: PathBuf,
+
+    #[arg(long, default_value = "assets")]
+    /// Folder of the unprocessed assets, relative to the root
+    assets: PathBuf,
+
+    #[arg(long, default_value = "imported_assets/Default")]
+    /// Folder the processed assets are written to, relative to the root
+    imported_assets: PathBuf,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let root = match std::env::current_dir() {
+        Ok(current_dir) => current_dir.join(&args.root),
+        Err(err) => {
+            eprintln!("Failed to get the current directory: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // Paths of file asset sources and of the transaction log are relative to this root.
+    std::env::set_var("BEVY_ASSET_ROOT", root);
+
+    let mut app = App::new();
+    app.add_plugins((
+        TaskPoolPlugin::default(),
+        AssetPlugin {
+            file_path: args.assets.to_string_lossy().into_owned(),
+            processed_file_path: args.imported_assets.to_string_lossy().into_owned(),
+            mode: AssetMode::Processed,
+            ..Default::default()
+        },
+        ImagePlugin::default(),
+    ));
+    let report = AssetProcessor::process_app_once(&mut app);
+    println!("{report}");
+    report.exit_code()
+}